@@ -3,35 +3,121 @@
 
 //! AST Evaluator - converts AST to geometry
 
-use super::{Node, NodeKind};
-use crate::geometry::{BooleanOp, Mesh, Primitive};
+use super::progress::{EvalProgress, ProgressTracker};
+use super::{evaluate_shape, Node, NodeKind, Vec3};
+use crate::geometry::{BooleanOp, BooleanQuality, Mesh, Primitive};
 use anyhow::{Context, Result};
 use dashmap::DashMap;
-use nalgebra::Matrix4;
-use std::sync::Arc;
+use nalgebra::{Matrix4, Unit, UnitQuaternion, Vector3};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Error returned when a render is stopped through its cancellation flag
+///
+/// Evaluators return it inside an [`anyhow::Error`]; test for it with
+/// `error.is::<Cancelled>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Render cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Fail with [`Cancelled`] once `cancel` has been set
+pub(crate) fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<()> {
+    match cancel {
+        Some(flag) if flag.load(Ordering::Relaxed) => Err(Cancelled.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Print an `echo()` message the way OpenSCAD does, returning the text after `ECHO: `
+pub(crate) fn echo(args: &[String]) -> String {
+    let message = args.join(", ");
+    eprintln!("ECHO: {}", message);
+    message
+}
+
+/// Whether a child of kind `kind` is an operand of its parent's boolean operation
+///
+/// `echo()` only prints, so like a passing `assert()` it adds nothing to the operand list;
+/// otherwise `difference() { echo(); a(); b(); }` would subtract everything from nothing.
+pub(crate) fn is_operand(kind: &NodeKind) -> bool {
+    !matches!(kind, NodeKind::Echo(_))
+}
 
 /// AST evaluator with caching support
 pub struct Evaluator {
     cache: Arc<DashMap<String, Mesh>>,
+    quality: BooleanQuality,
+    cancel: Option<Arc<AtomicBool>>,
+    progress: Option<ProgressTracker>,
+    echoes: Mutex<Vec<String>>,
 }
 
 impl Evaluator {
     pub fn new() -> Self {
+        Self::with_quality(BooleanQuality::Robust)
+    }
+
+    /// Evaluator whose boolean operations run at `quality`
+    pub fn with_quality(quality: BooleanQuality) -> Self {
         Self {
             cache: Arc::new(DashMap::new()),
+            quality,
+            cancel: None,
+            progress: None,
+            echoes: Mutex::new(Vec::new()),
         }
     }
 
+    /// Messages printed by `echo()` statements so far, oldest first
+    pub fn echoes(&self) -> Vec<String> {
+        self.echoes.lock().unwrap().clone()
+    }
+
+    /// Call `callback` each time a node with an ID finishes evaluating
+    ///
+    /// Counts restart with every [`evaluate`](Self::evaluate) call, so an evaluator with a
+    /// progress callback should not evaluate several trees at once.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&EvalProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(ProgressTracker::new(Arc::new(callback)));
+        self
+    }
+
+    /// Stop with a [`Cancelled`] error soon after `cancel` is set, e.g. from another thread
+    ///
+    /// The flag is checked before each node and between the steps of a boolean operation,
+    /// so a render stops once the boolean step in progress finishes.
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
     /// Evaluate an AST node and return a mesh
     pub fn evaluate(&self, node: &Node) -> Result<Mesh> {
+        if let Some(progress) = &self.progress {
+            progress.start(node);
+        }
+
         // Check cache if node has an ID
         if let Some(id) = &node.id {
             if let Some(mesh) = self.cache.get(id) {
+                self.finished(node);
                 return Ok(mesh.clone());
             }
         }
 
         let mesh = self.evaluate_node(&node.kind, &Matrix4::identity())?;
+        self.finished(node);
 
         // Store in cache if node has an ID
         if let Some(id) = &node.id {
@@ -41,7 +127,16 @@ impl Evaluator {
         Ok(mesh)
     }
 
+    /// Report `node`'s subtree to the progress callback, if any
+    fn finished(&self, node: &Node) {
+        if let Some(progress) = &self.progress {
+            progress.finish_subtree(node);
+        }
+    }
+
     fn evaluate_node(&self, kind: &NodeKind, transform: &Matrix4<f64>) -> Result<Mesh> {
+        check_cancelled(self.cancel.as_deref())?;
+
         match kind {
             NodeKind::Cube { size, center } => {
                 let mut mesh = Primitive::cube(*size, *center).to_mesh();
@@ -67,6 +162,42 @@ impl Evaluator {
                 Ok(mesh)
             }
 
+            NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            } => {
+                let mut mesh = Primitive::torus(*major_r, *minor_r, *major_fn, *minor_fn).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Polyhedron { points, faces, .. } => {
+                let mut mesh = Primitive::polyhedron(points.clone(), faces.clone()).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Import { path, .. } => {
+                let mut mesh = crate::io::import_mesh(path)?;
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Square { .. }
+            | NodeKind::Circle { .. }
+            | NodeKind::Polygon { .. }
+            | NodeKind::Offset { .. }
+            | NodeKind::Projection { .. } => {
+                let solid = |children: &[Node]| {
+                    self.evaluate_boolean(children, &Matrix4::identity(), BooleanOp::Union)
+                };
+                let mut mesh = evaluate_shape(kind, &solid)?.to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
             NodeKind::Union(children) => {
                 self.evaluate_boolean(children, transform, BooleanOp::Union)
             }
@@ -83,12 +214,59 @@ impl Evaluator {
                 let new_transform = transform * op.to_matrix();
 
                 if children.len() == 1 {
-                    self.evaluate_node(&children[0].kind, &new_transform)
+                    let (chain, leaf) = flatten_transforms(&children[0]);
+                    let mesh = self.evaluate_node(&leaf.kind, &(new_transform * chain))?;
+                    self.finished(&children[0]);
+                    Ok(mesh)
                 } else {
                     self.evaluate_boolean(children, &new_transform, BooleanOp::Union)
                 }
             }
 
+            NodeKind::Resize {
+                newsize,
+                auto,
+                children,
+            } => {
+                // Fit the children in the resize's own frame, then place the result
+                let identity = Matrix4::identity();
+                let mut mesh = if children.len() == 1 {
+                    let mesh = self.evaluate_node(&children[0].kind, &identity)?;
+                    self.finished(&children[0]);
+                    mesh
+                } else {
+                    self.evaluate_boolean(children, &identity, BooleanOp::Union)?
+                };
+                mesh.transform(&(transform * mesh.resize_matrix(newsize, *auto)));
+                Ok(mesh)
+            }
+
+            NodeKind::Color { rgba, children } => {
+                let mut mesh = if children.len() == 1 {
+                    let mesh = self.evaluate_node(&children[0].kind, transform)?;
+                    self.finished(&children[0]);
+                    mesh
+                } else {
+                    self.evaluate_boolean(children, transform, BooleanOp::Union)?
+                };
+                mesh.apply_color(*rgba);
+                Ok(mesh)
+            }
+
+            NodeKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if *condition { then_branch } else { else_branch };
+                self.evaluate_boolean(branch, transform, BooleanOp::Union)
+            }
+
+            NodeKind::Echo(args) => {
+                self.echoes.lock().unwrap().push(echo(args));
+                Ok(Mesh::empty())
+            }
+
             NodeKind::Empty => Ok(Mesh::empty()),
         }
     }
@@ -99,25 +277,39 @@ impl Evaluator {
         transform: &Matrix4<f64>,
         op: BooleanOp,
     ) -> Result<Mesh> {
-        if children.is_empty() {
-            return Ok(Mesh::empty());
-        }
-
-        let mut result = self
-            .evaluate_node(&children[0].kind, transform)
-            .context("Failed to evaluate first child")?;
-
-        for child in &children[1..] {
+        let mut result: Option<Mesh> = None;
+        for child in children {
+            let message = match result {
+                None => "Failed to evaluate first child",
+                Some(_) => "Failed to evaluate child",
+            };
             let child_mesh = self
                 .evaluate_node(&child.kind, transform)
-                .context("Failed to evaluate child")?;
+                .with_context(|| with_location(message, child))?;
+            self.finished(child);
 
-            result = result
-                .boolean_operation(&child_mesh, op.clone())
-                .context("Boolean operation failed")?;
+            if !is_operand(&child.kind) {
+                continue;
+            }
+            result = Some(match result {
+                None => child_mesh,
+                Some(mesh) => {
+                    check_cancelled(self.cancel.as_deref())?;
+                    mesh.boolean_operation_with_quality(&child_mesh, op.clone(), self.quality)
+                        .with_context(|| with_location("Boolean operation failed", child))?
+                }
+            });
         }
 
-        Ok(result)
+        Ok(result.unwrap_or_else(Mesh::empty))
+    }
+}
+
+/// Append the node's source location to an error message when the parser recorded one
+fn with_location(message: &str, node: &Node) -> String {
+    match node.span {
+        Some(span) => format!("{} at line {}, column {}", message, span.line, span.col),
+        None => message.to_string(),
     }
 }
 
@@ -127,11 +319,47 @@ impl Default for Evaluator {
     }
 }
 
+/// Rotation matrix for OpenSCAD's `rotate([x, y, z])`, with angles in degrees
+///
+/// OpenSCAD rotates about X first, then Y, then Z, all about the fixed world axes,
+/// so the composed matrix is `Rz · Ry · Rx`.
+pub fn rotation_matrix(angles_deg: Vec3) -> Matrix4<f64> {
+    let rx = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), angles_deg.x.to_radians());
+    let ry = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), angles_deg.y.to_radians());
+    let rz = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), angles_deg.z.to_radians());
+    (rz * ry * rx).to_homogeneous()
+}
+
+/// Rotation matrix for OpenSCAD's `rotate(a, v)`: `angle_deg` degrees about `axis`
+///
+/// A zero-length axis falls back to Z, matching `rotate(a)` with no `v`.
+pub fn axis_angle_matrix(angle_deg: f64, axis: Vec3) -> Matrix4<f64> {
+    let axis = Unit::try_new(axis, f64::EPSILON).unwrap_or_else(Vector3::z_axis);
+    UnitQuaternion::from_axis_angle(&axis, angle_deg.to_radians()).to_homogeneous()
+}
+
+/// Compose a chain of single-child transforms, such as `translate() rotate() scale() cube()`
+///
+/// Returns the chain's accumulated matrix, outermost first, and the first node below it that
+/// is not a single-child transform. Evaluators apply the matrix to that node once, instead of
+/// descending one transform at a time.
+pub fn flatten_transforms(node: &Node) -> (Matrix4<f64>, &Node) {
+    let mut matrix = Matrix4::identity();
+    let mut node = node;
+    while let NodeKind::Transform { op, children } = &node.kind {
+        let [child] = children.as_slice() else {
+            break;
+        };
+        matrix *= op.to_matrix();
+        node = child;
+    }
+    (matrix, node)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ast::TransformOp;
-    use nalgebra::Vector3;
 
     #[test]
     fn test_difference_with_transforms() {
@@ -207,4 +435,272 @@ mod tests {
         assert!(mesh.vertex_count() > 0);
         assert!(mesh.triangle_count() > 0);
     }
+
+    #[test]
+    fn test_resize_changes_only_specified_axes() {
+        let evaluator = Evaluator::new();
+        let size = |code: &str| {
+            let ast = crate::io::parse_scad(code).unwrap();
+            evaluator.evaluate(&ast).unwrap().bounding_box().size()
+        };
+
+        let resized = size("resize([20, 0, 0]) cube([10, 10, 10]);");
+        assert_eq!(resized, Vector3::new(20.0, 10.0, 10.0));
+
+        let auto = size("resize([20, 0, 0], auto=[false, true, false]) cube([10, 10, 10]);");
+        assert_eq!(auto, Vector3::new(20.0, 20.0, 10.0));
+
+        // The fit is computed in the resize's own frame, then transformed
+        let ast = crate::io::parse_scad(
+            "translate([5, 0, 0]) resize([4, 4, 4]) translate([100, 0, 0]) cube([1, 2, 4]);",
+        )
+        .unwrap();
+        let bbox = evaluator.evaluate(&ast).unwrap().bounding_box();
+        assert_eq!(bbox.size(), Vector3::new(4.0, 4.0, 4.0));
+        assert_eq!(bbox.min.x, 5.0 + 400.0);
+    }
+
+    #[test]
+    fn test_multmatrix_shear() {
+        let ast = crate::io::parse_scad(
+            "multmatrix([[1, 0, 0.5, 0], [0, 1, 0, 0], [0, 0, 1, 0]]) cube([10, 10, 10]);",
+        )
+        .unwrap();
+        let mesh = Evaluator::new().evaluate(&ast).unwrap();
+
+        // x' = x + z/2: the base stays put and the top face shifts by half the height
+        let face_x = |z: f64| {
+            let xs: Vec<f64> = mesh
+                .vertices
+                .iter()
+                .filter(|v| v.position.z == z)
+                .map(|v| v.position.x)
+                .collect();
+            let min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            (min, max)
+        };
+        assert_eq!(face_x(0.0), (0.0, 10.0));
+        assert_eq!(face_x(10.0), (5.0, 15.0));
+
+        // Shear preserves volume, and normals follow the slanted faces
+        let volume = crate::geometry::analyze(&mesh).volume;
+        assert!((volume - 1000.0).abs() < 1e-9);
+        let slanted = Vector3::new(1.0, 0.0, -0.5).normalize();
+        assert!(mesh
+            .vertices
+            .iter()
+            .any(|v| (v.normal - slanted).norm() < 1e-9));
+    }
+
+    #[test]
+    fn test_if_else_selects_branch() {
+        let render = |code: &str| {
+            let ast = crate::io::parse_scad(code).unwrap();
+            Evaluator::new().evaluate(&ast).unwrap()
+        };
+
+        let cube = render("flag = true; if (flag) cube(5); else sphere(5);");
+        assert_eq!(cube.triangle_count(), 12);
+
+        let sphere = render("flag = false; if (flag) cube(5); else sphere(5);");
+        assert!(sphere.triangle_count() > 12);
+
+        // Without an else, a false condition renders nothing
+        assert_eq!(render("if (1 > 2) cube(5);").triangle_count(), 0);
+    }
+
+    #[test]
+    fn test_offset_square_grows_bounding_box() {
+        let size = |code: &str| {
+            let ast = crate::io::parse_scad(code).unwrap();
+            Evaluator::new()
+                .evaluate(&ast)
+                .unwrap()
+                .bounding_box()
+                .size()
+        };
+
+        assert_eq!(size("square([10, 6]);"), Vector3::new(10.0, 6.0, 0.0));
+        assert_eq!(
+            size("offset(delta=2) square([10, 6]);"),
+            Vector3::new(14.0, 10.0, 0.0)
+        );
+        let rounded = size("offset(r=2, $fn=32) square([10, 6], center=true);");
+        assert!((rounded - Vector3::new(14.0, 10.0, 0.0)).norm() < 1e-9);
+        assert_eq!(
+            size("offset(-1) square([10, 6]);"),
+            Vector3::new(8.0, 4.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_projection_of_sphere_matches_radius() {
+        let size = |code: &str| {
+            let ast = crate::io::parse_scad(code).unwrap();
+            Evaluator::new()
+                .evaluate(&ast)
+                .unwrap()
+                .bounding_box()
+                .size()
+        };
+
+        let outline = size("projection() sphere(r=5, $fn=24);");
+        assert!((outline - Vector3::new(10.0, 10.0, 0.0)).norm() < 1e-9);
+        let section = size("projection(cut=true) sphere(r=5, $fn=24);");
+        assert!((section - Vector3::new(10.0, 10.0, 0.0)).norm() < 1e-9);
+
+        // Cutting above the solid leaves nothing
+        let ast =
+            crate::io::parse_scad("projection(cut=true) translate([0, 0, 1]) cube([4, 4, 4]);")
+                .unwrap();
+        assert_eq!(Evaluator::new().evaluate(&ast).unwrap().triangle_count(), 0);
+    }
+
+    #[test]
+    fn test_flattened_transform_chain_matches_sequential() {
+        let ops = [
+            TransformOp::Translate(Vector3::new(3.0, -2.0, 7.5)),
+            TransformOp::Rotate(Vector3::new(30.0, 45.0, 60.0)),
+            TransformOp::Scale(Vector3::new(2.0, 0.5, 1.5)),
+        ];
+        let cube = Node::new(NodeKind::Cube {
+            size: Vector3::new(1.0, 2.0, 3.0),
+            center: true,
+        });
+        let chain = ops.iter().rev().fold(cube, |child, op| {
+            Node::new(NodeKind::Transform {
+                op: op.clone(),
+                children: vec![child],
+            })
+        });
+
+        let (matrix, leaf) = flatten_transforms(&chain);
+        assert!(matches!(leaf.kind, NodeKind::Cube { .. }));
+
+        // Innermost transform first, one mesh pass per transform
+        let mut sequential = Primitive::cube(Vector3::new(1.0, 2.0, 3.0), true).to_mesh();
+        for op in ops.iter().rev() {
+            sequential.transform(&op.to_matrix());
+        }
+        let flattened = Evaluator::new().evaluate(&chain).unwrap();
+        assert!(flattened.approx_eq(&sequential, 1e-12));
+
+        let mut direct = Primitive::cube(Vector3::new(1.0, 2.0, 3.0), true).to_mesh();
+        direct.transform(&matrix);
+        assert!(direct.approx_eq(&flattened, 1e-12));
+    }
+
+    #[test]
+    fn test_rotation_matrix_is_zyx() {
+        // Rx(90) maps (x, y, z) to (x, -z, y) and Rz(90) maps it to (-y, x, z),
+        // so Rz(90) · Rx(90) sends (x, y, z) to (z, x, y)
+        #[rustfmt::skip]
+        let expected = Matrix4::new(
+            0.0, 0.0, 1.0, 0.0,
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        );
+        let m = rotation_matrix(Vector3::new(90.0, 0.0, 90.0));
+        assert!((m - expected).abs().max() < 1e-12, "{}", m);
+    }
+
+    /// `union() { translate([20 * i, 0, 0]) cube(10); ... }` with `count` cubes
+    fn row_of_cubes(count: usize) -> Node {
+        let cubes = (0..count)
+            .map(|i| {
+                Node::new(NodeKind::Transform {
+                    op: TransformOp::Translate(Vec3::new(20.0 * i as f64, 0.0, 0.0)),
+                    children: vec![Node::new(NodeKind::Cube {
+                        size: Vec3::new(10.0, 10.0, 10.0),
+                        center: false,
+                    })],
+                })
+            })
+            .collect();
+        Node::new(NodeKind::Union(cubes))
+    }
+
+    #[test]
+    fn test_cancellation_stops_large_union() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let evaluator = Evaluator::new().with_cancellation(Arc::clone(&cancel));
+        let root = row_of_cubes(200);
+
+        let setter = {
+            let cancel = Arc::clone(&cancel);
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                cancel.store(true, Ordering::Relaxed);
+                std::time::Instant::now()
+            })
+        };
+        let error = evaluator.evaluate(&root).unwrap_err();
+        let cancelled_at = setter.join().unwrap();
+
+        assert!(error.is::<Cancelled>(), "{:#}", error);
+        assert!(cancelled_at.elapsed() < std::time::Duration::from_secs(2));
+
+        // The other evaluators honor the flag too
+        assert!(
+            crate::ast::ParallelEvaluator::evaluate_with_cancellation(&root, &cancel)
+                .unwrap_err()
+                .is::<Cancelled>()
+        );
+        let incremental =
+            crate::ast::IncrementalEvaluator::from_ast(&root).with_cancellation(cancel);
+        assert!(incremental.evaluate(&root).unwrap_err().is::<Cancelled>());
+    }
+
+    #[test]
+    fn test_axis_angle_rotation_matches_scalar_form() {
+        let m = axis_angle_matrix(30.0, Vector3::new(0.0, 0.0, 2.0));
+        assert!(
+            (m - rotation_matrix(Vector3::new(0.0, 0.0, 30.0)))
+                .abs()
+                .max()
+                < 1e-12
+        );
+
+        let parse_op = |code: &str| match crate::io::parse_scad(code).unwrap().kind {
+            NodeKind::Transform { op, .. } => op.to_matrix(),
+            other => panic!("Expected transform, got {:?}", other),
+        };
+        let scalar = parse_op("rotate(30) cube(1);");
+        let axis_angle = parse_op("rotate(a=30, v=[0, 0, 1]) cube(1);");
+        let positional = parse_op("rotate(30, [0, 0, 1]) cube(1);");
+        assert!((scalar - m).abs().max() < 1e-12);
+        assert!((axis_angle - m).abs().max() < 1e-12);
+        assert!((positional - m).abs().max() < 1e-12);
+    }
+
+    #[test]
+    fn test_echo_is_recorded_and_renders_nothing() {
+        let ast = crate::io::parse_scad("echo(\"hi\"); cube([5, 5, 5]);").unwrap();
+        let evaluator = Evaluator::new();
+        let mesh = evaluator.evaluate(&ast).unwrap();
+
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(evaluator.echoes(), vec!["\"hi\"".to_string()]);
+    }
+
+    #[test]
+    fn test_echo_is_not_a_boolean_operand() {
+        let code = "difference() { echo(\"x\"); cube([10, 10, 10]); cube([5, 5, 5]); }";
+        let ast = crate::io::parse_scad(code).unwrap();
+
+        let evaluator = Evaluator::new();
+        let serial = evaluator.evaluate(&ast).unwrap();
+        assert_eq!(evaluator.echoes(), vec!["\"x\"".to_string()]);
+        let incremental = crate::ast::IncrementalEvaluator::from_ast(&ast)
+            .evaluate(&ast)
+            .unwrap();
+        let parallel = crate::ast::ParallelEvaluator::evaluate(&ast).unwrap();
+
+        for mesh in [serial, incremental, parallel] {
+            let volume = crate::geometry::analyze(&mesh).volume;
+            assert!((volume - 875.0).abs() < 1e-6, "volume {}", volume);
+        }
+    }
 }
@@ -5,21 +5,66 @@
 
 use super::{
     dependency_graph::{DependencyGraph, NodeId},
+    evaluate_shape,
+    evaluator::{check_cancelled, echo, is_operand},
+    flatten_transforms,
+    node::hash_f64s,
+    progress::{EvalProgress, ProgressTracker},
     Node, NodeKind,
 };
-use crate::geometry::{BooleanOp, Mesh, Primitive};
+use crate::geometry::{BooleanOp, Mesh, Primitive, Triangle, Vertex};
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use nalgebra::Matrix4;
-use std::sync::{Arc, RwLock};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::mem::{discriminant, size_of};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Thread-safe mesh cache
-pub type MeshCache = Arc<DashMap<NodeId, Arc<RwLock<Mesh>>>>;
+pub type MeshCache = Arc<DashMap<NodeId, Arc<Mesh>>>;
+
+/// How an edited AST differs from the one the cache was built for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditImpact {
+    /// Nothing changed; every cached mesh is still valid
+    Unchanged,
+    /// Only parameters of these primitive leaves changed; they are re-tessellated
+    /// and their ancestors recombined, while sibling subtrees stay cached
+    Leaves(Vec<NodeId>),
+    /// The tree structure changed and the whole cache was dropped
+    Full,
+}
 
 /// Incremental evaluator with dependency tracking
+///
+/// Meshes are cached twice: by node ID, which [`apply_edit`](Self::apply_edit) invalidates
+/// precisely, and by a structural hash of the subtree and its accumulated transform. The
+/// content-addressed cache survives every edit, so unchanged branches (ID'd or not) are
+/// reused even after a structural change.
+///
+/// Both caches grow without bound unless a budget is set with
+/// [`with_memory_budget`](Self::with_memory_budget).
 pub struct IncrementalEvaluator {
     cache: MeshCache,
+    /// Meshes keyed by [`content_key`] of the subtree that produced them
+    content_cache: DashMap<ContentKey, Arc<Mesh>>,
+    /// Lookups served from either cache since the last edit
+    hits: AtomicUsize,
+    /// Lookups that had to be evaluated since the last edit
+    misses: AtomicUsize,
+    /// Recency and size of every entry in both caches, for LRU eviction
+    usage: Mutex<CacheUsage>,
     dep_graph: DependencyGraph,
+    /// AST the cache currently corresponds to, used to classify edits
+    root: Option<Node>,
+    /// IDs of primitive leaves tessellated since the last edit
+    tessellated: Mutex<Vec<NodeId>>,
+    cancel: Option<Arc<AtomicBool>>,
+    progress: Option<ProgressTracker>,
+    echoes: Mutex<Vec<String>>,
 }
 
 impl IncrementalEvaluator {
@@ -27,6 +72,15 @@ impl IncrementalEvaluator {
         Self {
             cache: Arc::new(DashMap::new()),
             dep_graph: DependencyGraph::new(),
+            root: None,
+            tessellated: Mutex::new(Vec::new()),
+            content_cache: DashMap::new(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            usage: Mutex::new(CacheUsage::default()),
+            cancel: None,
+            progress: None,
+            echoes: Mutex::new(Vec::new()),
         }
     }
 
@@ -35,12 +89,114 @@ impl IncrementalEvaluator {
         Self {
             cache: Arc::new(DashMap::new()),
             dep_graph: DependencyGraph::from_ast(root),
+            root: Some(root.clone()),
+            tessellated: Mutex::new(Vec::new()),
+            content_cache: DashMap::new(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            usage: Mutex::new(CacheUsage::default()),
+            cancel: None,
+            progress: None,
+            echoes: Mutex::new(Vec::new()),
         }
     }
 
+    /// Messages printed by `echo()` statements so far, oldest first
+    ///
+    /// Subtrees served from the cache are not re-evaluated, so their echoes only appear
+    /// the first time.
+    pub fn echoes(&self) -> Vec<String> {
+        self.echoes.lock().unwrap().clone()
+    }
+
+    /// Stop with a [`Cancelled`](super::Cancelled) error soon after `cancel` is set
+    ///
+    /// Meshes finished before the cancellation stay cached, so a later evaluation resumes
+    /// from them.
+    pub fn with_cancellation(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Call `callback` each time a node with an ID finishes evaluating
+    ///
+    /// Nodes served from the cache count as finished too, so every evaluation runs up to the
+    /// node count of the tree's [`DependencyGraph`].
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&EvalProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(ProgressTracker::new(Arc::new(callback)));
+        self
+    }
+
+    /// Keep the estimated size of cached meshes under `bytes`
+    ///
+    /// Once over budget, the least recently used meshes are evicted from both caches until the
+    /// total fits again. Sizes are estimated from vertex and triangle counts, and a mesh held
+    /// by both caches counts once per cache. Evicted subtrees are simply recomputed when next
+    /// needed, so results are unaffected.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.usage.get_mut().unwrap().budget = Some(bytes);
+        self
+    }
+
+    /// Replace the AST with an edited version, invalidating as little of the cache as possible
+    ///
+    /// When the edit only changes parameters of ID'd primitive leaves (e.g. a sphere's `$fn`
+    /// or radius), just those leaves and their ancestors are invalidated: the next
+    /// [`evaluate`](Self::evaluate) re-tessellates the leaves and recombines the CSG above them.
+    /// Any structural change drops the whole ID cache, though subtrees whose geometry is
+    /// unchanged are still served from the content-addressed cache.
+    pub fn apply_edit(&mut self, updated_root: &Node) -> EditImpact {
+        self.tessellated.lock().unwrap().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+
+        let mut changed = Vec::new();
+        let parameter_only = self
+            .root
+            .as_ref()
+            .is_some_and(|old| diff_parameters(old, updated_root, &mut changed));
+
+        let impact = if !parameter_only {
+            self.cache.clear();
+            self.usage.get_mut().unwrap().forget_ids();
+            self.dep_graph = DependencyGraph::from_ast(updated_root);
+            EditImpact::Full
+        } else if changed.is_empty() {
+            EditImpact::Unchanged
+        } else {
+            for id in &changed {
+                self.invalidate(id);
+            }
+            EditImpact::Leaves(changed)
+        };
+
+        self.root = Some(updated_root.clone());
+        impact
+    }
+
+    /// IDs of primitive leaves tessellated since the last [`apply_edit`](Self::apply_edit)
+    pub fn tessellated_leaves(&self) -> Vec<NodeId> {
+        self.tessellated.lock().unwrap().clone()
+    }
+
     /// Full evaluation of the AST
     pub fn evaluate(&self, node: &Node) -> Result<Mesh> {
-        self.evaluate_node(&node.kind, &Matrix4::identity(), &node.id)
+        if let Some(progress) = &self.progress {
+            progress.start(node);
+        }
+        let mesh = self.evaluate_node(&node.kind, &Matrix4::identity(), &node.id)?;
+        self.finished(node);
+        Ok(mesh)
+    }
+
+    /// Report `node`'s subtree to the progress callback, if any
+    fn finished(&self, node: &Node) {
+        if let Some(progress) = &self.progress {
+            progress.finish_subtree(node);
+        }
     }
 
     /// Update a specific subtree and re-evaluate affected nodes
@@ -49,8 +205,8 @@ impl IncrementalEvaluator {
         let affected = self.dep_graph.get_affected_nodes(node_id);
 
         // Invalidate cache for affected nodes
-        for id in &affected {
-            self.cache.remove(id);
+        for id in affected {
+            self.remove_cached(id);
         }
 
         // Rebuild dependency graph for the updated subtree
@@ -63,15 +219,31 @@ impl IncrementalEvaluator {
     pub fn invalidate(&mut self, node_id: &NodeId) {
         let affected = self.dep_graph.get_affected_nodes(node_id);
         for id in affected {
-            self.cache.remove(&id);
+            self.remove_cached(id);
         }
     }
 
+    fn remove_cached(&mut self, id: NodeId) {
+        self.cache.remove(&id);
+        self.usage.get_mut().unwrap().forget(&CacheKey::Id(id));
+    }
+
     /// Get cached mesh if available
     pub fn get_cached(&self, node_id: &NodeId) -> Option<Mesh> {
+        let mesh = self.cache.get(node_id).map(|entry| Mesh::clone(&entry))?;
+        self.usage.lock().unwrap().touch(&CacheKey::Id(node_id.clone()));
+        Some(mesh)
+    }
+
+    /// Every mesh in the ID cache, keyed by node ID
+    ///
+    /// After an [`evaluate`](Self::evaluate) this holds the result of each ID'd node in the
+    /// tree, in world space. The meshes are shared with the cache, not copied.
+    pub fn cached_meshes(&self) -> HashMap<NodeId, Arc<Mesh>> {
         self.cache
-            .get(node_id)
-            .map(|entry| entry.read().unwrap().clone())
+            .iter()
+            .map(|entry| (entry.key().clone(), Arc::clone(entry.value())))
+            .collect()
     }
 
     /// Evaluate node with caching
@@ -84,22 +256,74 @@ impl IncrementalEvaluator {
         // Check cache if node has an ID
         if let Some(id) = node_id {
             if let Some(cached) = self.get_cached(id) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Ok(cached);
             }
         }
 
-        let mesh = self.evaluate_node_uncached(kind, transform)?;
+        let key = content_key(kind, transform);
+        let cached = self.content_cache.get(&key).map(|entry| Arc::clone(&entry));
+        let mesh = match cached {
+            Some(mesh) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                self.usage.lock().unwrap().touch(&CacheKey::Content(key.clone()));
+                mesh
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                let mesh = self.evaluate_node_uncached(kind, transform)?;
+                if let (true, Some(id)) = (kind.is_primitive(), node_id) {
+                    self.tessellated.lock().unwrap().push(id.clone());
+                }
+                let mesh = Arc::new(mesh);
+                self.store(CacheKey::Content(key), &mesh);
+                mesh
+            }
+        };
 
         // Store in cache if node has an ID
         if let Some(id) = node_id {
-            self.cache
-                .insert(id.clone(), Arc::new(RwLock::new(mesh.clone())));
+            self.store(CacheKey::Id(id.clone()), &mesh);
         }
 
-        Ok(mesh)
+        Ok(Mesh::clone(&mesh))
+    }
+
+    /// Insert `mesh` into the cache `key` belongs to, then evict down to the memory budget
+    ///
+    /// A mesh larger than the whole budget isn't cached at all, rather than flushing every
+    /// other entry only to be evicted itself.
+    fn store(&self, key: CacheKey, mesh: &Arc<Mesh>) {
+        let bytes = estimated_bytes(mesh);
+        let mut usage = self.usage.lock().unwrap();
+        let victims = if usage.budget.is_some_and(|budget| bytes > budget) {
+            usage.forget(&key);
+            vec![key]
+        } else {
+            match &key {
+                CacheKey::Id(id) => self.cache.insert(id.clone(), Arc::clone(mesh)),
+                CacheKey::Content(content) => {
+                    self.content_cache.insert(content.clone(), Arc::clone(mesh))
+                }
+            };
+            usage.record(key, bytes);
+            usage.evict_over_budget()
+        };
+        for victim in victims {
+            match victim {
+                CacheKey::Id(id) => {
+                    self.cache.remove(&id);
+                }
+                CacheKey::Content(content) => {
+                    self.content_cache.remove(&content);
+                }
+            }
+        }
     }
 
     fn evaluate_node_uncached(&self, kind: &NodeKind, transform: &Matrix4<f64>) -> Result<Mesh> {
+        check_cancelled(self.cancel.as_deref())?;
+
         match kind {
             NodeKind::Cube { size, center } => {
                 let mut mesh = Primitive::cube(*size, *center).to_mesh();
@@ -125,6 +349,42 @@ impl IncrementalEvaluator {
                 Ok(mesh)
             }
 
+            NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            } => {
+                let mut mesh = Primitive::torus(*major_r, *minor_r, *major_fn, *minor_fn).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Polyhedron { points, faces, .. } => {
+                let mut mesh = Primitive::polyhedron(points.clone(), faces.clone()).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Import { path, .. } => {
+                let mut mesh = crate::io::import_mesh(path)?;
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Square { .. }
+            | NodeKind::Circle { .. }
+            | NodeKind::Polygon { .. }
+            | NodeKind::Offset { .. }
+            | NodeKind::Projection { .. } => {
+                let solid = |children: &[Node]| {
+                    self.evaluate_boolean(children, &Matrix4::identity(), BooleanOp::Union)
+                };
+                let mut mesh = evaluate_shape(kind, &solid)?.to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
             NodeKind::Union(children) => {
                 self.evaluate_boolean(children, transform, BooleanOp::Union)
             }
@@ -141,12 +401,60 @@ impl IncrementalEvaluator {
                 let new_transform = transform * op.to_matrix();
 
                 if children.len() == 1 {
-                    self.evaluate_node(&children[0].kind, &new_transform, &children[0].id)
+                    let (chain, leaf) = flatten_transforms(&children[0]);
+                    let mesh =
+                        self.evaluate_node(&leaf.kind, &(new_transform * chain), &leaf.id)?;
+                    self.finished(&children[0]);
+                    Ok(mesh)
                 } else {
                     self.evaluate_boolean(children, &new_transform, BooleanOp::Union)
                 }
             }
 
+            NodeKind::Resize {
+                newsize,
+                auto,
+                children,
+            } => {
+                // Fit the children in the resize's own frame, then place the result
+                let identity = Matrix4::identity();
+                let mut mesh = if children.len() == 1 {
+                    let mesh = self.evaluate_node(&children[0].kind, &identity, &children[0].id)?;
+                    self.finished(&children[0]);
+                    mesh
+                } else {
+                    self.evaluate_boolean(children, &identity, BooleanOp::Union)?
+                };
+                mesh.transform(&(transform * mesh.resize_matrix(newsize, *auto)));
+                Ok(mesh)
+            }
+
+            NodeKind::Color { rgba, children } => {
+                let mut mesh = if children.len() == 1 {
+                    let mesh = self.evaluate_node(&children[0].kind, transform, &children[0].id)?;
+                    self.finished(&children[0]);
+                    mesh
+                } else {
+                    self.evaluate_boolean(children, transform, BooleanOp::Union)?
+                };
+                mesh.apply_color(*rgba);
+                Ok(mesh)
+            }
+
+            NodeKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if *condition { then_branch } else { else_branch };
+                self.evaluate_boolean(branch, transform, BooleanOp::Union)
+            }
+
+            NodeKind::Echo(args) => {
+                self.echoes.lock().unwrap().push(echo(args));
+                Ok(Mesh::empty())
+            }
+
             NodeKind::Empty => Ok(Mesh::empty()),
         }
     }
@@ -157,36 +465,293 @@ impl IncrementalEvaluator {
         transform: &Matrix4<f64>,
         op: BooleanOp,
     ) -> Result<Mesh> {
-        if children.is_empty() {
-            return Ok(Mesh::empty());
-        }
-
-        let mut result = self
-            .evaluate_node(&children[0].kind, transform, &children[0].id)
-            .context("Failed to evaluate first child")?;
-
-        for child in &children[1..] {
+        let mut result: Option<Mesh> = None;
+        for child in children {
+            let message = match result {
+                None => "Failed to evaluate first child",
+                Some(_) => "Failed to evaluate child",
+            };
             let child_mesh = self
                 .evaluate_node(&child.kind, transform, &child.id)
-                .context("Failed to evaluate child")?;
+                .context(message)?;
+            self.finished(child);
 
-            result = result
-                .boolean_operation(&child_mesh, op.clone())
-                .context("Boolean operation failed")?;
+            if !is_operand(&child.kind) {
+                continue;
+            }
+            result = Some(match result {
+                None => child_mesh,
+                Some(mesh) => {
+                    check_cancelled(self.cancel.as_deref())?;
+                    mesh.boolean_operation(&child_mesh, op.clone())
+                        .context("Boolean operation failed")?
+                }
+            });
         }
 
-        Ok(result)
+        Ok(result.unwrap_or_else(Mesh::empty))
     }
 
     /// Get cache statistics
     pub fn cache_stats(&self) -> CacheStats {
+        let usage = self.usage.lock().unwrap();
         CacheStats {
             cached_nodes: self.cache.len(),
             total_nodes: self.dep_graph.all_nodes().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            bytes: usage.bytes,
+            evictions: usage.evictions,
         }
     }
 }
 
+/// Entry in either of the evaluator's caches
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Id(NodeId),
+    Content(ContentKey),
+}
+
+/// Content address of a subtree evaluated under a transform
+///
+/// Keys compare by the full structural encoding, so a hash collision between two different
+/// subtrees can't serve one's mesh for the other. Only the hash goes into hashers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContentKey {
+    hash: u64,
+    structure: Arc<[u8]>,
+}
+
+impl Hash for ContentKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+/// Hasher that keeps every byte written to it, giving an exact encoding of what was hashed
+#[derive(Default)]
+struct StructureEncoder(Vec<u8>);
+
+impl Hasher for StructureEncoder {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&self.0);
+        hasher.finish()
+    }
+}
+
+/// LRU bookkeeping shared by the ID and content caches
+#[derive(Debug, Default)]
+struct CacheUsage {
+    budget: Option<usize>,
+    /// Incremented on every use; entries remember the tick they were last used at
+    clock: u64,
+    /// Last-used tick and estimated size of each cached mesh
+    entries: HashMap<CacheKey, (u64, usize)>,
+    bytes: usize,
+    evictions: usize,
+}
+
+impl CacheUsage {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        let now = self.tick();
+        if let Some((last_used, _)) = self.entries.get_mut(key) {
+            *last_used = now;
+        }
+    }
+
+    fn record(&mut self, key: CacheKey, bytes: usize) {
+        let now = self.tick();
+        if let Some((_, old)) = self.entries.insert(key, (now, bytes)) {
+            self.bytes -= old;
+        }
+        self.bytes += bytes;
+    }
+
+    fn forget(&mut self, key: &CacheKey) {
+        if let Some((_, bytes)) = self.entries.remove(key) {
+            self.bytes -= bytes;
+        }
+    }
+
+    fn forget_ids(&mut self) {
+        let bytes = &mut self.bytes;
+        self.entries.retain(|key, (_, size)| {
+            let keep = matches!(key, CacheKey::Content(_));
+            if !keep {
+                *bytes -= *size;
+            }
+            keep
+        });
+    }
+
+    /// Drop least recently used entries until under budget, returning their keys
+    fn evict_over_budget(&mut self) -> Vec<CacheKey> {
+        let Some(budget) = self.budget else {
+            return Vec::new();
+        };
+        let mut victims = Vec::new();
+        while self.bytes > budget {
+            let Some(oldest) =
+                self.entries.iter().min_by_key(|(_, (last_used, _))| *last_used).map(|(k, _)| k)
+            else {
+                break;
+            };
+            let oldest = oldest.clone();
+            self.forget(&oldest);
+            self.evictions += 1;
+            victims.push(oldest);
+        }
+        victims
+    }
+}
+
+/// Rough heap footprint of a cached mesh
+fn estimated_bytes(mesh: &Mesh) -> usize {
+    mesh.vertices.len() * size_of::<Vertex>() + mesh.triangles.len() * size_of::<Triangle>()
+}
+
+/// Content address of a subtree evaluated under `transform`
+///
+/// Meshes bake in their accumulated transform, so it is part of the key.
+fn content_key(kind: &NodeKind, transform: &Matrix4<f64>) -> ContentKey {
+    let mut encoder = StructureEncoder::default();
+    kind.hash_structure(&mut encoder);
+    hash_f64s(transform.as_slice(), &mut encoder);
+    ContentKey {
+        hash: encoder.finish(),
+        structure: encoder.0.into(),
+    }
+}
+
+/// Walk two ASTs in lockstep, collecting IDs of primitive leaves whose parameters changed
+///
+/// Returns false if anything other than ID'd primitive parameters differs, since cached
+/// meshes bake in their ancestors' transforms and can't be patched in place.
+fn diff_parameters(old: &Node, new: &Node, changed: &mut Vec<NodeId>) -> bool {
+    if old.id != new.id || discriminant(&old.kind) != discriminant(&new.kind) {
+        return false;
+    }
+
+    match (&old.kind, &new.kind) {
+        (a, b) if a.is_primitive() => {
+            if a == b {
+                true
+            } else if let Some(id) = &new.id {
+                changed.push(id.clone());
+                true
+            } else {
+                false
+            }
+        }
+        (
+            NodeKind::Transform {
+                op: old_op,
+                children: old_children,
+            },
+            NodeKind::Transform {
+                op: new_op,
+                children: new_children,
+            },
+        ) => old_op == new_op && diff_children(old_children, new_children, changed),
+        (
+            NodeKind::Resize {
+                newsize: old_size,
+                auto: old_auto,
+                children: old_children,
+            },
+            NodeKind::Resize {
+                newsize: new_size,
+                auto: new_auto,
+                children: new_children,
+            },
+        ) => {
+            old_size == new_size
+                && old_auto == new_auto
+                && diff_children(old_children, new_children, changed)
+        }
+        (
+            NodeKind::Offset {
+                r: old_r,
+                delta: old_delta,
+                chamfer: old_chamfer,
+                fn_: old_fn,
+                children: old_children,
+            },
+            NodeKind::Offset {
+                r: new_r,
+                delta: new_delta,
+                chamfer: new_chamfer,
+                fn_: new_fn,
+                children: new_children,
+            },
+        ) => {
+            (old_r, old_delta, old_chamfer, old_fn) == (new_r, new_delta, new_chamfer, new_fn)
+                && diff_children(old_children, new_children, changed)
+        }
+        (
+            NodeKind::Projection {
+                cut: old_cut,
+                children: old_children,
+            },
+            NodeKind::Projection {
+                cut: new_cut,
+                children: new_children,
+            },
+        ) => old_cut == new_cut && diff_children(old_children, new_children, changed),
+        (
+            NodeKind::Color {
+                rgba: old_rgba,
+                children: old_children,
+            },
+            NodeKind::Color {
+                rgba: new_rgba,
+                children: new_children,
+            },
+        ) => old_rgba == new_rgba && diff_children(old_children, new_children, changed),
+        (
+            NodeKind::If {
+                condition: old_condition,
+                then_branch: old_then,
+                else_branch: old_else,
+            },
+            NodeKind::If {
+                condition: new_condition,
+                then_branch: new_then,
+                else_branch: new_else,
+            },
+        ) => {
+            old_condition == new_condition
+                && diff_children(old_then, new_then, changed)
+                && diff_children(old_else, new_else, changed)
+        }
+        (NodeKind::Union(a), NodeKind::Union(b))
+        | (NodeKind::Difference(a), NodeKind::Difference(b))
+        | (NodeKind::Intersection(a), NodeKind::Intersection(b)) => diff_children(a, b, changed),
+        (NodeKind::Echo(a), NodeKind::Echo(b)) => a == b,
+        (NodeKind::Empty, NodeKind::Empty) => true,
+        _ => false,
+    }
+}
+
+fn diff_children(old: &[Node], new: &[Node], changed: &mut Vec<NodeId>) -> bool {
+    old.len() == new.len()
+        && old
+            .iter()
+            .zip(new)
+            .all(|(a, b)| diff_parameters(a, b, changed))
+}
+
 impl Default for IncrementalEvaluator {
     fn default() -> Self {
         Self::new()
@@ -198,14 +763,237 @@ impl Default for IncrementalEvaluator {
 pub struct CacheStats {
     pub cached_nodes: usize,
     pub total_nodes: usize,
+    /// Node evaluations served from cache since the last edit
+    pub hits: usize,
+    /// Node evaluations that had to be computed since the last edit
+    pub misses: usize,
+    /// Estimated size of all cached meshes, counted once per cache holding them
+    pub bytes: usize,
+    /// Meshes evicted to stay within the memory budget, over the evaluator's lifetime
+    pub evictions: usize,
 }
 
 impl CacheStats {
+    /// Percentage of node evaluations served from cache since the last edit
     pub fn hit_rate(&self) -> f32 {
-        if self.total_nodes == 0 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
             0.0
         } else {
-            (self.cached_nodes as f32 / self.total_nodes as f32) * 100.0
+            (self.hits as f32 / lookups as f32) * 100.0
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{TransformOp, Vec3};
+
+    fn scene(sphere_fn: u32) -> Node {
+        let sphere = Node::with_id(NodeKind::Sphere { r: 5.0, fn_: sphere_fn }, "sphere".into());
+        let cube = Node::with_id(
+            NodeKind::Cube {
+                size: Vec3::new(4.0, 4.0, 4.0),
+                center: true,
+            },
+            "cube".into(),
+        );
+        let moved = Node::with_id(
+            NodeKind::Transform {
+                op: TransformOp::Translate(Vec3::new(8.0, 0.0, 0.0)),
+                children: vec![cube],
+            },
+            "moved".into(),
+        );
+        Node::with_id(NodeKind::Union(vec![sphere, moved]), "root".into())
+    }
+
+    #[test]
+    fn test_fn_change_retessellates_only_that_leaf() {
+        let original = scene(16);
+        let mut evaluator = IncrementalEvaluator::from_ast(&original);
+        evaluator.evaluate(&original).unwrap();
+
+        let edited = scene(32);
+        let impact = evaluator.apply_edit(&edited);
+        assert_eq!(impact, EditImpact::Leaves(vec!["sphere".to_string()]));
+
+        // The untouched cube subtree is still cached
+        assert!(evaluator.get_cached(&"moved".to_string()).is_some());
+        assert!(evaluator.get_cached(&"root".to_string()).is_none());
+
+        let mesh = evaluator.evaluate(&edited).unwrap();
+        assert_eq!(evaluator.tessellated_leaves(), vec!["sphere".to_string()]);
+
+        // Recombined result matches a from-scratch evaluation
+        let fresh = IncrementalEvaluator::from_ast(&edited).evaluate(&edited).unwrap();
+        assert_eq!(mesh.triangle_count(), fresh.triangle_count());
+    }
+
+    #[test]
+    fn test_progress_reaches_dependency_graph_total() {
+        let original = scene(16);
+        let total = DependencyGraph::from_ast(&original).all_nodes().len();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let record = {
+            let calls = Arc::clone(&calls);
+            move |progress: &EvalProgress| calls.lock().unwrap().push(progress.clone())
+        };
+
+        let mut evaluator = IncrementalEvaluator::from_ast(&original).with_progress(record.clone());
+        evaluator.evaluate(&original).unwrap();
+        {
+            let calls = calls.lock().unwrap();
+            let order: Vec<&str> = calls.iter().map(|p| p.node.as_str()).collect();
+            assert_eq!(order, ["sphere", "cube", "moved", "root"]);
+            assert!(calls.iter().enumerate().all(|(i, p)| p.completed == i + 1));
+            assert!(calls.iter().all(|p| p.total == total));
+        }
+
+        // Cached subtrees still count once the mesh is reused
+        calls.lock().unwrap().clear();
+        evaluator.apply_edit(&scene(32));
+        evaluator.evaluate(&scene(32)).unwrap();
+        assert_eq!(calls.lock().unwrap().last().unwrap().completed, total);
+
+        calls.lock().unwrap().clear();
+        crate::ast::Evaluator::new()
+            .with_progress(record)
+            .evaluate(&original)
+            .unwrap();
+        assert_eq!(calls.lock().unwrap().len(), total);
+    }
+
+    #[test]
+    fn test_structural_change_drops_cache() {
+        let original = scene(16);
+        let mut evaluator = IncrementalEvaluator::from_ast(&original);
+        evaluator.evaluate(&original).unwrap();
+
+        let mut edited = scene(16);
+        if let NodeKind::Union(children) = &mut edited.kind {
+            children.pop();
+        }
+
+        assert_eq!(evaluator.apply_edit(&edited), EditImpact::Full);
+        assert!(evaluator.get_cached(&"sphere".to_string()).is_none());
+
+        // The sphere's geometry is unchanged, so it comes from the content cache
+        evaluator.evaluate(&edited).unwrap();
+        assert!(evaluator.tessellated_leaves().is_empty());
+        assert_eq!(evaluator.apply_edit(&edited), EditImpact::Unchanged);
+    }
+
+    #[test]
+    fn test_unchanged_branches_hit_content_cache() {
+        let scene = |last_size: f64| {
+            let cubes = (0..16)
+                .map(|i| {
+                    let size = if i == 15 { last_size } else { 2.0 };
+                    Node::new(NodeKind::Transform {
+                        op: TransformOp::Translate(Vec3::new(i as f64 * 3.0, 0.0, 0.0)),
+                        children: vec![Node::new(NodeKind::Cube {
+                            size: Vec3::new(size, size, size),
+                            center: false,
+                        })],
+                    })
+                })
+                .collect();
+            Node::new(NodeKind::Union(cubes))
+        };
+
+        let original = scene(2.0);
+        let mut evaluator = IncrementalEvaluator::from_ast(&original);
+        evaluator.evaluate(&original).unwrap();
+        assert_eq!(evaluator.cache_stats().hits, 0);
+
+        // Anonymous leaves can't be patched by ID, so this is a full edit
+        let edited = scene(2.5);
+        assert_eq!(evaluator.apply_edit(&edited), EditImpact::Full);
+        let mesh = evaluator.evaluate(&edited).unwrap();
+
+        let stats = evaluator.cache_stats();
+        assert_eq!(stats.hits, 15);
+        assert!(stats.hit_rate() > 80.0, "hit rate {}", stats.hit_rate());
+
+        let fresh = IncrementalEvaluator::from_ast(&edited)
+            .evaluate(&edited)
+            .unwrap();
+        assert_eq!(mesh.triangle_count(), fresh.triangle_count());
+    }
+
+    #[test]
+    fn test_memory_budget_evicts_least_recently_used() {
+        let root = scene(16);
+        let unbounded = IncrementalEvaluator::from_ast(&root);
+        let expected = unbounded.evaluate(&root).unwrap();
+        let stats = unbounded.cache_stats();
+        assert_eq!(stats.evictions, 0);
+        assert!(stats.bytes > 0);
+
+        // Room for the translated cube in one cache but not both
+        let cube = unbounded.get_cached(&"moved".to_string()).unwrap();
+        let budget = estimated_bytes(&cube) * 3 / 2;
+        let mut evaluator = IncrementalEvaluator::from_ast(&root).with_memory_budget(budget);
+        let mesh = evaluator.evaluate(&root).unwrap();
+        let stats = evaluator.cache_stats();
+        assert!(stats.evictions > 0);
+        assert!(stats.bytes <= budget, "{} bytes cached", stats.bytes);
+        assert_eq!(mesh.triangle_count(), expected.triangle_count());
+        assert!(mesh.approx_eq(&expected, 1e-9));
+
+        // The sphere and the union never fit; the most recently used cube entry survives
+        assert!(evaluator.get_cached(&"moved".to_string()).is_some());
+        assert!(evaluator.get_cached(&"cube".to_string()).is_none());
+        assert!(evaluator.get_cached(&"sphere".to_string()).is_none());
+        assert!(evaluator.get_cached(&"root".to_string()).is_none());
+
+        // Evicted subtrees are recomputed on demand
+        evaluator.apply_edit(&root);
+        let again = evaluator.evaluate(&root).unwrap();
+        assert!(again.approx_eq(&expected, 1e-9));
+        assert_eq!(evaluator.tessellated_leaves(), vec!["sphere".to_string()]);
+    }
+
+    #[test]
+    fn test_edited_import_is_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("part.stl");
+        let write = |mesh: &Mesh| crate::io::export_stl(mesh, path.to_str().unwrap()).unwrap();
+        let root = Node::new(NodeKind::Import {
+            path: path.clone(),
+            convexity: 1,
+        });
+
+        write(&Primitive::cube(Vec3::new(1.0, 1.0, 1.0), false).to_mesh());
+        let evaluator = IncrementalEvaluator::new();
+        assert_eq!(evaluator.evaluate(&root).unwrap().triangle_count(), 12);
+
+        // Same path, different file: the cached cube must not be served
+        write(&Primitive::sphere(1.0, 8).to_mesh());
+        let sphere_triangles = Primitive::sphere(1.0, 8).to_mesh().triangle_count();
+        assert_eq!(evaluator.evaluate(&root).unwrap().triangle_count(), sphere_triangles);
+    }
+
+    #[test]
+    fn test_content_keys_compare_whole_structure() {
+        let cube = |size: f64| NodeKind::Cube {
+            size: Vec3::new(size, size, size),
+            center: false,
+        };
+        let identity = Matrix4::identity();
+        let small = content_key(&cube(1.0), &identity);
+        assert_eq!(small, content_key(&cube(1.0), &identity));
+
+        // A colliding hash alone doesn't make another subtree's entry a hit
+        let large = ContentKey {
+            hash: small.hash,
+            ..content_key(&cube(2.0), &identity)
+        };
+        assert_ne!(small, large);
+        let cache = DashMap::new();
+        cache.insert(small, Arc::new(Primitive::cube(Vec3::new(1.0, 1.0, 1.0), false).to_mesh()));
+        assert!(cache.get(&large).is_none());
+    }
+}
@@ -10,9 +10,13 @@ mod evaluator;
 mod incremental_evaluator;
 mod node;
 mod parallel_evaluator;
+mod progress;
+mod shape_evaluator;
 
 pub use dependency_graph::{DependencyGraph, NodeId};
-pub use evaluator::Evaluator;
-pub use incremental_evaluator::{CacheStats, IncrementalEvaluator, MeshCache};
-pub use node::{Node, NodeKind, TransformOp, Vec3};
+pub use evaluator::{axis_angle_matrix, flatten_transforms, rotation_matrix, Cancelled, Evaluator};
+pub use incremental_evaluator::{CacheStats, EditImpact, IncrementalEvaluator, MeshCache};
+pub use node::{Node, NodeKind, Span, TransformOp, Vec2, Vec3};
 pub use parallel_evaluator::ParallelEvaluator;
+pub use progress::{EvalProgress, ProgressCallback};
+pub use shape_evaluator::evaluate_shape;
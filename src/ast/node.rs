@@ -4,29 +4,65 @@
 //! AST Node definitions
 
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+use std::path::PathBuf;
 
 /// 3D Vector type alias
 pub type Vec3 = nalgebra::Vector3<f64>;
 
+/// 2D Vector type alias
+pub type Vec2 = nalgebra::Vector2<f64>;
+
+/// Source location of the construct that produced a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset one past the last character
+    pub end: usize,
+    /// 1-based line of `start`
+    pub line: usize,
+    /// 1-based column of `start`
+    pub col: usize,
+}
+
 /// AST Node representing a single operation or primitive
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Node {
     pub kind: NodeKind,
     pub id: Option<String>,
+    /// Source span, only populated when parsing with spans enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
 impl Node {
     pub fn new(kind: NodeKind) -> Self {
-        Self { kind, id: None }
+        Self {
+            kind,
+            id: None,
+            span: None,
+        }
     }
 
     pub fn with_id(kind: NodeKind, id: String) -> Self {
-        Self { kind, id: Some(id) }
+        Self {
+            kind,
+            id: Some(id),
+            span: None,
+        }
+    }
+
+    /// Attach a source span to this node
+    pub fn with_span(mut self, span: Option<Span>) -> Self {
+        self.span = span;
+        self
     }
 }
 
 /// Types of AST nodes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeKind {
     // Primitives
     Cube {
@@ -48,6 +84,43 @@ pub enum NodeKind {
         r2: f64,
         fn_: u32,
     },
+    /// Ring around the Z axis (Polyframe extension, not in OpenSCAD)
+    Torus {
+        major_r: f64,
+        minor_r: f64,
+        major_fn: u32,
+        minor_fn: u32,
+    },
+    /// Solid from explicit `points` and `faces` of point indices, each face clockwise seen
+    /// from outside as in OpenSCAD; `convexity` is kept for round-tripping
+    Polyhedron {
+        points: Vec<nalgebra::Point3<f64>>,
+        faces: Vec<Vec<usize>>,
+        convexity: u32,
+    },
+
+    // 2D primitives, drawn as flat shapes on z=0 unless a 2D operation consumes them
+    Square {
+        size: Vec2,
+        center: bool,
+    },
+    Circle {
+        r: f64,
+        fn_: u32,
+    },
+    /// Simple polygon through `points` in order, either winding
+    Polygon {
+        points: Vec<Vec2>,
+    },
+
+    /// Mesh read from an STL or OBJ file when evaluated
+    ///
+    /// `path` is absolute, resolved when parsing. `convexity` only affects OpenSCAD's
+    /// preview and is kept for round-tripping.
+    Import {
+        path: PathBuf,
+        convexity: u32,
+    },
 
     // Boolean operations
     Union(Vec<Node>),
@@ -60,11 +133,90 @@ pub enum NodeKind {
         children: Vec<Node>,
     },
 
+    /// Scales its children to fit `newsize`, as computed by
+    /// [`Mesh::resize_matrix`](crate::geometry::Mesh::resize_matrix)
+    Resize {
+        newsize: Vec3,
+        auto: [bool; 3],
+        children: Vec<Node>,
+    },
+
+    /// Grows (positive) or shrinks (negative) its 2D children
+    ///
+    /// A nonzero `r` rounds the corners that open up, with `fn_` fragments per full turn;
+    /// otherwise edges move by `delta` and corners are mitered, or cut off with `chamfer`.
+    Offset {
+        r: f64,
+        delta: f64,
+        chamfer: bool,
+        fn_: u32,
+        children: Vec<Node>,
+    },
+
+    /// Flattens its 3D children into a 2D outline seen from above, or with `cut` into
+    /// their cross-section through z=0
+    Projection {
+        cut: bool,
+        children: Vec<Node>,
+    },
+
+    // Appearance
+    /// Tags the vertices of its children with an RGBA color; CSG that rebuilds
+    /// vertices (e.g. cut surfaces) leaves them uncolored
+    Color {
+        rgba: [f32; 4],
+        children: Vec<Node>,
+    },
+
+    /// `if`/`else`; the condition is evaluated against the enclosing scope when parsing
+    ///
+    /// Only the selected branch is expanded, so the other one is always empty.
+    If {
+        condition: bool,
+        then_branch: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+
+    /// `echo()`, with each argument already formatted as OpenSCAD prints it
+    ///
+    /// Arguments are evaluated when parsing, like `if` conditions. Evaluators print the
+    /// message and produce no geometry.
+    Echo(Vec<String>),
+
     // Empty node
     Empty,
 }
 
 impl NodeKind {
+    /// Whether this node is a primitive leaf
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            NodeKind::Cube { .. }
+                | NodeKind::Sphere { .. }
+                | NodeKind::Cylinder { .. }
+                | NodeKind::Cone { .. }
+                | NodeKind::Torus { .. }
+                | NodeKind::Polyhedron { .. }
+                | NodeKind::Square { .. }
+                | NodeKind::Circle { .. }
+                | NodeKind::Polygon { .. }
+                | NodeKind::Import { .. }
+        )
+    }
+
+    /// Whether this node produces a 2D shape rather than a solid
+    pub fn is_2d(&self) -> bool {
+        matches!(
+            self,
+            NodeKind::Square { .. }
+                | NodeKind::Circle { .. }
+                | NodeKind::Polygon { .. }
+                | NodeKind::Offset { .. }
+                | NodeKind::Projection { .. }
+        )
+    }
+
     /// Get child nodes for dependency tracking
     pub fn get_children(&self) -> Vec<&Node> {
         match self {
@@ -72,16 +224,163 @@ impl NodeKind {
             NodeKind::Difference(children) => children.iter().collect(),
             NodeKind::Intersection(children) => children.iter().collect(),
             NodeKind::Transform { children, .. } => children.iter().collect(),
+            NodeKind::Resize { children, .. } => children.iter().collect(),
+            NodeKind::Offset { children, .. } => children.iter().collect(),
+            NodeKind::Projection { children, .. } => children.iter().collect(),
+            NodeKind::Color { children, .. } => children.iter().collect(),
+            NodeKind::If {
+                then_branch,
+                else_branch,
+                ..
+            } => then_branch.iter().chain(else_branch).collect(),
             _ => Vec::new(),
         }
     }
+
+    /// Feed the geometry of this subtree into `state`
+    ///
+    /// Node IDs and source spans are ignored, so two subtrees hash equal exactly when they
+    /// describe the same geometry and can share a cached mesh. Imports also feed in the size
+    /// and modification time of their file, so editing it changes the hash.
+    pub fn hash_structure<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+        match self {
+            NodeKind::Cube { size, center } => {
+                hash_f64s(size.as_slice(), state);
+                center.hash(state);
+            }
+            NodeKind::Sphere { r, fn_ } => {
+                hash_f64s(&[*r], state);
+                fn_.hash(state);
+            }
+            NodeKind::Cylinder { h, r, fn_ } => {
+                hash_f64s(&[*h, *r], state);
+                fn_.hash(state);
+            }
+            NodeKind::Cone { h, r1, r2, fn_ } => {
+                hash_f64s(&[*h, *r1, *r2], state);
+                fn_.hash(state);
+            }
+            NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            } => {
+                hash_f64s(&[*major_r, *minor_r], state);
+                major_fn.hash(state);
+                minor_fn.hash(state);
+            }
+            NodeKind::Polyhedron {
+                points,
+                faces,
+                convexity,
+            } => {
+                points.len().hash(state);
+                for point in points {
+                    hash_f64s(point.coords.as_slice(), state);
+                }
+                faces.hash(state);
+                convexity.hash(state);
+            }
+            NodeKind::Square { size, center } => {
+                hash_f64s(size.as_slice(), state);
+                center.hash(state);
+            }
+            NodeKind::Circle { r, fn_ } => {
+                hash_f64s(&[*r], state);
+                fn_.hash(state);
+            }
+            NodeKind::Polygon { points } => {
+                points.len().hash(state);
+                for point in points {
+                    hash_f64s(point.as_slice(), state);
+                }
+            }
+            NodeKind::Import { path, convexity } => {
+                path.hash(state);
+                convexity.hash(state);
+                // The file can be rewritten under the same path, so its size and modification
+                // time count as part of the geometry
+                let stamp = std::fs::metadata(path).ok().map(|m| (m.len(), m.modified().ok()));
+                stamp.hash(state);
+            }
+            NodeKind::Union(children)
+            | NodeKind::Difference(children)
+            | NodeKind::Intersection(children) => hash_children(children, state),
+            NodeKind::Transform { op, children } => {
+                hash_f64s(op.to_matrix().as_slice(), state);
+                hash_children(children, state);
+            }
+            NodeKind::Resize {
+                newsize,
+                auto,
+                children,
+            } => {
+                hash_f64s(newsize.as_slice(), state);
+                auto.hash(state);
+                hash_children(children, state);
+            }
+            NodeKind::Offset {
+                r,
+                delta,
+                chamfer,
+                fn_,
+                children,
+            } => {
+                hash_f64s(&[*r, *delta], state);
+                chamfer.hash(state);
+                fn_.hash(state);
+                hash_children(children, state);
+            }
+            NodeKind::Projection { cut, children } => {
+                cut.hash(state);
+                hash_children(children, state);
+            }
+            NodeKind::Color { rgba, children } => {
+                hash_f64s(&rgba.map(f64::from), state);
+                hash_children(children, state);
+            }
+            NodeKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                condition.hash(state);
+                hash_children(then_branch, state);
+                hash_children(else_branch, state);
+            }
+            NodeKind::Echo(args) => args.hash(state),
+            NodeKind::Empty => {}
+        }
+    }
+}
+
+/// Hash floats by bit pattern, treating `-0.0` and `0.0` as the same value
+pub(crate) fn hash_f64s<H: Hasher>(values: &[f64], state: &mut H) {
+    for value in values {
+        (value + 0.0).to_bits().hash(state);
+    }
+}
+
+fn hash_children<H: Hasher>(children: &[Node], state: &mut H) {
+    children.len().hash(state);
+    for child in children {
+        child.kind.hash_structure(state);
+    }
 }
 
 /// Transformation operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransformOp {
     Translate(Vec3),
+    /// Euler angles in degrees, applied in OpenSCAD's X, Y, Z order
     Rotate(Vec3),
+    /// `rotate(a, v)`: `angle` degrees about `axis`
+    RotateAxis {
+        angle: f64,
+        axis: Vec3,
+    },
     Scale(Vec3),
     Mirror(Vec3),
     Multmatrix(nalgebra::Matrix4<f64>),
@@ -90,16 +389,13 @@ pub enum TransformOp {
 impl TransformOp {
     /// Convert transformation to a 4x4 matrix
     pub fn to_matrix(&self) -> nalgebra::Matrix4<f64> {
-        use nalgebra::{Matrix4, UnitQuaternion, Vector3};
+        use super::evaluator::{axis_angle_matrix, rotation_matrix};
+        use nalgebra::Matrix4;
 
         match self {
             TransformOp::Translate(v) => Matrix4::new_translation(v),
-            TransformOp::Rotate(angles) => {
-                let rx = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), angles.x.to_radians());
-                let ry = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), angles.y.to_radians());
-                let rz = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), angles.z.to_radians());
-                (rz * ry * rx).to_homogeneous()
-            }
+            TransformOp::Rotate(angles) => rotation_matrix(*angles),
+            TransformOp::RotateAxis { angle, axis } => axis_angle_matrix(*angle, *axis),
             TransformOp::Scale(s) => Matrix4::new_nonuniform_scaling(s),
             TransformOp::Mirror(axis) => {
                 let mut m = Matrix4::identity();
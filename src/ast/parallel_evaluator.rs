@@ -2,23 +2,216 @@
 // Copyright (c) 2025 Polyframe Inc.
 
 //! Parallel AST evaluator using rayon
+//!
+//! The tree is flattened into a graph of tasks, one per node that produces or combines
+//! meshes. Tasks whose children are all complete are ready, and a rayon scope drains them:
+//! each finished task readies its parent once the parent's last child is done, so
+//! independent subtrees anywhere in the tree are evaluated concurrently.
 
-use super::{Node, NodeKind};
+use super::evaluator::{check_cancelled, echo, is_operand};
+use super::{evaluate_shape, flatten_transforms, Node, NodeKind};
 use crate::geometry::{BooleanOp, Mesh, ParallelBooleanExecutor, Primitive};
-use anyhow::Result;
+use anyhow::{Error, Result};
 use nalgebra::Matrix4;
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 /// Parallel evaluator for AST
 pub struct ParallelEvaluator;
 
+/// One node of the task graph, with the transform accumulated from its ancestors
+struct Task<'a> {
+    kind: &'a NodeKind,
+    transform: Matrix4<f64>,
+    parent: Option<usize>,
+    /// Tasks whose meshes this one combines, in source order; empty for leaves
+    children: Vec<usize>,
+}
+
+/// Task graph of a tree, with the state shared by the workers draining it
+struct Schedule<'a> {
+    cancel: Option<&'a AtomicBool>,
+    tasks: Vec<Task<'a>>,
+    /// Children of each task not yet complete; the task is ready at zero
+    pending: Vec<AtomicUsize>,
+    results: Vec<Mutex<Option<Mesh>>>,
+    /// First error raised; once set, no further tasks start
+    error: Mutex<Option<Error>>,
+    failed: AtomicBool,
+}
+
+impl<'a> Schedule<'a> {
+    fn new(cancel: Option<&'a AtomicBool>, root: &'a NodeKind) -> Self {
+        let mut tasks = Vec::new();
+        Self::add_task(&mut tasks, root, Matrix4::identity(), None);
+        Self {
+            cancel,
+            pending: tasks.iter().map(|t| AtomicUsize::new(t.children.len())).collect(),
+            results: tasks.iter().map(|_| Mutex::new(None)).collect(),
+            tasks,
+            error: Mutex::new(None),
+            failed: AtomicBool::new(false),
+        }
+    }
+
+    /// Add the task for `kind` and, below it, the tasks for its children
+    fn add_task(
+        tasks: &mut Vec<Task<'a>>,
+        kind: &'a NodeKind,
+        transform: Matrix4<f64>,
+        parent: Option<usize>,
+    ) -> usize {
+        // Children evaluated in the node's frame, mirroring `ParallelEvaluator::evaluate_node`
+        let (children, child_transform): (&'a [Node], Matrix4<f64>) = match kind {
+            NodeKind::Union(children)
+            | NodeKind::Difference(children)
+            | NodeKind::Intersection(children) => (children, transform),
+            NodeKind::Transform { op, children } => {
+                let new_transform = transform * op.to_matrix();
+                if let [child] = children.as_slice() {
+                    let (chain, leaf) = flatten_transforms(child);
+                    return Self::add_task(tasks, &leaf.kind, new_transform * chain, parent);
+                }
+                (children, new_transform)
+            }
+            NodeKind::Resize { children, .. } => (children, Matrix4::identity()),
+            NodeKind::Color { children, .. } => (children, transform),
+            NodeKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => (if *condition { then_branch } else { else_branch }, transform),
+            _ => (&[], transform),
+        };
+
+        let index = tasks.len();
+        tasks.push(Task {
+            kind,
+            transform,
+            parent,
+            children: Vec::new(),
+        });
+        let child_tasks = children
+            .iter()
+            .map(|child| Self::add_task(tasks, &child.kind, child_transform, Some(index)))
+            .collect();
+        tasks[index].children = child_tasks;
+        index
+    }
+
+    /// Evaluate every task, leaves first, and return the root's mesh
+    fn run(self) -> Result<Mesh> {
+        rayon::scope(|scope| {
+            for (index, task) in self.tasks.iter().enumerate() {
+                if task.children.is_empty() {
+                    let this = &self;
+                    scope.spawn(move |scope| this.execute(scope, index));
+                }
+            }
+        });
+
+        if let Some(error) = self.error.into_inner().unwrap() {
+            return Err(error);
+        }
+        Ok(self.results[0].lock().unwrap().take().unwrap_or_else(Mesh::empty))
+    }
+
+    /// Evaluate a ready task, then spawn its parent if this was the last child it waited on
+    fn execute<'s>(&'s self, scope: &rayon::Scope<'s>, index: usize) {
+        if self.failed.load(Ordering::Relaxed) {
+            return;
+        }
+
+        match self.evaluate_task(index) {
+            Ok(mesh) => *self.results[index].lock().unwrap() = Some(mesh),
+            Err(error) => {
+                self.error.lock().unwrap().get_or_insert(error);
+                self.failed.store(true, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        if let Some(parent) = self.tasks[index].parent {
+            if self.pending[parent].fetch_sub(1, Ordering::AcqRel) == 1 {
+                scope.spawn(move |scope| self.execute(scope, parent));
+            }
+        }
+    }
+
+    fn evaluate_task(&self, index: usize) -> Result<Mesh> {
+        check_cancelled(self.cancel)?;
+        let task = &self.tasks[index];
+        if task.children.is_empty() && !Self::combines(task.kind) {
+            return ParallelEvaluator::evaluate_node(self.cancel, task.kind, &task.transform);
+        }
+
+        let meshes: Vec<Mesh> = task
+            .children
+            .iter()
+            .filter(|&&child| is_operand(self.tasks[child].kind))
+            .map(|&child| self.results[child].lock().unwrap().take().unwrap_or_else(Mesh::empty))
+            .collect();
+        check_cancelled(self.cancel)?;
+
+        match task.kind {
+            NodeKind::Difference(_) => ParallelEvaluator::combine(meshes, BooleanOp::Difference),
+            NodeKind::Intersection(_) => {
+                ParallelEvaluator::combine(meshes, BooleanOp::Intersection)
+            }
+            NodeKind::Resize { newsize, auto, .. } => {
+                let mut mesh = ParallelEvaluator::combine(meshes, BooleanOp::Union)?;
+                mesh.transform(&(task.transform * mesh.resize_matrix(newsize, *auto)));
+                Ok(mesh)
+            }
+            NodeKind::Color { rgba, .. } => {
+                let mut mesh = ParallelEvaluator::combine(meshes, BooleanOp::Union)?;
+                mesh.apply_color(*rgba);
+                Ok(mesh)
+            }
+            _ => ParallelEvaluator::combine(meshes, BooleanOp::Union),
+        }
+    }
+
+    /// Whether the task for `kind` combines its children's meshes rather than making its own
+    fn combines(kind: &NodeKind) -> bool {
+        matches!(
+            kind,
+            NodeKind::Union(_)
+                | NodeKind::Difference(_)
+                | NodeKind::Intersection(_)
+                | NodeKind::Transform { .. }
+                | NodeKind::Resize { .. }
+                | NodeKind::Color { .. }
+                | NodeKind::If { .. }
+        )
+    }
+}
+
 impl ParallelEvaluator {
     /// Evaluate AST with parallel processing
+    ///
+    /// Independent subtrees are scheduled concurrently; see the module documentation.
     pub fn evaluate(node: &Node) -> Result<Mesh> {
-        Self::evaluate_node(&node.kind, &Matrix4::identity())
+        Schedule::new(None, &node.kind).run()
+    }
+
+    /// Evaluate AST with parallel processing, stopping with [`Cancelled`](super::Cancelled)
+    /// soon after `cancel` is set
+    ///
+    /// The flag is checked before each node and before children are combined; a combine
+    /// step already running finishes first.
+    pub fn evaluate_with_cancellation(node: &Node, cancel: &AtomicBool) -> Result<Mesh> {
+        Schedule::new(Some(cancel), &node.kind).run()
     }
 
-    fn evaluate_node(kind: &NodeKind, transform: &Matrix4<f64>) -> Result<Mesh> {
+    fn evaluate_node(
+        cancel: Option<&AtomicBool>,
+        kind: &NodeKind,
+        transform: &Matrix4<f64>,
+    ) -> Result<Mesh> {
+        check_cancelled(cancel)?;
+
         match kind {
             NodeKind::Cube { size, center } => {
                 let mut mesh = Primitive::cube(*size, *center).to_mesh();
@@ -44,33 +237,124 @@ impl ParallelEvaluator {
                 Ok(mesh)
             }
 
+            NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            } => {
+                let mut mesh = Primitive::torus(*major_r, *minor_r, *major_fn, *minor_fn).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Polyhedron { points, faces, .. } => {
+                let mut mesh = Primitive::polyhedron(points.clone(), faces.clone()).to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Import { path, .. } => {
+                let mut mesh = crate::io::import_mesh(path)?;
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
+            NodeKind::Square { .. }
+            | NodeKind::Circle { .. }
+            | NodeKind::Polygon { .. }
+            | NodeKind::Offset { .. }
+            | NodeKind::Projection { .. } => {
+                let solid = |children: &[Node]| {
+                    Self::evaluate_parallel_boolean(
+                        cancel,
+                        children,
+                        &Matrix4::identity(),
+                        BooleanOp::Union,
+                    )
+                };
+                let mut mesh = evaluate_shape(kind, &solid)?.to_mesh();
+                mesh.transform(transform);
+                Ok(mesh)
+            }
+
             NodeKind::Union(children) => {
-                Self::evaluate_parallel_boolean(children, transform, BooleanOp::Union)
+                Self::evaluate_parallel_boolean(cancel, children, transform, BooleanOp::Union)
             }
 
             NodeKind::Difference(children) => {
-                Self::evaluate_parallel_boolean(children, transform, BooleanOp::Difference)
+                Self::evaluate_parallel_boolean(cancel, children, transform, BooleanOp::Difference)
             }
 
-            NodeKind::Intersection(children) => {
-                Self::evaluate_parallel_boolean(children, transform, BooleanOp::Intersection)
-            }
+            NodeKind::Intersection(children) => Self::evaluate_parallel_boolean(
+                cancel,
+                children,
+                transform,
+                BooleanOp::Intersection,
+            ),
 
             NodeKind::Transform { op, children } => {
                 let new_transform = transform * op.to_matrix();
 
                 if children.len() == 1 {
-                    Self::evaluate_node(&children[0].kind, &new_transform)
+                    let (chain, leaf) = flatten_transforms(&children[0]);
+                    Self::evaluate_node(cancel, &leaf.kind, &(new_transform * chain))
                 } else {
-                    Self::evaluate_parallel_boolean(children, &new_transform, BooleanOp::Union)
+                    Self::evaluate_parallel_boolean(
+                        cancel,
+                        children,
+                        &new_transform,
+                        BooleanOp::Union,
+                    )
                 }
             }
 
+            NodeKind::Resize {
+                newsize,
+                auto,
+                children,
+            } => {
+                // Fit the children in the resize's own frame, then place the result
+                let identity = Matrix4::identity();
+                let mut mesh = if children.len() == 1 {
+                    Self::evaluate_node(cancel, &children[0].kind, &identity)?
+                } else {
+                    Self::evaluate_parallel_boolean(cancel, children, &identity, BooleanOp::Union)?
+                };
+                mesh.transform(&(transform * mesh.resize_matrix(newsize, *auto)));
+                Ok(mesh)
+            }
+
+            NodeKind::Color { rgba, children } => {
+                let mut mesh = if children.len() == 1 {
+                    Self::evaluate_node(cancel, &children[0].kind, transform)?
+                } else {
+                    Self::evaluate_parallel_boolean(cancel, children, transform, BooleanOp::Union)?
+                };
+                mesh.apply_color(*rgba);
+                Ok(mesh)
+            }
+
+            NodeKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if *condition { then_branch } else { else_branch };
+                Self::evaluate_parallel_boolean(cancel, branch, transform, BooleanOp::Union)
+            }
+
+            NodeKind::Echo(args) => {
+                echo(args);
+                Ok(Mesh::empty())
+            }
+
             NodeKind::Empty => Ok(Mesh::empty()),
         }
     }
 
     fn evaluate_parallel_boolean(
+        cancel: Option<&AtomicBool>,
         children: &[Node],
         transform: &Matrix4<f64>,
         op: BooleanOp,
@@ -82,16 +366,26 @@ impl ParallelEvaluator {
         // Evaluate children in parallel
         let meshes: Result<Vec<Mesh>> = children
             .par_iter()
-            .map(|child| Self::evaluate_node(&child.kind, transform))
+            .map(|child| Self::evaluate_node(cancel, &child.kind, transform))
             .collect();
 
-        let meshes = meshes?;
+        let meshes = meshes?
+            .into_iter()
+            .zip(children)
+            .filter(|(_, child)| is_operand(&child.kind))
+            .map(|(mesh, _)| mesh)
+            .collect();
+        check_cancelled(cancel)?;
+        Self::combine(meshes, op)
+    }
 
-        // Combine results based on operation
+    /// Combine evaluated children according to `op`
+    fn combine(meshes: Vec<Mesh>, op: BooleanOp) -> Result<Mesh> {
         match op {
             BooleanOp::Union => ParallelBooleanExecutor::union_parallel(meshes),
             BooleanOp::Difference => ParallelBooleanExecutor::difference_parallel(meshes),
             BooleanOp::Intersection => ParallelBooleanExecutor::intersection_parallel(meshes),
+            BooleanOp::SymmetricDifference => ParallelBooleanExecutor::execute_parallel(meshes, op),
         }
     }
 }
@@ -128,4 +422,82 @@ mod tests {
         let mesh = ParallelEvaluator::evaluate(&root).unwrap();
         assert!(mesh.vertex_count() > 0);
     }
+
+    #[test]
+    fn test_wide_union_matches_serial_evaluator() {
+        use crate::ast::{Evaluator, TransformOp};
+
+        // Independent subtrees, each with its own boolean, side by side under one union
+        let subtree = |i: usize| {
+            let block = Node::new(NodeKind::Difference(vec![
+                Node::new(NodeKind::Cube {
+                    size: Vec3::new(10.0, 10.0, 10.0),
+                    center: true,
+                }),
+                Node::new(NodeKind::Transform {
+                    op: TransformOp::Translate(Vec3::new(0.0, 0.0, 5.0)),
+                    children: vec![Node::new(NodeKind::Cube {
+                        size: Vec3::new(4.0, 4.0, 4.0),
+                        center: true,
+                    })],
+                }),
+            ]));
+            Node::new(NodeKind::Transform {
+                op: TransformOp::Translate(Vec3::new(20.0 * i as f64, 0.0, 0.0)),
+                children: vec![Node::new(NodeKind::Color {
+                    rgba: [0.1 * i as f32, 0.5, 0.5, 1.0],
+                    children: vec![block],
+                })],
+            })
+        };
+        let root = Node::new(NodeKind::Union((0..6).map(subtree).collect()));
+
+        let parallel = ParallelEvaluator::evaluate(&root).unwrap();
+        let serial = Evaluator::new().evaluate(&root).unwrap();
+        assert!(parallel.triangle_count() > 6 * 12);
+        assert_eq!(parallel.triangle_count(), serial.triangle_count());
+
+        // Vertex order depends on how the union was reduced, so compare triangles as
+        // position triples, each rotated to start at its smallest corner to keep its winding
+        let triangles = |mesh: &Mesh| {
+            let mut triangles: Vec<[[f64; 3]; 3]> = mesh
+                .triangles
+                .iter()
+                .map(|t| {
+                    let mut corners = t.indices.map(|i| {
+                        let p = mesh.vertices[i].position;
+                        [p.x, p.y, p.z]
+                    });
+                    let first = (0..3)
+                        .min_by(|&a, &b| corners[a].partial_cmp(&corners[b]).unwrap())
+                        .unwrap();
+                    corners.rotate_left(first);
+                    corners
+                })
+                .collect();
+            triangles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            triangles
+        };
+        for (a, b) in triangles(&parallel).iter().zip(&triangles(&serial)) {
+            for (p, q) in a.iter().zip(b) {
+                assert!(p.iter().zip(q).all(|(x, y)| (x - y).abs() < 1e-6), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_evaluate_reports_errors() {
+        let missing = Node::new(NodeKind::Import {
+            path: std::env::temp_dir().join("polyframe-missing-import.stl"),
+            convexity: 1,
+        });
+        let cube = Node::new(NodeKind::Cube {
+            size: Vec3::new(1.0, 1.0, 1.0),
+            center: false,
+        });
+        let root = Node::new(NodeKind::Union(vec![cube, missing]));
+
+        let error = ParallelEvaluator::evaluate(&root).unwrap_err();
+        assert!(error.to_string().contains("not found"), "{}", error);
+    }
 }
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Progress reporting for evaluators
+
+use super::{DependencyGraph, Node, NodeId};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Progress of an evaluation, passed to a [`ProgressCallback`]
+///
+/// Only nodes with an ID are counted, the same set a [`DependencyGraph`] tracks, so
+/// `completed` reaches `total` when the evaluation finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalProgress {
+    pub completed: usize,
+    pub total: usize,
+    /// Node whose mesh was just finished
+    pub node: NodeId,
+}
+
+/// Called from the evaluating thread each time a node with an ID finishes
+pub type ProgressCallback = Arc<dyn Fn(&EvalProgress) + Send + Sync>;
+
+/// Counts finished nodes for one evaluation and forwards them to a callback
+pub(crate) struct ProgressTracker {
+    callback: ProgressCallback,
+    total: AtomicUsize,
+    completed: AtomicUsize,
+    finished: Mutex<HashSet<NodeId>>,
+}
+
+impl ProgressTracker {
+    pub(crate) fn new(callback: ProgressCallback) -> Self {
+        Self {
+            callback,
+            total: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            finished: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Reset the counts for an evaluation of `root`
+    pub(crate) fn start(&self, root: &Node) {
+        let total = DependencyGraph::from_ast(root).all_nodes().len();
+        self.total.store(total, Ordering::Relaxed);
+        self.completed.store(0, Ordering::Relaxed);
+        self.finished.lock().unwrap().clear();
+    }
+
+    /// Mark `node` and every ID'd node below it finished, children first
+    ///
+    /// Nodes already reported are skipped. Evaluators call this when a subtree's mesh is
+    /// ready, which also covers subtrees served from a cache and transform chains that were
+    /// folded into their leaf.
+    pub(crate) fn finish_subtree(&self, node: &Node) {
+        for child in node.kind.get_children() {
+            self.finish_subtree(child);
+        }
+        let Some(id) = &node.id else {
+            return;
+        };
+        if !self.finished.lock().unwrap().insert(id.clone()) {
+            return;
+        }
+
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        (self.callback)(&EvalProgress {
+            completed,
+            total: self.total.load(Ordering::Relaxed),
+            node: id.clone(),
+        });
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Evaluation of 2D subtrees into planar shapes
+
+use super::evaluator::echo;
+use super::{Node, NodeKind};
+use crate::geometry::{Mesh, Shape2D};
+use anyhow::Result;
+
+/// Evaluate a 2D node into a [`Shape2D`]
+///
+/// Unions, transforms, colors, and conditionals pass their 2D children through. Solids and
+/// 2D differences or intersections are skipped with a warning, as OpenSCAD ignores 3D
+/// children of 2D operations.
+///
+/// `solid` evaluates the 3D children of `projection()`, so each evaluator can supply its own
+/// (cached or parallel) mesh evaluation.
+pub fn evaluate_shape(kind: &NodeKind, solid: &dyn Fn(&[Node]) -> Result<Mesh>) -> Result<Shape2D> {
+    match kind {
+        NodeKind::Square { size, center } => Ok(Shape2D::square(*size, *center)),
+        NodeKind::Circle { r, fn_ } => Ok(Shape2D::circle(*r, *fn_)),
+        NodeKind::Polygon { points } => Ok(Shape2D::polygon(points)),
+        NodeKind::Offset {
+            r,
+            delta,
+            chamfer,
+            fn_,
+            children,
+        } => {
+            let shape = evaluate_shapes(children, solid)?;
+            Ok(if *r != 0.0 {
+                shape.offset_round(*r, *fn_)
+            } else {
+                shape.offset_delta(*delta, *chamfer)
+            })
+        }
+        NodeKind::Projection { cut, children } => {
+            let mesh = solid(children)?;
+            Ok(if *cut {
+                Shape2D::cross_section(&mesh)
+            } else {
+                Shape2D::projection(&mesh)
+            })
+        }
+        NodeKind::Transform { op, children } => {
+            let mut shape = evaluate_shapes(children, solid)?;
+            shape.transform(&op.to_matrix());
+            Ok(shape)
+        }
+        NodeKind::Union(children) | NodeKind::Color { children, .. } => {
+            evaluate_shapes(children, solid)
+        }
+        NodeKind::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => evaluate_shapes(if *condition { then_branch } else { else_branch }, solid),
+        NodeKind::Echo(args) => {
+            echo(args);
+            Ok(Shape2D::new())
+        }
+        NodeKind::Empty => Ok(Shape2D::new()),
+        _ => {
+            eprintln!("Warning: Ignoring non-2D object in a 2D operation");
+            Ok(Shape2D::new())
+        }
+    }
+}
+
+/// Evaluate each child and keep all of their contours
+fn evaluate_shapes(children: &[Node], solid: &dyn Fn(&[Node]) -> Result<Mesh>) -> Result<Shape2D> {
+    let mut shape = Shape2D::new();
+    for child in children {
+        shape.extend(evaluate_shape(&child.kind, solid)?);
+    }
+    Ok(shape)
+}
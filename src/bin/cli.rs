@@ -3,10 +3,14 @@
 
 //! Polyframe Kernel CLI
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use clap::{Parser, Subcommand};
+use polyframe::evaluation::run_with_timeout;
 use polyframe::io;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "polyframe-kernel")]
@@ -23,13 +27,17 @@ struct Cli {
     #[arg(short, long, value_name = "FILE")]
     output: Option<String>,
 
-    /// Output format (stl, 3mf, gltf)
+    /// Output format (stl, 3mf, gltf, ply, obj)
     #[arg(short, long, default_value = "stl")]
     format: String,
 
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Write the output even if the mesh fails export validation
+    #[arg(long, global = true)]
+    force: bool,
 }
 
 #[derive(Subcommand)]
@@ -40,13 +48,17 @@ enum Commands {
         input: String,
 
         /// Output file
-        #[arg(short, long)]
-        output: String,
+        #[arg(short, long, required_unless_present = "dry_run")]
+        output: Option<String>,
 
         /// Output format
         #[arg(short, long, default_value = "stl")]
         format: String,
 
+        /// Parse, evaluate, and check the design, then report stats without writing output
+        #[arg(long)]
+        dry_run: bool,
+
         /// Lazy rendering mode (defer rendering until explicitly requested)
         #[arg(long)]
         lazy: bool,
@@ -58,6 +70,10 @@ enum Commands {
         /// Use incremental evaluation
         #[arg(long)]
         incremental: bool,
+
+        /// Abort with an error if evaluation takes longer than this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<f64>,
     },
 
     /// Compare Polyframe output with OpenSCAD
@@ -110,6 +126,27 @@ enum Commands {
         format: String,
     },
 
+    /// Apply a boolean operation to two STL files
+    Boolean {
+        /// First operand (STL)
+        a: String,
+
+        /// Second operand (STL)
+        b: String,
+
+        /// Operation (union, difference, intersection, xor)
+        #[arg(long, default_value = "union")]
+        op: String,
+
+        /// Boolean quality (fast, robust, exact)
+        #[arg(long, default_value = "robust")]
+        quality: String,
+
+        /// Output STL file
+        #[arg(short, long)]
+        output: String,
+    },
+
     /// Show version information
     Version,
 }
@@ -122,18 +159,25 @@ fn main() -> Result<()> {
             input,
             output,
             format,
+            dry_run,
             lazy,
             parallel,
             incremental,
+            timeout,
         }) => {
             render_command(
                 input,
-                output,
-                format,
-                *lazy,
-                *parallel,
-                *incremental,
-                cli.verbose,
+                &RenderOptions {
+                    output: output.as_deref(),
+                    format,
+                    dry_run: *dry_run,
+                    lazy: *lazy,
+                    parallel: *parallel,
+                    incremental: *incremental,
+                    force: cli.force,
+                    timeout: *timeout,
+                    verbose: cli.verbose,
+                },
             )?;
         }
         Some(Commands::Compare {
@@ -153,13 +197,31 @@ fn main() -> Result<()> {
         Some(Commands::Analyze { input, format }) => {
             analyze_command(input, format, cli.verbose)?;
         }
+        Some(Commands::Boolean {
+            a,
+            b,
+            op,
+            quality,
+            output,
+        }) => {
+            boolean_command(a, b, op, quality, output, cli.verbose)?;
+        }
         Some(Commands::Version) => {
             println!("Polyframe Kernel v{}", env!("CARGO_PKG_VERSION"));
         }
         None => {
             // Default behavior: render input to output
             if let (Some(input), Some(output)) = (&cli.input, &cli.output) {
-                render_command(input, output, &cli.format, false, false, false, cli.verbose)?;
+                render_command(
+                    input,
+                    &RenderOptions {
+                        output: Some(output.as_str()),
+                        format: &cli.format,
+                        force: cli.force,
+                        verbose: cli.verbose,
+                        ..Default::default()
+                    },
+                )?;
             } else {
                 eprintln!("Error: Input and output files required");
                 eprintln!("Usage: polyframe-kernel <INPUT> --output <OUTPUT>");
@@ -171,15 +233,55 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn render_command(
-    input: &str,
-    output: &str,
-    format: &str,
+/// Export formats accepted by `render`
+const SUPPORTED_FORMATS: &[&str] = &["stl", "3mf", "gltf", "glb", "step", "stp", "ply", "obj"];
+
+/// Settings for [`render_command`], mirroring the flags of `render`
+#[derive(Default)]
+struct RenderOptions<'a> {
+    /// Output file; only optional for a dry run
+    output: Option<&'a str>,
+    format: &'a str,
+    /// Check the design and print stats instead of writing `output`
+    dry_run: bool,
     lazy: bool,
     parallel: bool,
     incremental: bool,
+    /// Write the output even if it fails export validation
+    force: bool,
+    /// Cancel evaluation after this many seconds
+    timeout: Option<f64>,
     verbose: bool,
-) -> Result<()> {
+}
+
+/// Render `input` and export it to `options.output`
+///
+/// With `dry_run` every step up to the export still runs, including the checks the chosen
+/// exporter would make, and a stats summary is printed instead of writing the output.
+fn render_command(input: &str, options: &RenderOptions) -> Result<()> {
+    let &RenderOptions {
+        output,
+        format,
+        dry_run,
+        lazy,
+        parallel,
+        incremental,
+        force,
+        timeout,
+        verbose,
+    } = options;
+
+    let timeout = timeout
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds).map_err(|_| {
+                anyhow!(
+                    "Invalid --timeout {}: expected a non-negative number of seconds",
+                    seconds
+                )
+            })
+        })
+        .transpose()?;
+
     if verbose {
         println!("Rendering: {}", input);
         if lazy {
@@ -210,31 +312,39 @@ fn render_command(
 
     // Choose evaluator based on flags
     let render_start = std::time::Instant::now();
-    let mesh = if incremental {
-        // Use incremental evaluator
-        use polyframe::IncrementalEvaluator;
-        let evaluator = IncrementalEvaluator::from_ast(&ast);
-        let result = evaluator.evaluate(&ast)?;
+    let evaluate = move |cancel: Arc<AtomicBool>| -> Result<polyframe::Mesh> {
+        if incremental {
+            // Use incremental evaluator
+            use polyframe::IncrementalEvaluator;
+            let evaluator = IncrementalEvaluator::from_ast(&ast).with_cancellation(cancel);
+            let result = evaluator.evaluate(&ast)?;
+
+            if verbose {
+                let stats = evaluator.cache_stats();
+                println!(
+                    "Cache stats: {}/{} nodes cached ({:.1}% hit rate)",
+                    stats.cached_nodes,
+                    stats.total_nodes,
+                    stats.hit_rate()
+                );
+            }
 
-        if verbose {
-            let stats = evaluator.cache_stats();
-            println!(
-                "Cache stats: {}/{} nodes cached ({:.1}% hit rate)",
-                stats.cached_nodes,
-                stats.total_nodes,
-                stats.hit_rate()
-            );
+            Ok(result)
+        } else if parallel {
+            // Use parallel evaluator
+            use polyframe::ast::ParallelEvaluator;
+            ParallelEvaluator::evaluate_with_cancellation(&ast, &cancel)
+        } else {
+            // Use standard evaluator
+            let evaluator = polyframe::ast::Evaluator::new().with_cancellation(cancel);
+            evaluator.evaluate(&ast)
         }
+    };
 
-        result
-    } else if parallel {
-        // Use parallel evaluator
-        use polyframe::ast::ParallelEvaluator;
-        ParallelEvaluator::evaluate(&ast)?
-    } else {
-        // Use standard evaluator
-        let evaluator = polyframe::ast::Evaluator::new();
-        evaluator.evaluate(&ast)?
+    let mesh = match timeout {
+        Some(timeout) => run_with_timeout(timeout, evaluate)
+            .with_context(|| format!("Failed to render {}", input))?,
+        None => evaluate(Arc::new(AtomicBool::new(false)))?,
     };
 
     let render_time = render_start.elapsed();
@@ -253,18 +363,53 @@ fn render_command(
         return Ok(());
     }
 
+    let format = format.to_lowercase();
+    if !SUPPORTED_FORMATS.contains(&format.as_str()) {
+        eprintln!("Error: Unsupported format: {}", format);
+        eprintln!("Supported formats: {}", SUPPORTED_FORMATS.join(", "));
+        std::process::exit(1);
+    }
+
+    if dry_run {
+        if format == "3mf" && !force {
+            io::validate_3mf(&mesh)?;
+        }
+        // Primitives emit separate vertices per face, so check topology on a welded copy
+        let validation = mesh.as_indexed(1e-6).validate();
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        println!("Dry run: {} rendered, no output written", input);
+        println!("  Vertices:  {}", mesh.vertex_count());
+        println!("  Triangles: {}", mesh.triangle_count());
+        println!("  Closed:    {}", yes_no(validation.is_closed));
+        println!("  Manifold:  {}", yes_no(validation.is_manifold));
+        println!("  Parse:     {:.2?}", parse_time);
+        println!("  Render:    {:.2?}", render_time);
+        return Ok(());
+    }
+    let output = output.context("An output file is required unless --dry-run is given")?;
+
     // Export based on format
     let export_start = std::time::Instant::now();
-    match format.to_lowercase().as_str() {
+    match format.as_str() {
         "stl" => io::export_stl(&mesh, output)?,
+        "3mf" if force => io::export_3mf_unvalidated(&mesh, output)?,
         "3mf" => io::export_3mf(&mesh, output)?,
-        "gltf" | "glb" => io::export_gltf(&mesh, output)?,
+        "gltf" => io::export_gltf_with_format(
+            &mesh,
+            output,
+            io::GltfFormat::Json,
+            io::AttributeLayout::default(),
+        )?,
+        "glb" => io::export_gltf_with_format(
+            &mesh,
+            output,
+            io::GltfFormat::Binary,
+            io::AttributeLayout::default(),
+        )?,
         "step" | "stp" => io::export_step(&mesh, output)?,
-        _ => {
-            eprintln!("Error: Unsupported format: {}", format);
-            eprintln!("Supported formats: stl, 3mf, gltf, glb, step");
-            std::process::exit(1);
-        }
+        "ply" => io::export_ply(&mesh, output, true)?,
+        "obj" => io::export_obj(&mesh, output, io::FaceFormat::default())?,
+        _ => unreachable!("format {} was checked above", format),
     }
     let export_time = export_start.elapsed();
 
@@ -360,6 +505,13 @@ fn eval_command(dataset: &[String], out: &str, verbose: bool) -> Result<()> {
                         dataset_path
                     );
                 }
+                evaluation::DatasetSource::OpenScadTestsuite(_) => {
+                    println!(
+                        "{} Loading OpenSCAD testsuite from {}",
+                        "ℹ".bright_blue(),
+                        dataset_path
+                    );
+                }
             }
         }
 
@@ -586,3 +738,57 @@ fn analyze_command(input: &str, format: &str, verbose: bool) -> Result<()> {
 
     Ok(())
 }
+
+fn boolean_command(
+    a: &str,
+    b: &str,
+    op: &str,
+    quality: &str,
+    output: &str,
+    verbose: bool,
+) -> Result<()> {
+    use polyframe::geometry::{BooleanOp, BooleanQuality};
+
+    let op = match op.to_lowercase().as_str() {
+        "union" => BooleanOp::Union,
+        "difference" => BooleanOp::Difference,
+        "intersection" => BooleanOp::Intersection,
+        "xor" | "symmetric_difference" => BooleanOp::SymmetricDifference,
+        _ => anyhow::bail!(
+            "Unknown boolean operation: {} (expected union, difference, intersection, xor)",
+            op
+        ),
+    };
+    let quality = match quality.to_lowercase().as_str() {
+        "fast" => BooleanQuality::Fast,
+        "robust" => BooleanQuality::Robust,
+        "exact" => BooleanQuality::Exact,
+        _ => anyhow::bail!(
+            "Unknown boolean quality: {} (expected fast, robust, exact)",
+            quality
+        ),
+    };
+
+    const WELD_EPSILON: f64 = 1e-6;
+    let mut mesh_a = io::import_stl(a)?;
+    mesh_a.deduplicate_vertices(WELD_EPSILON);
+    let mut mesh_b = io::import_stl(b)?;
+    mesh_b.deduplicate_vertices(WELD_EPSILON);
+
+    let start = std::time::Instant::now();
+    let result = mesh_a.boolean_operation_with_quality(&mesh_b, op.clone(), quality)?;
+
+    if verbose {
+        println!("{:?} ({:?}) in {:.2?}", op, quality, start.elapsed());
+        println!("Vertices: {}", result.vertex_count());
+        println!("Triangles: {}", result.triangle_count());
+    }
+
+    io::export_stl(&result, output)?;
+    println!(
+        "Successfully wrote {:?} of {} and {} -> {}",
+        op, a, b, output
+    );
+
+    Ok(())
+}
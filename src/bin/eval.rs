@@ -9,11 +9,12 @@ use clap::{Parser, Subcommand};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use polyframe::evaluation::{
-    load_corpus, run_and_compare, run_model_task, Comparison, EvaluationReport, Reporter,
+    load_corpus, run_and_compare_with_timeout, CompareOptions, EvaluationReport, Reporter,
     RegressionSuite, Fuzzer, FuzzerConfig,
 };
 use rayon::prelude::*;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "polyframe-eval")]
@@ -42,6 +43,10 @@ enum Commands {
         /// Output directory for results
         #[arg(short, long, default_value = "tests/evaluation/outputs")]
         out: String,
+
+        /// Seconds each model may take before it is recorded as an error
+        #[arg(long, default_value = "60")]
+        timeout: u64,
     },
 
     /// Run fuzz testing
@@ -53,6 +58,10 @@ enum Commands {
         /// Output directory for fuzz results
         #[arg(short, long, default_value = "tests/evaluation/outputs/fuzz")]
         out: String,
+
+        /// RNG seed, to reproduce an earlier run; random if omitted
+        #[arg(long)]
+        seed: Option<u64>,
     },
 
     /// Generate markdown report from JSON
@@ -65,21 +74,36 @@ enum Commands {
         #[arg(short, long, default_value = "tests/evaluation/report.md")]
         output: String,
     },
+
+    /// Compare a JSON report against a baseline, failing on newly broken models
+    Compare {
+        /// Baseline JSON report, e.g. from the main branch
+        #[arg(short, long)]
+        baseline: String,
+
+        /// JSON report to check
+        #[arg(short, long, default_value = "tests/evaluation/outputs/latest.json")]
+        current: String,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Eval { dataset, file, out } => {
-            eval_command(dataset.as_deref(), file.as_deref(), out, cli.verbose)?;
+        Commands::Eval { dataset, file, out, timeout } => {
+            let timeout = Duration::from_secs(*timeout);
+            eval_command(dataset.as_deref(), file.as_deref(), out, timeout, cli.verbose)?;
         }
-        Commands::Fuzz { count, out } => {
-            fuzz_command(*count, out, cli.verbose)?;
+        Commands::Fuzz { count, out, seed } => {
+            fuzz_command(*count, out, seed.unwrap_or_else(rand::random), cli.verbose)?;
         }
         Commands::GenerateReport { input, output } => {
             generate_report_command(input, output)?;
         }
+        Commands::Compare { baseline, current } => {
+            compare_command(baseline, current)?;
+        }
     }
 
     Ok(())
@@ -89,6 +113,7 @@ fn eval_command(
     dataset: Option<&str>,
     file: Option<&str>,
     out: &str,
+    timeout: Duration,
     verbose: bool,
 ) -> Result<()> {
     if verbose {
@@ -114,7 +139,7 @@ fn eval_command(
             println!("Evaluating single file: {}", file_path);
         }
 
-        match run_and_compare(&path) {
+        match run_and_compare_with_timeout(&path, &CompareOptions::default(), timeout) {
             Ok(result) => {
                 report.add_result(result);
             }
@@ -170,7 +195,8 @@ fn eval_command(
                     pb.set_message(format!("Evaluating {}", entry.path.display()));
                 }
 
-                let result = match run_and_compare(&entry.path) {
+                let options = CompareOptions::default();
+                let result = match run_and_compare_with_timeout(&entry.path, &options, timeout) {
                     Ok(r) => Ok(r),
                     Err(e) => {
                         // Add to regression suite
@@ -223,6 +249,9 @@ fn eval_command(
     let md_path = output_dir.join("report.md");
     Reporter::write_markdown(&report, &md_path)?;
 
+    let csv_path = output_dir.join("report.csv");
+    Reporter::write_csv(&report, &csv_path)?;
+
     // Print summary
     if verbose {
         print_summary(&report);
@@ -244,11 +273,12 @@ fn eval_command(
     Ok(())
 }
 
-fn fuzz_command(count: usize, out: &str, verbose: bool) -> Result<()> {
+fn fuzz_command(count: usize, out: &str, seed: u64, verbose: bool) -> Result<()> {
     if verbose {
         println!("{}", "Starting fuzz testing...".bold());
         println!("Generating {} random SCAD files", count);
     }
+    println!("{} {} (rerun with --seed {})", "Seed:".bold(), seed, seed);
 
     let output_dir = PathBuf::from(out);
     std::fs::create_dir_all(&output_dir)?;
@@ -257,6 +287,8 @@ fn fuzz_command(count: usize, out: &str, verbose: bool) -> Result<()> {
         count,
         max_depth: 5,
         max_primitives: 10,
+        seed,
+        ..Default::default()
     };
     let mut fuzzer = Fuzzer::new(config);
 
@@ -291,6 +323,10 @@ fn fuzz_command(count: usize, out: &str, verbose: bool) -> Result<()> {
                 if verbose {
                     println!("  ✗ {}: Parse failed - {}", name, e);
                 }
+
+                // Save the smallest program that still fails next to the original
+                let minimal = Fuzzer::minimize(scad_code, |code| polyframe::parse_scad(code).is_err());
+                std::fs::write(output_dir.join(format!("{}.min.scad", name)), minimal)?;
             }
         }
     }
@@ -321,6 +357,31 @@ fn generate_report_command(input: &str, output: &str) -> Result<()> {
     Ok(())
 }
 
+fn compare_command(baseline: &str, current: &str) -> Result<()> {
+    let old = Reporter::read_json(&PathBuf::from(baseline))?;
+    let new = Reporter::read_json(&PathBuf::from(current))?;
+    let delta = RegressionSuite::compare_reports(&old, &new);
+
+    for model in &delta.fixes {
+        println!("{} {}", "Fixed:".green(), model);
+    }
+    for model in &delta.regressions {
+        println!("{} {}", "Regressed:".red(), model);
+    }
+    println!(
+        "{} {} regressions, {} fixes",
+        "Summary:".bold(),
+        delta.regressions.len(),
+        delta.fixes.len()
+    );
+
+    if delta.has_regressions() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
 fn print_summary(report: &EvaluationReport) {
     println!("\n{}", "═".repeat(80).bright_black());
     println!("{}", "Evaluation Summary".bold());
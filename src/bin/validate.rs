@@ -58,7 +58,7 @@ enum Commands {
         #[arg(short, long)]
         input: String,
 
-        /// Output format (json, markdown, terminal)
+        /// Output format (json, markdown, html, junit, terminal)
         #[arg(short, long, default_value = "markdown")]
         format: String,
     },
@@ -146,8 +146,19 @@ fn run_all_validation(
     let json_path = output_path.join("validation_report.json");
     ValidationReporter::write_json(&report, &json_path)?;
 
+    // Full runs are appended to the history so the report can show the trend across releases
+    let history_path = output_path.join("validation_history.jsonl");
+    ValidationReporter::append_history(&report, &history_path)?;
+    let history = ValidationReporter::load_history(&history_path)?;
+
     let md_path = output_path.join("validation_report.md");
-    ValidationReporter::write_markdown(&report, &md_path)?;
+    ValidationReporter::write_markdown_with_history(&report, &history, &md_path)?;
+
+    let junit_path = output_path.join("validation_report.xml");
+    ValidationReporter::write_junit(&report, &junit_path)?;
+
+    let html_path = output_path.join("validation_report.html");
+    ValidationReporter::write_html(&report, &html_path)?;
 
     // Print summary
     ValidationReporter::print_summary_with_verbose(&report, verbose);
@@ -235,11 +246,21 @@ fn generate_report(input: &str, format: &str, output_dir: &str) -> Result<()> {
             ValidationReporter::write_markdown(&report, &md_path)?;
             println!("{} Generated Markdown report: {}", "Success:".green(), md_path.display());
         }
+        "html" => {
+            let html_path = output_path.join("report.html");
+            ValidationReporter::write_html(&report, &html_path)?;
+            println!("{} Generated HTML report: {}", "Success:".green(), html_path.display());
+        }
+        "junit" | "xml" => {
+            let junit_path = output_path.join("report.xml");
+            ValidationReporter::write_junit(&report, &junit_path)?;
+            println!("{} Generated JUnit report: {}", "Success:".green(), junit_path.display());
+        }
         "terminal" | "term" => {
             ValidationReporter::print_summary(&report);
         }
         _ => {
-            return Err(anyhow::anyhow!("Unknown format: {}. Use json, markdown, or terminal", format));
+            return Err(anyhow::anyhow!("Unknown format: {}. Use json, markdown, html, junit, or terminal", format));
         }
     }
 
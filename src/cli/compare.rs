@@ -10,13 +10,19 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
-use crate::evaluation::{generate_diff_image, render_stl_to_png};
+use crate::evaluation::{compare_presets, VisualMetric};
+use image::DynamicImage;
+
+/// Side of each camera preset's view in the 2x2 preview montages
+const PREVIEW_TILE_SIZE: u32 = 512;
 
 #[derive(Debug, Clone)]
 pub struct PreviewConfig {
     pub output_dir: PathBuf,
     pub copy_stl: bool,
     pub generate_diff: bool,
+    /// How the views are scored for the comparison's `visual_diff_delta`
+    pub visual_metric: VisualMetric,
 }
 
 impl PreviewConfig {
@@ -25,6 +31,7 @@ impl PreviewConfig {
             output_dir,
             copy_stl: true,
             generate_diff: true,
+            visual_metric: VisualMetric::default(),
         }
     }
 
@@ -147,21 +154,36 @@ pub fn compare_with_openscad(
             result.openscad_stl = Some(os_stl);
         }
 
+        // Every camera preset, so differences visible from only one side are caught
+        let views = compare_presets(
+            &openscad_mesh,
+            &polyframe_mesh,
+            PREVIEW_TILE_SIZE,
+            preview_cfg.visual_metric,
+        )
+        .context("Failed to render previews")?;
+
         let pf_png = preview_cfg.output_dir.join("polyframe.png");
-        render_stl_to_png(&polyframe_output, &pf_png)
-            .context("Failed to render Polyframe STL preview")?;
-        result.polyframe_preview = Some(pf_png.clone());
+        DynamicImage::ImageRgba8(views.polyframe)
+            .to_rgb8()
+            .save(&pf_png)
+            .with_context(|| format!("Failed to save PNG to {}", pf_png.display()))?;
+        result.polyframe_preview = Some(pf_png);
 
         let os_png = preview_cfg.output_dir.join("openscad.png");
-        render_stl_to_png(&openscad_output, &os_png)
-            .context("Failed to render OpenSCAD STL preview")?;
-        result.openscad_preview = Some(os_png.clone());
+        DynamicImage::ImageRgba8(views.openscad)
+            .to_rgb8()
+            .save(&os_png)
+            .with_context(|| format!("Failed to save PNG to {}", os_png.display()))?;
+        result.openscad_preview = Some(os_png);
 
         if preview_cfg.generate_diff {
             let diff_png = preview_cfg.output_dir.join("diff.png");
-            let delta =
-                generate_diff_image(&os_png, &pf_png, &diff_png).context("Failed to generate visual diff")?;
-            result.visual_diff_delta = Some(delta);
+            views
+                .diff
+                .save(&diff_png)
+                .with_context(|| format!("Failed to save diff image to {}", diff_png.display()))?;
+            result.visual_diff_delta = Some(views.delta);
             result.diff_preview = Some(diff_png);
         }
     }
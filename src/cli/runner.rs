@@ -84,47 +84,7 @@ impl Runner {
 
     /// Load STL file into mesh
     pub fn load_stl(&self, path: &Path) -> Result<Mesh> {
-        use crate::geometry::{Triangle, Vertex};
-        use nalgebra::{Point3, Vector3};
-        use std::fs::File;
-        use stl_io::read_stl;
-
-        let mut file = File::open(path).context(format!("Failed to open STL file: {:?}", path))?;
-
-        let stl = read_stl(&mut file).context("Failed to read STL file")?;
-
-        let mut mesh = Mesh::new();
-
-        // stl_io returns an IndexedMesh with vertices and faces
-        for face in &stl.faces {
-            let vertices = &stl.vertices;
-
-            // Get the three vertices of this face
-            let v0_pos = &vertices[face.vertices[0]];
-            let v1_pos = &vertices[face.vertices[1]];
-            let v2_pos = &vertices[face.vertices[2]];
-
-            let normal = Vector3::new(face.normal[0] as f64, face.normal[1] as f64, face.normal[2] as f64);
-
-            let v0 = mesh.add_vertex(Vertex::new(
-                Point3::new(v0_pos[0] as f64, v0_pos[1] as f64, v0_pos[2] as f64),
-                normal,
-            ));
-
-            let v1 = mesh.add_vertex(Vertex::new(
-                Point3::new(v1_pos[0] as f64, v1_pos[1] as f64, v1_pos[2] as f64),
-                normal,
-            ));
-
-            let v2 = mesh.add_vertex(Vertex::new(
-                Point3::new(v2_pos[0] as f64, v2_pos[1] as f64, v2_pos[2] as f64),
-                normal,
-            ));
-
-            mesh.add_triangle(Triangle::new([v0, v1, v2]));
-        }
-
-        Ok(mesh)
+        crate::io::import_stl(path)
     }
 
     /// Check if OpenSCAD is available
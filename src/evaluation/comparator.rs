@@ -6,6 +6,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 /// Tolerance constants (passed to MeshDiff::compare)
@@ -18,6 +19,12 @@ pub const BBOX_DELTA_THRESHOLD: f64 = 0.001; // 0.001 units per dimension
 pub const VOLUME_DELTA_THRESHOLD: f32 = 0.001; // 0.1%
 pub const SURFACE_AREA_DELTA_THRESHOLD: f32 = 0.001; // 0.1%
 
+/// Cells along the longest side of the bounding box in [`DiffResult::divergent_cells`]
+pub const DIFF_GRID_RESOLUTION: usize = 8;
+
+/// Cap on the samples per triangle edge when rasterizing surfaces into the diff grid
+const MAX_EDGE_SAMPLES: usize = 256;
+
 /// Delta statistics for comparison
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeltaStats {
@@ -37,6 +44,34 @@ pub struct DiffResult {
     pub passed: bool,
     pub deltas: DeltaStats,
     pub error: Option<String>,
+    /// Grid cells where only one of the meshes has surface, ordered by index
+    ///
+    /// The grid has cubic cells over the union of both bounding boxes, with
+    /// [`DIFF_GRID_RESOLUTION`] cells along its longest side. A cell counts as having surface if
+    /// any part of a triangle passes through it, so this localizes missing or extra features
+    /// (e.g. a hole present in only one mesh) but not a face shifted within the same cell.
+    #[serde(default)]
+    pub divergent_cells: Vec<DivergentCell>,
+}
+
+/// Which mesh of a comparison something was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffSide {
+    OpenScad,
+    Polyframe,
+}
+
+/// Cell of the comparison grid with surface from only one mesh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DivergentCell {
+    /// Cell index along x, y and z
+    pub index: [usize; 3],
+    /// Lower corner of the cell
+    pub min: [f64; 3],
+    /// Upper corner of the cell
+    pub max: [f64; 3],
+    /// The mesh that has surface in the cell
+    pub present_in: DiffSide,
 }
 
 /// Comparison result
@@ -51,6 +86,40 @@ pub struct Comparison {
     pub vertex_count_openscad: usize,
     pub triangle_count_poly: usize,
     pub triangle_count_openscad: usize,
+    /// Whether both meshes have the same genus; `None` when either genus is undefined
+    /// (open or non-manifold mesh) and the check was skipped
+    #[serde(default)]
+    pub genus_match: Option<bool>,
+    /// Whether both meshes have the same number of connected shells
+    #[serde(default = "default_true")]
+    pub component_count_match: bool,
+    /// Difference of the signed volumes relative to the larger of the two
+    ///
+    /// Unlike the count deltas this ignores tessellation, so two renders of the same solid
+    /// at different facet counts come out close to zero.
+    #[serde(default)]
+    pub volume_diff: f32,
+}
+
+impl Comparison {
+    /// True unless a topology check ran and found a mismatch
+    pub fn topology_matches(&self) -> bool {
+        self.component_count_match && self.genus_match != Some(false)
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Options controlling what a comparison requires for passing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompareOptions {
+    /// Fail comparisons whose genus or component counts differ
+    pub require_topology: bool,
+    /// Pass or fail on [`Comparison::volume_diff`] alone, at this relative tolerance,
+    /// instead of on vertex/triangle counts and bounding boxes
+    pub volume_tolerance: Option<f32>,
 }
 
 /// Compute geometry statistics from a mesh
@@ -128,12 +197,81 @@ pub fn compare_mesh(
         passed,
         deltas,
         error: None,
+        divergent_cells: divergent_cells(openscad_mesh, polyframe_mesh),
     })
 }
 
+/// Cells of the diff grid where exactly one of the meshes has surface
+fn divergent_cells(
+    openscad_mesh: &crate::geometry::Mesh,
+    polyframe_mesh: &crate::geometry::Mesh,
+) -> Vec<DivergentCell> {
+    if openscad_mesh.triangles.is_empty() && polyframe_mesh.triangles.is_empty() {
+        return Vec::new();
+    }
+    let mut bounds = crate::geometry::BoundingBox::empty();
+    for vertex in openscad_mesh.vertices.iter().chain(&polyframe_mesh.vertices) {
+        bounds.expand_to_include(&vertex.position);
+    }
+    let size = bounds.size();
+    let cell = size.max() / DIFF_GRID_RESOLUTION as f64;
+    if cell <= 0.0 {
+        return Vec::new();
+    }
+    let cells = size.map(|extent| ((extent / cell).ceil() as usize).max(1));
+    let cell_of = |p: nalgebra::Point3<f64>| {
+        [0, 1, 2].map(|k| {
+            let i = ((p[k] - bounds.min[k]) / cell).floor().max(0.0) as usize;
+            i.min(cells[k] - 1)
+        })
+    };
+    let corner = |k: usize, i: usize| (bounds.min[k] + i as f64 * cell).min(bounds.max[k]);
+
+    let occupied = |mesh: &crate::geometry::Mesh| {
+        let mut found = HashSet::new();
+        for triangle in &mesh.triangles {
+            let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position);
+            let longest = (b - a).norm().max((c - b).norm()).max((a - c).norm());
+            // Sample at half a cell so no cell the triangle crosses is skipped
+            let n = ((2.0 * longest / cell).ceil() as usize).clamp(1, MAX_EDGE_SAMPLES);
+            for i in 0..=n {
+                for j in 0..=n - i {
+                    let p = a + (b - a) * (i as f64 / n as f64) + (c - a) * (j as f64 / n as f64);
+                    found.insert(cell_of(p));
+                }
+            }
+        }
+        found
+    };
+    let (openscad, polyframe) = (occupied(openscad_mesh), occupied(polyframe_mesh));
+
+    let mut divergent: Vec<DivergentCell> = openscad
+        .difference(&polyframe)
+        .map(|index| (*index, DiffSide::OpenScad))
+        .chain(polyframe.difference(&openscad).map(|index| (*index, DiffSide::Polyframe)))
+        .map(|(index, present_in)| DivergentCell {
+            index,
+            min: [0, 1, 2].map(|k| corner(k, index[k])),
+            max: [0, 1, 2].map(|k| corner(k, index[k] + 1)),
+            present_in,
+        })
+        .collect();
+    divergent.sort_by_key(|cell| cell.index);
+    divergent
+}
+
 /// Compare two STL files
 pub fn compare_stl_files(polyframe_path: &Path, openscad_path: &Path) -> Result<Comparison> {
-    use crate::cli::{MeshDiff, Runner};
+    compare_stl_files_with_options(polyframe_path, openscad_path, &CompareOptions::default())
+}
+
+/// Compare two STL files, optionally requiring matching topology to pass
+pub fn compare_stl_files_with_options(
+    polyframe_path: &Path,
+    openscad_path: &Path,
+    options: &CompareOptions,
+) -> Result<Comparison> {
+    use crate::cli::Runner;
 
     let runner = Runner::new();
 
@@ -141,15 +279,48 @@ pub fn compare_stl_files(polyframe_path: &Path, openscad_path: &Path) -> Result<
     let poly_mesh = runner.load_stl(polyframe_path)?;
     let openscad_mesh = runner.load_stl(openscad_path)?;
 
+    Ok(compare_meshes_with_options(
+        &poly_mesh,
+        &openscad_mesh,
+        options,
+    ))
+}
+
+/// Compare a Polyframe mesh against a reference mesh, optionally requiring matching topology
+pub fn compare_meshes_with_options(
+    poly_mesh: &crate::geometry::Mesh,
+    openscad_mesh: &crate::geometry::Mesh,
+    options: &CompareOptions,
+) -> Comparison {
+    use crate::cli::MeshDiff;
+    use crate::geometry::{analyze_topology, signed_volume};
+
     // Use the same comparison logic as the compare command (with sophisticated tolerances)
-    let comparison_result = MeshDiff::compare(&poly_mesh, &openscad_mesh, BBOX_TOL);
+    let comparison_result = MeshDiff::compare(poly_mesh, openscad_mesh, BBOX_TOL);
 
     // Checksum comparison
-    let poly_checksum = calc_mesh_checksum(&poly_mesh);
-    let openscad_checksum = calc_mesh_checksum(&openscad_mesh);
+    let poly_checksum = calc_mesh_checksum(poly_mesh);
+    let openscad_checksum = calc_mesh_checksum(openscad_mesh);
     let checksum_match = poly_checksum == openscad_checksum;
 
-    Ok(Comparison {
+    // Topology comparison (genus is only defined for closed manifolds)
+    let poly_topology = analyze_topology(poly_mesh);
+    let openscad_topology = analyze_topology(openscad_mesh);
+    let genus_match = match (poly_topology.genus, openscad_topology.genus) {
+        (Some(poly), Some(openscad)) => Some(poly == openscad),
+        _ => None,
+    };
+    let component_count_match = poly_topology.component_count == openscad_topology.component_count;
+
+    let (poly_volume, openscad_volume) = (signed_volume(poly_mesh), signed_volume(openscad_mesh));
+    let larger_volume = poly_volume.abs().max(openscad_volume.abs());
+    let volume_diff = if larger_volume > 0.0 {
+        ((poly_volume - openscad_volume).abs() / larger_volume) as f32
+    } else {
+        0.0
+    };
+
+    let mut comparison = Comparison {
         vertices_diff: comparison_result.vertex_delta as f32,
         triangles_diff: comparison_result.triangle_delta as f32,
         bbox_diff: comparison_result.bbox_delta as f32,
@@ -159,7 +330,19 @@ pub fn compare_stl_files(polyframe_path: &Path, openscad_path: &Path) -> Result<
         vertex_count_openscad: openscad_mesh.vertex_count(),
         triangle_count_poly: poly_mesh.triangle_count(),
         triangle_count_openscad: openscad_mesh.triangle_count(),
-    })
+        genus_match,
+        component_count_match,
+        volume_diff,
+    };
+
+    if let Some(tolerance) = options.volume_tolerance {
+        comparison.passed = volume_diff <= tolerance;
+    }
+    if options.require_topology {
+        comparison.passed &= comparison.topology_matches();
+    }
+
+    comparison
 }
 
 /// Calculate SHA256 checksum of mesh data
@@ -222,4 +405,122 @@ mod tests {
         let _ = std::fs::remove_file(&path_a);
         let _ = std::fs::remove_file(&path_b);
     }
+
+    /// Rectangular plate with a square through-hole, built directly so it does not
+    /// depend on boolean difference
+    fn drilled_plate(size: f64, hole: f64, thickness: f64) -> crate::geometry::Mesh {
+        use crate::geometry::{Mesh, Triangle, Vertex};
+        use nalgebra::{Point3, Vector3};
+
+        let (o, i) = (size / 2.0, hole / 2.0);
+        let outer = [(-o, -o), (o, -o), (o, o), (-o, o)];
+        let inner = [(-i, -i), (i, -i), (i, i), (-i, i)];
+
+        let mut mesh = Mesh::new();
+        let mut ring = |z: f64, square: &[(f64, f64); 4]| -> [usize; 4] {
+            square.map(|(x, y)| mesh.add_vertex(Vertex::new(Point3::new(x, y, z), Vector3::z())))
+        };
+        let (outer_bottom, outer_top) = (ring(0.0, &outer), ring(thickness, &outer));
+        let (inner_bottom, inner_top) = (ring(0.0, &inner), ring(thickness, &inner));
+
+        let mut quad = |a: usize, b: usize, c: usize, d: usize| {
+            mesh.add_triangle(Triangle::new([a, b, c]));
+            mesh.add_triangle(Triangle::new([a, c, d]));
+        };
+        for k in 0..4 {
+            let n = (k + 1) % 4;
+            quad(outer_top[k], outer_top[n], inner_top[n], inner_top[k]);
+            quad(
+                outer_bottom[n],
+                outer_bottom[k],
+                inner_bottom[k],
+                inner_bottom[n],
+            );
+            quad(outer_bottom[k], outer_bottom[n], outer_top[n], outer_top[k]);
+            quad(inner_bottom[n], inner_bottom[k], inner_top[k], inner_top[n]);
+        }
+        mesh.recompute_normals();
+        mesh
+    }
+
+    #[test]
+    fn test_comparison_flags_genus_mismatch() {
+        use crate::geometry::Primitive;
+        use nalgebra::{Matrix4, Vector3};
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let drilled_path = dir.path().join("drilled.stl");
+        let solid_path = dir.path().join("solid.stl");
+
+        let drilled = drilled_plate(20.0, 6.0, 2.0);
+        let mut solid = Primitive::cube(Vector3::new(20.0, 20.0, 2.0), false).to_mesh();
+        solid.transform(&Matrix4::new_translation(&Vector3::new(-10.0, -10.0, 0.0)));
+        assert_eq!(drilled.bounding_box().min, solid.bounding_box().min);
+        assert_eq!(drilled.bounding_box().max, solid.bounding_box().max);
+
+        crate::io::export_stl(&drilled, drilled_path.to_str().unwrap()).unwrap();
+        crate::io::export_stl(&solid, solid_path.to_str().unwrap()).unwrap();
+
+        let comparison = compare_stl_files(&drilled_path, &solid_path).unwrap();
+        assert_eq!(comparison.genus_match, Some(false));
+        assert!(comparison.component_count_match);
+        assert!(!comparison.topology_matches());
+
+        let options = CompareOptions {
+            require_topology: true,
+            ..Default::default()
+        };
+        let strict = compare_stl_files_with_options(&drilled_path, &solid_path, &options).unwrap();
+        assert!(!strict.passed);
+
+        // Identical topology passes the strict check
+        let same = compare_stl_files_with_options(&solid_path, &solid_path, &options).unwrap();
+        assert_eq!(same.genus_match, Some(true));
+        assert!(same.passed);
+    }
+
+    #[test]
+    fn test_divergent_cells_locate_missing_hole() {
+        use crate::geometry::Primitive;
+        use nalgebra::{Matrix4, Vector3};
+
+        let drilled = drilled_plate(20.0, 6.0, 2.0);
+        let mut solid = Primitive::cube(Vector3::new(20.0, 20.0, 2.0), false).to_mesh();
+        solid.transform(&Matrix4::new_translation(&Vector3::new(-10.0, -10.0, 0.0)));
+
+        assert!(compare_mesh(&solid, &solid).unwrap().divergent_cells.is_empty());
+
+        let diff = compare_mesh(&solid, &drilled).unwrap();
+        assert!(!diff.divergent_cells.is_empty());
+        for cell in &diff.divergent_cells {
+            // Only the solid plate has surface where the hole is
+            assert_eq!(cell.present_in, DiffSide::OpenScad);
+            let center = [0, 1].map(|k| (cell.min[k] + cell.max[k]) / 2.0);
+            assert!(center.iter().all(|c| c.abs() < 3.0), "{:?}", cell);
+        }
+    }
+
+    #[test]
+    fn test_volume_tolerance_ignores_tessellation() {
+        use crate::geometry::Primitive;
+
+        let coarse = Primitive::sphere(10.0, 16).to_mesh();
+        let fine = Primitive::sphere(10.0, 64).to_mesh();
+
+        let by_counts = compare_meshes_with_options(&coarse, &fine, &CompareOptions::default());
+        assert!(!by_counts.passed, "{:?}", by_counts);
+        assert!(by_counts.volume_diff > 0.0 && by_counts.volume_diff < 0.1);
+
+        let options = CompareOptions {
+            volume_tolerance: Some(0.1),
+            ..Default::default()
+        };
+        assert!(compare_meshes_with_options(&coarse, &fine, &options).passed);
+
+        // A solid of different size still fails
+        let smaller = Primitive::sphere(9.0, 64).to_mesh();
+        let comparison = compare_meshes_with_options(&smaller, &fine, &options);
+        assert!(!comparison.passed, "{:?}", comparison);
+    }
 }
@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) 2025 Polyframe Inc.
 
-//! Dataset discovery for .scad files and JSON exercises
+//! Dataset discovery for .scad files, JSON exercises, and OpenSCAD's own testsuite
 
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
@@ -32,6 +33,40 @@ pub struct Validation {
 pub enum DatasetSource {
     Folder(PathBuf),
     JsonFile(PathBuf),
+    /// Root of an OpenSCAD `tests/` tree, containing `data/scad` and `regression`
+    OpenScadTestsuite(PathBuf),
+}
+
+/// OpenSCAD features the parser does not handle yet
+///
+/// Testsuite files calling any of these still load, but are marked as expected failures.
+const UNSUPPORTED_FEATURES: &[&str] = &[
+    "children",
+    "for",
+    "function",
+    "hull",
+    "include",
+    "intersection_for",
+    "let",
+    "linear_extrude",
+    "minkowski",
+    "render",
+    "rotate_extrude",
+    "surface",
+    "text",
+    "use",
+];
+
+/// A `.scad` file from OpenSCAD's regression testsuite
+#[derive(Debug, Clone)]
+pub struct TestsuiteCase {
+    pub path: PathBuf,
+    /// Path relative to `data/scad`, e.g. `3D/features/cube-tests.scad`
+    pub name: String,
+    /// Reference outputs named `<stem>-expected.<ext>` under `regression/<test>/`
+    pub expected_outputs: Vec<PathBuf>,
+    /// Why the case is expected to fail, if it uses unsupported features
+    pub expected_failure: Option<String>,
 }
 
 /// Corpus entry for bulk loading
@@ -46,6 +81,7 @@ pub struct CorpusEntry {
 pub enum ModelTask {
     FromFile(PathBuf),
     FromJson(Exercise),
+    FromTestsuite(TestsuiteCase),
 }
 
 impl ModelTask {
@@ -54,6 +90,15 @@ impl ModelTask {
         match self {
             ModelTask::FromFile(path) => path.file_name().unwrap().to_str().unwrap().to_string(),
             ModelTask::FromJson(exercise) => exercise.id.clone(),
+            ModelTask::FromTestsuite(case) => case.name.clone(),
+        }
+    }
+
+    /// Reason this task is expected to fail, if any
+    pub fn expected_failure(&self) -> Option<&str> {
+        match self {
+            ModelTask::FromTestsuite(case) => case.expected_failure.as_deref(),
+            _ => None,
         }
     }
 
@@ -64,6 +109,8 @@ impl ModelTask {
                 std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))
             }
             ModelTask::FromJson(exercise) => Ok(exercise.input.clone()),
+            ModelTask::FromTestsuite(case) => std::fs::read_to_string(&case.path)
+                .context(format!("Failed to read {}", case.path.display())),
         }
     }
 }
@@ -73,6 +120,7 @@ pub fn load_dataset(source: DatasetSource) -> Result<Vec<ModelTask>> {
     match source {
         DatasetSource::Folder(path) => discover_scad_files(&path),
         DatasetSource::JsonFile(path) => load_json_exercises(&path),
+        DatasetSource::OpenScadTestsuite(path) => load_openscad_testsuite(&path),
     }
 }
 
@@ -80,6 +128,8 @@ pub fn load_dataset(source: DatasetSource) -> Result<Vec<ModelTask>> {
 pub fn detect_source(path: &Path) -> DatasetSource {
     if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
         DatasetSource::JsonFile(path.to_path_buf())
+    } else if let Some(root) = testsuite_root(path) {
+        DatasetSource::OpenScadTestsuite(root)
     } else {
         DatasetSource::Folder(path.to_path_buf())
     }
@@ -121,6 +171,156 @@ fn load_json_exercises(path: &Path) -> Result<Vec<ModelTask>> {
     Ok(exercises.into_iter().map(ModelTask::FromJson).collect())
 }
 
+/// Find the testsuite root for either an OpenSCAD checkout or its `tests/` directory
+fn testsuite_root(path: &Path) -> Option<PathBuf> {
+    [path.to_path_buf(), path.join("tests")]
+        .into_iter()
+        .find(|root| root.join("data").join("scad").is_dir())
+}
+
+/// Load OpenSCAD's regression testsuite, pairing each file with its expected outputs
+fn load_openscad_testsuite(path: &Path) -> Result<Vec<ModelTask>> {
+    let Some(root) = testsuite_root(path) else {
+        bail!("No data/scad directory under {}", path.display());
+    };
+    let scad_dir = root.join("data").join("scad");
+
+    // Expected outputs live in regression/<test>/<stem>-expected.<ext>
+    let mut expected: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for entry in WalkDir::new(root.join("regression"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Some(stem) = entry.path().file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if let Some(model) = stem.strip_suffix("-expected") {
+            expected
+                .entry(model.to_string())
+                .or_default()
+                .push(entry.path().to_path_buf());
+        }
+    }
+
+    let mut tasks = Vec::new();
+    for entry in WalkDir::new(&scad_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "scad") {
+            continue;
+        }
+
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Warning: Failed to read {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let name = path
+            .strip_prefix(&scad_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let mut expected_outputs = expected.get(stem.as_ref()).cloned().unwrap_or_default();
+        expected_outputs.sort();
+
+        tasks.push(ModelTask::FromTestsuite(TestsuiteCase {
+            path: path.to_path_buf(),
+            name,
+            expected_outputs,
+            expected_failure: unsupported_feature(&source)
+                .map(|feature| format!("uses unsupported `{}`", feature)),
+        }));
+    }
+
+    tasks.sort_by_key(|a| a.name());
+    Ok(tasks)
+}
+
+/// First unsupported feature used by `source`, ignoring comments and strings
+fn unsupported_feature(source: &str) -> Option<&'static str> {
+    let code = strip_comments_and_strings(source);
+    let bytes = code.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !(bytes[i].is_ascii_alphabetic() || bytes[i] == b'_') {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            i += 1;
+        }
+        // `$fn` and friends are identifiers, not calls
+        if start > 0 && bytes[start - 1] == b'$' {
+            continue;
+        }
+
+        let word = &code[start..i];
+        let next = code[i..].trim_start().chars().next();
+        // Calls are followed by `(` and includes by `<`; `function` is followed by a name
+        let is_use = matches!(next, Some('(') | Some('<')) || word == "function";
+        if let Some(feature) = UNSUPPORTED_FEATURES.iter().find(|f| **f == word) {
+            if is_use {
+                return Some(feature);
+            }
+        }
+    }
+
+    None
+}
+
+fn strip_comments_and_strings(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            ('"', _) => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                out.push_str("\"\"");
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
 /// Legacy function for backwards compatibility
 pub fn discover_models(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut models = Vec::new();
@@ -221,4 +421,19 @@ mod tests {
         assert_eq!(tasks.len(), 1);
         assert_eq!(tasks[0].name(), "test.scad");
     }
+
+    #[test]
+    fn test_unsupported_feature() {
+        assert_eq!(unsupported_feature("cube([1, 2, 3]);"), None);
+        assert_eq!(unsupported_feature("text = 1; cube(text, $fn = 8);"), None);
+        assert_eq!(unsupported_feature("// hull() {}\ncube([1, 1, 1]);"), None);
+        assert_eq!(unsupported_feature("echo(\"for (x)\");"), None);
+        assert_eq!(unsupported_feature("assert(true) cube([1, 1, 1]);"), None);
+        assert_eq!(unsupported_feature("polygon([[0, 0], [1, 0], [0, 1]]);"), None);
+        assert_eq!(unsupported_feature("import(\"part.stl\");"), None);
+        assert_eq!(unsupported_feature("polyhedron(points = p, faces = f);"), None);
+        assert_eq!(unsupported_feature("for (i = [0:2]) cube([1, 1, 1]);"), Some("for"));
+        assert_eq!(unsupported_feature("include <lib.scad>"), Some("include"));
+        assert_eq!(unsupported_feature("function f(x) = x;"), Some("function"));
+    }
 }
@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Differential testing of boolean qualities
+//!
+//! Each program is rendered with [`BooleanQuality::Robust`] and [`BooleanQuality::Exact`] and
+//! the results are compared by volume and shell count. No OpenSCAD is needed: both paths see
+//! identically tessellated primitives, so they may only differ by how cut surfaces are snapped
+//! and split. A larger difference means one of the two is wrong.
+
+use crate::ast::Evaluator;
+use crate::geometry::{analyze, analyze_topology, BooleanQuality, Mesh};
+use anyhow::{Context, Result};
+
+/// Tolerances for calling two renders of the same program equivalent
+#[derive(Debug, Clone, Copy)]
+pub struct DifferentialConfig {
+    /// Allowed volume difference as a fraction of the larger volume
+    pub relative_tolerance: f64,
+    /// Allowed volume difference per unit of surface area, for cut surfaces that one path
+    /// snaps or splits differently than the other
+    pub area_tolerance: f64,
+}
+
+impl Default for DifferentialConfig {
+    fn default() -> Self {
+        Self {
+            relative_tolerance: 1e-6,
+            area_tolerance: 1e-6,
+        }
+    }
+}
+
+/// Robust and exact renders of one program
+#[derive(Debug, Clone)]
+pub struct DifferentialCase {
+    pub name: String,
+    pub code: String,
+    pub robust_volume: f64,
+    pub exact_volume: f64,
+    pub robust_shells: usize,
+    pub exact_shells: usize,
+    /// Largest volume difference accepted for this program
+    pub tolerance: f64,
+}
+
+impl DifferentialCase {
+    pub fn volume_difference(&self) -> f64 {
+        (self.robust_volume - self.exact_volume).abs()
+    }
+
+    /// Whether the renders disagree beyond the tolerance or split into different shells
+    pub fn diverges(&self) -> bool {
+        self.volume_difference() > self.tolerance || self.robust_shells != self.exact_shells
+    }
+}
+
+/// Outcome of a differential run
+#[derive(Debug, Clone, Default)]
+pub struct DifferentialReport {
+    pub cases: Vec<DifferentialCase>,
+    /// Programs that failed to parse or render, with the error
+    pub errors: Vec<(String, String)>,
+}
+
+impl DifferentialReport {
+    pub fn divergent(&self) -> impl Iterator<Item = &DifferentialCase> {
+        self.cases.iter().filter(|case| case.diverges())
+    }
+
+    /// One line per divergent case and error, followed by the program that triggered it
+    pub fn describe_failures(&self) -> String {
+        let mut out = String::new();
+        for case in self.divergent() {
+            out.push_str(&format!(
+                "{}: robust volume {:.6} ({} shells), exact volume {:.6} ({} shells), \
+                 tolerance {:.2e}\n{}\n",
+                case.name,
+                case.robust_volume,
+                case.robust_shells,
+                case.exact_volume,
+                case.exact_shells,
+                case.tolerance,
+                case.code
+            ));
+        }
+        for (name, error) in &self.errors {
+            out.push_str(&format!("{}: {}\n", name, error));
+        }
+        out
+    }
+}
+
+/// Render `code` under both qualities and compare the results
+pub fn compare_qualities(
+    name: &str,
+    code: &str,
+    config: &DifferentialConfig,
+) -> Result<DifferentialCase> {
+    let ast = crate::parse_scad(code).context("Failed to parse program")?;
+    let render = |quality| -> Result<Mesh> {
+        Evaluator::with_quality(quality)
+            .evaluate(&ast)
+            .with_context(|| format!("{:?} render failed", quality))
+    };
+    let robust = render(BooleanQuality::Robust)?;
+    let exact = render(BooleanQuality::Exact)?;
+
+    let (robust_stats, exact_stats) = (analyze(&robust), analyze(&exact));
+    let volume = robust_stats.volume.abs().max(exact_stats.volume.abs());
+    let area = robust_stats.surface_area.max(exact_stats.surface_area);
+
+    Ok(DifferentialCase {
+        name: name.to_string(),
+        code: code.to_string(),
+        robust_volume: robust_stats.volume,
+        exact_volume: exact_stats.volume,
+        robust_shells: analyze_topology(&robust).component_count,
+        exact_shells: analyze_topology(&exact).component_count,
+        tolerance: config.relative_tolerance * volume + config.area_tolerance * area,
+    })
+}
+
+/// Compare every `(name, code)` program, e.g. from [`Fuzzer::run_boolean_trees`]
+///
+/// [`Fuzzer::run_boolean_trees`]: super::Fuzzer::run_boolean_trees
+pub fn run_differential(
+    programs: &[(String, String)],
+    config: &DifferentialConfig,
+) -> DifferentialReport {
+    let mut report = DifferentialReport::default();
+    for (name, code) in programs {
+        match compare_qualities(name, code, config) {
+            Ok(case) => report.cases.push(case),
+            Err(e) => report.errors.push((name.clone(), format!("{:#}", e))),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_union_agrees() -> Result<()> {
+        let case = compare_qualities(
+            "overlap",
+            "union() { cube([10, 10, 10]); translate([5, 5, 5]) cube([10, 10, 10]); }",
+            &DifferentialConfig::default(),
+        )?;
+
+        assert!((case.exact_volume - 1875.0).abs() < 1e-6);
+        assert!(!case.diverges(), "{:?}", case);
+        Ok(())
+    }
+
+    #[test]
+    fn test_divergence_is_reported() {
+        let case = DifferentialCase {
+            name: "disjoint".to_string(),
+            code: "intersection() { ... }".to_string(),
+            robust_volume: 84.7,
+            exact_volume: 0.0,
+            robust_shells: 1,
+            exact_shells: 0,
+            tolerance: 1e-4,
+        };
+        let report = DifferentialReport {
+            cases: vec![case],
+            errors: vec![("broken".to_string(), "parse error".to_string())],
+        };
+
+        assert_eq!(report.divergent().count(), 1);
+        let description = report.describe_failures();
+        assert!(description.contains("disjoint: robust volume 84.700000"));
+        assert!(description.contains("intersection() { ... }"));
+        assert!(description.contains("broken: parse error"));
+    }
+}
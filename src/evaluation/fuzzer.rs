@@ -5,13 +5,52 @@
 //! Generates random valid SCAD code to test parser and renderer
 
 use anyhow::Result;
-use rand::Rng;
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Relative frequency of each construct [`Fuzzer::generate`] emits
+///
+/// A zero weight disables a construct. Nodes at `max_depth` are always primitives, and if
+/// every weight is zero the fuzzer emits primitives only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzerWeights {
+    pub primitive: u32,
+    pub translate: u32,
+    pub rotate: u32,
+    pub scale: u32,
+    pub mirror: u32,
+    pub union: u32,
+    pub difference: u32,
+    pub intersection: u32,
+    /// A module definition wrapping a subtree, followed by a call to it
+    pub module: u32,
+}
+
+impl Default for FuzzerWeights {
+    fn default() -> Self {
+        Self {
+            primitive: 4,
+            translate: 1,
+            rotate: 1,
+            scale: 1,
+            mirror: 1,
+            union: 1,
+            difference: 1,
+            intersection: 1,
+            module: 1,
+        }
+    }
+}
 
 /// Fuzzer configuration
 pub struct FuzzerConfig {
     pub count: usize,
     pub max_depth: usize,
     pub max_primitives: usize,
+    /// RNG seed; the same seed and limits always generate the same programs
+    pub seed: u64,
+    pub weights: FuzzerWeights,
 }
 
 impl Default for FuzzerConfig {
@@ -20,6 +59,8 @@ impl Default for FuzzerConfig {
             count: 500,
             max_depth: 5,
             max_primitives: 10,
+            seed: rand::random(),
+            weights: FuzzerWeights::default(),
         }
     }
 }
@@ -27,28 +68,54 @@ impl Default for FuzzerConfig {
 /// Fuzzer for generating random SCAD code
 pub struct Fuzzer {
     config: FuzzerConfig,
-    rng: rand::rngs::ThreadRng,
+    rng: StdRng,
 }
 
 impl Fuzzer {
     pub fn new(config: FuzzerConfig) -> Self {
         Self {
+            rng: StdRng::seed_from_u64(config.seed),
             config,
-            rng: rand::thread_rng(),
         }
     }
 
-    /// Generate a random SCAD program
+    /// Seed this fuzzer was created with, for reproducing a run
+    pub fn seed(&self) -> u64 {
+        self.config.seed
+    }
+
+    /// Generate a random SCAD program, picking constructs by the configured weights
     pub fn generate(&mut self, depth: usize) -> String {
         if depth >= self.config.max_depth {
             return self.generate_primitive();
         }
 
-        match self.rng.gen_range(0..=4) {
-            0 => self.generate_primitive(),
-            1 => self.generate_transform(depth),
-            2 => self.generate_boolean(depth),
-            3 => self.generate_module(depth),
+        let w = self.config.weights;
+        let weights = [
+            w.primitive,
+            w.translate,
+            w.rotate,
+            w.scale,
+            w.mirror,
+            w.union,
+            w.difference,
+            w.intersection,
+            w.module,
+        ];
+        let choice = match WeightedIndex::new(weights) {
+            Ok(distribution) => self.rng.sample(distribution),
+            Err(_) => 0,
+        };
+
+        match choice {
+            1 => self.generate_translate(depth),
+            2 => self.generate_rotate(depth),
+            3 => self.generate_scale(depth),
+            4 => self.generate_mirror(depth),
+            5 => self.generate_boolean(depth, "union"),
+            6 => self.generate_boolean(depth, "difference"),
+            7 => self.generate_boolean(depth, "intersection"),
+            8 => self.generate_module(depth),
             _ => self.generate_primitive(),
         }
     }
@@ -104,20 +171,9 @@ impl Fuzzer {
         format!("cylinder(h={:.2}, r1={:.2}, r2={:.2}, center={}{});", h, r1, r2, center, fn_val)
     }
 
-    /// Generate a random transform
-    fn generate_transform(&mut self, depth: usize) -> String {
-        let child = self.generate(depth + 1);
-        match self.rng.gen_range(0..=3) {
-            0 => self.generate_translate(child),
-            1 => self.generate_rotate(child),
-            2 => self.generate_scale(child),
-            3 => self.generate_mirror(child),
-            _ => self.generate_translate(child),
-        }
-    }
-
     /// Generate translate transform
-    fn generate_translate(&mut self, child: String) -> String {
+    fn generate_translate(&mut self, depth: usize) -> String {
+        let child = self.generate(depth + 1);
         let x = self.rng.gen_range(-50.0..=50.0);
         let y = self.rng.gen_range(-50.0..=50.0);
         let z = self.rng.gen_range(-50.0..=50.0);
@@ -125,7 +181,8 @@ impl Fuzzer {
     }
 
     /// Generate rotate transform
-    fn generate_rotate(&mut self, child: String) -> String {
+    fn generate_rotate(&mut self, depth: usize) -> String {
+        let child = self.generate(depth + 1);
         let x = self.rng.gen_range(0.0..=360.0);
         let y = self.rng.gen_range(0.0..=360.0);
         let z = self.rng.gen_range(0.0..=360.0);
@@ -133,7 +190,8 @@ impl Fuzzer {
     }
 
     /// Generate scale transform
-    fn generate_scale(&mut self, child: String) -> String {
+    fn generate_scale(&mut self, depth: usize) -> String {
+        let child = self.generate(depth + 1);
         if self.rng.gen_bool(0.5) {
             let s = self.rng.gen_range(0.1..=3.0);
             format!("scale({:.2}) {{\n    {}\n}}", s, child)
@@ -146,27 +204,62 @@ impl Fuzzer {
     }
 
     /// Generate mirror transform
-    fn generate_mirror(&mut self, child: String) -> String {
+    fn generate_mirror(&mut self, depth: usize) -> String {
+        let child = self.generate(depth + 1);
         let x = if self.rng.gen_bool(0.5) { 1.0 } else { 0.0 };
         let y = if self.rng.gen_bool(0.5) { 1.0 } else { 0.0 };
         let z = if self.rng.gen_bool(0.5) { 1.0 } else { 0.0 };
         format!("mirror([{:.1}, {:.1}, {:.1}]) {{\n    {}\n}}", x, y, z, child)
     }
 
-    /// Generate a random boolean operation
-    fn generate_boolean(&mut self, depth: usize) -> String {
-        let count = self.rng.gen_range(2..=self.config.max_primitives.min(5));
-        let mut children = Vec::new();
-        for _ in 0..count {
-            children.push(self.generate(depth + 1));
-        }
-        
-        match self.rng.gen_range(0..=2) {
-            0 => format!("union() {{\n    {}\n}}", children.join("\n    ")),
-            1 => format!("difference() {{\n    {}\n    {}\n}}", children[0], children[1]),
-            2 => format!("intersection() {{\n    {}\n}}", children.join("\n    ")),
-            _ => format!("union() {{\n    {}\n}}", children.join("\n    ")),
+    /// Generate a boolean operation over 2 to `max_primitives` (at most 5) random subtrees
+    fn generate_boolean(&mut self, depth: usize, op: &str) -> String {
+        let count = self.rng.gen_range(2..=self.config.max_primitives.clamp(2, 5));
+        let children: Vec<String> = (0..count).map(|_| self.generate(depth + 1)).collect();
+        format!("{}() {{\n    {}\n}}", op, children.join("\n    "))
+    }
+
+    /// Generate a random tree of boolean operations over translated primitives
+    ///
+    /// Unlike [`Fuzzer::generate`], every program is pure CSG with coarse tessellation
+    /// (`$fn` at most 16), so it renders quickly and its volume is meaningful.
+    pub fn generate_boolean_tree(&mut self, depth: usize) -> String {
+        if depth >= self.config.max_depth || (depth > 0 && self.rng.gen_bool(0.4)) {
+            return self.generate_csg_leaf();
         }
+
+        let count = self.rng.gen_range(2..=self.config.max_primitives.clamp(2, 4));
+        let children: Vec<String> = (0..count)
+            .map(|_| self.generate_boolean_tree(depth + 1))
+            .collect();
+        let op = match self.rng.gen_range(0..=2) {
+            0 => "union",
+            1 => "difference",
+            _ => "intersection",
+        };
+        format!("{}() {{\n    {}\n}}", op, children.join("\n    "))
+    }
+
+    /// A box, sphere, or cylinder near the origin, so siblings overlap
+    fn generate_csg_leaf(&mut self) -> String {
+        let primitive = match self.rng.gen_range(0..=2) {
+            0 => {
+                let [x, y, z] = [0; 3].map(|_| self.rng.gen_range(2.0..=12.0));
+                format!("cube([{:.2}, {:.2}, {:.2}], center=true);", x, y, z)
+            }
+            1 => {
+                let r = self.rng.gen_range(1.0..=7.0);
+                format!("sphere(r={:.2}, $fn={});", r, self.rng.gen_range(6..=16))
+            }
+            _ => {
+                let h = self.rng.gen_range(2.0..=12.0);
+                let r = self.rng.gen_range(1.0..=6.0);
+                let fn_ = self.rng.gen_range(6..=16);
+                format!("cylinder(h={:.2}, r={:.2}, center=true, $fn={});", h, r, fn_)
+            }
+        };
+        let [x, y, z] = [0; 3].map(|_| self.rng.gen_range(-4.0..=4.0));
+        format!("translate([{:.2}, {:.2}, {:.2}]) {}", x, y, z, primitive)
     }
 
     /// Generate a random module (simplified - just a wrapper)
@@ -188,6 +281,156 @@ impl Fuzzer {
         
         results
     }
+
+    /// Generate `count` programs with [`Fuzzer::generate_boolean_tree`]
+    pub fn run_boolean_trees(&mut self) -> Vec<(String, String)> {
+        (0..self.config.count)
+            .map(|i| (format!("csg_{:05}", i), self.generate_boolean_tree(0)))
+            .collect()
+    }
+
+    /// Shrink a failing program to a smaller one that still fails
+    ///
+    /// `still_fails` is called on each candidate and returns true if it shows the same
+    /// failure. Candidates drop one statement, or replace a block such as `union() { ... }`
+    /// by one of its children; the first that still fails is kept, until no single step
+    /// does. A program that does not fail to begin with is returned unchanged.
+    pub fn minimize(failing_scad: &str, mut still_fails: impl FnMut(&str) -> bool) -> String {
+        if !still_fails(failing_scad) {
+            return failing_scad.to_string();
+        }
+
+        let mut pos = 0;
+        let mut statements = parse_statements(failing_scad, &mut pos);
+        let mut best = failing_scad.to_string();
+        'shrink: loop {
+            for candidate in reductions(&statements) {
+                let code = render_statements(&candidate);
+                if still_fails(&code) {
+                    statements = candidate;
+                    best = code;
+                    continue 'shrink;
+                }
+            }
+            return best;
+        }
+    }
+}
+
+/// Statement of a program being minimized
+#[derive(Debug, Clone)]
+enum Statement {
+    /// Anything ending in `;`, such as `translate([1, 0, 0]) cube(1);`
+    Leaf(String),
+    /// `head { body }`
+    Block { head: String, body: Vec<Statement> },
+}
+
+/// Split source into statements up to the `}` closing the current block
+///
+/// Only braces, semicolons, strings, and comments are recognized; the text in between is
+/// kept verbatim.
+fn parse_statements(code: &str, pos: &mut usize) -> Vec<Statement> {
+    let bytes = code.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = *pos;
+    let flush = |statements: &mut Vec<Statement>, text: &str| {
+        if !text.trim().is_empty() {
+            statements.push(Statement::Leaf(text.trim().to_string()));
+        }
+    };
+
+    while *pos < bytes.len() {
+        let rest = &code[*pos..];
+        if rest.starts_with("//") {
+            *pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with("/*") {
+            *pos += rest.find("*/").map_or(rest.len(), |end| end + 2);
+            continue;
+        }
+
+        *pos += 1;
+        match bytes[*pos - 1] {
+            b'"' => {
+                // Skip to the closing quote, stepping over escapes
+                while *pos < bytes.len() && bytes[*pos] != b'"' {
+                    *pos += if bytes[*pos] == b'\\' { 2 } else { 1 };
+                }
+                *pos = (*pos + 1).min(bytes.len());
+            }
+            b';' => {
+                flush(&mut statements, &code[start..*pos]);
+                start = *pos;
+            }
+            b'{' => {
+                let head = code[start..*pos - 1].trim().to_string();
+                let body = parse_statements(code, pos);
+                statements.push(Statement::Block { head, body });
+                start = *pos;
+            }
+            b'}' => {
+                flush(&mut statements, &code[start..*pos - 1]);
+                return statements;
+            }
+            _ => {}
+        }
+    }
+
+    flush(&mut statements, &code[start.min(bytes.len())..]);
+    statements
+}
+
+fn render_statements(statements: &[Statement]) -> String {
+    fn render(statements: &[Statement], indent: usize, out: &mut String) {
+        for statement in statements {
+            let pad = "    ".repeat(indent);
+            match statement {
+                Statement::Leaf(text) => out.push_str(&format!("{}{}\n", pad, text)),
+                Statement::Block { head, body } => {
+                    out.push_str(&format!("{}{} {{\n", pad, head));
+                    render(body, indent + 1, out);
+                    out.push_str(&format!("{}}}\n", pad));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    render(statements, 0, &mut out);
+    out
+}
+
+/// Every program one shrinking step away from `statements`, largest cuts first
+fn reductions(statements: &[Statement]) -> Vec<Vec<Statement>> {
+    let mut candidates = Vec::new();
+    for i in 0..statements.len() {
+        let mut without = statements.to_vec();
+        without.remove(i);
+        candidates.push(without);
+    }
+
+    for (i, statement) in statements.iter().enumerate() {
+        let Statement::Block { head, body } = statement else {
+            continue;
+        };
+        for child in body {
+            let mut hoisted = statements.to_vec();
+            hoisted[i] = child.clone();
+            candidates.push(hoisted);
+        }
+        for reduced in reductions(body) {
+            let mut inner = statements.to_vec();
+            inner[i] = Statement::Block {
+                head: head.clone(),
+                body: reduced,
+            };
+            candidates.push(inner);
+        }
+    }
+
+    candidates
 }
 
 /// Test parse success/failure parity between OpenSCAD and Polyframe
@@ -212,6 +455,8 @@ mod tests {
             count: 10,
             max_depth: 3,
             max_primitives: 5,
+            seed: 7,
+            weights: FuzzerWeights::default(),
         };
         let mut fuzzer = Fuzzer::new(config);
         let results = fuzzer.run();
@@ -223,6 +468,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_same_seed_generates_same_programs() {
+        let run = |seed| {
+            Fuzzer::new(FuzzerConfig {
+                count: 20,
+                seed,
+                ..Default::default()
+            })
+            .run()
+        };
+
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    /// Whether a `difference()` opens inside another boolean operation's block
+    fn has_nested_difference(code: &str) -> bool {
+        let mut open_blocks: Vec<&str> = Vec::new();
+        let mut word_start = 0;
+        for (i, c) in code.char_indices() {
+            match c {
+                '{' => {
+                    let head = code[word_start..i].trim();
+                    let name = head.split('(').next().unwrap_or("").trim();
+                    let in_boolean = open_blocks
+                        .iter()
+                        .any(|b| matches!(*b, "union" | "difference" | "intersection"));
+                    if name == "difference" && in_boolean {
+                        return true;
+                    }
+                    open_blocks.push(name);
+                    word_start = i + 1;
+                }
+                '}' => {
+                    open_blocks.pop();
+                    word_start = i + 1;
+                }
+                ';' => word_start = i + 1,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_batch_covers_nested_booleans_and_parses() {
+        let mut fuzzer = Fuzzer::new(FuzzerConfig {
+            count: 300,
+            max_depth: 4,
+            seed: 1,
+            ..Default::default()
+        });
+        let programs = fuzzer.run();
+
+        assert!(programs.iter().any(|(_, code)| has_nested_difference(code)));
+        for (name, code) in &programs {
+            assert!(crate::parse_scad(code).is_ok(), "{} does not parse:\n{}", name, code);
+        }
+        for op in ["translate", "rotate", "scale", "mirror", "union", "intersection"] {
+            assert!(programs.iter().any(|(_, code)| code.contains(op)), "no {}", op);
+        }
+    }
+
+    #[test]
+    fn test_zero_weights_disable_constructs() {
+        let mut fuzzer = Fuzzer::new(FuzzerConfig {
+            count: 50,
+            seed: 3,
+            weights: FuzzerWeights {
+                primitive: 0,
+                translate: 1,
+                union: 1,
+                rotate: 0,
+                scale: 0,
+                mirror: 0,
+                difference: 0,
+                intersection: 0,
+                module: 0,
+            },
+            ..Default::default()
+        });
+
+        for (_, code) in fuzzer.run() {
+            assert!(!code.contains("difference") && !code.contains("module"));
+            assert!(code.starts_with("translate") || code.starts_with("union"));
+        }
+    }
+
+    #[test]
+    fn test_minimize_finds_offending_statement() {
+        let program = r#"
+cube([1, 2, 3]);
+sphere(r=4);
+translate([5, 0, 0]) cylinder(h=2, r=1);
+union() {
+    cube([2, 2, 2]);
+    difference() {
+        sphere(r=3); // keeps a comment { with a brace
+        rotate([0, 0, 45]) {
+            cube([1, 1, 1]);
+            cube([2, 4, 6;
+        }
+    }
+}
+echo("not; a { statement");
+mirror([1, 0, 0]) cube([3, 3, 3]);
+"#;
+        let fails = |code: &str| crate::parse_scad(code).is_err();
+
+        assert_eq!(Fuzzer::minimize(program, fails), "cube([2, 4, 6;\n");
+
+        // A passing program comes back as is
+        let passing = "cube([1, 1, 1]);";
+        assert_eq!(Fuzzer::minimize(passing, fails), passing);
+    }
+
     #[test]
     fn test_generate_primitive() {
         let config = FuzzerConfig::default();
@@ -5,6 +5,7 @@
 
 pub mod comparator;
 pub mod dataset;
+pub mod differential;
 pub mod fuzzer;
 pub mod metrics;
 pub mod regression;
@@ -12,17 +13,34 @@ pub mod reporter;
 pub mod runner;
 pub mod visual_diff;
 
-pub use comparator::{compare_mesh, compare_stl_files, Comparison, DeltaStats, DiffResult};
+pub use comparator::{
+    compare_mesh, compare_meshes_with_options, compare_stl_files, compare_stl_files_with_options,
+    CompareOptions, Comparison, DeltaStats, DiffResult, DiffSide, DivergentCell,
+    DIFF_GRID_RESOLUTION,
+};
 pub use dataset::{
     detect_source, discover_models, load_corpus, load_dataset, CorpusEntry, DatasetSource,
-    Exercise, ModelTask,
+    Exercise, ModelTask, TestsuiteCase,
+};
+pub use differential::{
+    compare_qualities, run_differential, DifferentialCase, DifferentialConfig, DifferentialReport,
 };
-pub use fuzzer::{Fuzzer, FuzzerConfig, test_parse_parity};
+pub use fuzzer::{Fuzzer, FuzzerConfig, FuzzerWeights, test_parse_parity};
 pub use metrics::Metrics;
-pub use regression::{RegressionMetadata, RegressionSuite};
+pub use regression::{
+    RegressionDelta, RegressionMetadata, RegressionSuite, SnapshotOutcome, SnapshotTolerance,
+    SpeedupChange,
+};
 pub use reporter::{EvaluationReport, Reporter};
-pub use runner::{run_and_compare, run_model_task, run_openscad, run_polyframe, RunResult};
-pub use visual_diff::{compare_images, generate_diff_image, render_stl_to_png};
+pub use runner::{
+    run_and_compare, run_and_compare_with_options, run_and_compare_with_timeout, run_model_task,
+    run_model_task_with_timeout, run_openscad, run_polyframe, run_with_timeout, RunResult,
+};
+pub use visual_diff::{
+    compare_images, compare_images_with, compare_presets, generate_diff_image, image_delta,
+    render_mesh, render_mesh_to_png, render_mesh_with_camera, render_stl_to_png, CameraPreset,
+    OrbitCamera, PresetDiff, VisualMetric,
+};
 
 use anyhow::Result;
 use std::path::PathBuf;
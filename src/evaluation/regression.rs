@@ -4,9 +4,13 @@
 //! Regression suite manager
 //! Automatically tracks failed tests and supports regression replay
 
-use anyhow::{Context, Result};
+use super::reporter::EvaluationReport;
+use crate::geometry::clearance::hausdorff_distance;
+use crate::geometry::{signed_volume, Mesh};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -22,9 +26,68 @@ pub struct RegressionMetadata {
     pub polyframe_result: Option<String>,
 }
 
+/// Speedup of one model in two evaluation runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeedupChange {
+    pub model: String,
+    pub old_speedup: f32,
+    pub new_speedup: f32,
+}
+
+/// Differences between two evaluation reports, keyed by model
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegressionDelta {
+    /// Models that passed before and now fail or error
+    pub regressions: Vec<String>,
+    /// Models that failed or errored before and now pass
+    pub fixes: Vec<String>,
+    /// Models evaluated in both runs, with their speedup in each
+    pub speedup_changes: Vec<SpeedupChange>,
+}
+
+impl RegressionDelta {
+    /// Whether the new run broke anything the old one passed
+    pub fn has_regressions(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+}
+
+/// Allowed drift between a mesh and its golden snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTolerance {
+    /// Largest Hausdorff distance between the surfaces, in model units
+    pub distance: f64,
+    /// Largest volume change, as a fraction of the larger volume
+    pub volume: f64,
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self {
+            distance: 1e-6,
+            volume: 1e-6,
+        }
+    }
+}
+
+/// Result of a [`RegressionSuite::snapshot`] that didn't fail
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotOutcome {
+    /// There was no golden yet, so the mesh was written as one at this path
+    Recorded(PathBuf),
+    /// The mesh matched its golden within tolerance
+    Matched {
+        /// Hausdorff distance to the golden
+        distance: f64,
+        /// Volume change as a fraction of the larger volume
+        volume_diff: f64,
+    },
+}
+
 /// Regression suite manager
 pub struct RegressionSuite {
     regressions_dir: PathBuf,
+    snapshot_tolerance: SnapshotTolerance,
 }
 
 impl RegressionSuite {
@@ -32,9 +95,16 @@ impl RegressionSuite {
     pub fn new(regressions_dir: impl AsRef<Path>) -> Self {
         Self {
             regressions_dir: regressions_dir.as_ref().to_path_buf(),
+            snapshot_tolerance: SnapshotTolerance::default(),
         }
     }
 
+    /// Use `tolerance` when comparing meshes against their golden snapshots
+    pub fn with_snapshot_tolerance(mut self, tolerance: SnapshotTolerance) -> Self {
+        self.snapshot_tolerance = tolerance;
+        self
+    }
+
     /// Initialize the regression directory
     pub fn initialize(&self) -> Result<()> {
         fs::create_dir_all(&self.regressions_dir)
@@ -130,17 +200,187 @@ impl RegressionSuite {
         Ok(metadata)
     }
 
+    /// Path of the golden snapshot for `name`, under `golden/` in the regressions directory
+    pub fn golden_path(&self, name: &str) -> PathBuf {
+        self.regressions_dir.join("golden").join(format!("{}.mesh", name))
+    }
+
+    /// Check `mesh` against its golden snapshot, recording one if there is none yet
+    ///
+    /// Goldens are stored canonicalized (welded, with vertices and triangles sorted) so the
+    /// same geometry always writes the same bytes. The comparison itself ignores vertex and
+    /// triangle order: it fails when the Hausdorff distance or relative volume change exceeds
+    /// the suite's [`SnapshotTolerance`], catching drift that leaves counts unchanged. Delete
+    /// the golden file to accept an intended change.
+    pub fn snapshot(&self, name: &str, mesh: &Mesh) -> Result<SnapshotOutcome> {
+        let path = self.golden_path(name);
+        let canonical = canonicalized(mesh);
+
+        if !path.exists() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).context("Failed to create golden directory")?;
+            }
+            fs::write(&path, canonical.to_bytes()?)
+                .context(format!("Failed to write {}", path.display()))?;
+            return Ok(SnapshotOutcome::Recorded(path));
+        }
+
+        let golden = Mesh::from_bytes(
+            &fs::read(&path).context(format!("Failed to read {}", path.display()))?,
+        )
+        .context(format!("Failed to decode {}", path.display()))?;
+
+        let distance = hausdorff_distance(&canonical, &golden);
+        let (volume, golden_volume) = (signed_volume(&canonical), signed_volume(&golden));
+        let larger_volume = volume.abs().max(golden_volume.abs());
+        let volume_diff = if larger_volume > 0.0 {
+            (volume - golden_volume).abs() / larger_volume
+        } else {
+            0.0
+        };
+
+        let tolerance = &self.snapshot_tolerance;
+        if !(distance <= tolerance.distance && volume_diff <= tolerance.volume) {
+            bail!(
+                "Snapshot '{}' drifted from {}: Hausdorff distance {:.3e} (tolerance {:.3e}), \
+                 volume change {:.3e} (tolerance {:.3e})",
+                name,
+                path.display(),
+                distance,
+                tolerance.distance,
+                volume_diff,
+                tolerance.volume
+            );
+        }
+
+        Ok(SnapshotOutcome::Matched {
+            distance,
+            volume_diff,
+        })
+    }
+
+    /// Diff two evaluation reports to find newly broken and newly fixed models
+    ///
+    /// Models present in only one report are ignored. Results are in the order of `new`.
+    pub fn compare_reports(old: &EvaluationReport, new: &EvaluationReport) -> RegressionDelta {
+        let old_results: HashMap<&str, _> =
+            old.results.iter().map(|r| (r.model.as_str(), r)).collect();
+        let old_errors: Vec<&str> = old.error_details.iter().map(|e| e.model.as_str()).collect();
+
+        let mut delta = RegressionDelta::default();
+        for result in &new.results {
+            let model = result.model.as_str();
+            match old_results.get(model) {
+                Some(before) => {
+                    if before.comparison.passed && !result.comparison.passed {
+                        delta.regressions.push(result.model.clone());
+                    } else if !before.comparison.passed && result.comparison.passed {
+                        delta.fixes.push(result.model.clone());
+                    }
+                    delta.speedup_changes.push(SpeedupChange {
+                        model: result.model.clone(),
+                        old_speedup: before.metrics.speedup_ratio,
+                        new_speedup: result.metrics.speedup_ratio,
+                    });
+                }
+                None if result.comparison.passed && old_errors.contains(&model) => {
+                    delta.fixes.push(result.model.clone());
+                }
+                None => {}
+            }
+        }
+
+        // A model that errors out has no result, which is as broken as a failed comparison
+        for error in &new.error_details {
+            if old_results.get(error.model.as_str()).is_some_and(|r| r.comparison.passed) {
+                delta.regressions.push(error.model.clone());
+            }
+        }
+
+        delta
+    }
+
     /// Get the regressions directory path
     pub fn regressions_dir(&self) -> &Path {
         &self.regressions_dir
     }
 }
 
+/// Welded copy of `mesh` with vertices sorted by position and triangles by their indices
+///
+/// Each triangle is rotated to start at its lowest index, preserving its winding.
+fn canonicalized(mesh: &Mesh) -> Mesh {
+    let welded = mesh.as_indexed(1e-9);
+
+    let mut order: Vec<usize> = (0..welded.vertices.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (pa, pb) = (welded.vertices[a].position, welded.vertices[b].position);
+        (0..3).map(|k| pa[k].total_cmp(&pb[k])).find(|o| o.is_ne()).unwrap_or(a.cmp(&b))
+    });
+    let mut remap = vec![0; order.len()];
+    for (new, &old) in order.iter().enumerate() {
+        remap[old] = new;
+    }
+
+    let mut canonical = Mesh::with_capacity(order.len(), welded.triangles.len());
+    for &old in &order {
+        canonical.add_vertex(welded.vertices[old]);
+    }
+    let mut triangles: Vec<[usize; 3]> = welded
+        .triangles
+        .iter()
+        .map(|t| {
+            let mut indices = t.indices.map(|i| remap[i]);
+            let lowest = (0..3).min_by_key(|&k| indices[k]).unwrap();
+            indices.rotate_left(lowest);
+            indices
+        })
+        .collect();
+    triangles.sort_unstable();
+    for indices in triangles {
+        canonical.add_triangle(crate::geometry::Triangle::new(indices));
+    }
+    canonical
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::evaluation::runner::EvaluationResult;
+    use crate::evaluation::{Comparison, Metrics, RunResult};
     use tempfile::TempDir;
 
+    fn result_for(model: &str, passed: bool, speedup: f32) -> EvaluationResult {
+        EvaluationResult {
+            model: model.to_string(),
+            openscad_result: None,
+            polyframe_result: RunResult {
+                file: model.to_string(),
+                time_ms: 10,
+                output_path: PathBuf::from("out.stl"),
+            },
+            comparison: Comparison {
+                vertices_diff: 0.0,
+                triangles_diff: 0.0,
+                bbox_diff: 0.0,
+                checksum_match: passed,
+                passed,
+                vertex_count_poly: 8,
+                vertex_count_openscad: 8,
+                triangle_count_poly: 12,
+                triangle_count_openscad: 12,
+                genus_match: None,
+                component_count_match: true,
+                volume_diff: 0.0,
+            },
+            metrics: Metrics {
+                openscad_time_ms: 20,
+                polyframe_time_ms: 10,
+                speedup_ratio: speedup,
+            },
+        }
+    }
+
     #[test]
     fn test_regression_suite() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -170,5 +410,82 @@ mod tests {
         
         Ok(())
     }
-}
 
+    #[test]
+    fn test_snapshot_catches_geometry_drift() -> Result<()> {
+        use crate::geometry::Primitive;
+        use nalgebra::Vector3;
+
+        let temp_dir = TempDir::new()?;
+        let suite = RegressionSuite::new(temp_dir.path());
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+
+        let SnapshotOutcome::Recorded(golden) = suite.snapshot("cube", &cube)? else {
+            panic!("first snapshot should record a golden");
+        };
+        assert_eq!(golden, temp_dir.path().join("golden/cube.mesh"));
+
+        // Reordering triangles changes nothing geometric
+        let mut reordered = cube.clone();
+        reordered.triangles.reverse();
+        assert_eq!(
+            suite.snapshot("cube", &reordered)?,
+            SnapshotOutcome::Matched {
+                distance: 0.0,
+                volume_diff: 0.0
+            }
+        );
+
+        // Same counts, but one corner pulled outward
+        let mut perturbed = cube.clone();
+        for vertex in &mut perturbed.vertices {
+            if vertex.position == nalgebra::Point3::new(10.0, 10.0, 10.0) {
+                vertex.position.z += 0.1;
+            }
+        }
+        assert_eq!(perturbed.triangle_count(), cube.triangle_count());
+        let error = suite.snapshot("cube", &perturbed).unwrap_err();
+        assert!(error.to_string().contains("drifted"), "{}", error);
+
+        // A looser tolerance accepts the drift
+        let loose = RegressionSuite::new(temp_dir.path()).with_snapshot_tolerance(
+            SnapshotTolerance {
+                distance: 0.2,
+                volume: 0.01,
+            },
+        );
+        assert!(loose.snapshot("cube", &perturbed).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_reports_finds_regressions_and_fixes() {
+        let mut old = EvaluationReport::new();
+        old.add_result(result_for("cube.scad", true, 2.0));
+        old.add_result(result_for("gear.scad", false, 1.5));
+        old.add_result(result_for("hinge.scad", true, 3.0));
+
+        let mut new = EvaluationReport::new();
+        new.add_result(result_for("cube.scad", false, 2.5));
+        new.add_result(result_for("gear.scad", true, 1.0));
+        new.add_error("hinge.scad".to_string(), "parse error".to_string());
+
+        let delta = RegressionSuite::compare_reports(&old, &new);
+
+        assert!(delta.has_regressions());
+        assert_eq!(delta.regressions, vec!["cube.scad", "hinge.scad"]);
+        assert_eq!(delta.fixes, vec!["gear.scad"]);
+        assert_eq!(
+            delta.speedup_changes[0],
+            SpeedupChange {
+                model: "cube.scad".to_string(),
+                old_speedup: 2.0,
+                new_speedup: 2.5,
+            }
+        );
+        assert_eq!(delta.speedup_changes.len(), 2);
+
+        assert!(!RegressionSuite::compare_reports(&old, &old).has_regressions());
+    }
+}
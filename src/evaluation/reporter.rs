@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) 2025 Polyframe Inc.
 
-//! Report generation (JSON and Markdown)
+//! Report generation (JSON, Markdown, and CSV)
 
 use super::runner::EvaluationResult;
 use anyhow::{Context, Result};
@@ -293,20 +293,59 @@ impl Reporter {
         Ok(())
     }
 
-    /// Generate report from existing JSON report file
-    pub fn generate_report(json_path: &Path, output_path: &Path) -> Result<()> {
+    /// Write one CSV row per evaluated model, for spreadsheet analysis
+    ///
+    /// Models that failed to evaluate have no deltas and are left out, as in the JSON results.
+    pub fn write_csv(report: &EvaluationReport, path: &Path) -> Result<()> {
+        let mut csv = String::from(
+            "model,openscad_time_ms,polyframe_time_ms,vertices_diff,triangles_diff,bbox_diff,speedup,passed\n",
+        );
+        for result in &report.results {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                csv_field(&result.model),
+                result.metrics.openscad_time_ms,
+                result.metrics.polyframe_time_ms,
+                result.comparison.vertices_diff,
+                result.comparison.triangles_diff,
+                result.comparison.bbox_diff,
+                result.metrics.speedup_ratio,
+                result.comparison.passed
+            ));
+        }
+
+        fs::write(path, csv).with_context(|| format!("Failed to write CSV report: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Load a report previously written by [`Reporter::write_json`]
+    pub fn read_json(json_path: &Path) -> Result<EvaluationReport> {
         let json_content = fs::read_to_string(json_path)
             .context(format!("Failed to read JSON report: {}", json_path.display()))?;
-        let report: EvaluationReport = serde_json::from_str(&json_content)
-            .context("Failed to parse JSON report")?;
+        serde_json::from_str(&json_content).context("Failed to parse JSON report")
+    }
+
+    /// Generate report from existing JSON report file
+    pub fn generate_report(json_path: &Path, output_path: &Path) -> Result<()> {
+        let report = Self::read_json(json_path)?;
         Self::write_markdown(&report, output_path)?;
         Ok(())
     }
 }
 
+/// Quote a CSV field if it contains a separator, quote, or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::evaluation::{Comparison, Metrics, RunResult};
 
     #[test]
     fn test_report_creation() {
@@ -315,4 +354,64 @@ mod tests {
         assert_eq!(report.passed, 0);
         assert_eq!(report.failed, 0);
     }
+
+    fn result_for(model: &str, passed: bool) -> EvaluationResult {
+        EvaluationResult {
+            model: model.to_string(),
+            openscad_result: None,
+            polyframe_result: RunResult {
+                file: model.to_string(),
+                time_ms: 12,
+                output_path: "out.stl".into(),
+            },
+            comparison: Comparison {
+                vertices_diff: 0.125,
+                triangles_diff: 0.5,
+                bbox_diff: 1e-6,
+                checksum_match: false,
+                passed,
+                vertex_count_poly: 8,
+                vertex_count_openscad: 8,
+                triangle_count_poly: 12,
+                triangle_count_openscad: 12,
+                genus_match: None,
+                component_count_match: true,
+                volume_diff: 0.0,
+            },
+            metrics: Metrics {
+                openscad_time_ms: 30,
+                polyframe_time_ms: 12,
+                speedup_ratio: 2.5,
+            },
+        }
+    }
+
+    #[test]
+    fn test_csv_has_a_row_per_result() -> Result<()> {
+        let mut report = EvaluationReport::new();
+        report.add_result(result_for("models/cube.scad", true));
+        report.add_result(result_for("models/gear, \"metric\".scad", false));
+        report.add_error("models/broken.scad".to_string(), "parse error".to_string());
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("report.csv");
+        Reporter::write_csv(&report, &path)?;
+
+        let csv = fs::read_to_string(&path)?;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), report.results.len() + 1);
+        assert!(lines[0].starts_with("model,openscad_time_ms,"));
+        assert!(lines[2].starts_with("\"models/gear, \"\"metric\"\".scad\","));
+
+        for line in &lines[1..] {
+            // Only the model can contain commas, so split the other columns off the end
+            let fields: Vec<&str> = line.rsplitn(8, ',').collect();
+            for number in &fields[1..7] {
+                number.parse::<f64>()?;
+            }
+            fields[0].parse::<bool>()?;
+        }
+
+        Ok(())
+    }
 }
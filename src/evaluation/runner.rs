@@ -3,12 +3,13 @@
 
 //! Process runner for OpenSCAD and Polyframe
 
-use super::{comparator, Comparison, Metrics};
-use anyhow::{bail, Context, Result};
+use super::{comparator, CompareOptions, Comparison, Metrics};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 use tempfile::TempDir;
@@ -119,6 +120,14 @@ pub fn run_polyframe(file: &Path) -> Result<RunResult> {
 
 /// Run Polyframe on a .scad file with custom timeout
 pub fn run_polyframe_with_timeout(file: &Path, timeout: Duration) -> Result<RunResult> {
+    let file = file.to_path_buf();
+    run_with_timeout(timeout, move |cancel| {
+        run_polyframe_with_cancellation(&file, cancel)
+    })
+}
+
+/// Run Polyframe on a .scad file, stopping early once `cancel` is set
+fn run_polyframe_with_cancellation(file: &Path, cancel: Arc<AtomicBool>) -> Result<RunResult> {
     // Create output directory if it doesn't exist
     let output_dir = PathBuf::from("tests/evaluation/outputs/polyframe");
     std::fs::create_dir_all(&output_dir)?;
@@ -130,35 +139,18 @@ pub fn run_polyframe_with_timeout(file: &Path, timeout: Duration) -> Result<RunR
 
     let start = Instant::now();
 
-    // Run in a separate thread with timeout
-    let file_path = file.to_path_buf();
-    let output_path_clone = output_path.clone();
-    let (tx, rx) = mpsc::channel();
-
-    thread::spawn(move || {
-        let result = (|| -> Result<()> {
-            // Use the library directly
-            let mesh = crate::render_file(file_path.to_str().unwrap())
-                .context("Failed to render with Polyframe")?;
-
-            crate::io::export_stl(&mesh, output_path_clone.to_str().unwrap())
-                .context("Failed to export STL")?;
-
-            Ok(())
-        })();
-        let _ = tx.send(result);
-    });
+    // Use the library directly
+    let ast = crate::import_scad_file(file.to_str().unwrap())
+        .context("Failed to render with Polyframe")?;
+    let mesh = crate::ast::Evaluator::new()
+        .with_cancellation(cancel)
+        .evaluate(&ast)
+        .context("Failed to render with Polyframe")?;
 
-    let result = match rx.recv_timeout(timeout) {
-        Ok(Ok(())) => Ok(()),
-        Ok(Err(e)) => Err(e),
-        Err(_) => bail!("Polyframe render timed out after {:?}", timeout),
-    };
+    crate::io::export_stl(&mesh, output_path.to_str().unwrap()).context("Failed to export STL")?;
 
     let time_ms = start.elapsed().as_millis();
 
-    result?;
-
     Ok(RunResult {
         file: file.display().to_string(),
         time_ms,
@@ -166,17 +158,79 @@ pub fn run_polyframe_with_timeout(file: &Path, timeout: Duration) -> Result<RunR
     })
 }
 
+/// Run `task` on a worker thread, cancelling it with a "timed out" error after `timeout`
+///
+/// `task` receives a flag that is set once the deadline passes, to hand to an evaluator's
+/// `with_cancellation`. The worker is joined before the error is returned, so a task that
+/// never checks the flag still runs to completion.
+pub fn run_with_timeout<T, F>(timeout: Duration, task: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(Arc<AtomicBool>) -> Result<T> + Send + 'static,
+{
+    let cancel = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let _ = tx.send(task(cancel));
+        })
+    };
+
+    let result = match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancel.store(true, Ordering::Relaxed);
+            Err(anyhow!("Task timed out after {:?}", timeout))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(anyhow!("Task panicked before finishing")),
+    };
+    let _ = worker.join();
+    result
+}
+
 /// Run both renderers and compare outputs (legacy path-based)
 pub fn run_and_compare(file: &Path) -> Result<EvaluationResult> {
+    run_and_compare_with_options(file, &CompareOptions::default())
+}
+
+/// Run both renderers and compare outputs, optionally requiring matching topology
+pub fn run_and_compare_with_options(
+    file: &Path,
+    options: &CompareOptions,
+) -> Result<EvaluationResult> {
     // Run Polyframe (always)
     let polyframe_result = run_polyframe(file).context("Polyframe execution failed")?;
+    compare_with_openscad(file, options, polyframe_result)
+}
+
+/// [`run_and_compare_with_options`], stopping the Polyframe render once `cancel` is set
+pub(crate) fn run_and_compare_with_cancellation(
+    file: &Path,
+    options: &CompareOptions,
+    cancel: Arc<AtomicBool>,
+) -> Result<EvaluationResult> {
+    let polyframe_result =
+        run_polyframe_with_cancellation(file, cancel).context("Polyframe execution failed")?;
+    compare_with_openscad(file, options, polyframe_result)
+}
 
+/// Run OpenSCAD on `file` and compare its output with `polyframe_result`
+fn compare_with_openscad(
+    file: &Path,
+    options: &CompareOptions,
+    polyframe_result: RunResult,
+) -> Result<EvaluationResult> {
     // Try to run OpenSCAD (optional)
     let openscad_result = run_openscad(file).ok();
 
     // Compare if we have both outputs
     let comparison = if let Some(ref openscad) = openscad_result {
-        comparator::compare_stl_files(&polyframe_result.output_path, &openscad.output_path)?
+        comparator::compare_stl_files_with_options(
+            &polyframe_result.output_path,
+            &openscad.output_path,
+            options,
+        )?
     } else {
         // No OpenSCAD available, create a "passed" comparison
         Comparison {
@@ -189,6 +243,9 @@ pub fn run_and_compare(file: &Path) -> Result<EvaluationResult> {
             vertex_count_openscad: 0,
             triangle_count_poly: 0,
             triangle_count_openscad: 0,
+            genus_match: None,
+            component_count_match: true,
+            volume_diff: 0.0,
         }
     };
 
@@ -215,14 +272,45 @@ pub fn run_and_compare(file: &Path) -> Result<EvaluationResult> {
     })
 }
 
+/// Run both renderers and compare outputs, giving up after `timeout`
+pub fn run_and_compare_with_timeout(
+    file: &Path,
+    options: &CompareOptions,
+    timeout: Duration,
+) -> Result<EvaluationResult> {
+    let (file, options) = (file.to_path_buf(), *options);
+    run_with_timeout(timeout, move |cancel| {
+        run_and_compare_with_cancellation(&file, &options, cancel)
+    })
+}
+
+/// Run model task, giving up after `timeout`
+pub fn run_model_task_with_timeout(
+    task: &super::dataset::ModelTask,
+    timeout: Duration,
+) -> Result<EvaluationResult> {
+    let task = task.clone();
+    run_with_timeout(timeout, move |cancel| {
+        run_model_task_with_cancellation(&task, cancel)
+    })
+}
+
 /// Run model task (supports both file and JSON sources)
 pub fn run_model_task(task: &super::dataset::ModelTask) -> Result<EvaluationResult> {
+    run_model_task_with_cancellation(task, Arc::default())
+}
+
+/// [`run_model_task`], stopping the Polyframe render once `cancel` is set
+pub(crate) fn run_model_task_with_cancellation(
+    task: &super::dataset::ModelTask,
+    cancel: Arc<AtomicBool>,
+) -> Result<EvaluationResult> {
     let name = task.name();
     let source = task.source()?;
 
     // Run Polyframe from source
     let polyframe_result =
-        run_polyframe_from_source(&name, &source).context("Polyframe execution failed")?;
+        run_polyframe_from_source(&name, &source, cancel).context("Polyframe execution failed")?;
 
     // Try to run OpenSCAD from source (optional)
     let openscad_result = run_openscad_from_source(&name, &source).ok();
@@ -242,6 +330,9 @@ pub fn run_model_task(task: &super::dataset::ModelTask) -> Result<EvaluationResu
             vertex_count_openscad: 0,
             triangle_count_poly: 0,
             triangle_count_openscad: 0,
+            genus_match: None,
+            component_count_match: true,
+            volume_diff: 0.0,
         }
     };
 
@@ -268,8 +359,12 @@ pub fn run_model_task(task: &super::dataset::ModelTask) -> Result<EvaluationResu
     })
 }
 
-/// Run Polyframe from source string
-fn run_polyframe_from_source(name: &str, source: &str) -> Result<RunResult> {
+/// Run Polyframe from source string, stopping early once `cancel` is set
+fn run_polyframe_from_source(
+    name: &str,
+    source: &str,
+    cancel: Arc<AtomicBool>,
+) -> Result<RunResult> {
     let output_dir = PathBuf::from("tests/evaluation/outputs/polyframe");
     std::fs::create_dir_all(&output_dir)?;
     
@@ -281,7 +376,8 @@ fn run_polyframe_from_source(name: &str, source: &str) -> Result<RunResult> {
     let start = Instant::now();
 
     // Render from source
-    let mesh = crate::render(source).context("Failed to render with Polyframe")?;
+    let mesh = crate::render_with_cancellation(source, cancel)
+        .context("Failed to render with Polyframe")?;
 
     crate::io::export_stl(&mesh, output_path.to_str().unwrap()).context("Failed to export STL")?;
 
@@ -1,19 +1,69 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) 2025 Polyframe Inc.
 //
-//! Visual diff rendering for STL files
+//! Visual diff rendering for STL files and meshes
 //! Renders STL files to PNG images and compares them pixel-by-pixel
 //
 use anyhow::{bail, Context, Result};
-use image::{Rgb, RgbImage};
-use nalgebra::{Rotation3, Vector2, Vector3};
+use crate::geometry::{BoundingBox as MeshBounds, Mesh};
+use image::{DynamicImage, GenericImage, ImageFormat, Rgb, RgbImage, Rgba, RgbaImage};
+use nalgebra::{Point3, Rotation3, Vector2, Vector3};
 use std::fs::File;
+use std::io::Cursor;
 use std::path::Path;
 use stl_io::read_stl;
 
 const DEFAULT_WIDTH: u32 = 1024;
 const DEFAULT_HEIGHT: u32 = 768;
 
+/// Background color of rendered previews
+const BACKGROUND: Rgba<u8> = Rgba([15, 18, 26, 255]);
+
+/// Field of view of [`OrbitCamera`] renders across the shorter image side, in degrees
+const FIELD_OF_VIEW: f64 = 30.0;
+
+/// Perspective camera orbiting the center of a mesh's bounding box
+///
+/// Z is up. At zero azimuth and elevation the camera looks along +Y at the front of the
+/// model, as OpenSCAD's front view does; positive azimuth swings it counterclockwise seen
+/// from above, so 90 looks at the right side, and elevation 90 looks straight down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCamera {
+    /// Angle around the Z axis, in degrees
+    pub azimuth: f64,
+    /// Angle above the XY plane, in degrees
+    pub elevation: f64,
+    /// Distance from the bounding box center, in model units
+    pub distance: f64,
+}
+
+impl OrbitCamera {
+    pub fn new(azimuth: f64, elevation: f64, distance: f64) -> Self {
+        Self {
+            azimuth,
+            elevation,
+            distance,
+        }
+    }
+
+    /// Camera at the given angles, just far enough back to keep all of `mesh` in view
+    pub fn framing(mesh: &Mesh, azimuth: f64, elevation: f64) -> Self {
+        Self::new(azimuth, elevation, framing_distance(&mesh.bounding_box()))
+    }
+
+    /// Unit vectors pointing right, up and forward in the image, in model space
+    fn basis(&self) -> [Vector3<f64>; 3] {
+        let (azimuth, elevation) = (self.azimuth.to_radians(), self.elevation.to_radians());
+        let forward = -Vector3::new(
+            azimuth.sin() * elevation.cos(),
+            -azimuth.cos() * elevation.cos(),
+            elevation.sin(),
+        );
+        let right = Vector3::new(azimuth.cos(), azimuth.sin(), 0.0);
+        [right, right.cross(&forward), forward]
+    }
+}
+
 /// Render an STL mesh to a PNG image using a lightweight orthographic renderer.
 /// The renderer performs simple triangle rasterization with depth buffering so
 /// that developers can visually spot-check OpenSCAD vs Polyframe outputs.
@@ -34,28 +84,268 @@ pub fn render_stl_to_png(stl_path: &Path, output_png: &Path) -> Result<()> {
         );
     }
 
-    let rotation = Rotation3::from_euler_angles(-0.9, 0.8, 0.3);
-    let transformed: Vec<Vector3<f32>> = stl
+    let positions: Vec<Vector3<f32>> = stl
+        .vertices
+        .iter()
+        .map(|v| Vector3::new(v[0], v[1], v[2]))
+        .collect();
+    let faces: Vec<[usize; 3]> = stl.faces.iter().map(|face| face.vertices).collect();
+
+    let image = render_triangles(&positions, &faces, DEFAULT_WIDTH, DEFAULT_HEIGHT);
+
+    DynamicImage::ImageRgba8(image)
+        .to_rgb8()
+        .save(output_png)
+        .with_context(|| format!("Failed to save PNG to {}", output_png.display()))?;
+
+    Ok(())
+}
+
+/// Render a mesh to an RGBA image without any external tool
+///
+/// Uses the same fixed camera, Lambert shading, and z-buffered rasterizer as
+/// [`render_stl_to_png`], so renders are deterministic across machines.
+pub fn render_mesh(mesh: &Mesh, width: u32, height: u32) -> Result<RgbaImage> {
+    if mesh.triangles.is_empty() {
+        bail!("Mesh does not contain any triangles to render");
+    }
+    if width == 0 || height == 0 {
+        bail!("Render size must be non-zero, got {}x{}", width, height);
+    }
+    mesh.check_triangle_indices()?;
+
+    let positions: Vec<Vector3<f32>> = mesh
+        .vertices
+        .iter()
+        .map(|v| {
+            Vector3::new(
+                v.position.x as f32,
+                v.position.y as f32,
+                v.position.z as f32,
+            )
+        })
+        .collect();
+    let faces: Vec<[usize; 3]> = mesh.triangles.iter().map(|t| t.indices).collect();
+
+    Ok(render_triangles(&positions, &faces, width, height))
+}
+
+/// Distance at which the whole of `bounds` fits in an [`OrbitCamera`]'s view from any angle
+fn framing_distance(bounds: &MeshBounds) -> f64 {
+    let radius = (bounds.size().norm() / 2.0).max(1e-6);
+    1.05 * radius / (FIELD_OF_VIEW / 2.0).to_radians().sin()
+}
+
+/// Standard viewpoints for visual comparisons, so no side of a model goes unchecked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CameraPreset {
+    Isometric,
+    Front,
+    Top,
+    Right,
+}
+
+impl CameraPreset {
+    /// Every preset, in the order they're laid out in montages
+    pub const ALL: [CameraPreset; 4] = [
+        CameraPreset::Isometric,
+        CameraPreset::Front,
+        CameraPreset::Top,
+        CameraPreset::Right,
+    ];
+
+    /// Azimuth and elevation in degrees, as for [`OrbitCamera`]
+    pub fn angles(self) -> (f64, f64) {
+        match self {
+            // Elevation where the three axes appear equally foreshortened
+            CameraPreset::Isometric => (45.0, 35.264),
+            CameraPreset::Front => (0.0, 0.0),
+            CameraPreset::Top => (0.0, 90.0),
+            CameraPreset::Right => (90.0, 0.0),
+        }
+    }
+
+    /// Camera from this preset's direction keeping all of `mesh` in view
+    pub fn camera(self, mesh: &Mesh) -> OrbitCamera {
+        let (azimuth, elevation) = self.angles();
+        OrbitCamera::framing(mesh, azimuth, elevation)
+    }
+}
+
+/// Renders of two meshes from every [`CameraPreset`], and where they differ
+#[derive(Debug, Clone)]
+pub struct PresetDiff {
+    /// Delta of each preset's view as scored by the [`VisualMetric`], in [`CameraPreset::ALL`]
+    /// order
+    pub per_preset: Vec<(CameraPreset, f32)>,
+    /// Largest per-preset delta, so a difference visible from only one side counts in full
+    pub delta: f32,
+    /// 2x2 montage of the first mesh's views
+    pub openscad: RgbaImage,
+    /// 2x2 montage of the second mesh's views
+    pub polyframe: RgbaImage,
+    /// 2x2 montage with differing pixels in red over the first mesh in gray
+    pub diff: RgbImage,
+}
+
+/// Render both meshes from every [`CameraPreset`] into `tile_size` square views and diff them
+///
+/// Both meshes are framed by the union of their bounding boxes, so geometry present in only
+/// one of them shows up as a difference instead of rescaling the whole view. Each view is
+/// scored by `metric`, while the diff montage always marks every differing pixel.
+pub fn compare_presets(
+    openscad_mesh: &Mesh,
+    polyframe_mesh: &Mesh,
+    tile_size: u32,
+    metric: VisualMetric,
+) -> Result<PresetDiff> {
+    let mut bounds = openscad_mesh.bounding_box();
+    for corner in [polyframe_mesh.bounding_box().min, polyframe_mesh.bounding_box().max] {
+        bounds.expand_to_include(&corner);
+    }
+    let target = bounds.center();
+    let distance = framing_distance(&bounds);
+
+    let montage = || RgbaImage::from_pixel(tile_size * 2, tile_size * 2, BACKGROUND);
+    let (mut openscad, mut polyframe) = (montage(), montage());
+    let mut diff = RgbImage::new(tile_size * 2, tile_size * 2);
+    let mut per_preset = Vec::with_capacity(CameraPreset::ALL.len());
+
+    for (k, preset) in CameraPreset::ALL.into_iter().enumerate() {
+        let (azimuth, elevation) = preset.angles();
+        let camera = OrbitCamera::new(azimuth, elevation, distance);
+        let render = |mesh| render_view(mesh, &camera, target, tile_size, tile_size);
+        let (openscad_view, polyframe_view) = (render(openscad_mesh)?, render(polyframe_mesh)?);
+        let (diff_view, _) = diff_pixels(&openscad_view, &polyframe_view);
+
+        let (x, y) = ((k as u32 % 2) * tile_size, (k as u32 / 2) * tile_size);
+        openscad.copy_from(&openscad_view, x, y)?;
+        polyframe.copy_from(&polyframe_view, x, y)?;
+        diff.copy_from(&diff_view, x, y)?;
+        per_preset.push((preset, image_delta(&openscad_view, &polyframe_view, metric)));
+    }
+
+    Ok(PresetDiff {
+        delta: per_preset.iter().map(|(_, delta)| *delta).fold(0.0, f32::max),
+        per_preset,
+        openscad,
+        polyframe,
+        diff,
+    })
+}
+
+/// Render a mesh through `camera` and encode it as PNG
+///
+/// A pure-Rust z-buffered rasterizer with flat Lambert shading, lit from just above and left
+/// of the camera, so visual diffs need no OpenSCAD install. Triangles reaching behind the
+/// camera are skipped rather than clipped.
+pub fn render_mesh_to_png(
+    mesh: &Mesh,
+    camera: &OrbitCamera,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>> {
+    let image = render_mesh_with_camera(mesh, camera, width, height)?;
+    let mut png = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(image)
+        .to_rgb8()
+        .write_to(&mut png, ImageFormat::Png)
+        .context("Failed to encode PNG")?;
+    Ok(png.into_inner())
+}
+
+/// Render a mesh through `camera` to an RGBA image, see [`render_mesh_to_png`]
+pub fn render_mesh_with_camera(
+    mesh: &Mesh,
+    camera: &OrbitCamera,
+    width: u32,
+    height: u32,
+) -> Result<RgbaImage> {
+    render_view(mesh, camera, mesh.bounding_box().center(), width, height)
+}
+
+/// Render a mesh through `camera` orbiting `target`
+fn render_view(
+    mesh: &Mesh,
+    camera: &OrbitCamera,
+    target: Point3<f64>,
+    width: u32,
+    height: u32,
+) -> Result<RgbaImage> {
+    if mesh.triangles.is_empty() {
+        bail!("Mesh does not contain any triangles to render");
+    }
+    if width == 0 || height == 0 {
+        bail!("Render size must be non-zero, got {}x{}", width, height);
+    }
+    mesh.check_triangle_indices()?;
+
+    let [right, up, forward] = camera.basis();
+    let eye = target - forward * camera.distance;
+    let focal = width.min(height) as f64 / 2.0 / (FIELD_OF_VIEW / 2.0).to_radians().tan();
+    let center = Vector2::new(width as f64 / 2.0, height as f64 / 2.0);
+
+    // Camera-space position of every vertex, with z the distance in front of the camera
+    let view: Vec<Vector3<f64>> = mesh
         .vertices
         .iter()
-        .map(|v| rotation * Vector3::new(v[0], v[1], v[2]))
+        .map(|v| {
+            let offset = v.position - eye;
+            Vector3::new(offset.dot(&right), offset.dot(&up), offset.dot(&forward))
+        })
+        .collect();
+    let projected: Vec<Vector2<f32>> = view
+        .iter()
+        .map(|p| {
+            let screen = center + Vector2::new(p.x, -p.y) * (focal / p.z);
+            Vector2::new(screen.x as f32, screen.y as f32)
+        })
         .collect();
 
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+    let mut depth_buffer = vec![f32::NEG_INFINITY; (width * height) as usize];
+    let light_dir = Vector3::new(-0.3, 0.5, -1.0).normalize();
+
+    for triangle in &mesh.triangles {
+        let [i0, i1, i2] = triangle.indices;
+        let corners = [view[i0], view[i1], view[i2]];
+        if corners.iter().any(|p| p.z <= 1e-9) {
+            continue;
+        }
+
+        let normal = (corners[1] - corners[0]).cross(&(corners[2] - corners[0]));
+        if normal.norm_squared() < 1e-12 {
+            continue;
+        }
+        let intensity = normal.normalize().dot(&light_dir).abs().clamp(0.05, 1.0) as f32;
+
+        // Reciprocal depth interpolates linearly in screen space, and is larger when closer
+        let depths = corners.map(|p| (1.0 / p.z) as f32);
+        let pts = [projected[i0], projected[i1], projected[i2]];
+        rasterize_triangle(&mut image, &mut depth_buffer, pts, depths, intensity);
+    }
+
+    Ok(image)
+}
+
+fn render_triangles(
+    positions: &[Vector3<f32>],
+    faces: &[[usize; 3]],
+    width: u32,
+    height: u32,
+) -> RgbaImage {
+    let rotation = Rotation3::from_euler_angles(-0.9, 0.8, 0.3);
+    let transformed: Vec<Vector3<f32>> = positions.iter().map(|p| rotation * p).collect();
+
     let bounds = BoundingBox::from_points(&transformed);
-    let projected = project_vertices(&transformed, &bounds);
+    let projected = project_vertices(&transformed, &bounds, width, height);
     let depths: Vec<f32> = transformed.iter().map(|p| -p.z).collect();
 
-    let mut image =
-        RgbImage::from_pixel(DEFAULT_WIDTH, DEFAULT_HEIGHT, Rgb([15, 18, 26])); // dark background
-    let mut depth_buffer = vec![f32::NEG_INFINITY; (DEFAULT_WIDTH * DEFAULT_HEIGHT) as usize];
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+    let mut depth_buffer = vec![f32::NEG_INFINITY; (width * height) as usize];
     let light_dir = Vector3::new(0.35, 0.55, 1.0).normalize();
 
-    for face in &stl.faces {
-        let indices = face.vertices;
-        let i0 = indices[0] as usize;
-        let i1 = indices[1] as usize;
-        let i2 = indices[2] as usize;
-
+    for &[i0, i1, i2] in faces {
         let v0 = transformed[i0];
         let v1 = transformed[i1];
         let v2 = transformed[i2];
@@ -85,50 +375,114 @@ pub fn render_stl_to_png(stl_path: &Path, output_png: &Path) -> Result<()> {
     }
 
     image
-        .save(output_png)
-        .with_context(|| format!("Failed to save PNG to {}", output_png.display()))?;
+}
 
-    Ok(())
+/// How two renders are scored against each other
+///
+/// Both give a percentage, 0 for identical images and 100 for images of different sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VisualMetric {
+    /// Percentage of pixels where any channel differs by more than 5 levels
+    ///
+    /// Sensitive to anti-aliasing and shading noise along edges.
+    PerPixel,
+    /// One minus the mean structural similarity (SSIM) of luminance over 8x8 windows, as a
+    /// percentage
+    ///
+    /// Compares local structure rather than exact values, so edges shifted or blended by a
+    /// pixel barely register while missing or extra shapes do.
+    #[default]
+    Ssim,
+}
+
+impl VisualMetric {
+    /// Largest delta at which two renders still count as the same
+    pub fn tolerance(self) -> f32 {
+        match self {
+            VisualMetric::PerPixel => 0.5,
+            VisualMetric::Ssim => 5.0,
+        }
+    }
+
+    /// Whether `delta`, as scored by this metric, is within [`tolerance`](Self::tolerance)
+    pub fn passes(self, delta: f32) -> bool {
+        delta <= self.tolerance()
+    }
 }
 
+/// Side of the windows SSIM is computed over
+const SSIM_WINDOW: u32 = 8;
+
 /// Compare two PNG images and return pixel delta percentage
 pub fn compare_images(png1: &Path, png2: &Path) -> Result<f32> {
-    use image::GenericImageView;
+    compare_images_with(png1, png2, VisualMetric::PerPixel)
+}
 
+/// Compare two PNG images and return their delta as scored by `metric`
+pub fn compare_images_with(png1: &Path, png2: &Path, metric: VisualMetric) -> Result<f32> {
     let img1 = image::open(png1)
         .context(format!("Failed to open image: {}", png1.display()))?;
     let img2 = image::open(png2)
         .context(format!("Failed to open image: {}", png2.display()))?;
 
-    let (width1, height1) = img1.dimensions();
-    let (width2, height2) = img2.dimensions();
+    Ok(image_delta(&img1.to_rgba8(), &img2.to_rgba8(), metric))
+}
 
-    if width1 != width2 || height1 != height2 {
-        return Ok(100.0); // 100% different if dimensions don't match
+/// Delta between two rendered images as scored by `metric`
+pub fn image_delta(img1: &RgbaImage, img2: &RgbaImage, metric: VisualMetric) -> f32 {
+    if img1.dimensions() != img2.dimensions() {
+        return 100.0; // 100% different if dimensions don't match
     }
 
-    let mut diff_pixels = 0u64;
-    let total_pixels = (width1 * height1) as u64;
-
-    for y in 0..height1 {
-        for x in 0..width1 {
-            let pixel1 = img1.get_pixel(x, y);
-            let pixel2 = img2.get_pixel(x, y);
+    match metric {
+        VisualMetric::PerPixel => {
+            let total_pixels = (img1.width() * img1.height()) as f32;
+            diff_pixels(img1, img2).1 as f32 / total_pixels * 100.0
+        }
+        VisualMetric::Ssim => ((1.0 - mean_ssim(img1, img2)) * 100.0).max(0.0) as f32,
+    }
+}
 
-            // Calculate RGB difference
-            let r_diff = (pixel1[0] as i32 - pixel2[0] as i32).abs();
-            let g_diff = (pixel1[1] as i32 - pixel2[1] as i32).abs();
-            let b_diff = (pixel1[2] as i32 - pixel2[2] as i32).abs();
+/// Mean SSIM of the luminance of two equally sized images, over windows at half-window steps
+///
+/// Images smaller than a window are compared as a single window.
+fn mean_ssim(img1: &RgbaImage, img2: &RgbaImage) -> f64 {
+    // Stabilizing constants from Wang et al. for 8-bit channels
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
 
-            // Consider pixels different if any channel differs by more than threshold
-            if r_diff > 5 || g_diff > 5 || b_diff > 5 {
-                diff_pixels += 1;
+    let luma = |p: &Rgba<u8>| 0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64;
+    let (width, height) = img1.dimensions();
+    let (window_w, window_h) = (SSIM_WINDOW.min(width), SSIM_WINDOW.min(height));
+    let starts = |size: u32, window: u32| (0..=size - window).step_by((window as usize / 2).max(1));
+
+    let mut total = 0.0;
+    let mut windows = 0;
+    for y0 in starts(height, window_h) {
+        for x0 in starts(width, window_w) {
+            let (mut sum1, mut sum2, mut sq1, mut sq2, mut cross) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for y in y0..y0 + window_h {
+                for x in x0..x0 + window_w {
+                    let (a, b) = (luma(img1.get_pixel(x, y)), luma(img2.get_pixel(x, y)));
+                    sum1 += a;
+                    sum2 += b;
+                    sq1 += a * a;
+                    sq2 += b * b;
+                    cross += a * b;
+                }
             }
+
+            let n = (window_w * window_h) as f64;
+            let (mean1, mean2) = (sum1 / n, sum2 / n);
+            let (var1, var2) = (sq1 / n - mean1 * mean1, sq2 / n - mean2 * mean2);
+            let covariance = cross / n - mean1 * mean2;
+            total += ((2.0 * mean1 * mean2 + C1) * (2.0 * covariance + C2))
+                / ((mean1 * mean1 + mean2 * mean2 + C1) * (var1 + var2 + C2));
+            windows += 1;
         }
     }
 
-    let delta_pct = (diff_pixels as f32 / total_pixels as f32) * 100.0;
-    Ok(delta_pct)
+    total / windows as f64
 }
 
 /// Generate visual diff image showing differences between two STL renders
@@ -137,27 +491,38 @@ pub fn generate_diff_image(
     polyframe_png: &Path,
     output_diff: &Path,
 ) -> Result<f32> {
-    use image::{GenericImageView, ImageBuffer, Rgb, RgbImage};
-
     let img1 = image::open(openscad_png)
         .context(format!("Failed to open image: {}", openscad_png.display()))?;
     let img2 = image::open(polyframe_png)
         .context(format!("Failed to open image: {}", polyframe_png.display()))?;
 
-    let (width, height) = img1.dimensions();
+    let (diff_img, diff_pixels) = diff_pixels(&img1.to_rgba8(), &img2.to_rgba8());
+    let total_pixels = (diff_img.width() * diff_img.height()) as u64;
 
-    let mut diff_img: RgbImage = ImageBuffer::new(width, height);
+    diff_img
+        .save(output_diff)
+        .context(format!("Failed to save diff image to {}", output_diff.display()))?;
+
+    let delta_pct = (diff_pixels as f32 / total_pixels as f32) * 100.0;
+    Ok(delta_pct)
+}
+
+/// Diff image of two renders, sized like the first, and the number of differing pixels
+///
+/// Pixels of the second image outside the first count as matching.
+fn diff_pixels(img1: &RgbaImage, img2: &RgbaImage) -> (RgbImage, u64) {
+    let (width, height) = img1.dimensions();
+    let mut diff_img = RgbImage::new(width, height);
     let mut diff_pixels = 0u64;
-    let total_pixels = (width * height) as u64;
 
-    for y in 0..height {
-        for x in 0..width {
+    for y in 0..height.min(img2.height()) {
+        for x in 0..width.min(img2.width()) {
             let pixel1 = img1.get_pixel(x, y);
             let pixel2 = img2.get_pixel(x, y);
 
-            let r_diff = (pixel1[0] as i32 - pixel2[0] as i32).abs() as u8;
-            let g_diff = (pixel1[1] as i32 - pixel2[1] as i32).abs() as u8;
-            let b_diff = (pixel1[2] as i32 - pixel2[2] as i32).abs() as u8;
+            let r_diff = (pixel1[0] as i32 - pixel2[0] as i32).unsigned_abs();
+            let g_diff = (pixel1[1] as i32 - pixel2[1] as i32).unsigned_abs();
+            let b_diff = (pixel1[2] as i32 - pixel2[2] as i32).unsigned_abs();
 
             // Highlight differences in red
             if r_diff > 5 || g_diff > 5 || b_diff > 5 {
@@ -171,15 +536,15 @@ pub fn generate_diff_image(
         }
     }
 
-    diff_img
-        .save(output_diff)
-        .context(format!("Failed to save diff image to {}", output_diff.display()))?;
-
-    let delta_pct = (diff_pixels as f32 / total_pixels as f32) * 100.0;
-    Ok(delta_pct)
+    (diff_img, diff_pixels)
 }
 
-fn project_vertices(points: &[Vector3<f32>], bounds: &BoundingBox) -> Vec<Vector2<f32>> {
+fn project_vertices(
+    points: &[Vector3<f32>],
+    bounds: &BoundingBox,
+    width: u32,
+    height: u32,
+) -> Vec<Vector2<f32>> {
     if points.is_empty() {
         return Vec::new();
     }
@@ -187,12 +552,12 @@ fn project_vertices(points: &[Vector3<f32>], bounds: &BoundingBox) -> Vec<Vector
     let span_x = (bounds.max_x - bounds.min_x).max(1e-3);
     let span_y = (bounds.max_y - bounds.min_y).max(1e-3);
     let scale = 0.9
-        * (DEFAULT_WIDTH as f32 / span_x)
-            .min(DEFAULT_HEIGHT as f32 / span_y)
+        * (width as f32 / span_x)
+            .min(height as f32 / span_y)
             .max(1e-3);
 
-    let x_offset = (DEFAULT_WIDTH as f32 - span_x * scale) * 0.5;
-    let y_offset = (DEFAULT_HEIGHT as f32 - span_y * scale) * 0.5;
+    let x_offset = (width as f32 - span_x * scale) * 0.5;
+    let y_offset = (height as f32 - span_y * scale) * 0.5;
 
     points
         .iter()
@@ -206,7 +571,7 @@ fn project_vertices(points: &[Vector3<f32>], bounds: &BoundingBox) -> Vec<Vector
 }
 
 fn rasterize_triangle(
-    image: &mut RgbImage,
+    image: &mut RgbaImage,
     depth_buffer: &mut [f32],
     points: [Vector2<f32>; 3],
     depths: [f32; 3],
@@ -269,10 +634,11 @@ fn rasterize_triangle(
             if depth > depth_buffer[idx] {
                 depth_buffer[idx] = depth;
                 let shade = (intensity * 205.0 + 40.0).clamp(0.0, 255.0) as u8;
-                let color = Rgb([
+                let color = Rgba([
                     shade,
                     (shade as f32 * 0.92) as u8,
                     (shade as f32 * 0.78 + 20.0).min(255.0) as u8,
+                    255,
                 ]);
                 image.put_pixel(x as u32, y as u32, color);
             }
@@ -337,5 +703,101 @@ mod tests {
         let delta = compare_images(&png1, &png2).unwrap();
         assert!(delta < 1.0, "Expected nearly identical renders, got {}", delta);
     }
-}
 
+    #[test]
+    fn test_render_mesh_silhouette() {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let image = render_mesh(&mesh, 64, 48).unwrap();
+
+        assert_eq!(image.dimensions(), (64, 48));
+        // The cube is scaled to fill the frame, so the center is covered and the corner is not
+        assert_ne!(*image.get_pixel(32, 24), BACKGROUND);
+        assert_eq!(*image.get_pixel(0, 0), BACKGROUND);
+
+        let covered = image.pixels().filter(|p| **p != BACKGROUND).count();
+        assert!(covered > 64 * 48 / 4, "Only {} pixels covered", covered);
+    }
+
+    #[test]
+    fn test_render_mesh_to_png_through_orbit_camera() {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let camera = OrbitCamera::framing(&mesh, 30.0, 25.0);
+
+        let png = render_mesh_to_png(&mesh, &camera, 80, 60).unwrap();
+        let image = image::load_from_memory_with_format(&png, ImageFormat::Png)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(image.dimensions(), (80, 60));
+        assert_ne!(*image.get_pixel(40, 30), BACKGROUND);
+        assert_eq!(*image.get_pixel(0, 0), BACKGROUND);
+
+        // Seen head-on from the front, only one face is visible, so it's uniformly shaded
+        let front = render_mesh_with_camera(&mesh, &OrbitCamera::new(0.0, 0.0, 60.0), 40, 40)
+            .unwrap();
+        let shade = *front.get_pixel(20, 20);
+        assert_ne!(shade, BACKGROUND);
+        assert!((16..24).all(|x| *front.get_pixel(x, 20) == shade));
+
+        // A camera further away sees a smaller silhouette
+        let far = render_mesh_with_camera(&mesh, &OrbitCamera::new(0.0, 0.0, 120.0), 40, 40)
+            .unwrap();
+        let covered = |image: &RgbaImage| image.pixels().filter(|p| **p != BACKGROUND).count();
+        assert!(covered(&far) < covered(&front));
+    }
+
+    #[test]
+    fn test_presets_catch_feature_on_back_face() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mut bumped = cube.clone();
+        let mut bump = Primitive::cube(Vector3::new(2.0, 2.0, 2.0), false).to_mesh();
+        bump.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(4.0, 10.0, 4.0)));
+        bumped.merge(&bump);
+
+        let same = compare_presets(&cube, &cube, 48, VisualMetric::PerPixel).unwrap();
+        assert_eq!(same.delta, 0.0);
+        assert_eq!(same.diff.dimensions(), (96, 96));
+
+        let diff = compare_presets(&cube, &bumped, 48, VisualMetric::PerPixel).unwrap();
+        let delta_of = |preset| diff.per_preset.iter().find(|(p, _)| *p == preset).unwrap().1;
+        // The cube hides the bump from the front, but the top and right views see it
+        assert_eq!(delta_of(CameraPreset::Front), 0.0);
+        assert!(delta_of(CameraPreset::Top) > 0.0);
+        assert!(delta_of(CameraPreset::Right) > 0.0);
+        assert!(diff.delta > 0.0);
+        assert!(diff.diff.pixels().any(|p| *p == Rgb([255, 0, 0])));
+    }
+
+    /// Copy of `image` with every silhouette edge pixel blended halfway into the pixel to its
+    /// right, as anti-aliasing would
+    fn antialiased(image: &RgbaImage) -> RgbaImage {
+        let mut blended = image.clone();
+        for y in 0..image.height() {
+            for x in 0..image.width() - 1 {
+                let (here, next) = (image.get_pixel(x, y), image.get_pixel(x + 1, y));
+                if (*here == BACKGROUND) != (*next == BACKGROUND) {
+                    let mix = |k: usize| ((here[k] as u16 + next[k] as u16) / 2) as u8;
+                    blended.put_pixel(x, y, Rgba([mix(0), mix(1), mix(2), 255]));
+                }
+            }
+        }
+        blended
+    }
+
+    #[test]
+    fn test_ssim_tolerates_antialiasing_but_not_shape_changes() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let render = render_mesh(&cube, 96, 96).unwrap();
+        let smoothed = antialiased(&render);
+
+        assert_eq!(image_delta(&render, &render, VisualMetric::Ssim), 0.0);
+        let per_pixel = image_delta(&render, &smoothed, VisualMetric::PerPixel);
+        let ssim = image_delta(&render, &smoothed, VisualMetric::Ssim);
+        assert!(!VisualMetric::PerPixel.passes(per_pixel), "per-pixel delta {}", per_pixel);
+        assert!(VisualMetric::Ssim.passes(ssim), "SSIM delta {}", ssim);
+
+        let sphere = Primitive::sphere(5.0, 32).to_mesh();
+        let other = render_mesh(&sphere, 96, 96).unwrap();
+        let ssim = image_delta(&render, &other, VisualMetric::Ssim);
+        assert!(!VisualMetric::Ssim.passes(ssim), "SSIM delta {}", ssim);
+    }
+}
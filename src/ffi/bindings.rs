@@ -38,7 +38,7 @@ impl JsMesh {
 
     /// Get bounding box as array [minX, minY, minZ, maxX, maxY, maxZ]
     #[napi]
-    pub fn bounding_box(&self) -> Vec<f32> {
+    pub fn bounding_box(&self) -> Vec<f64> {
         let bbox = self.inner.bounding_box();
         vec![
             bbox.min.x, bbox.min.y, bbox.min.z, bbox.max.x, bbox.max.y, bbox.max.z,
@@ -66,6 +66,16 @@ pub fn render_file(path: String) -> Result<JsMesh> {
     Ok(JsMesh { inner: mesh })
 }
 
+/// Render SCAD source code straight to STL bytes, without touching the filesystem
+#[cfg(feature = "napi")]
+#[napi]
+pub fn render_to_stl_buffer(source: String, binary: bool) -> Result<Buffer> {
+    crate::render(&source)
+        .and_then(|mesh| crate::io::stl_bytes(&mesh, binary))
+        .map(Buffer::from)
+        .map_err(|e| Error::from_reason(format!("Render error: {:#}", e)))
+}
+
 /// Parse SCAD and return JSON AST
 #[cfg(feature = "napi")]
 #[napi]
@@ -82,3 +92,26 @@ pub fn parse_scad(source: String) -> Result<String> {
 pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
+
+// Test binaries only link inside Node, where the N-API symbols are provided
+#[cfg(all(test, feature = "napi"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stl_buffer_parses_back() {
+        let buffer = render_to_stl_buffer("cube([10, 10, 10]);".to_string(), true).unwrap();
+
+        let mut reader = std::io::Cursor::new(buffer.to_vec());
+        let triangles = stl_io::create_stl_reader(&mut reader).unwrap().count();
+        assert_eq!(triangles, 12);
+    }
+
+    #[test]
+    fn test_stl_buffer_reports_parse_errors() {
+        let Err(error) = render_to_stl_buffer("cube([10, 10".to_string(), false) else {
+            panic!("unterminated call rendered");
+        };
+        assert!(error.reason.starts_with("Render error:"));
+    }
+}
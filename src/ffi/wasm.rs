@@ -3,7 +3,10 @@
 
 //! WASM bindings using wasm-bindgen
 
+use crate::geometry::Mesh;
+use crate::io::ParseError;
 use crate::{io, render};
+use js_sys::{Error, Float32Array, Reflect, Uint32Array};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -46,11 +49,11 @@ impl WasmMesh {
                 let normal = (v0.normal + v1.normal + v2.normal) / 3.0;
 
                 StlTriangle {
-                    normal: Normal::new([normal.x, normal.y, normal.z]),
+                    normal: Normal::new([normal.x as f32, normal.y as f32, normal.z as f32]),
                     vertices: [
-                        StlVertex::new([v0.position.x, v0.position.y, v0.position.z]),
-                        StlVertex::new([v1.position.x, v1.position.y, v1.position.z]),
-                        StlVertex::new([v2.position.x, v2.position.y, v2.position.z]),
+                        StlVertex::new(v0.position.coords.map(|c| c as f32).into()),
+                        StlVertex::new(v1.position.coords.map(|c| c as f32).into()),
+                        StlVertex::new(v2.position.coords.map(|c| c as f32).into()),
                     ],
                 }
             })
@@ -71,11 +74,90 @@ pub fn render_scad(source: &str) -> Result<WasmMesh, JsValue> {
     Ok(WasmMesh { inner: mesh })
 }
 
+/// Mesh as flat typed arrays, ready to upload as WebGL or Three.js buffers
+///
+/// Vertices are not shared between triangles: each triangle has its own three corners, so
+/// `positions` and `normals` hold nine floats per triangle and `indices` is `0..n`, for
+/// callers that always draw indexed geometry.
+#[wasm_bindgen]
+pub struct RenderedMesh {
+    positions: Vec<f32>,
+    normals: Vec<f32>,
+    indices: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl RenderedMesh {
+    /// Corner positions, `x, y, z` per corner
+    pub fn positions(&self) -> Float32Array {
+        Float32Array::from(self.positions.as_slice())
+    }
+
+    /// Corner normals, `x, y, z` per corner
+    pub fn normals(&self) -> Float32Array {
+        Float32Array::from(self.normals.as_slice())
+    }
+
+    /// Corner indices, three per triangle
+    pub fn indices(&self) -> Uint32Array {
+        Uint32Array::from(self.indices.as_slice())
+    }
+
+    /// Get triangle count
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+}
+
+impl From<&Mesh> for RenderedMesh {
+    fn from(mesh: &Mesh) -> Self {
+        let corners = mesh.triangles.len() * 3;
+        let mut positions = Vec::with_capacity(corners * 3);
+        let mut normals = Vec::with_capacity(corners * 3);
+        for vertex in mesh
+            .triangles
+            .iter()
+            .flat_map(|t| t.indices.map(|i| &mesh.vertices[i]))
+        {
+            positions.extend(vertex.position.iter().map(|&c| c as f32));
+            normals.extend(vertex.normal.iter().map(|&c| c as f32));
+        }
+
+        Self {
+            positions,
+            normals,
+            indices: (0..corners as u32).collect(),
+        }
+    }
+}
+
+/// Parse and render SCAD source code into typed arrays
+///
+/// Failures throw a JS `Error` carrying the parser's or evaluator's message; syntax errors
+/// also set its `line` and `column`.
+#[wasm_bindgen]
+pub fn wasm_render(source: &str) -> Result<RenderedMesh, JsValue> {
+    let mesh = render(source).map_err(|e| js_error("Render error", &e))?;
+
+    Ok(RenderedMesh::from(&mesh))
+}
+
+/// JS `Error` for a failed call, with `line` and `column` properties for syntax errors
+fn js_error(prefix: &str, error: &anyhow::Error) -> JsValue {
+    let js = Error::new(&format!("{}: {:#}", prefix, error));
+    if let Some(parse) = error.chain().find_map(|e| e.downcast_ref::<ParseError>()) {
+        for (key, value) in [("line", parse.line), ("column", parse.column)] {
+            // Setting a property on a fresh Error object cannot fail
+            let _ = Reflect::set(&js, &key.into(), &(value as u32).into());
+        }
+    }
+    js.into()
+}
+
 /// Parse SCAD source code and return JSON AST
 #[wasm_bindgen]
 pub fn parse_scad_to_json(source: &str) -> Result<String, JsValue> {
-    let ast =
-        io::parse_scad(source).map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+    let ast = io::parse_scad(source).map_err(|e| js_error("Parse error", &e))?;
 
     serde_json::to_string_pretty(&ast)
         .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
@@ -96,4 +178,31 @@ mod tests {
         let result = render_scad("cube([10, 10, 10]);");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_rendered_mesh_unshares_corners() {
+        let mesh = render("cube([10, 10, 10]);").unwrap();
+        let rendered = RenderedMesh::from(&mesh);
+
+        assert_eq!(rendered.triangle_count(), mesh.triangle_count());
+        assert_eq!(rendered.positions.len(), mesh.triangle_count() * 9);
+        assert_eq!(rendered.normals.len(), rendered.positions.len());
+        assert!(rendered
+            .indices
+            .iter()
+            .enumerate()
+            .all(|(i, &index)| index as usize == i));
+    }
+
+    // JS arrays only exist on a wasm target: `wasm-pack test --node --features wasm`
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_wasm_render_positions_are_whole_triangles() {
+        let rendered = wasm_render("cube([10, 10, 10]);").unwrap();
+        let positions = rendered.positions();
+
+        assert!(positions.length() > 0);
+        assert_eq!(positions.length() % 9, 0);
+        assert_eq!(rendered.indices().length() * 3, positions.length());
+    }
 }
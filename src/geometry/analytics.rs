@@ -3,8 +3,15 @@
 
 //! Geometry analytics and statistics
 
-use super::Mesh;
+use super::bvh::BVH;
+use super::{welded_for_topology, BoundingBox, Mesh, MeshValidation, Triangle};
+use anyhow::{bail, Result};
+use nalgebra::{Matrix3, Point3, Vector3};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Steepest downward face angle below horizontal that prints without support
+pub const OVERHANG_LIMIT_DEGREES: f64 = 45.0;
 
 /// Geometry statistics and analytics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +122,286 @@ pub fn analyze(mesh: &Mesh) -> GeometryStats {
     }
 }
 
+/// Connectivity summary of a mesh
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyStats {
+    /// Number of connected shells
+    pub component_count: usize,
+    /// Total number of handles (through-holes), summed over all shells.
+    /// `None` when the mesh is not a closed 2-manifold and genus is undefined.
+    pub genus: Option<usize>,
+}
+
+/// Count connected shells and, for closed manifold meshes, the genus
+///
+/// Coincident vertices are merged first, so per-face primitive vertices do not split shells.
+/// Genus follows from the Euler characteristic: `V - E + F = 2 * (components - genus)`.
+pub fn analyze_topology(mesh: &Mesh) -> TopologyStats {
+    let mut mesh = mesh.clone();
+    mesh.remove_invalid_triangles();
+    let welded = welded_for_topology(&mesh);
+
+    // Union-find over vertices joined by triangles
+    let mut parent: Vec<usize> = (0..welded.vertices.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut used = vec![false; welded.vertices.len()];
+    let mut edges = HashSet::new();
+    for triangle in &welded.triangles {
+        let [a, b, c] = triangle.indices;
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            used[u] = true;
+            edges.insert((u.min(v), u.max(v)));
+            let (ru, rv) = (find(&mut parent, u), find(&mut parent, v));
+            if ru != rv {
+                parent[ru] = rv;
+            }
+        }
+    }
+
+    let mut roots = HashSet::new();
+    let mut vertex_count = 0usize;
+    for (i, &is_used) in used.iter().enumerate() {
+        if is_used {
+            vertex_count += 1;
+            roots.insert(find(&mut parent, i));
+        }
+    }
+    let component_count = roots.len();
+
+    let genus = if MeshValidation::diagnose(&welded).is_watertight() {
+        let euler = vertex_count as i64 - edges.len() as i64 + welded.triangles.len() as i64;
+        let twice_genus = 2 * component_count as i64 - euler;
+        (twice_genus >= 0 && twice_genus % 2 == 0).then_some((twice_genus / 2) as usize)
+    } else {
+        None
+    };
+
+    TopologyStats {
+        component_count,
+        genus,
+    }
+}
+
+/// Derived metrics for 3D-printing advice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintabilityStats {
+    /// Surface area divided by volume (1/mm); high values mean thin, fragile features.
+    /// Zero when the mesh encloses no volume.
+    pub surface_to_volume_ratio: f64,
+    /// Area of downward faces steeper than [`OVERHANG_LIMIT_DEGREES`] that need support (mm²).
+    /// Faces resting on the build plate are not counted.
+    pub overhang_area: f64,
+    /// Smallest distance between opposing surfaces (mm), `None` if no opposing surface was found
+    pub min_wall_thickness: Option<f64>,
+}
+
+/// Estimate printability for a part built along the `up` axis
+///
+/// Wall thickness is measured by casting a ray inward from every triangle centroid and taking
+/// the nearest triangle facing the opposite way.
+pub fn analyze_printability(mesh: &Mesh, up: Vector3<f64>) -> PrintabilityStats {
+    let volume = calculate_volume(mesh);
+    let surface_area = calculate_surface_area(mesh);
+    let surface_to_volume_ratio = if volume > 1e-12 {
+        surface_area / volume
+    } else {
+        0.0
+    };
+
+    let valid: Vec<usize> = (0..mesh.triangles.len())
+        .filter(|&i| mesh.triangles[i].indices.iter().all(|&v| v < mesh.vertices.len()))
+        .collect();
+    if valid.is_empty() {
+        return PrintabilityStats {
+            surface_to_volume_ratio,
+            overhang_area: 0.0,
+            min_wall_thickness: None,
+        };
+    }
+
+    let corners = |i: usize| mesh.triangles[i].indices.map(|v| mesh.vertices[v].position);
+    let up = up.try_normalize(1e-12).unwrap_or_else(Vector3::z);
+
+    // Overhangs: faces pointing down more steeply than the limit, excluding the bottom layer
+    const PLATE_EPSILON: f64 = 1e-6;
+    let plate_height = mesh
+        .vertices
+        .iter()
+        .map(|v| v.position.coords.dot(&up))
+        .fold(f64::INFINITY, f64::min);
+    let limit = OVERHANG_LIMIT_DEGREES.to_radians().sin();
+
+    let mut overhang_area = 0.0;
+    for &i in &valid {
+        let [p0, p1, p2] = corners(i);
+        let cross = (p1 - p0).cross(&(p2 - p0));
+        let Some(normal) = cross.try_normalize(1e-12) else {
+            continue;
+        };
+        let on_plate = [p0, p1, p2]
+            .iter()
+            .all(|p| p.coords.dot(&up) - plate_height < PLATE_EPSILON);
+        if -normal.dot(&up) > limit && !on_plate {
+            overhang_area += cross.norm() / 2.0;
+        }
+    }
+
+    PrintabilityStats {
+        surface_to_volume_ratio,
+        overhang_area,
+        min_wall_thickness: estimate_min_wall_thickness(mesh, &valid),
+    }
+}
+
+/// Shortest inward ray from a triangle centroid to a triangle facing the other way
+fn estimate_min_wall_thickness(mesh: &Mesh, triangles: &[usize]) -> Option<f64> {
+    const MIN_DISTANCE: f64 = 1e-9;
+
+    let corners = |i: usize| mesh.triangles[i].indices.map(|v| mesh.vertices[v].position);
+    let normal = |i: usize| {
+        let [p0, p1, p2] = corners(i);
+        (p1 - p0).cross(&(p2 - p0)).try_normalize(1e-12)
+    };
+
+    let bounds: Vec<(usize, BoundingBox)> = triangles
+        .iter()
+        .map(|&i| {
+            let mut bbox = BoundingBox::empty();
+            for p in corners(i) {
+                bbox.expand_to_include(&p);
+            }
+            (i, bbox)
+        })
+        .collect();
+    let mesh_bbox = mesh.bounding_box();
+    let reach = (mesh_bbox.max - mesh_bbox.min).norm();
+    let bvh = BVH::build(bounds);
+
+    let mut thinnest: Option<f64> = None;
+    for &i in triangles {
+        let Some(n) = normal(i) else {
+            continue;
+        };
+        let [p0, p1, p2] = corners(i);
+        let origin = Point3::from((p0.coords + p1.coords + p2.coords) / 3.0);
+        let direction = -n;
+        let limit = thinnest.unwrap_or(reach);
+
+        let mut ray_box = BoundingBox::empty();
+        ray_box.expand_to_include(&origin);
+        ray_box.expand_to_include(&(origin + direction * limit));
+
+        for j in bvh.query_triangles(&ray_box) {
+            if j == i || normal(j).is_none_or(|m| m.dot(&n) >= 0.0) {
+                continue;
+            }
+            if let Some(t) = ray_triangle_distance(&origin, &direction, &corners(j)) {
+                if t > MIN_DISTANCE && t < thinnest.unwrap_or(f64::INFINITY) {
+                    thinnest = Some(t);
+                }
+            }
+        }
+    }
+
+    thinnest
+}
+
+/// Distance along `direction` from `origin` to the triangle, if the ray hits it
+fn ray_triangle_distance(
+    origin: &Point3<f64>,
+    direction: &Vector3<f64>,
+    triangle: &[Point3<f64>; 3],
+) -> Option<f64> {
+    const EPS: f64 = 1e-12;
+
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let h = direction.cross(&edge2);
+    let a = edge1.dot(&h);
+    if a.abs() < EPS {
+        return None;
+    }
+
+    let f = 1.0 / a;
+    let s = origin - triangle[0];
+    let u = f * s.dot(&h);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(&edge1);
+    let v = f * direction.dot(&q);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some(f * edge2.dot(&q))
+}
+
+/// Volume integrals of a closed mesh, taken over the enclosed solid
+pub(crate) struct VolumeIntegrals {
+    /// Signed volume (negative if the mesh is wound inward)
+    pub volume: f64,
+    /// Integral of the position vector
+    pub first_moment: Vector3<f64>,
+    /// Integral of `p * p^T`
+    pub second_moment: Matrix3<f64>,
+}
+
+/// Integrate volume, first, and second moments over a closed mesh
+///
+/// Sums the signed tetrahedra formed by each triangle and the origin, using the
+/// closed-form polynomial integrals over a tetrahedron.
+pub(crate) fn volume_integrals(mesh: &Mesh) -> Result<VolumeIntegrals> {
+    mesh.check_triangle_indices()?;
+
+    let welded = welded_for_topology(mesh);
+    let validation = welded.validate();
+    if welded.triangles.is_empty() || !validation.is_closed || !validation.is_manifold {
+        bail!(
+            "Mass properties require a closed manifold mesh ({} boundary edges)",
+            validation.boundary_edge_count
+        );
+    }
+
+    let mut volume = 0.0;
+    let mut first_moment = Vector3::zeros();
+    let mut second_moment = Matrix3::zeros();
+
+    for triangle in &mesh.triangles {
+        let a = mesh.vertices[triangle.indices[0]].position.coords;
+        let b = mesh.vertices[triangle.indices[1]].position.coords;
+        let c = mesh.vertices[triangle.indices[2]].position.coords;
+
+        let tet_volume = a.dot(&b.cross(&c)) / 6.0;
+        let sum = a + b + c;
+
+        volume += tet_volume;
+        first_moment += sum * (tet_volume / 4.0);
+        // Over a tetrahedron with one vertex at the origin:
+        // integral of p_k * p_l = V / 20 * (sum_i p_ik * p_il + S_k * S_l)
+        second_moment += (a * a.transpose() + b * b.transpose() + c * c.transpose()
+            + sum * sum.transpose())
+            * (tet_volume / 20.0);
+    }
+
+    if volume.abs() < 1e-12 {
+        bail!("Mass properties require a mesh enclosing a non-zero volume");
+    }
+
+    Ok(VolumeIntegrals {
+        volume,
+        first_moment,
+        second_moment,
+    })
+}
+
 /// Calculate bounding box
 fn calculate_bounding_box(mesh: &Mesh) -> [f64; 6] {
     let mut min_x = f64::MAX;
@@ -137,21 +424,30 @@ fn calculate_bounding_box(mesh: &Mesh) -> [f64; 6] {
     [min_x, min_y, min_z, max_x, max_y, max_z]
 }
 
-/// Calculate mesh volume using signed volume of triangles
-fn calculate_volume(mesh: &Mesh) -> f64 {
-    let mut volume = 0.0;
-
-    for triangle in &mesh.triangles {
-        let v0 = &mesh.vertices[triangle.indices[0]].position;
-        let v1 = &mesh.vertices[triangle.indices[1]].position;
-        let v2 = &mesh.vertices[triangle.indices[2]].position;
+/// Volume enclosed by a closed mesh, negative when its triangles face inward
+///
+/// Sums the signed volumes of the tetrahedra formed by each triangle and the origin.
+pub fn signed_volume(mesh: &Mesh) -> f64 {
+    signed_volume_of(mesh, &mesh.triangles)
+}
 
-        // Signed volume of tetrahedron formed by triangle and origin
-        let signed_vol = v0.coords.dot(&v1.coords.cross(&v2.coords)) / 6.0;
-        volume += signed_vol as f64;
-    }
+/// [`signed_volume`] of a closed subset of the mesh's triangles
+pub(crate) fn signed_volume_of<'a>(
+    mesh: &Mesh,
+    triangles: impl IntoIterator<Item = &'a Triangle>,
+) -> f64 {
+    triangles
+        .into_iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position.coords);
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
 
-    volume.abs()
+/// Calculate mesh volume using signed volume of triangles
+fn calculate_volume(mesh: &Mesh) -> f64 {
+    signed_volume(mesh).abs()
 }
 
 /// Calculate total surface area
@@ -283,4 +579,60 @@ mod tests {
         assert_eq!(stats.vertex_count, 36);
         assert_eq!(stats.triangle_count, 12);
     }
+
+    #[test]
+    fn test_topology_cube_and_split() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let stats = analyze_topology(&cube);
+        assert_eq!(stats.component_count, 1);
+        assert_eq!(stats.genus, Some(0));
+
+        let mut pair = cube.clone();
+        let mut shifted = cube.clone();
+        shifted.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(20.0, 0.0, 0.0)));
+        pair.merge(&shifted);
+        let stats = analyze_topology(&pair);
+        assert_eq!(stats.component_count, 2);
+        assert_eq!(stats.genus, Some(0));
+
+        // An open mesh has no defined genus
+        let mut open = cube;
+        open.triangles.pop();
+        let stats = analyze_topology(&open);
+        assert_eq!(stats.component_count, 1);
+        assert_eq!(stats.genus, None);
+    }
+
+    #[test]
+    fn test_printability_cube_on_plate() {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let stats = analyze_printability(&mesh, Vector3::z());
+
+        assert!((stats.surface_to_volume_ratio - 0.6).abs() < 1e-9);
+        assert_eq!(stats.overhang_area, 0.0);
+        assert!((stats.min_wall_thickness.unwrap() - 10.0).abs() < 1e-9);
+
+        let plate = Primitive::cube(Vector3::new(20.0, 20.0, 1.5), false).to_mesh();
+        let stats = analyze_printability(&plate, Vector3::z());
+        assert!((stats.min_wall_thickness.unwrap() - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_printability_overhang_depends_on_orientation() {
+        // A T shape: a 20 x 20 cap resting on a 4 x 4 stem
+        let mut shape = Primitive::cube(Vector3::new(4.0, 4.0, 10.0), false).to_mesh();
+        shape.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(8.0, 8.0, 0.0)));
+        let mut cap = Primitive::cube(Vector3::new(20.0, 20.0, 2.0), false).to_mesh();
+        cap.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(0.0, 0.0, 10.0)));
+        shape.merge(&cap);
+
+        // Upright, the whole underside of the cap hangs in the air
+        let upright = analyze_printability(&shape, Vector3::z());
+        assert!((upright.overhang_area - 400.0).abs() < 1e-9);
+
+        // Upside down the cap rests on the plate; the parts are merged rather than unioned,
+        // so the only downward face left is the 4 x 4 top of the stem buried in the cap
+        let flipped = analyze_printability(&shape, -Vector3::z());
+        assert!((flipped.overhang_area - 16.0).abs() < 1e-9);
+    }
 }
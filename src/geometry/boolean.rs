@@ -7,12 +7,15 @@ use super::{Mesh, Triangle, Vertex};
 use anyhow::Result;
 use nalgebra::Point3;
 use parry3d::shape::TriMesh;
+use std::borrow::Cow;
 
 #[derive(Debug, Clone)]
 pub enum BooleanOp {
     Union,
     Difference,
     Intersection,
+    /// `(A − B) ∪ (B − A)`: the regions covered by exactly one operand
+    SymmetricDifference,
 }
 
 /// Quality level for boolean operations
@@ -22,6 +25,10 @@ pub enum BooleanQuality {
     Fast,
     /// Robust implementation (uses intersection splitting and robust predicates)
     Robust,
+    /// Slowest implementation: plane sides, coplanarity and inside/outside are decided by exact
+    /// orientation tests on coordinates snapped to a 1e-9 grid, so coplanar and shared faces
+    /// are resolved consistently; only the cut points are rounded
+    Exact,
 }
 
 impl Default for BooleanQuality {
@@ -45,10 +52,46 @@ pub fn perform_boolean_operation_with_quality(
 ) -> Result<Mesh> {
     use super::csg;
 
+    // The CSG loops index vertices directly, so drop malformed triangles up front
+    let mesh_a = without_invalid_triangles(mesh_a, "first");
+    let mesh_b = without_invalid_triangles(mesh_b, "second");
+    let (mesh_a, mesh_b) = (mesh_a.as_ref(), mesh_b.as_ref());
+
+    if quality == BooleanQuality::Exact {
+        return super::exact_csg::exact_boolean(mesh_a, mesh_b, &op);
+    }
+
     match op {
         BooleanOp::Union => csg::csg_union_with_quality(mesh_a, mesh_b, quality),
         BooleanOp::Difference => csg::csg_difference(mesh_a, mesh_b),
         BooleanOp::Intersection => csg::csg_intersection(mesh_a, mesh_b),
+        BooleanOp::SymmetricDifference => symmetric_difference(mesh_a, mesh_b, quality),
+    }
+}
+
+/// `(A − B) ∪ (B − A)`, composed from the other operations at the same quality
+pub(crate) fn symmetric_difference(
+    mesh_a: &Mesh,
+    mesh_b: &Mesh,
+    quality: BooleanQuality,
+) -> Result<Mesh> {
+    let a_only =
+        perform_boolean_operation_with_quality(mesh_a, mesh_b, BooleanOp::Difference, quality)?;
+    let b_only =
+        perform_boolean_operation_with_quality(mesh_b, mesh_a, BooleanOp::Difference, quality)?;
+    perform_boolean_operation_with_quality(&a_only, &b_only, BooleanOp::Union, quality)
+}
+
+/// Borrow the mesh if its indices are valid, otherwise warn and return a copy without the bad triangles
+pub(crate) fn without_invalid_triangles<'a>(mesh: &'a Mesh, label: &str) -> Cow<'a, Mesh> {
+    match mesh.check_triangle_indices() {
+        Ok(()) => Cow::Borrowed(mesh),
+        Err(e) => {
+            eprintln!("Warning: skipping malformed triangles in {} operand: {}", label, e);
+            let mut cleaned = mesh.clone();
+            cleaned.remove_invalid_triangles();
+            Cow::Owned(cleaned)
+        }
     }
 }
 
@@ -114,4 +157,50 @@ mod tests {
         let result = perform_boolean_operation(&mesh_a, &mesh_b, BooleanOp::Union);
         assert!(result.is_ok());
     }
+
+    fn cube_at(offset: nalgebra::Vector3<f64>) -> Mesh {
+        let mut mesh = Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.transform(&nalgebra::Matrix4::new_translation(&offset));
+        mesh
+    }
+
+    #[test]
+    fn test_symmetric_difference() -> Result<()> {
+        use crate::geometry::analyze;
+
+        let xor = |a: &Mesh, b: &Mesh| {
+            perform_boolean_operation_with_quality(
+                a,
+                b,
+                BooleanOp::SymmetricDifference,
+                BooleanQuality::Exact,
+            )
+        };
+        let a = cube_at(nalgebra::Vector3::zeros());
+
+        // Overlap is [5, 10]^3, so each cube keeps 1000 - 125
+        let b = cube_at(nalgebra::Vector3::new(5.0, 5.0, 5.0));
+        let result = xor(&a, &b)?;
+        assert!((analyze(&result).volume - 1750.0).abs() < 1e-6);
+
+        assert_eq!(xor(&a, &a.clone())?.triangle_count(), 0);
+
+        let far = cube_at(nalgebra::Vector3::new(20.0, 0.0, 0.0));
+        assert!((analyze(&xor(&a, &far)?).volume - 2000.0).abs() < 1e-6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_boolean_skips_out_of_range_indices() {
+        let mut mesh_a = Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let bad_index = mesh_a.vertices.len() + 100;
+        mesh_a.add_triangle(Triangle::new([0, 1, bad_index]));
+        let mesh_b = Primitive::sphere(5.0, 16).to_mesh();
+
+        for op in [BooleanOp::Union, BooleanOp::Difference, BooleanOp::Intersection] {
+            let result = perform_boolean_operation(&mesh_a, &mesh_b, op).unwrap();
+            assert!(result.check_triangle_indices().is_ok());
+        }
+    }
 }
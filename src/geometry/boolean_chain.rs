@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Fluent multi-operand boolean operations
+
+use super::{BooleanOp, BooleanQuality, Mesh, ParallelBooleanExecutor};
+use anyhow::Result;
+use std::mem::discriminant;
+
+/// Collects boolean operations against a base mesh and resolves them in one pass
+///
+/// The chain has left-to-right semantics: `a.union(b).difference(c)` is `(a ∪ b) − c`.
+/// Runs of the same operation are reduced as a balanced tree instead of one operand at a time,
+/// and a run of differences subtracts the union of its operands.
+///
+/// ```ignore
+/// let result = a.boolean_chain().union(&b).difference(&c).build(quality)?;
+/// ```
+pub struct MeshBuilder<'a> {
+    base: Mesh,
+    operations: Vec<(BooleanOp, &'a Mesh)>,
+}
+
+impl<'a> MeshBuilder<'a> {
+    /// Start a chain from `base`
+    pub fn new(base: Mesh) -> Self {
+        Self {
+            base,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn union(self, other: &'a Mesh) -> Self {
+        self.then(BooleanOp::Union, other)
+    }
+
+    pub fn difference(self, other: &'a Mesh) -> Self {
+        self.then(BooleanOp::Difference, other)
+    }
+
+    pub fn intersection(self, other: &'a Mesh) -> Self {
+        self.then(BooleanOp::Intersection, other)
+    }
+
+    pub fn symmetric_difference(self, other: &'a Mesh) -> Self {
+        self.then(BooleanOp::SymmetricDifference, other)
+    }
+
+    /// Append an operation to the chain
+    pub fn then(mut self, op: BooleanOp, other: &'a Mesh) -> Self {
+        self.operations.push((op, other));
+        self
+    }
+
+    /// Resolve the chain into a single mesh
+    pub fn build(self, quality: BooleanQuality) -> Result<Mesh> {
+        let mut result = self.base;
+        let mut operations = self.operations.as_slice();
+
+        while let Some((op, _)) = operations.first() {
+            let run_len = operations
+                .iter()
+                .take_while(|(next, _)| discriminant(next) == discriminant(op))
+                .count();
+            let operands: Vec<&Mesh> = operations[..run_len].iter().map(|(_, m)| *m).collect();
+            operations = &operations[run_len..];
+
+            result = match op {
+                // All associative, so a run can be regrouped freely
+                BooleanOp::Union | BooleanOp::Intersection | BooleanOp::SymmetricDifference => {
+                    let mut all = Vec::with_capacity(operands.len() + 1);
+                    all.push(&result);
+                    all.extend(operands);
+                    ParallelBooleanExecutor::reduce_balanced(&all, op, quality)?
+                }
+                BooleanOp::Difference if operands.len() == 1 => {
+                    result.boolean_operation_with_quality(operands[0], op.clone(), quality)?
+                }
+                BooleanOp::Difference => {
+                    let subtrahend = ParallelBooleanExecutor::reduce_balanced(
+                        &operands,
+                        &BooleanOp::Union,
+                        quality,
+                    )?;
+                    result.boolean_operation_with_quality(&subtrahend, op.clone(), quality)?
+                }
+            };
+        }
+
+        Ok(result)
+    }
+}
+
+impl From<Mesh> for MeshBuilder<'_> {
+    fn from(base: Mesh) -> Self {
+        Self::new(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{analyze, Primitive};
+    use nalgebra::{Matrix4, Vector3};
+
+    fn cube_at(size: f64, offset: Vector3<f64>) -> Mesh {
+        let mut mesh = Primitive::cube(Vector3::new(size, size, size), false).to_mesh();
+        mesh.transform(&Matrix4::new_translation(&offset));
+        mesh
+    }
+
+    #[test]
+    fn test_chain_matches_sequential() -> Result<()> {
+        let quality = BooleanQuality::Robust;
+        let a = cube_at(10.0, Vector3::zeros());
+        let b = cube_at(10.0, Vector3::new(5.0, 0.0, 0.0));
+        let c = cube_at(4.0, Vector3::new(12.0, 3.0, 3.0));
+
+        let step = a
+            .boolean_operation_with_quality(&b, BooleanOp::Union, quality)?
+            .boolean_operation_with_quality(&c, BooleanOp::Difference, quality)?;
+        let chain = Mesh::boolean_chain(a.clone())
+            .union(&b)
+            .difference(&c)
+            .build(quality)?;
+
+        assert_eq!(chain.triangle_count(), step.triangle_count());
+        assert_eq!(analyze(&chain).volume, analyze(&step).volume);
+        assert_eq!(analyze(&chain).bbox, analyze(&step).bbox);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_balanced_union_run_matches_sequential() -> Result<()> {
+        let quality = BooleanQuality::Robust;
+        let cubes: Vec<Mesh> = (0..4)
+            .map(|i| cube_at(5.0, Vector3::new(i as f64 * 3.0, 0.0, 0.0)))
+            .collect();
+
+        let mut step = cubes[0].clone();
+        for cube in &cubes[1..] {
+            step = step.boolean_operation_with_quality(cube, BooleanOp::Union, quality)?;
+        }
+        let chain = cubes[1..]
+            .iter()
+            .fold(MeshBuilder::from(cubes[0].clone()), |chain, cube| {
+                chain.union(cube)
+            })
+            .build(quality)?;
+
+        // Grouping changes intermediate tessellation, so compare extents rather than triangles
+        let (chain, step) = (analyze(&chain), analyze(&step));
+        assert!(chain.triangle_count > 0);
+        assert_eq!(chain.bbox, step.bbox);
+
+        Ok(())
+    }
+}
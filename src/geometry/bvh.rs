@@ -5,7 +5,10 @@
 //! Used to accelerate triangle-triangle intersection tests
 
 use super::BoundingBox;
-use nalgebra::Point3;
+use nalgebra::{Point3, Vector3};
+
+/// Ray hits closer together than this count as a tie, resolved toward the lower item index
+const RAY_TIE_EPSILON: f64 = 1e-9;
 
 /// BVH node
 #[derive(Debug, Clone)]
@@ -180,6 +183,164 @@ impl BVH {
         }
     }
 
+    /// Items in the leaves whose bounding boxes the ray from `origin` along `direction` passes
+    /// through, a superset of the items whose own boxes it passes
+    pub fn query_ray(&self, origin: &Point3<f64>, direction: &Vector3<f64>) -> Vec<usize> {
+        let mut result = Vec::new();
+        Self::query_ray_recursive(&self.root, origin, direction, &mut result);
+        result
+    }
+
+    fn query_ray_recursive(
+        node: &BVHNode,
+        origin: &Point3<f64>,
+        direction: &Vector3<f64>,
+        result: &mut Vec<usize>,
+    ) {
+        if Self::ray_bbox_entry(origin, direction, &node.bbox).is_none() {
+            return;
+        }
+
+        if node.is_leaf() {
+            result.extend_from_slice(&node.triangle_indices);
+        } else {
+            for child in [node.left.as_deref(), node.right.as_deref()].into_iter().flatten() {
+                Self::query_ray_recursive(child, origin, direction, result);
+            }
+        }
+    }
+
+    /// Item nearest to `point` by the caller's `distance`, or `None` if the BVH is empty
+    ///
+    /// Subtrees whose box is no closer than the best item so far are skipped, so `distance`
+    /// must never be less than the distance from `point` to the item's bounding box.
+    pub fn nearest(
+        &self,
+        point: &Point3<f64>,
+        mut distance: impl FnMut(usize) -> f64,
+    ) -> Option<(usize, f64)> {
+        let mut best = None;
+        Self::nearest_recursive(&self.root, point, &mut distance, &mut best);
+        best
+    }
+
+    fn nearest_recursive(
+        node: &BVHNode,
+        point: &Point3<f64>,
+        distance: &mut impl FnMut(usize) -> f64,
+        best: &mut Option<(usize, f64)>,
+    ) {
+        if best.is_some_and(|(_, d)| Self::point_bbox_distance(point, &node.bbox) >= d) {
+            return;
+        }
+
+        if node.is_leaf() {
+            for &index in &node.triangle_indices {
+                let d = distance(index);
+                if best.is_none_or(|(_, best_d)| d < best_d) {
+                    *best = Some((index, d));
+                }
+            }
+            return;
+        }
+
+        // Visit the nearer child first, so the farther one is more often pruned
+        let mut children: Vec<&BVHNode> =
+            [node.left.as_deref(), node.right.as_deref()].into_iter().flatten().collect();
+        children.sort_by(|a, b| {
+            Self::point_bbox_distance(point, &a.bbox)
+                .total_cmp(&Self::point_bbox_distance(point, &b.bbox))
+        });
+        for child in children {
+            Self::nearest_recursive(child, point, distance, best);
+        }
+    }
+
+    /// Item first hit by the ray from `origin` along `direction`, with the caller's hit distance
+    ///
+    /// `hit` returns the ray parameter at which the item is hit, or `None` if it is missed.
+    /// Items hit at the same parameter (within a small tolerance) resolve to the lowest index,
+    /// so a ray through a shared edge gives the same answer whatever the traversal order.
+    pub fn raycast(
+        &self,
+        origin: &Point3<f64>,
+        direction: &Vector3<f64>,
+        mut hit: impl FnMut(usize) -> Option<f64>,
+    ) -> Option<(usize, f64)> {
+        let mut best = None;
+        Self::raycast_recursive(&self.root, origin, direction, &mut hit, &mut best);
+        best
+    }
+
+    fn raycast_recursive(
+        node: &BVHNode,
+        origin: &Point3<f64>,
+        direction: &Vector3<f64>,
+        hit: &mut impl FnMut(usize) -> Option<f64>,
+        best: &mut Option<(usize, f64)>,
+    ) {
+        let Some(entry) = Self::ray_bbox_entry(origin, direction, &node.bbox) else {
+            return;
+        };
+        if best.is_some_and(|(_, t)| entry > t + RAY_TIE_EPSILON) {
+            return;
+        }
+
+        if node.is_leaf() {
+            for &index in &node.triangle_indices {
+                let Some(t) = hit(index) else {
+                    continue;
+                };
+                let better = best.is_none_or(|(best_index, best_t)| {
+                    t < best_t - RAY_TIE_EPSILON
+                        || (t <= best_t + RAY_TIE_EPSILON && index < best_index)
+                });
+                if better {
+                    *best = Some((index, t));
+                }
+            }
+            return;
+        }
+
+        for child in [node.left.as_deref(), node.right.as_deref()].into_iter().flatten() {
+            Self::raycast_recursive(child, origin, direction, hit, best);
+        }
+    }
+
+    /// Ray parameter at which the ray enters `bbox` (zero if it starts inside), or `None`
+    /// if it misses the box entirely
+    fn ray_bbox_entry(
+        origin: &Point3<f64>,
+        direction: &Vector3<f64>,
+        bbox: &BoundingBox,
+    ) -> Option<f64> {
+        let (mut near, mut far) = (0.0_f64, f64::INFINITY);
+        for k in 0..3 {
+            if direction[k] == 0.0 {
+                if origin[k] < bbox.min[k] || origin[k] > bbox.max[k] {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = (
+                (bbox.min[k] - origin[k]) / direction[k],
+                (bbox.max[k] - origin[k]) / direction[k],
+            );
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+        (near <= far).then_some(near)
+    }
+
+    /// Distance from `point` to the closest point of `bbox`, zero inside it
+    fn point_bbox_distance(point: &Point3<f64>, bbox: &BoundingBox) -> f64 {
+        let gap = |p: f64, min: f64, max: f64| (min - p).max(p - max).max(0.0);
+        let dx = gap(point.x, bbox.min.x, bbox.max.x);
+        let dy = gap(point.y, bbox.min.y, bbox.max.y);
+        let dz = gap(point.z, bbox.min.z, bbox.max.z);
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
     /// Check if two bounding boxes intersect
     fn bboxes_intersect(a: &BoundingBox, b: &BoundingBox) -> bool {
         a.min.x <= b.max.x
@@ -258,5 +419,45 @@ mod tests {
         let results = bvh.query_triangles(&mesh_bbox);
         assert!(results.len() > 0);
     }
-}
 
+    #[test]
+    fn test_bvh_nearest() {
+        let centers: Vec<Point3<f64>> =
+            (0..50).map(|i| Point3::new(i as f64, (i % 7) as f64, 0.0)).collect();
+        let items = centers
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| (idx, BoundingBox::new(*c, *c)))
+            .collect();
+        let bvh = BVH::build(items);
+
+        let query = Point3::new(20.2, 6.0, 0.5);
+        let (nearest, distance) = bvh.nearest(&query, |idx| (centers[idx] - query).norm()).unwrap();
+        let expected = (0..50)
+            .min_by(|&a, &b| (centers[a] - query).norm().total_cmp(&(centers[b] - query).norm()))
+            .unwrap();
+        assert_eq!(nearest, expected);
+        assert!((distance - (centers[expected] - query).norm()).abs() < 1e-12);
+
+        assert!(BVH::build(Vec::new()).nearest(&query, |_| 0.0).is_none());
+    }
+
+    #[test]
+    fn test_bvh_query_ray() {
+        let items = (0..20)
+            .map(|i| {
+                let c = Point3::new(i as f64, (i % 4) as f64, 0.0);
+                (i, BoundingBox::new(c, c + Vector3::repeat(0.5)))
+            })
+            .collect();
+        let bvh = BVH::build(items);
+
+        // Leaves are reported whole, but every box the ray passes must be among them and the
+        // boxes behind its origin must not
+        let hits = bvh.query_ray(&Point3::new(4.2, 1.25, 0.25), &Vector3::x());
+        for i in [5, 9, 13, 17] {
+            assert!(hits.contains(&i), "box {} is on the ray", i);
+        }
+        assert!(!hits.contains(&0) && !hits.contains(&1));
+    }
+}
@@ -4,7 +4,7 @@
 //! Face fragment classification for CSG operations
 //! Determines if face fragments are inside, outside, or on boundary of solids
 
-use super::{Mesh, robust_predicates};
+use super::{bvh::BVH, BoundingBox, Mesh, robust_predicates};
 use nalgebra::{Point3, Vector3};
 
 /// Classification of a face fragment
@@ -18,10 +18,55 @@ pub enum Classification {
     OnBoundary,
 }
 
-/// Classify a face fragment relative to a mesh
+/// Points closer than this to a triangle lie on the boundary
+const BOUNDARY_EPS: f64 = 1e-5; // Increased from 1e-6 to be more lenient
+
+/// A mesh indexed for repeated point classification
+///
+/// Each test only visits the triangles the BVH puts near the point or along the ray, rather
+/// than every triangle of the mesh.
+pub struct Solid<'a> {
+    mesh: &'a Mesh,
+    bvh: BVH,
+}
+
+impl<'a> Solid<'a> {
+    pub fn new(mesh: &'a Mesh) -> Self {
+        // Pad the boxes by the boundary tolerance so a point on a triangle is always in its box
+        let triangles = mesh
+            .triangles
+            .iter()
+            .enumerate()
+            .map(|(idx, tri)| {
+                let mut bbox = BoundingBox::empty();
+                for i in tri.indices {
+                    bbox.expand_to_include(&mesh.vertices[i].position);
+                }
+                let margin = Vector3::repeat(BOUNDARY_EPS);
+                (idx, BoundingBox::new(bbox.min - margin, bbox.max + margin))
+            })
+            .collect();
+        Self {
+            mesh,
+            bvh: BVH::build(triangles),
+        }
+    }
+
+    /// The mesh being classified against
+    pub fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    /// Corners of triangle `idx`
+    fn corners(&self, idx: usize) -> [Point3<f64>; 3] {
+        self.mesh.triangles[idx].indices.map(|i| self.mesh.vertices[i].position)
+    }
+}
+
+/// Classify a face fragment relative to a solid
 pub fn classify_face_fragment(
     face_vertices: &[Point3<f64>; 3],
-    other_mesh: &Mesh,
+    other_mesh: &Solid,
 ) -> Classification {
     // Check multiple points on the triangle for more robust classification
     // This prevents misclassification when centroid is inside but triangle is partially outside
@@ -86,6 +131,24 @@ pub fn classify_face_fragment(
     }
 }
 
+/// Classify a fragment that does not cross the other mesh's surface by its centroid
+///
+/// Fragments cut along the other surface lie entirely on one side of it, so unlike
+/// [`classify_face_fragment`] a corner touching the surface says nothing about the rest.
+pub fn classify_split_fragment(
+    face_vertices: &[Point3<f64>; 3],
+    other_mesh: &Solid,
+) -> Classification {
+    let centroid = compute_robust_centroid(face_vertices);
+    if is_point_on_boundary(&centroid, other_mesh) {
+        Classification::OnBoundary
+    } else if is_point_inside_solid(&centroid, other_mesh) {
+        Classification::Inside
+    } else {
+        Classification::Outside
+    }
+}
+
 /// Compute robust centroid of triangle
 fn compute_robust_centroid(vertices: &[Point3<f64>; 3]) -> Point3<f64> {
     // Use Kahan summation for better accuracy
@@ -117,17 +180,16 @@ fn compute_robust_centroid(vertices: &[Point3<f64>; 3]) -> Point3<f64> {
 }
 
 /// Test if point is inside solid using ray casting with robust predicates
-fn is_point_inside_solid(point: &Point3<f64>, mesh: &Mesh) -> bool {
-    // Cast ray in +X direction and count intersections
-    let ray_dir = Vector3::new(1.0, 0.0, 0.0);
+fn is_point_inside_solid(point: &Point3<f64>, solid: &Solid) -> bool {
+    // Cast a ray tilted off the axes so it doesn't graze face diagonals (see PARITY_RAY),
+    // and count intersections
+    let ray_dir = super::robust_csg::PARITY_RAY;
     let mut intersection_count = 0;
     
-    for tri in &mesh.triangles {
-        let v0 = &mesh.vertices[tri.indices[0]].position;
-        let v1 = &mesh.vertices[tri.indices[1]].position;
-        let v2 = &mesh.vertices[tri.indices[2]].position;
+    for idx in solid.bvh.query_ray(point, &ray_dir) {
+        let [v0, v1, v2] = solid.corners(idx);
         
-        if ray_intersects_triangle_robust(point, &ray_dir, v0, v1, v2) {
+        if ray_intersects_triangle_robust(point, &ray_dir, &v0, &v1, &v2) {
             intersection_count += 1;
         }
     }
@@ -138,13 +200,17 @@ fn is_point_inside_solid(point: &Point3<f64>, mesh: &Mesh) -> bool {
 
 /// Test if point is on boundary (within epsilon of any triangle)
 /// Made more lenient to catch more boundary cases
-fn is_point_on_boundary(point: &Point3<f64>, mesh: &Mesh) -> bool {
-    const EPS: f64 = 1e-5; // Increased from 1e-6 to be more lenient
+fn is_point_on_boundary(point: &Point3<f64>, solid: &Solid) -> bool {
+    boundary_normal(point, solid).is_some()
+}
+
+/// Unit normal of the first triangle the point lies on (within the boundary epsilon)
+pub fn boundary_normal(point: &Point3<f64>, solid: &Solid) -> Option<Vector3<f64>> {
+    let mut candidates = solid.bvh.query_triangles(&BoundingBox::new(*point, *point));
+    candidates.sort_unstable();
     
-    for tri in &mesh.triangles {
-        let v0 = &mesh.vertices[tri.indices[0]].position;
-        let v1 = &mesh.vertices[tri.indices[1]].position;
-        let v2 = &mesh.vertices[tri.indices[2]].position;
+    for idx in candidates {
+        let [v0, v1, v2] = solid.corners(idx);
         
         // Compute distance to triangle plane
         let ab = v1 - v0;
@@ -161,15 +227,15 @@ fn is_point_on_boundary(point: &Point3<f64>, mesh: &Mesh) -> bool {
         
         let dist = (normal.dot(&point.coords) - d).abs();
         
-        if dist < EPS {
+        if dist < BOUNDARY_EPS {
             // Check if point projects onto triangle (with some tolerance)
-            if point_in_triangle_robust(point, v0, v1, v2, &normal) {
-                return true;
+            if point_in_triangle_robust(point, &v0, &v1, &v2, &normal) {
+                return Some(normal);
             }
         }
     }
     
-    false
+    None
 }
 
 /// Robust ray-triangle intersection test
@@ -259,6 +325,16 @@ mod tests {
     use super::*;
     use crate::geometry::Primitive;
 
+    #[test]
+    fn test_point_inside_solid_ray_misses_face_diagonals() {
+        // A +X ray from the center would hit the +X face exactly on its diagonal, counting twice
+        let mesh = Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let solid = Solid::new(&mesh);
+
+        assert!(is_point_inside_solid(&Point3::origin(), &solid));
+        assert!(!is_point_inside_solid(&Point3::new(-10.0, 0.0, 0.0), &solid));
+    }
+
     #[test]
     fn test_classify_face_fragment() {
         let mesh = Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
@@ -270,7 +346,7 @@ mod tests {
             Point3::new(20.0, 1.0, 0.0),
         ];
         
-        let classification = classify_face_fragment(&face_outside, &mesh);
+        let classification = classify_face_fragment(&face_outside, &Solid::new(&mesh));
         assert_eq!(classification, Classification::Outside);
     }
 }
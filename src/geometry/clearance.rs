@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Clearance between parts, for assembly and fit checks
+//!
+//! [`min_clearance`] measures the gap between two meshes, or estimates how deep they
+//! interpenetrate. Both meshes should be closed and outward-facing, since the inside test
+//! uses their winding numbers.
+
+use super::bvh::BVH;
+use super::exact_csg::winding_number;
+use super::triangle_intersection::{triangle_triangle_intersection, IntersectionType};
+use super::{BoundingBox, Mesh};
+use nalgebra::Point3;
+
+/// Triangle soup of one mesh with a BVH over it, for nearest-surface queries
+struct Surface {
+    triangles: Vec<[Point3<f64>; 3]>,
+    bvh: BVH,
+    bounds: BoundingBox,
+}
+
+impl Surface {
+    fn new(mesh: &Mesh) -> Self {
+        let triangles: Vec<[Point3<f64>; 3]> = mesh
+            .triangles
+            .iter()
+            .map(|t| t.indices.map(|i| mesh.vertices[i].position))
+            .collect();
+        let boxes = triangles
+            .iter()
+            .enumerate()
+            .map(|(idx, triangle)| (idx, triangle_bbox(triangle)))
+            .collect();
+        Self {
+            bvh: BVH::build(boxes),
+            bounds: mesh.bounding_box(),
+            triangles,
+        }
+    }
+
+    /// Distance from `point` to the nearest point on the surface
+    fn distance(&self, point: &Point3<f64>) -> f64 {
+        self.bvh
+            .nearest(point, |idx| {
+                (closest_point_on_triangle(point, &self.triangles[idx]) - point).norm()
+            })
+            .map_or(f64::INFINITY, |(_, distance)| distance)
+    }
+
+    /// Nearest point on the surface with its triangle index and distance
+    fn closest(&self, point: &Point3<f64>) -> Option<(Point3<f64>, usize, f64)> {
+        let (idx, distance) = self.bvh.nearest(point, |idx| {
+            (closest_point_on_triangle(point, &self.triangles[idx]) - point).norm()
+        })?;
+        Some((
+            closest_point_on_triangle(point, &self.triangles[idx]),
+            idx,
+            distance,
+        ))
+    }
+
+    /// Whether `point` is strictly inside the closed surface
+    fn contains(&self, point: &Point3<f64>) -> bool {
+        let bounds = &self.bounds;
+        let in_bounds = (0..3).all(|k| bounds.min[k] < point[k] && point[k] < bounds.max[k]);
+        in_bounds && winding_number(&self.triangles, point) > 0.5
+    }
+
+    /// Pairs of triangles of `self` and `other` whose bounding boxes overlap
+    fn candidate_pairs<'a>(
+        &'a self,
+        other: &'a Surface,
+    ) -> impl Iterator<Item = (&'a [Point3<f64>; 3], &'a [Point3<f64>; 3])> {
+        self.triangles.iter().flat_map(move |triangle| {
+            other
+                .bvh
+                .query_triangles(&triangle_bbox(triangle))
+                .into_iter()
+                .map(move |idx| (triangle, &other.triangles[idx]))
+        })
+    }
+
+    /// Whether any triangle of `self` crosses or touches one of `other`
+    fn intersects(&self, other: &Surface) -> bool {
+        self.candidate_pairs(other)
+            .any(|(a, b)| triangle_triangle_intersection(a, b).intersects)
+    }
+}
+
+/// Minimum surface-to-surface distance between two meshes, negative when they overlap
+///
+/// For disjoint meshes this is the smallest distance from a vertex of either mesh to the
+/// other's surface, checked both ways. That is exact whenever a vertex is involved in the
+/// closest pair, as for flat-faced parts; for two edges passing close to each other it can
+/// overestimate by up to the tessellation error.
+///
+/// When the meshes interpenetrate, the result is minus the depth of the deepest vertex of
+/// either mesh inside the other, an estimate of how far they must move apart. Surfaces that
+/// cross or touch without any vertex inside the other give zero. Returns infinity if either
+/// mesh is empty.
+pub fn min_clearance(a: &Mesh, b: &Mesh) -> f64 {
+    if a.triangles.is_empty() || b.triangles.is_empty() {
+        return f64::INFINITY;
+    }
+
+    let (surface_a, surface_b) = (Surface::new(a), Surface::new(b));
+    let mut gap = f64::INFINITY;
+    let mut depth = 0.0_f64;
+    let mut overlapping = false;
+    for (mesh, other) in [(a, &surface_b), (b, &surface_a)] {
+        for vertex in &mesh.vertices {
+            let distance = other.distance(&vertex.position);
+            if other.contains(&vertex.position) {
+                overlapping = true;
+                depth = depth.max(distance);
+            } else {
+                gap = gap.min(distance);
+            }
+        }
+    }
+
+    if depth > 0.0 {
+        -depth
+    } else if overlapping || surface_a.intersects(&surface_b) {
+        0.0
+    } else {
+        gap
+    }
+}
+
+/// Symmetric Hausdorff distance between the surfaces of two meshes
+///
+/// The largest distance from a vertex of either mesh to the other's surface, so like
+/// [`min_clearance`] it samples at vertices: a bulge in the middle of a large face of one mesh
+/// only shows up if the other mesh has a vertex there. Zero for identical surfaces, and
+/// infinity if exactly one mesh is empty.
+pub fn hausdorff_distance(a: &Mesh, b: &Mesh) -> f64 {
+    match (a.triangles.is_empty(), b.triangles.is_empty()) {
+        (true, true) => return 0.0,
+        (true, false) | (false, true) => return f64::INFINITY,
+        (false, false) => {}
+    }
+
+    let (surface_a, surface_b) = (Surface::new(a), Surface::new(b));
+    [(a, &surface_b), (b, &surface_a)]
+        .into_iter()
+        .flat_map(|(mesh, other)| mesh.vertices.iter().map(|v| other.distance(&v.position)))
+        .fold(0.0, f64::max)
+}
+
+/// Whether the surfaces of two meshes cross or touch, see [`Mesh::intersects`]
+pub(crate) fn surfaces_intersect(a: &Mesh, b: &Mesh) -> bool {
+    if a.triangles.is_empty() || b.triangles.is_empty() {
+        return false;
+    }
+    Surface::new(a).intersects(&Surface::new(b))
+}
+
+/// Segments where the surfaces of two meshes cross, see [`Mesh::intersection_segments`]
+pub(crate) fn intersection_segments(a: &Mesh, b: &Mesh) -> Vec<(Point3<f64>, Point3<f64>)> {
+    if a.triangles.is_empty() || b.triangles.is_empty() {
+        return Vec::new();
+    }
+
+    let (surface_a, surface_b) = (Surface::new(a), Surface::new(b));
+    surface_a
+        .candidate_pairs(&surface_b)
+        .filter_map(|(tri_a, tri_b)| {
+            let result = triangle_triangle_intersection(tri_a, tri_b);
+            if result.intersection_type != IntersectionType::Segment {
+                return None;
+            }
+            // More than two points are collinear; the segment spans the farthest pair
+            let points = &result.intersection_points;
+            let mut ends = (points[0], points[1]);
+            for (i, p) in points.iter().enumerate() {
+                for q in &points[i + 1..] {
+                    if (q - p).norm() > (ends.1 - ends.0).norm() {
+                        ends = (*p, *q);
+                    }
+                }
+            }
+            Some(ends)
+        })
+        .collect()
+}
+
+/// Nearest point on the surface of `mesh` to `point`, with its triangle index and distance
+///
+/// Points inside a closed mesh get the nearest point on its boundary, at a positive distance.
+/// A mesh without triangles gives `(point, usize::MAX, f64::INFINITY)`.
+pub(crate) fn closest_point_on_mesh(mesh: &Mesh, point: &Point3<f64>) -> (Point3<f64>, usize, f64) {
+    if mesh.triangles.is_empty() {
+        return (*point, usize::MAX, f64::INFINITY);
+    }
+    Surface::new(mesh)
+        .closest(point)
+        .unwrap_or((*point, usize::MAX, f64::INFINITY))
+}
+
+/// Closest point to `p` on a triangle, from Ericson's "Real-Time Collision Detection" 5.1.5
+pub(crate) fn closest_point_on_triangle(
+    p: &Point3<f64>,
+    triangle: &[Point3<f64>; 3],
+) -> Point3<f64> {
+    let [a, b, c] = triangle;
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return *a;
+    }
+
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return *b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return *c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Inside the face; degenerate triangles never get here with a zero denominator
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+fn triangle_bbox(triangle: &[Point3<f64>; 3]) -> BoundingBox {
+    let mut bbox = BoundingBox::empty();
+    for p in triangle {
+        bbox.expand_to_include(p);
+    }
+    bbox
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+    use nalgebra::{Matrix4, Vector3};
+
+    fn cube_at(offset: Vector3<f64>) -> Mesh {
+        let mut cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        cube.transform(&Matrix4::new_translation(&offset));
+        cube
+    }
+
+    #[test]
+    fn test_clearance_between_separate_cubes() {
+        let a = cube_at(Vector3::zeros());
+        let b = cube_at(Vector3::new(12.0, 3.0, -4.0));
+
+        assert!((min_clearance(&a, &b) - 2.0).abs() < 1e-9);
+        assert!((min_clearance(&b, &a) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overlapping_cubes_have_negative_clearance() {
+        let a = cube_at(Vector3::zeros());
+        let b = cube_at(Vector3::new(8.0, 2.0, 2.0));
+
+        // The corner of each cube sits 2mm inside the other
+        assert!((min_clearance(&a, &b) + 2.0).abs() < 1e-9);
+
+        // Face contact is a collision with no depth
+        let touching = cube_at(Vector3::new(10.0, 0.0, 0.0));
+        assert_eq!(min_clearance(&a, &touching), 0.0);
+    }
+
+    #[test]
+    fn test_hausdorff_distance_of_shifted_cube() {
+        let cube = cube_at(Vector3::zeros());
+        assert_eq!(hausdorff_distance(&cube, &cube), 0.0);
+
+        let shifted = cube_at(Vector3::new(0.5, 0.0, 0.0));
+        assert!((hausdorff_distance(&cube, &shifted) - 0.5).abs() < 1e-9);
+        assert_eq!(hausdorff_distance(&cube, &Mesh::empty()), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_interpenetrating_cubes_intersect() {
+        let cube = cube_at(Vector3::zeros());
+        let overlapping = cube_at(Vector3::new(5.0, 5.0, 5.0));
+        assert!(cube.intersects(&overlapping));
+
+        let segments = cube.intersection_segments(&overlapping);
+        assert!(!segments.is_empty());
+        // Every segment lies on both surfaces
+        let on_both = |p| cube.closest_point(p).2 < 1e-9 && overlapping.closest_point(p).2 < 1e-9;
+        for (p, q) in &segments {
+            assert!((q - p).norm() > 0.0);
+            assert!(on_both(*p) && on_both(*q), "{:?}", (p, q));
+        }
+
+        let separated = cube_at(Vector3::new(20.0, 0.0, 0.0));
+        assert!(!cube.intersects(&separated));
+        assert!(cube.intersection_segments(&separated).is_empty());
+        assert!(!cube.intersects(&Mesh::empty()));
+    }
+
+    #[test]
+    fn test_closest_point_from_cube_center() {
+        let cube = cube_at(Vector3::zeros());
+        let (point, triangle, distance) = cube.closest_point(Point3::new(5.0, 5.0, 5.0));
+
+        assert!((distance - 5.0).abs() < 1e-9);
+        assert!((point - Point3::new(5.0, 5.0, 5.0)).norm() - 5.0 < 1e-9);
+        // The point is on a face of the cube, and on the triangle reported
+        let on_face = (0..3).any(|k| point[k].abs() < 1e-9 || (point[k] - 10.0).abs() < 1e-9);
+        assert!(on_face, "{}", point);
+        let corners = cube.triangles[triangle]
+            .indices
+            .map(|i| cube.vertices[i].position);
+        assert!((closest_point_on_triangle(&point, &corners) - point).norm() < 1e-9);
+
+        let (outside, _, distance) = cube.closest_point(Point3::new(13.0, 14.0, 5.0));
+        assert!((outside - Point3::new(10.0, 10.0, 5.0)).norm() < 1e-9);
+        assert!((distance - 5.0).abs() < 1e-9);
+
+        assert_eq!(Mesh::new().closest_point(Point3::origin()).2, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_closest_point_on_triangle_regions() {
+        let triangle = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(4.0, 0.0, 0.0),
+            Point3::new(0.0, 4.0, 0.0),
+        ];
+        let closest = |x, y, z| closest_point_on_triangle(&Point3::new(x, y, z), &triangle);
+
+        assert_eq!(closest(1.0, 1.0, 3.0), Point3::new(1.0, 1.0, 0.0));
+        assert_eq!(closest(-1.0, -1.0, 0.0), triangle[0]);
+        assert_eq!(closest(2.0, -5.0, 1.0), Point3::new(2.0, 0.0, 0.0));
+        assert_eq!(closest(4.0, 4.0, 0.0), Point3::new(2.0, 2.0, 0.0));
+    }
+}
@@ -158,7 +158,9 @@ impl BSPNode {
     }
 
     fn build(&mut self, polygons: Vec<Polygon>) {
-        const MAX_DEPTH: usize = 50; // Prevent infinite recursion
+        // Bounds recursion; a convex solid builds a chain one node per face plane, so this must
+        // comfortably exceed the plane count of typical curved primitives
+        const MAX_DEPTH: usize = 512;
         self.build_with_depth(polygons, 0, MAX_DEPTH);
     }
 
@@ -241,18 +243,6 @@ impl BSPNode {
         }
     }
 
-    /// Clip to BSP, keeping only polygons on the front side (outside)
-    /// Used for union operations to remove internal polygons
-    fn clip_to_front_only(&mut self, bsp: &BSPNode) {
-        self.polygons = bsp.clip_polygons_front_only(&self.polygons);
-        if let Some(ref mut front) = self.front {
-            front.clip_to_front_only(bsp);
-        }
-        if let Some(ref mut back) = self.back {
-            back.clip_to_front_only(bsp);
-        }
-    }
-
     fn clip_polygons(&self, polygons: &[Polygon]) -> Vec<Polygon> {
         if self.plane.is_none() {
             return polygons.to_vec();
@@ -327,39 +317,6 @@ impl BSPNode {
         front_result
     }
 
-    /// Clip polygons, keeping only those on the front side (for union operations)
-    /// Front side = outside the solid (empty space), back side = inside (solid)
-    /// Coplanar polygons are discarded as they represent internal surfaces in union
-    fn clip_polygons_front_only(&self, polygons: &[Polygon]) -> Vec<Polygon> {
-        if self.plane.is_none() {
-            // Empty BSP means all space - if we're looking for "outside", return all
-            // (This happens at leaf nodes that represent empty space)
-            return polygons.to_vec();
-        }
-
-        let mut front = Vec::new();
-        let mut back = Vec::new();
-        // Coplanar polygons are discarded - they're on the boundary and become internal in union
-
-        for poly in polygons {
-            let (mut f, mut b, cf, cb) = self.plane.as_ref().unwrap().split_polygon(poly);
-            front.append(&mut f);
-            back.append(&mut b);
-            // Discard coplanar polygons (cf, cb) - they're internal surfaces in union
-        }
-
-        // Recursively clip front polygons (outside space)
-        let front = if let Some(ref front_node) = self.front {
-            front_node.clip_polygons_front_only(&front)
-        } else {
-            // No front node = we've reached empty space outside the solid - keep all front polygons
-            front
-        };
-
-        // Back polygons are inside the solid - discard them completely
-        front
-    }
-
     fn invert(&mut self) {
         for poly in &mut self.polygons {
             poly.flip();
@@ -380,8 +337,13 @@ impl BSPNode {
 
 /// Convert mesh to polygons
 fn mesh_to_polygons(mesh: &Mesh) -> Vec<Polygon> {
+    // Zero-area triangles (e.g. at sphere poles) have no plane and would poison the BSP tree
     mesh.triangles
         .iter()
+        .filter(|tri| {
+            let [p0, p1, p2] = tri.indices.map(|i| mesh.vertices[i].position);
+            (p1 - p0).cross(&(p2 - p0)).norm() > 1e-12
+        })
         .map(|tri| Polygon {
             vertices: vec![
                 mesh.vertices[tri.indices[0]],
@@ -452,6 +414,9 @@ pub fn csg_union_with_quality(
             // Use the full robust union with intersection splitting
             super::robust_csg::robust_union_core(a, b)
         }
+        super::boolean::BooleanQuality::Exact => {
+            super::exact_csg::exact_boolean(a, b, &super::boolean::BooleanOp::Union)
+        }
     }
 }
 
@@ -512,29 +477,54 @@ pub(crate) fn csg_difference_bsp_internal(a: &Mesh, b: &Mesh) -> Result<Mesh> {
         return Ok(a.clone());
     }
 
-    let tree_a = BSPNode::new(polys_a.clone());
-    let tree_b = BSPNode::new(polys_b.clone());
+    let (mut result_polys, cut_polys) = difference_bsp_parts(&polys_a, &polys_b);
+    result_polys.extend(cut_polys);
+
+    Ok(polygons_to_mesh(&result_polys))
+}
+
+/// BSP difference split into the parts of A outside B and the inverted parts of B inside A
+fn difference_bsp_parts(polys_a: &[Polygon], polys_b: &[Polygon]) -> (Vec<Polygon>, Vec<Polygon>) {
+    let tree_a = BSPNode::new(polys_a.to_vec());
+    let tree_b = BSPNode::new(polys_b.to_vec());
 
     // Step 1: Keep parts of A that are outside B
-    // Use clip_polygons_front_only to keep only polygons outside B
-    let a_outside_b = tree_b.clip_polygons_front_only(&polys_a);
-
-    // Step 2: Keep parts of B (inverted) that are inside A
-    // Invert B to get its complement
-    let mut tree_b_inv = tree_b.clone();
-    tree_b_inv.invert();
-    let polys_b_inv = tree_b_inv.all_polygons();
-    
-    // Clip inverted B to A, keeping only parts inside A
-    // We need to clip inverted B's polygons to A and keep the parts that are inside A
-    // This is done by clipping to A and keeping front polygons (inside A)
-    let b_inv_inside_a = tree_a.clip_polygons_front_only(&polys_b_inv);
-    
-    // Step 3: Combine the results
-    let mut result_polys = a_outside_b;
-    result_polys.extend(b_inv_inside_a);
+    // clip_polygons drops everything in B's solid cells, recursing into both subtrees
+    let a_outside_b = tree_b.clip_polygons(polys_a);
+
+    // Step 2: Keep parts of B that are inside A, then invert them to face into the cavity
+    // Clipping against inverted A drops everything outside the original A
+    let mut tree_a_inv = tree_a;
+    tree_a_inv.invert();
+    let mut b_inv_inside_a = tree_a_inv.clip_polygons(polys_b);
+    for poly in &mut b_inv_inside_a {
+        poly.flip();
+    }
 
-    Ok(polygons_to_mesh(&result_polys))
+    (a_outside_b, b_inv_inside_a)
+}
+
+/// Compute A - B along with the cut surface: the part of B's surface that now bounds the cavity
+///
+/// Uses the BSP difference, so the cut is exactly the inverted fragments of B that close the
+/// hole and its normals point into the cavity. Returns `(difference, cut)`; the cut is empty when
+/// B misses A.
+pub fn difference_with_cut(a: &Mesh, b: &Mesh) -> Result<(Mesh, Mesh)> {
+    let polys_a = mesh_to_polygons(a);
+    let polys_b = mesh_to_polygons(b);
+
+    if polys_a.is_empty() {
+        return Ok((Mesh::empty(), Mesh::empty()));
+    }
+    if polys_b.is_empty() {
+        return Ok((a.clone(), Mesh::empty()));
+    }
+
+    let (mut result_polys, cut_polys) = difference_bsp_parts(&polys_a, &polys_b);
+    let cut = polygons_to_mesh(&cut_polys);
+    result_polys.extend(cut_polys);
+
+    Ok((polygons_to_mesh(&result_polys), cut))
 }
 
 /// Perform CSG difference using BSP trees
@@ -545,26 +535,31 @@ pub fn csg_difference(a: &Mesh, b: &Mesh) -> Result<Mesh> {
 }
 
 /// Perform CSG intersection using BSP trees
+/// A ∩ B: keep parts of A inside B, keep parts of B inside A, combine
 pub fn csg_intersection(a: &Mesh, b: &Mesh) -> Result<Mesh> {
     let polys_a = mesh_to_polygons(a);
     let polys_b = mesh_to_polygons(b);
 
+    if polys_a.is_empty() || polys_b.is_empty() {
+        return Ok(Mesh::empty());
+    }
+
     let mut tree_a = BSPNode::new(polys_a);
     let mut tree_b = BSPNode::new(polys_b);
 
-    // A ∩ B is computed as: invert A, clip B to A, invert A, clip A to B, combine
-    tree_a.invert();
-    tree_b.clip_to(&tree_a);
-    tree_a.invert();
-    tree_a.clip_to(&tree_b);
+    // Clipping against an inverted tree drops everything outside the original solid
+    let mut inside_a = tree_a.clone();
+    inside_a.invert();
+    let mut inside_b = tree_b.clone();
+    inside_b.invert();
+    tree_a.clip_to(&inside_b);
+    tree_b.clip_to(&inside_a);
 
     let mut result_polys = tree_a.all_polygons();
     result_polys.extend(tree_b.all_polygons());
 
-    let mut result = polygons_to_mesh(&result_polys);
-    // Normals already recomputed in polygons_to_mesh, but ensure they're correct
-    result.recompute_normals();
-    Ok(result)
+    // Normals already recomputed in polygons_to_mesh
+    Ok(polygons_to_mesh(&result_polys))
 }
 
 #[cfg(test)]
@@ -702,4 +697,112 @@ mod tests {
         assert!(mesh.vertex_count() > 0);
         assert!(mesh.triangle_count() > 0);
     }
+
+    #[test]
+    fn test_bsp_difference_removes_overlap() -> Result<()> {
+        // A corner notch: fragments of A outside B plus inverted fragments of B inside A
+        let a = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), true).to_mesh();
+        let b = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), false).to_mesh();
+
+        let result = csg_difference_bsp_internal(&a, &b)?;
+        assert!((crate::geometry::analyze(&result).volume - 7000.0).abs() < 1e-6);
+        assert!(result.bounding_box().approx_eq(&a.bounding_box(), 1e-9));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csg_intersection_keeps_overlap() -> Result<()> {
+        // Offset cubes share a 10 x 10 x 10 corner
+        let a = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), true).to_mesh();
+        let b = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), false).to_mesh();
+
+        let result = csg_intersection(&a, &b)?;
+        assert!((crate::geometry::analyze(&result).volume - 1000.0).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_csg_intersection_of_disjoint_shapes_is_empty() -> Result<()> {
+        let a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mut b = Primitive::sphere(4.0, 12).to_mesh();
+        b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(30.0, 0.0, 0.0)));
+
+        assert_eq!(csg_intersection(&a, &b)?.triangle_count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bsp_difference_with_many_planes() -> Result<()> {
+        // 96 side planes build a deeper BSP chain than the old depth limit of 50 allowed
+        let cube = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), true).to_mesh();
+        let mut drill = Primitive::cylinder(30.0, 5.0, 96).to_mesh();
+        drill.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(0.0, 0.0, -15.0)));
+
+        let result = csg_difference_bsp_internal(&cube, &drill)?;
+        let hole_area = 48.0 * 25.0 * (2.0 * std::f64::consts::PI / 96.0).sin();
+        let expected = 8000.0 - hole_area * 20.0;
+        let volume = crate::geometry::analyze(&result).volume;
+        assert!((volume - expected).abs() < 1e-6, "volume {} expected {}", volume, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bsp_difference_ignores_zero_area_triangles() -> Result<()> {
+        // A pocket with a sliver triangle, which has no plane to split along
+        let cube = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), true).to_mesh();
+        let mut pocket = Primitive::cube(Vector3::new(4.0, 4.0, 4.0), true).to_mesh();
+        let corner = pocket.vertices[pocket.triangles[0].indices[0]];
+        let first = pocket.add_vertex(corner);
+        let second = pocket.add_vertex(corner);
+        pocket.add_triangle(Triangle::new([first, second, pocket.triangles[0].indices[1]]));
+
+        let result = csg_difference_bsp_internal(&cube, &pocket)?;
+        assert!((crate::geometry::analyze(&result).volume - (8000.0 - 64.0)).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_difference_with_cut_is_sphere_cap() -> Result<()> {
+        // Sphere centered on the cube's top face: the cut is the lower half of the sphere
+        let cube = Primitive::cube(Vector3::new(20.0, 20.0, 20.0), true).to_mesh();
+        let center = Vector3::new(0.0, 0.0, 10.0);
+        let mut sphere = Primitive::sphere(6.0, 16).to_mesh();
+        sphere.transform(&nalgebra::Matrix4::new_translation(&center));
+
+        let (result, cut) = difference_with_cut(&cube, &sphere)?;
+        assert!(cut.triangle_count() > 0);
+        assert!(cut.triangle_count() < result.triangle_count());
+
+        // The cube loses the half of the sphere inside it
+        let removed = 8000.0 - crate::geometry::analyze(&result).volume;
+        let half_sphere = 2.0 / 3.0 * std::f64::consts::PI * 216.0;
+        assert!((removed - half_sphere).abs() / half_sphere < 0.05, "removed {}", removed);
+
+        for triangle in &cut.triangles {
+            let [p0, p1, p2] = triangle.indices.map(|i| cut.vertices[i].position.coords);
+            for p in [p0, p1, p2] {
+                assert!(((p - center).norm() - 6.0).abs() < 1e-6);
+                assert!(p.z <= 10.0 + 1e-6);
+            }
+
+            // Winding faces the sphere center, i.e. into the cavity
+            let centroid = (p0 + p1 + p2) / 3.0;
+            let normal = (p1 - p0).cross(&(p2 - p0));
+            assert!(normal.dot(&(center - centroid)) > 0.0);
+        }
+
+        // Roughly a hemisphere's area, 2πr²
+        let area: f64 = cut
+            .triangles
+            .iter()
+            .map(|t| {
+                let [p0, p1, p2] = t.indices.map(|i| cut.vertices[i].position);
+                (p1 - p0).cross(&(p2 - p0)).norm() / 2.0
+            })
+            .sum();
+        let hemisphere = 2.0 * std::f64::consts::PI * 36.0;
+        assert!((area - hemisphere).abs() / hemisphere < 0.1, "area {}", area);
+
+        Ok(())
+    }
 }
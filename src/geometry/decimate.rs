@@ -0,0 +1,365 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Quadric error metric mesh simplification
+//!
+//! Garland-Heckbert edge collapse: every vertex carries the sum of the squared-distance
+//! quadrics of its planes, and the edge whose collapse adds the least error is removed first.
+
+use super::halfedge::HalfEdgeMesh;
+use super::{welded_for_topology, Mesh, Triangle, Vertex};
+use nalgebra::{Matrix4, Point3, Vector3, Vector4};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Weight of the constraint planes that pin boundary edges in place
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+
+/// Smallest `cos` between a face normal before and after a collapse
+const MIN_NORMAL_COS: f64 = 1e-3;
+
+/// Edge collapse candidate, ordered so the cheapest collapse is popped first
+struct Candidate {
+    cost: f64,
+    a: usize,
+    b: usize,
+    position: Point3<f64>,
+    stamps: (u32, u32),
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Working state of the simplification
+struct Decimator {
+    positions: Vec<Point3<f64>>,
+    quadrics: Vec<Matrix4<f64>>,
+    faces: Vec<[usize; 3]>,
+    face_alive: Vec<bool>,
+    vertex_faces: Vec<Vec<usize>>,
+    stamps: Vec<u32>,
+    live_faces: usize,
+}
+
+/// Simplify `mesh` to roughly `target_triangles` triangles
+///
+/// Coincident vertices are welded first. Collapses that would flip a face or break the
+/// link condition (and so create non-manifold topology) are skipped, which means a mesh can
+/// stop short of the target when no valid collapse remains.
+pub(crate) fn decimate(mesh: &Mesh, target_triangles: usize) -> Mesh {
+    if mesh.triangles.len() <= target_triangles {
+        return mesh.clone();
+    }
+
+    let welded = welded_for_topology(mesh);
+    let he_mesh = HalfEdgeMesh::from_mesh(&welded);
+    let mut decimator = Decimator::new(&he_mesh);
+
+    let mut heap = BinaryHeap::new();
+    for edge in &he_mesh.edges {
+        let he = &he_mesh.half_edges[edge.half_edge_a];
+        let (a, b) = (he_mesh.half_edges[he.prev].vertex, he.vertex);
+        heap.push(decimator.candidate(a, b));
+    }
+
+    while decimator.live_faces > target_triangles {
+        let Some(candidate) = heap.pop() else {
+            break;
+        };
+        let (a, b) = (candidate.a, candidate.b);
+        if (decimator.stamps[a], decimator.stamps[b]) != candidate.stamps {
+            continue;
+        }
+        if !decimator.can_collapse(a, b, &candidate.position) {
+            continue;
+        }
+
+        decimator.collapse(a, b, candidate.position);
+        for neighbor in decimator.neighbors(a) {
+            heap.push(decimator.candidate(a, neighbor));
+        }
+    }
+
+    decimator.into_mesh()
+}
+
+impl Decimator {
+    fn new(he_mesh: &HalfEdgeMesh) -> Self {
+        let positions = he_mesh.vertices.clone();
+        let faces: Vec<[usize; 3]> = he_mesh
+            .faces
+            .iter()
+            .map(|f| [f[0] as usize, f[1] as usize, f[2] as usize])
+            .collect();
+
+        let mut quadrics = vec![Matrix4::zeros(); positions.len()];
+        let mut vertex_faces = vec![Vec::new(); positions.len()];
+        for (face_idx, face) in faces.iter().enumerate() {
+            let [p0, p1, p2] = face.map(|v| positions[v]);
+            let normal = (p1 - p0).cross(&(p2 - p0));
+            if let Some(normal) = normal.try_normalize(1e-12) {
+                let quadric = plane_quadric(&normal, &p0);
+                for &v in face {
+                    quadrics[v] += quadric;
+                }
+            }
+            for &v in face {
+                vertex_faces[v].push(face_idx);
+            }
+        }
+
+        // Boundary edges get a perpendicular plane so open borders don't shrink
+        for he in &he_mesh.half_edges {
+            if he.twin.is_some() {
+                continue;
+            }
+            let (a, b) = (he_mesh.half_edges[he.prev].vertex, he.vertex);
+            let face = faces[he.face];
+            let [p0, p1, p2] = face.map(|v| positions[v]);
+            let face_normal = (p1 - p0).cross(&(p2 - p0));
+            let edge = positions[b] - positions[a];
+            if let Some(normal) = edge.cross(&face_normal).try_normalize(1e-12) {
+                let quadric = plane_quadric(&normal, &positions[a]) * BOUNDARY_WEIGHT;
+                quadrics[a] += quadric;
+                quadrics[b] += quadric;
+            }
+        }
+
+        Self {
+            stamps: vec![0; positions.len()],
+            face_alive: vec![true; faces.len()],
+            live_faces: faces.len(),
+            positions,
+            quadrics,
+            faces,
+            vertex_faces,
+        }
+    }
+
+    /// Cost and target position of collapsing edge `a`-`b`
+    fn candidate(&self, a: usize, b: usize) -> Candidate {
+        let quadric = self.quadrics[a] + self.quadrics[b];
+
+        // Minimize the quadric if it is well conditioned, otherwise try the endpoints and midpoint
+        let mut solve = quadric;
+        solve.set_row(3, &Vector4::new(0.0, 0.0, 0.0, 1.0).transpose());
+        let optimal = solve
+            .try_inverse()
+            .map(|inverse| inverse * Vector4::new(0.0, 0.0, 0.0, 1.0))
+            .filter(|p| p.iter().all(|c| c.is_finite()))
+            .map(|p| Point3::new(p.x, p.y, p.z));
+
+        let (pa, pb) = (self.positions[a], self.positions[b]);
+        let (cost, position) = optimal
+            .into_iter()
+            .chain([pa, pb, nalgebra::center(&pa, &pb)])
+            .map(|p| (quadric_error(&quadric, &p), p))
+            .min_by(|x, y| x.0.total_cmp(&y.0))
+            .unwrap();
+
+        Candidate {
+            cost,
+            a,
+            b,
+            position,
+            stamps: (self.stamps[a], self.stamps[b]),
+        }
+    }
+
+    fn neighbors(&self, v: usize) -> HashSet<usize> {
+        self.vertex_faces[v]
+            .iter()
+            .flat_map(|&f| self.faces[f])
+            .filter(|&n| n != v)
+            .collect()
+    }
+
+    /// Check the link condition and that no surviving face flips or degenerates
+    fn can_collapse(&self, a: usize, b: usize, position: &Point3<f64>) -> bool {
+        let shared_faces: Vec<usize> = self.vertex_faces[a]
+            .iter()
+            .copied()
+            .filter(|&f| self.faces[f].contains(&b))
+            .collect();
+        if shared_faces.is_empty() {
+            return false;
+        }
+
+        // Vertices adjacent to both endpoints must be exactly the apexes of the shared faces
+        let apexes: HashSet<usize> = shared_faces
+            .iter()
+            .flat_map(|&f| self.faces[f])
+            .filter(|&v| v != a && v != b)
+            .collect();
+        let common: HashSet<usize> = self
+            .neighbors(a)
+            .intersection(&self.neighbors(b))
+            .copied()
+            .collect();
+        if common != apexes {
+            return false;
+        }
+
+        // Collapsing the last faces of a closed shell would leave nothing behind
+        if self.live_faces <= shared_faces.len() + 2 {
+            return false;
+        }
+
+        for &f in self.vertex_faces[a].iter().chain(&self.vertex_faces[b]) {
+            if shared_faces.contains(&f) {
+                continue;
+            }
+            let face = self.faces[f];
+            let before = face.map(|v| self.positions[v]);
+            let after = face.map(|v| {
+                if v == a || v == b {
+                    *position
+                } else {
+                    self.positions[v]
+                }
+            });
+            let normal_before = (before[1] - before[0]).cross(&(before[2] - before[0]));
+            let normal_after = (after[1] - after[0]).cross(&(after[2] - after[0]));
+            let (Some(n0), Some(n1)) = (
+                normal_before.try_normalize(1e-12),
+                normal_after.try_normalize(1e-12),
+            ) else {
+                return false;
+            };
+            if n0.dot(&n1) < MIN_NORMAL_COS {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Merge `b` into `a`, moving `a` to `position`
+    fn collapse(&mut self, a: usize, b: usize, position: Point3<f64>) {
+        for f in std::mem::take(&mut self.vertex_faces[b]) {
+            if !self.face_alive[f] {
+                continue;
+            }
+            if self.faces[f].contains(&a) {
+                self.face_alive[f] = false;
+                self.live_faces -= 1;
+            } else {
+                for v in &mut self.faces[f] {
+                    if *v == b {
+                        *v = a;
+                    }
+                }
+                self.vertex_faces[a].push(f);
+            }
+        }
+
+        let face_alive = &self.face_alive;
+        self.vertex_faces[a].retain(|&f| face_alive[f]);
+        for n in self.neighbors(a) {
+            self.vertex_faces[n].retain(|&f| face_alive[f]);
+            self.stamps[n] += 1;
+        }
+
+        self.positions[a] = position;
+        self.quadrics[a] = self.quadrics[a] + self.quadrics[b];
+        self.stamps[a] += 1;
+        self.stamps[b] += 1;
+    }
+
+    fn into_mesh(self) -> Mesh {
+        let mut mesh = Mesh::new();
+        let mut remap = vec![usize::MAX; self.positions.len()];
+
+        for (face, _) in self
+            .faces
+            .iter()
+            .zip(&self.face_alive)
+            .filter(|(_, &alive)| alive)
+        {
+            let indices = face.map(|v| {
+                if remap[v] == usize::MAX {
+                    remap[v] = mesh.add_vertex(Vertex::new(self.positions[v], Vector3::zeros()));
+                }
+                remap[v]
+            });
+            mesh.add_triangle(Triangle::new(indices));
+        }
+
+        mesh.recompute_normals();
+        mesh
+    }
+}
+
+/// Fundamental error quadric of the plane through `point` with unit `normal`
+fn plane_quadric(normal: &Vector3<f64>, point: &Point3<f64>) -> Matrix4<f64> {
+    let plane = Vector4::new(normal.x, normal.y, normal.z, -normal.dot(&point.coords));
+    plane * plane.transpose()
+}
+
+fn quadric_error(quadric: &Matrix4<f64>, point: &Point3<f64>) -> f64 {
+    let p = point.to_homogeneous();
+    (p.transpose() * quadric * p)[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+
+    #[test]
+    fn test_decimate_sphere_keeps_shape() {
+        let sphere = Primitive::sphere(10.0, 64).to_mesh();
+        assert!(sphere.triangle_count() > 2000);
+
+        let decimated = decimate(&sphere, 200);
+        assert!(
+            decimated.triangle_count().abs_diff(200) <= 2,
+            "got {} triangles",
+            decimated.triangle_count()
+        );
+
+        let original = sphere.bounding_box();
+        let simplified = decimated.bounding_box();
+        for axis in 0..3 {
+            let extent = original.max[axis] - original.min[axis];
+            assert!((simplified.min[axis] - original.min[axis]).abs() < extent * 0.05);
+            assert!((simplified.max[axis] - original.max[axis]).abs() < extent * 0.05);
+        }
+
+        // Still a closed shell with every directed edge used once (no flipped faces)
+        let validation = decimated.validate();
+        assert!(validation.is_closed && validation.is_manifold);
+        let mut directed = HashSet::new();
+        for t in &decimated.triangles {
+            for k in 0..3 {
+                assert!(directed.insert((t.indices[k], t.indices[(k + 1) % 3])));
+            }
+        }
+        let volume = crate::geometry::analyze(&decimated).volume;
+        assert!(volume > 0.9 * 4.0 / 3.0 * std::f64::consts::PI * 1000.0);
+    }
+
+    #[test]
+    fn test_decimate_below_current_count_is_noop() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let result = decimate(&cube, 100);
+        assert_eq!(result.triangle_count(), cube.triangle_count());
+    }
+}
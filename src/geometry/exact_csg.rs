@@ -0,0 +1,524 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Exact-predicate CSG
+//!
+//! Every triangle is cut by the planes of the other operand's nearby triangles, so each
+//! fragment lies entirely on one side of the other surface. Which side of a plane a point is
+//! on, whether a fragment lies on a face of the other operand, and whether a ray crosses a
+//! triangle are all decided by the exact [`orient3d`] and [`orient2d`] signs of the snapped
+//! coordinates. Only the cut points themselves are rounded, to the snap grid.
+//!
+//! Coplanar fragments are classified by which way the touching face points, and the rest by
+//! the signed number of faces a ray from the fragment crosses, counted with a BVH.
+
+use super::bvh::BVH;
+use super::predicates::{orient2d, orient3d};
+use super::robust_predicates::{oriented_volume, triangle_area};
+use super::triangle_intersection::triangle_triangle_intersection;
+use super::{BooleanOp, BoundingBox, Mesh, Triangle, Vertex};
+use anyhow::Result;
+use nalgebra::{Point2, Point3, Vector3};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Grid that input vertices and intersection points are snap-rounded to
+const SNAP_GRID: f64 = 1e-9;
+
+/// Directions tried in turn for the inside test, tilted off the axes and off each other so a
+/// ray that runs exactly through an edge or vertex has another to fall back on
+const RAY_DIRECTIONS: [[f64; 3]; 4] = [
+    [1.0, 0.0137, 0.0071],
+    [0.0113, 1.0, 0.0157],
+    [0.0089, 0.0131, 1.0],
+    [-0.71, 0.39, 0.59],
+];
+
+/// Fragments smaller than this after snapping are dropped
+const MIN_FRAGMENT_AREA: f64 = 1e-14;
+
+/// Where a fragment sits relative to the other solid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Inside,
+    Outside,
+    /// On the other surface, facing the same way
+    SameFacing,
+    /// On the other surface, facing the opposite way
+    OppositeFacing,
+}
+
+/// Triangle soup of one operand with its BVH
+struct Operand {
+    triangles: Vec<[Point3<f64>; 3]>,
+    bvh: BVH,
+    bounds: BoundingBox,
+}
+
+/// Perform `op` on `a` and `b` using exact orientation tests
+pub(crate) fn exact_boolean(a: &Mesh, b: &Mesh, op: &BooleanOp) -> Result<Mesh> {
+    if a.triangles.is_empty() || b.triangles.is_empty() {
+        return Ok(match op {
+            BooleanOp::Union if a.triangles.is_empty() => b.clone(),
+            BooleanOp::SymmetricDifference if a.triangles.is_empty() => b.clone(),
+            BooleanOp::Union | BooleanOp::Difference | BooleanOp::SymmetricDifference => a.clone(),
+            BooleanOp::Intersection => Mesh::empty(),
+        });
+    }
+
+    if let BooleanOp::SymmetricDifference = op {
+        return super::boolean::symmetric_difference(a, b, super::BooleanQuality::Exact);
+    }
+
+    let a = Operand::new(a);
+    let b = Operand::new(b);
+
+    // Coplanar faces are kept once: from A when both solids lie on the same side of it
+    // (union, intersection), and from A alone when B lies on its far side (difference)
+    let keep_a = |side: Side| match op {
+        BooleanOp::Union => matches!(side, Side::Outside | Side::SameFacing),
+        BooleanOp::Intersection => matches!(side, Side::Inside | Side::SameFacing),
+        BooleanOp::Difference => matches!(side, Side::Outside | Side::OppositeFacing),
+        BooleanOp::SymmetricDifference => unreachable!("composed from difference and union"),
+    };
+    let keep_b = |side: Side| match op {
+        BooleanOp::Union => side == Side::Outside,
+        BooleanOp::Intersection | BooleanOp::Difference => side == Side::Inside,
+        BooleanOp::SymmetricDifference => unreachable!("composed from difference and union"),
+    };
+    let flip_b = matches!(op, BooleanOp::Difference);
+
+    let mut result = Mesh::new();
+    for fragment in a.fragments_against(&b) {
+        if keep_a(b.classify(&fragment)) {
+            add_fragment(&mut result, fragment);
+        }
+    }
+    for fragment in b.fragments_against(&a) {
+        if keep_b(a.classify(&fragment)) {
+            add_fragment(
+                &mut result,
+                if flip_b {
+                    [fragment[0], fragment[2], fragment[1]]
+                } else {
+                    fragment
+                },
+            );
+        }
+    }
+
+    // Snapped coordinates are shared exactly, so welding only needs to merge identical points
+    result.deduplicate_vertices(SNAP_GRID);
+    result.recompute_normals();
+    Ok(result)
+}
+
+/// Outer surface of a possibly self-intersecting mesh
+///
+/// Each triangle is cut by the planes of the non-adjacent triangles it crosses. A fragment is
+/// kept when the winding number, probed `probe` away on either side of it, says the solid lies
+/// behind it and not in front, so folds, overlapping shells, and buried pieces are dropped.
+/// Uncut triangles joined by edges can't change sides, so each such patch is probed once.
+pub(crate) fn exact_self_union(mesh: &Mesh, probe: f64) -> Mesh {
+    let solid = Operand::new(mesh);
+    let count = solid.triangles.len();
+
+    let cut_planes: Vec<Vec<[Point3<f64>; 3]>> = solid
+        .triangles
+        .iter()
+        .enumerate()
+        .map(|(i, triangle)| {
+            solid
+                .bvh
+                .query_triangles(&triangle_bbox(triangle, 0.0))
+                .into_iter()
+                .filter(|&j| j != i)
+                .map(|j| &solid.triangles[j])
+                // Snapped corners are shared exactly, so adjacency is plain equality
+                .filter(|other| !other.iter().any(|p| triangle.contains(p)))
+                .filter(|other| triangle_triangle_intersection(triangle, other).intersects)
+                .copied()
+                .collect()
+        })
+        .collect();
+
+    // Patches of uncut triangles, joined across shared edges
+    let key = |p: &Point3<f64>| p.coords.map(f64::to_bits);
+    let mut patch: Vec<usize> = (0..count).collect();
+    fn find(patch: &mut [usize], mut i: usize) -> usize {
+        while patch[i] != i {
+            patch[i] = patch[patch[i]];
+            i = patch[i];
+        }
+        i
+    }
+    let mut edges = HashMap::new();
+    for (i, triangle) in solid.triangles.iter().enumerate() {
+        if !cut_planes[i].is_empty() {
+            continue;
+        }
+        for k in 0..3 {
+            let (a, b) = (key(&triangle[k]), key(&triangle[(k + 1) % 3]));
+            let edge = if a.as_slice() < b.as_slice() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            if let Some(&j) = edges.get(&edge) {
+                let (ri, rj) = (find(&mut patch, i), find(&mut patch, j));
+                patch[ri] = rj;
+            } else {
+                edges.insert(edge, i);
+            }
+        }
+    }
+
+    let is_boundary = |fragment: &[Point3<f64>; 3]| {
+        let Some((normal, _)) = plane_of(fragment) else {
+            return false;
+        };
+        let centroid =
+            Point3::from((fragment[0].coords + fragment[1].coords + fragment[2].coords) / 3.0);
+        let front = solid.winding_number(&(centroid + normal * probe));
+        let back = solid.winding_number(&(centroid - normal * probe));
+        back > 0.5 && front < 0.5
+    };
+
+    let mut result = Mesh::new();
+    let mut patch_kept: HashMap<usize, bool> = HashMap::new();
+    for (i, triangle) in solid.triangles.iter().enumerate() {
+        if cut_planes[i].is_empty() {
+            let root = find(&mut patch, i);
+            if *patch_kept
+                .entry(root)
+                .or_insert_with(|| is_boundary(triangle))
+            {
+                add_fragment(&mut result, *triangle);
+            }
+            continue;
+        }
+
+        let mut pieces = vec![*triangle];
+        for plane in &cut_planes[i] {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| split_by_plane(piece, plane))
+                .collect();
+        }
+        for fragment in pieces {
+            if is_boundary(&fragment) {
+                add_fragment(&mut result, fragment);
+            }
+        }
+    }
+
+    result.deduplicate_vertices(SNAP_GRID);
+    result.recompute_normals();
+    result
+}
+
+impl Operand {
+    fn new(mesh: &Mesh) -> Self {
+        let triangles: Vec<[Point3<f64>; 3]> = mesh
+            .triangles
+            .iter()
+            .map(|t| t.indices.map(|i| snap(&mesh.vertices[i].position)))
+            .filter(|t| triangle_area(&t[0], &t[1], &t[2]) > MIN_FRAGMENT_AREA)
+            .collect();
+        let bvh = BVH::build(
+            triangles
+                .iter()
+                .enumerate()
+                // Pad flat boxes so rays along their faces still visit them
+                .map(|(idx, t)| (idx, triangle_bbox(t, SNAP_GRID)))
+                .collect(),
+        );
+        let mut bounds = BoundingBox::empty();
+        for p in triangles.iter().flatten() {
+            bounds.expand_to_include(p);
+        }
+        Self {
+            triangles,
+            bvh,
+            bounds,
+        }
+    }
+
+    /// Split every triangle by the planes of `other`'s triangles that overlap it
+    fn fragments_against(&self, other: &Operand) -> Vec<[Point3<f64>; 3]> {
+        let mut fragments = Vec::new();
+
+        for triangle in &self.triangles {
+            let mut planes: Vec<&[Point3<f64>; 3]> = Vec::new();
+            for idx in other.bvh.query_triangles(&triangle_bbox(triangle, 0.0)) {
+                let plane = &other.triangles[idx];
+                // Faces lying exactly in a plane already cut along would only cut again
+                let duplicate = planes
+                    .iter()
+                    .any(|[a, b, c]| plane.iter().all(|p| orient3d(a, b, c, p) == 0.0));
+                if !duplicate {
+                    planes.push(plane);
+                }
+            }
+
+            let mut pieces = vec![*triangle];
+            for plane in planes {
+                pieces = pieces
+                    .iter()
+                    .flat_map(|piece| split_by_plane(piece, plane))
+                    .collect();
+            }
+            fragments.extend(pieces);
+        }
+
+        fragments
+    }
+
+    /// Classify a fragment that lies entirely on one side of this operand's surface
+    fn classify(&self, fragment: &[Point3<f64>; 3]) -> Side {
+        let centroid =
+            Point3::from((fragment[0].coords + fragment[1].coords + fragment[2].coords) / 3.0);
+        let normal = (fragment[1] - fragment[0]).cross(&(fragment[2] - fragment[0]));
+
+        for idx in self.bvh.query_triangles(&triangle_bbox(fragment, 0.0)) {
+            let triangle = &self.triangles[idx];
+            let [a, b, c] = triangle;
+            let Some((plane_normal, _)) = plane_of(triangle) else {
+                continue;
+            };
+            if fragment.iter().all(|p| orient3d(a, b, c, p) == 0.0)
+                && contains_coplanar(triangle, &plane_normal, &centroid)
+            {
+                return if normal.dot(&plane_normal) > 0.0 {
+                    Side::SameFacing
+                } else {
+                    Side::OppositeFacing
+                };
+            }
+        }
+
+        if self.contains(&centroid) {
+            Side::Inside
+        } else {
+            Side::Outside
+        }
+    }
+
+    /// Whether `point`, which must not lie on the surface, is inside it
+    ///
+    /// Counts the faces a ray from `point` crosses, +1 where it leaves through the front and
+    /// -1 where it enters, which is the winding number of a closed surface. Rays that run
+    /// exactly through an edge or vertex are retried along the next of [`RAY_DIRECTIONS`],
+    /// and if every one does, the generalized winding number decides.
+    fn contains(&self, point: &Point3<f64>) -> bool {
+        // Far enough that the end of the ray is outside every face's box
+        let (min, max) = (self.bounds.min, self.bounds.max);
+        let reach = 2.0 * ((max - min).norm() + (point - self.bounds.center()).norm()) + 1.0;
+
+        'directions: for direction in RAY_DIRECTIONS {
+            let direction = Vector3::from(direction).normalize();
+            let far = point + direction * reach;
+            let mut winding = 0;
+            for idx in self.bvh.query_ray(point, &direction) {
+                let [a, b, c] = &self.triangles[idx];
+                let (near_side, far_side) = (orient3d(a, b, c, point), orient3d(a, b, c, &far));
+                if near_side == 0.0 || far_side == 0.0 {
+                    continue 'directions;
+                }
+                if near_side.signum() == far_side.signum() {
+                    continue;
+                }
+                let edges = [
+                    orient3d(point, &far, a, b),
+                    orient3d(point, &far, b, c),
+                    orient3d(point, &far, c, a),
+                ];
+                if edges.contains(&0.0) {
+                    continue 'directions;
+                }
+                if edges.iter().all(|&e| e > 0.0) || edges.iter().all(|&e| e < 0.0) {
+                    winding += if near_side < 0.0 { 1 } else { -1 };
+                }
+            }
+            return winding > 0;
+        }
+
+        winding_number(&self.triangles, point) > 0.5
+    }
+
+    fn winding_number(&self, point: &Point3<f64>) -> f64 {
+        winding_number(&self.triangles, point)
+    }
+}
+
+/// Generalized winding number of `point`, summing the signed solid angle of each triangle
+///
+/// About 1 inside a closed outward-facing surface, 0 outside, and 0.5 on it.
+pub(crate) fn winding_number(triangles: &[[Point3<f64>; 3]], point: &Point3<f64>) -> f64 {
+    let total: f64 = triangles
+        .iter()
+        .map(|[p0, p1, p2]| {
+            let (a, b, c) = (p0 - point, p1 - point, p2 - point);
+            let (la, lb, lc) = (a.norm(), b.norm(), c.norm());
+            let det = oriented_volume(point, p0, p1, p2);
+            let denom = la * lb * lc + a.dot(&b) * lc + b.dot(&c) * la + c.dot(&a) * lb;
+            2.0 * det.atan2(denom)
+        })
+        .sum();
+    total / (4.0 * PI)
+}
+
+/// Split a triangle by the plane through `plane`'s corners, keeping its winding on both sides
+fn split_by_plane(triangle: &[Point3<f64>; 3], plane: &[Point3<f64>; 3]) -> Vec<[Point3<f64>; 3]> {
+    let sides = triangle.map(|p| side_of(plane, &p));
+    if !(sides.contains(&1) && sides.contains(&-1)) {
+        return vec![*triangle];
+    }
+    let Some((normal, d)) = plane_of(plane) else {
+        return vec![*triangle];
+    };
+
+    let mut front = Vec::with_capacity(4);
+    let mut back = Vec::with_capacity(4);
+    for i in 0..3 {
+        let (p, q) = (triangle[i], triangle[(i + 1) % 3]);
+        let (sp, sq) = (sides[i], sides[(i + 1) % 3]);
+        if sp >= 0 {
+            front.push(p);
+        }
+        if sp <= 0 {
+            back.push(p);
+        }
+        if sp * sq < 0 {
+            let (dp, dq) = (normal.dot(&p.coords) - d, normal.dot(&q.coords) - d);
+            let crossing = snap(&(p + (q - p) * (dp / (dp - dq))));
+            front.push(crossing);
+            back.push(crossing);
+        }
+    }
+
+    [front, back]
+        .iter()
+        .flat_map(|polygon| {
+            (1..polygon.len().saturating_sub(1)).map(|k| [polygon[0], polygon[k], polygon[k + 1]])
+        })
+        .filter(|t| triangle_area(&t[0], &t[1], &t[2]) > MIN_FRAGMENT_AREA)
+        .collect()
+}
+
+/// Side of the plane through `plane`'s corners that `point` is on: 1 in front, -1 behind, and
+/// 0 exactly on it
+fn side_of(plane: &[Point3<f64>; 3], point: &Point3<f64>) -> i8 {
+    let [a, b, c] = plane;
+    let orientation = orient3d(a, b, c, point);
+    if orientation > 0.0 {
+        1
+    } else if orientation < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Whether `point`, already on the plane of `triangle`, falls inside or on its edges
+///
+/// Dropping the coordinate the normal is largest along projects the triangle without
+/// flattening it, so the test is a sign check of [`orient2d`] in that projection.
+fn contains_coplanar(
+    triangle: &[Point3<f64>; 3],
+    normal: &Vector3<f64>,
+    point: &Point3<f64>,
+) -> bool {
+    let axis = normal.iamax();
+    let project = |p: &Point3<f64>| {
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        Point2::new(p[u], p[v])
+    };
+    let [a, b, c] = triangle.map(|p| project(&p));
+    let point = project(point);
+    let turn = orient2d(&a, &b, &c).signum();
+    [(a, b), (b, c), (c, a)]
+        .iter()
+        .all(|(p, q)| orient2d(p, q, &point) * turn >= 0.0)
+}
+
+/// Unit normal and offset of a triangle's plane, or `None` if it is degenerate
+fn plane_of(triangle: &[Point3<f64>; 3]) -> Option<(Vector3<f64>, f64)> {
+    let normal = (triangle[1] - triangle[0])
+        .cross(&(triangle[2] - triangle[0]))
+        .try_normalize(1e-15)?;
+    Some((normal, normal.dot(&triangle[0].coords)))
+}
+
+fn triangle_bbox(triangle: &[Point3<f64>; 3], margin: f64) -> BoundingBox {
+    let mut bbox = BoundingBox::empty();
+    for p in triangle {
+        bbox.expand_to_include(p);
+    }
+    let margin = Vector3::repeat(margin);
+    BoundingBox::new(bbox.min - margin, bbox.max + margin)
+}
+
+fn snap(point: &Point3<f64>) -> Point3<f64> {
+    point.map(|c| (c / SNAP_GRID).round() * SNAP_GRID)
+}
+
+fn add_fragment(mesh: &mut Mesh, fragment: [Point3<f64>; 3]) {
+    let indices = fragment.map(|p| mesh.add_vertex(Vertex::new(p, Vector3::zeros())));
+    mesh.add_triangle(Triangle::new(indices));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{analyze, Primitive};
+    use nalgebra::Matrix4;
+
+    fn cube_at(size: f64, offset: Vector3<f64>) -> Mesh {
+        let mut mesh = Primitive::cube(Vector3::new(size, size, size), false).to_mesh();
+        mesh.transform(&Matrix4::new_translation(&offset));
+        mesh
+    }
+
+    #[test]
+    fn test_exact_difference_of_overlapping_cubes() -> Result<()> {
+        let a = cube_at(10.0, Vector3::zeros());
+        let b = cube_at(10.0, Vector3::new(5.0, 5.0, 5.0));
+
+        let result = exact_boolean(&a, &b, &BooleanOp::Difference)?;
+        assert!((analyze(&result).volume - 875.0).abs() < 1e-6);
+
+        let result = exact_boolean(&a, &b, &BooleanOp::Intersection)?;
+        assert!((analyze(&result).volume - 125.0).abs() < 1e-6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_touching_cubes_difference_keeps_shared_face() -> Result<()> {
+        let a = cube_at(10.0, Vector3::zeros());
+        let b = cube_at(10.0, Vector3::new(10.0, 0.0, 0.0));
+
+        let result = exact_boolean(&a, &b, &BooleanOp::Difference)?;
+        assert_eq!(result.triangle_count(), 12);
+        assert!((analyze(&result).volume - 1000.0).abs() < 1e-9);
+
+        let result = exact_boolean(&a, &b, &BooleanOp::Intersection)?;
+        assert_eq!(result.triangle_count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_union_keeps_nearly_touching_faces() -> Result<()> {
+        // A gap of twenty snap cells is small, but the faces are not coplanar, so both stay
+        let a = cube_at(10.0, Vector3::zeros());
+        let b = cube_at(10.0, Vector3::new(10.0 + 2e-8, 0.0, 0.0));
+
+        let result = exact_boolean(&a, &b, &BooleanOp::Union)?;
+        assert_eq!(result.triangle_count(), 24);
+        assert!((analyze(&result).volume - 2000.0).abs() < 1e-6);
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@
 
 use super::{Mesh, Triangle, Vertex};
 use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
 
 /// Half-edge in a half-edge mesh
 /// Each edge has two half-edges, one for each direction
@@ -152,7 +153,6 @@ impl HalfEdgeMesh {
 
     /// Build edge map and connect twin half-edges
     fn build_edge_map(&mut self) {
-        use std::collections::HashMap;
         
         // Map: (min_vertex, max_vertex) -> half_edge_index
         let mut edge_map: HashMap<(usize, usize), usize> = HashMap::new();
@@ -237,6 +237,145 @@ impl HalfEdgeMesh {
         new_vertex_idx
     }
 
+    /// Twin of each half-edge, kept only where exactly two faces share the edge
+    ///
+    /// Twins along non-manifold edges pair up arbitrarily, so they say nothing reliable
+    /// about how neighboring faces are oriented.
+    fn manifold_twins(&self) -> Vec<Option<usize>> {
+        let mut edge_uses: HashMap<(usize, usize), usize> = HashMap::new();
+        for he in &self.half_edges {
+            let from = self.half_edges[he.prev].vertex;
+            *edge_uses.entry((from.min(he.vertex), from.max(he.vertex))).or_default() += 1;
+        }
+
+        self.half_edges
+            .iter()
+            .map(|he| {
+                let from = self.half_edges[he.prev].vertex;
+                let uses = edge_uses[&(from.min(he.vertex), from.max(he.vertex))];
+                he.twin.filter(|_| uses == 2)
+            })
+            .collect()
+    }
+
+    /// Group faces into connected components across manifold edges
+    ///
+    /// Faces are only connected through shared edges, so patches meeting at a T-junction or
+    /// along an edge with more than two faces land in separate components.
+    pub fn face_components(&self) -> Vec<Vec<usize>> {
+        let twins = self.manifold_twins();
+        let mut component_of = vec![usize::MAX; self.faces.len()];
+        let mut components = Vec::new();
+
+        for seed in 0..self.faces.len() {
+            if component_of[seed] != usize::MAX {
+                continue;
+            }
+            let id = components.len();
+            component_of[seed] = id;
+            let mut faces = vec![seed];
+            let mut next = 0;
+            while next < faces.len() {
+                let face = faces[next];
+                next += 1;
+                for twin in &twins[3 * face..3 * face + 3] {
+                    let Some(twin) = *twin else { continue };
+                    let neighbor = self.half_edges[twin].face;
+                    if component_of[neighbor] == usize::MAX {
+                        component_of[neighbor] = id;
+                        faces.push(neighbor);
+                    }
+                }
+            }
+            components.push(faces);
+        }
+
+        components
+    }
+
+    /// Connected components (see [`face_components`](Self::face_components)) in which every
+    /// edge is shared by exactly two faces
+    pub fn closed_components(&self) -> Vec<Vec<usize>> {
+        let twins = self.manifold_twins();
+        self.face_components()
+            .into_iter()
+            .filter(|component| {
+                component
+                    .iter()
+                    .all(|&face| twins[3 * face..3 * face + 3].iter().all(Option::is_some))
+            })
+            .collect()
+    }
+
+    /// Make face winding consistent within each closed connected component
+    ///
+    /// Orientation is flood-filled from a seed face: a neighbor that runs the shared edge in
+    /// the same direction as its already visited face gets flipped. Each component then keeps
+    /// whichever orientation covers more of its area as it arrived, so a few faces wound the
+    /// wrong way are corrected rather than spreading. Components with boundary or
+    /// non-manifold edges are left as they are, since faces around a hole can be reached
+    /// along paths that disagree. Returns the number of faces flipped.
+    pub fn orient_faces(&mut self) -> usize {
+        let twins = self.manifold_twins();
+        let mut flip = vec![false; self.faces.len()];
+        let mut visited = vec![false; self.faces.len()];
+
+        for component in self.closed_components() {
+            let seed = component[0];
+            visited[seed] = true;
+            let mut stack = vec![seed];
+            while let Some(face) = stack.pop() {
+                for (he_idx, twin) in twins.iter().enumerate().skip(3 * face).take(3) {
+                    let Some(twin) = *twin else { continue };
+                    let neighbor = self.half_edges[twin].face;
+                    if visited[neighbor] {
+                        continue;
+                    }
+                    flip[neighbor] = flip[face] ^ self.same_direction(he_idx, twin);
+                    visited[neighbor] = true;
+                    stack.push(neighbor);
+                }
+            }
+
+            let (mut kept_area, mut flipped_area) = (0.0, 0.0);
+            for &face in &component {
+                let area = self.face_area(face);
+                if flip[face] {
+                    flipped_area += area;
+                } else {
+                    kept_area += area;
+                }
+            }
+            if flipped_area > kept_area {
+                for &face in &component {
+                    flip[face] = !flip[face];
+                }
+            }
+        }
+
+        let mut flipped = 0;
+        for (face, _) in flip.iter().enumerate().filter(|(_, &f)| f) {
+            self.faces[face].swap(1, 2);
+            flipped += 1;
+        }
+        if flipped > 0 {
+            self.build_topology();
+        }
+        flipped
+    }
+
+    /// Whether two twin half-edges run their shared edge the same way (pointing at the same
+    /// vertex), meaning their faces are wound against each other
+    fn same_direction(&self, he_idx: usize, twin: usize) -> bool {
+        self.half_edges[he_idx].vertex == self.half_edges[twin].vertex
+    }
+
+    /// Area of a face
+    fn face_area(&self, face: usize) -> f64 {
+        let [a, b, c] = self.faces[face].map(|v| self.vertices[v as usize]);
+        (b - a).cross(&(c - a)).norm() / 2.0
+    }
+
     /// Get vertex count
     pub fn vertex_count(&self) -> usize {
         self.vertices.len()
@@ -283,5 +422,18 @@ mod tests {
         assert_eq!(converted_mesh.vertex_count(), mesh.vertex_count());
         assert_eq!(converted_mesh.triangle_count(), mesh.triangle_count());
     }
-}
 
+    #[test]
+    fn test_orient_faces_fixes_flipped_face() {
+        let mut mesh =
+            Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.weld_vertices(1e-6);
+        mesh.triangles[3].indices.swap(1, 2);
+
+        let mut he_mesh = HalfEdgeMesh::from_mesh(&mesh);
+        assert_eq!(he_mesh.face_components().len(), 1);
+        assert_eq!(he_mesh.orient_faces(), 1);
+        assert_eq!(he_mesh.orient_faces(), 0);
+        assert!(crate::geometry::validate_winding_order(&he_mesh.to_mesh()));
+    }
+}
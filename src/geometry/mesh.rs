@@ -4,20 +4,32 @@
 //! Mesh representation and utilities
 
 use super::{BooleanOp, BoundingBox};
+use crate::utils::PointHasher;
 use anyhow::Result;
-use nalgebra::{Matrix4, Point3, Vector3};
+use nalgebra::{Matrix3, Matrix4, Point3, Vector3};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Vertex with position and normal
+/// Triangle count above which normals are recomputed in parallel
+const PARALLEL_NORMALS_THRESHOLD: usize = 10_000;
+
+/// Vertex with position, normal, and optional color
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Vertex {
     pub position: Point3<f64>,
     pub normal: Vector3<f64>,
+    /// sRGB-encoded RGBA in `0.0..=1.0` as written in `color()`; alpha is linear
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[f32; 4]>,
 }
 
 impl Vertex {
     pub fn new(position: Point3<f64>, normal: Vector3<f64>) -> Self {
-        Self { position, normal }
+        Self {
+            position,
+            normal,
+            color: None,
+        }
     }
 
     pub fn transform(&mut self, matrix: &Matrix4<f64>) {
@@ -29,6 +41,17 @@ impl Vertex {
             .unwrap_or(*matrix);
         self.normal = normal_matrix.transform_vector(&self.normal).normalize();
     }
+
+    /// Color a fraction `t` of the way from this vertex to `other`, blended per channel
+    /// If only one of them has a color it is kept as is, so splitting an edge never drops it
+    pub(crate) fn color_towards(&self, other: &Vertex, t: f64) -> Option<[f32; 4]> {
+        match (self.color, other.color) {
+            (Some(a), Some(b)) => {
+                Some(std::array::from_fn(|k| crate::utils::math::lerp(a[k], b[k], t as f32)))
+            }
+            (a, b) => a.or(b),
+        }
+    }
 }
 
 /// Triangle defined by three vertex indices
@@ -88,6 +111,39 @@ impl Mesh {
         }
     }
 
+    /// Scale about the origin that gives this mesh the extents `newsize`, as OpenSCAD's `resize()`
+    ///
+    /// Zero components keep their current extent, unless the matching `auto` flag is set, in
+    /// which case they take the largest scale factor of the specified axes.
+    pub fn resize_matrix(&self, newsize: &Vector3<f64>, auto: [bool; 3]) -> Matrix4<f64> {
+        if self.triangles.is_empty() {
+            return Matrix4::identity();
+        }
+
+        let size = self.bounding_box().size();
+        let mut scale = Vector3::repeat(1.0);
+        let mut autoscale: f64 = 0.0;
+        for axis in 0..3 {
+            if newsize[axis] == 0.0 {
+                continue;
+            }
+            if size[axis] == 0.0 {
+                eprintln!("Warning: resize() along an axis where the object is flat is ignored");
+                continue;
+            }
+            scale[axis] = newsize[axis] / size[axis];
+            autoscale = autoscale.max(scale[axis]);
+        }
+
+        for axis in 0..3 {
+            if auto[axis] && newsize[axis] == 0.0 && autoscale > 0.0 {
+                scale[axis] = autoscale;
+            }
+        }
+
+        Matrix4::new_nonuniform_scaling(&scale)
+    }
+
     /// Compute bounding box
     pub fn bounding_box(&self) -> BoundingBox {
         BoundingBox::from_vertices(&self.vertices)
@@ -103,6 +159,117 @@ impl Mesh {
         self.triangles.len()
     }
 
+    /// Whether both meshes have the same topology and colors, with positions and normals
+    /// within `epsilon` per component (inclusive, so `0.0` means bit-for-bit equal values)
+    pub fn approx_eq(&self, other: &Mesh, epsilon: f64) -> bool {
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() <= epsilon);
+
+        self.vertices.len() == other.vertices.len()
+            && self.triangles.len() == other.triangles.len()
+            && self.triangles.iter().zip(&other.triangles).all(|(a, b)| a.indices == b.indices)
+            && self.vertices.iter().zip(&other.vertices).all(|(a, b)| {
+                close(a.position.coords.as_slice(), b.position.coords.as_slice())
+                    && close(a.normal.as_slice(), b.normal.as_slice())
+                    && a.color == b.color
+            })
+    }
+
+    /// Center of mass of the enclosed solid, assuming uniform density
+    ///
+    /// Fails for open or non-manifold meshes, which don't enclose a volume.
+    pub fn centroid(&self) -> Result<Point3<f64>> {
+        let integrals = super::analytics::volume_integrals(self)?;
+        Ok(Point3::from(integrals.first_moment / integrals.volume))
+    }
+
+    /// Inertia tensor of the enclosed solid about its centroid, for a uniform `density`
+    ///
+    /// Fails for open or non-manifold meshes, which don't enclose a volume.
+    pub fn inertia_tensor(&self, density: f64) -> Result<Matrix3<f64>> {
+        let integrals = super::analytics::volume_integrals(self)?;
+        let center = integrals.first_moment / integrals.volume;
+
+        // Second moments about the centroid; scaling by the sign of the volume keeps the
+        // result positive even if the mesh is wound inward
+        let sign = integrals.volume.signum();
+        let covariance = (integrals.second_moment
+            - integrals.volume * center * center.transpose())
+            * (density * sign);
+
+        Ok(Matrix3::identity() * covariance.trace() - covariance)
+    }
+
+    /// Check edge connectivity and triangle validity
+    /// Topology is index-based, so weld coincident vertices first when triangles don't share them
+    pub fn validate(&self) -> super::MeshValidation {
+        super::validate_mesh(self)
+    }
+
+    /// Simplify to about `target_triangles` triangles by quadric error edge collapse
+    /// Collapses that would flip a face or create non-manifold edges are rejected,
+    /// so the result may keep more triangles than requested
+    pub fn decimate(&self, target_triangles: usize) -> Mesh {
+        super::decimate::decimate(self, target_triangles)
+    }
+
+    /// Smooth the mesh with `iterations` passes of Loop subdivision
+    /// Each pass splits every triangle into four; boundary edges follow the boundary rule
+    pub fn subdivide_loop(&self, iterations: usize) -> Mesh {
+        super::subdivision::subdivide_loop(self, iterations)
+    }
+
+    /// Find pairs of non-adjacent triangles that intersect, as `(i, j)` with `i < j`
+    /// Triangles sharing a vertex position are adjacent and skipped
+    pub fn self_intersections(&self) -> Vec<(usize, usize)> {
+        super::find_self_intersections(self)
+    }
+
+    /// Nearest triangle hit by the ray from `origin` along `direction`, or `None` if it misses
+    /// A BVH is built for each call; rays through a shared edge report the lowest triangle index
+    pub fn raycast(
+        &self,
+        origin: &Point3<f64>,
+        direction: &Vector3<f64>,
+    ) -> Option<super::RayHit> {
+        super::raycast::raycast(self, origin, direction)
+    }
+
+    /// Nearest point on the surface to `p`, with the triangle it lies on and its distance
+    /// Points inside the mesh get the nearest point on its surface, at a positive distance
+    /// A mesh without triangles gives `(p, usize::MAX, f64::INFINITY)`
+    pub fn closest_point(&self, p: Point3<f64>) -> (Point3<f64>, usize, f64) {
+        super::clearance::closest_point_on_mesh(self, &p)
+    }
+
+    /// Whether the surface of this mesh crosses or touches the surface of `other`
+    /// Only surfaces are tested, so a mesh entirely inside the other doesn't count; see
+    /// [`min_clearance`](super::clearance::min_clearance) for that. A BVH is built for each call
+    pub fn intersects(&self, other: &Mesh) -> bool {
+        super::clearance::surfaces_intersect(self, other)
+    }
+
+    /// Segments along which the surface of this mesh crosses the surface of `other`
+    /// Each comes from one intersecting pair of triangles; coplanar overlaps and single touching
+    /// points are left out
+    pub fn intersection_segments(&self, other: &Mesh) -> Vec<(Point3<f64>, Point3<f64>)> {
+        super::clearance::intersection_segments(self, other)
+    }
+
+    /// Triangulate boundary loops of at most `max_boundary_len` edges, leaving larger holes open
+    /// Holes are found by index, so weld coincident vertices first when triangles don't share them
+    /// Vertex normals are recomputed after any hole is filled
+    /// Returns the number of holes filled
+    pub fn fill_holes(&mut self, max_boundary_len: usize) -> usize {
+        super::fill_boundary_holes(self, max_boundary_len)
+    }
+
+    /// Flip back-facing triangles so every connected component winds outward
+    /// Adjacency is by index, so weld coincident vertices first when triangles don't share them
+    /// Returns the number of triangles flipped; vertex normals are left for the caller to recompute
+    pub fn fix_winding_order(&mut self) -> usize {
+        super::fix_winding_order(self)
+    }
+
     /// Perform boolean operation with another mesh
     /// Defaults to Robust quality for better results
     pub fn boolean_operation(&self, other: &Mesh, op: BooleanOp) -> Result<Mesh> {
@@ -119,6 +286,25 @@ impl Mesh {
         super::boolean::perform_boolean_operation_with_quality(self, other, op, quality)
     }
 
+    /// Start a fluent chain of boolean operations with this mesh as the first operand
+    pub fn boolean_chain<'a>(self) -> super::MeshBuilder<'a> {
+        super::MeshBuilder::new(self)
+    }
+
+    /// Color every vertex that doesn't have a color yet
+    ///
+    /// Existing colors are kept, so the innermost `color()` wins as in OpenSCAD.
+    pub fn apply_color(&mut self, rgba: [f32; 4]) {
+        for vertex in &mut self.vertices {
+            vertex.color.get_or_insert(rgba);
+        }
+    }
+
+    /// Whether any vertex carries a color
+    pub fn has_colors(&self) -> bool {
+        self.vertices.iter().any(|v| v.color.is_some())
+    }
+
     /// Merge with another mesh (simple union without CSG)
     pub fn merge(&mut self, other: &Mesh) {
         let offset = self.vertices.len();
@@ -137,36 +323,12 @@ impl Mesh {
     /// This removes duplicate vertices and updates triangle indices
     /// Returns the number of vertices removed
     pub fn weld_vertices(&mut self, epsilon: f64) -> usize {
-        if self.vertices.is_empty() {
+        if self.vertices.is_empty() || epsilon <= 0.0 {
             return 0;
         }
 
         let original_count = self.vertices.len();
-        let mut new_vertices: Vec<Vertex> = Vec::new();
-        let mut new_indices: Vec<usize> = vec![0; original_count];
-
-        // Build vertex map: for each vertex, find the index of the first vertex within epsilon
-        for i in 0..original_count {
-            let pos_i = self.vertices[i].position;
-            let mut found_match = false;
-
-            // Check against already processed vertices
-            for j in 0..new_vertices.len() {
-                let pos_j = new_vertices[j].position;
-                if (pos_i - pos_j).norm() < epsilon {
-                    // Found a match - map this vertex to the existing one
-                    new_indices[i] = j;
-                    found_match = true;
-                    break;
-                }
-            }
-
-            if !found_match {
-                // New unique vertex
-                new_indices[i] = new_vertices.len();
-                new_vertices.push(self.vertices[i]);
-            }
-        }
+        let (new_indices, kept) = self.weld_map(epsilon);
 
         // Update triangle indices
         for triangle in &mut self.triangles {
@@ -176,11 +338,55 @@ impl Mesh {
         }
 
         // Replace vertices
-        self.vertices = new_vertices;
+        self.vertices = kept.iter().map(|&i| self.vertices[i]).collect();
 
         original_count - self.vertices.len()
     }
 
+    /// Convert a triangle soup into an indexed mesh
+    ///
+    /// Welds vertices within `epsilon` and drops vertices no triangle refers to. Returns the
+    /// total number of vertices removed; running it again on the result removes nothing.
+    pub fn deduplicate_vertices(&mut self, epsilon: f64) -> usize {
+        self.weld_vertices(epsilon) + self.remove_orphaned_vertices()
+    }
+
+    /// Indexed copy of the mesh, see [`deduplicate_vertices`](Self::deduplicate_vertices)
+    pub fn as_indexed(&self, epsilon: f64) -> Mesh {
+        let mut indexed = self.clone();
+        indexed.deduplicate_vertices(epsilon);
+        indexed
+    }
+
+    /// Compute which vertices would be merged by [`weld_vertices`](Self::weld_vertices)
+    ///
+    /// Returns the welded index of every vertex, and the original index of each kept vertex.
+    /// Each vertex maps to the earliest kept vertex within epsilon, as found by [`PointHasher`].
+    pub(crate) fn weld_map(&self, epsilon: f64) -> (Vec<usize>, Vec<usize>) {
+        if epsilon <= 0.0 {
+            let identity: Vec<usize> = (0..self.vertices.len()).collect();
+            return (identity.clone(), identity);
+        }
+
+        let mut hasher = PointHasher::new(epsilon);
+        let mut kept: Vec<usize> = Vec::new();
+        let new_indices = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| {
+                let j = hasher.insert(&vertex.position);
+                if j == kept.len() {
+                    // New unique vertex
+                    kept.push(i);
+                }
+                j
+            })
+            .collect();
+
+        (new_indices, kept)
+    }
+
     /// Remove coplanar duplicate triangles with z-fighting prevention
     /// When two triangles are coplanar and overlapping, keeps only one based on consistent rules:
     /// 1. If from different meshes: keep triangle from mesh A (first mesh)
@@ -353,7 +559,40 @@ impl Mesh {
         removed
     }
 
-    /// Remove duplicate triangles (triangles with identical vertex indices in same order)
+    /// Run the cleanup passes selected in `options`
+    ///
+    /// Every triangle is treated as coming from the same operand, so the coplanar pass only
+    /// drops triangles whose corners coincide with an earlier one; partial overlaps are kept.
+    /// Use [`cleanup_with_sources`](Self::cleanup_with_sources) to resolve overlaps between
+    /// operands. Returns the number of triangles removed.
+    pub fn cleanup(&mut self, options: &CleanupOptions) -> usize {
+        let sources = vec![0; self.triangles.len()];
+        self.cleanup_with_sources(options, &sources)
+    }
+
+    /// Run the cleanup passes, with `mesh_source` giving the operand each triangle came from
+    ///
+    /// When coplanar triangles from both operands overlap, the one from mesh A (source 0) is kept.
+    pub fn cleanup_with_sources(&mut self, options: &CleanupOptions, mesh_source: &[usize]) -> usize {
+        let before = self.triangles.len();
+
+        if options.weld_epsilon > 0.0 {
+            self.weld_vertices(options.weld_epsilon);
+        }
+        if options.remove_coplanar_duplicates {
+            self.remove_coplanar_duplicates(mesh_source, None);
+        }
+        if options.remove_duplicate_triangles {
+            self.remove_duplicate_triangles();
+        }
+        if options.remove_orphaned_vertices {
+            self.remove_orphaned_vertices();
+        }
+
+        before - self.triangles.len()
+    }
+
+    /// Remove duplicate triangles (triangles with identical vertex indices in the same cyclic order)
     /// Also removes degenerate triangles (where two or more vertices are the same)
     /// Also removes coplanar overlapping triangles (triangles that are essentially the same)
     /// Returns the number of triangles removed
@@ -380,10 +619,15 @@ impl Mesh {
                 continue; // Skip invalid triangles
             }
 
-            // Use exact indices (preserve winding order)
-            // For union operations, we want to keep triangles even if they share edges
-            // Only remove exact duplicates (same indices in same order)
-            let key = (i0, i1, i2);
+            // Rotate the smallest index first so the key ignores the starting corner but keeps
+            // winding order; opposite-facing copies are not duplicates
+            let key = if i0 < i1 && i0 < i2 {
+                (i0, i1, i2)
+            } else if i1 < i2 {
+                (i1, i2, i0)
+            } else {
+                (i2, i0, i1)
+            };
 
             if !seen.contains(&key) {
                 // Don't check for coplanar overlaps here - that's done separately
@@ -508,193 +752,112 @@ impl Mesh {
             return true;
         }
         
-        // Second check: Are centroids very close? (catches z-fighting cases)
-        let centroid_vx = (v0x + v1x + v2x) / 3.0;
-        let centroid_vy = (v0y + v1y + v2y) / 3.0;
-        let centroid_ux = (u0x + u1x + u2x) / 3.0;
-        let centroid_uy = (u0y + u1y + u2y) / 3.0;
-        
-        let dx = centroid_vx - centroid_ux;
-        let dy = centroid_vy - centroid_uy;
-        let centroid_dist = (dx * dx + dy * dy).sqrt();
-        
-        // Calculate triangle sizes
-        let avg_edge_length = ((v1x - v0x).powi(2) + (v1y - v0y).powi(2)).sqrt() +
-                              ((v2x - v1x).powi(2) + (v2y - v1y).powi(2)).sqrt() +
-                              ((v0x - v2x).powi(2) + (v0y - v2y).powi(2)).sqrt();
-        let avg_edge_length_u = ((u1x - u0x).powi(2) + (u1y - u0y).powi(2)).sqrt() +
-                                ((u2x - u1x).powi(2) + (u2y - u1y).powi(2)).sqrt() +
-                                ((u0x - u2x).powi(2) + (u0y - u2y).powi(2)).sqrt();
-        let min_edge_length = avg_edge_length.min(avg_edge_length_u) / 3.0;
-        
-        // If centroids are extremely close (within 1% of average edge length), likely duplicates
-        // This catches z-fighting where triangles are nearly identical but slightly offset
-        // Very conservative - only extremely close centroids indicate duplicates
-        if centroid_dist < min_edge_length * 0.01 && min_edge_length > epsilon {
-            return true;
-        }
-        
-        // Count how many vertices of triangle 1 are inside triangle 2
-        let mut v_inside_count = 0;
-        if Self::point_in_triangle_2d(v0x, v0y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) {
-            v_inside_count += 1;
-        }
-        if Self::point_in_triangle_2d(v1x, v1y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) {
-            v_inside_count += 1;
-        }
-        if Self::point_in_triangle_2d(v2x, v2y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) {
-            v_inside_count += 1;
-        }
-        
-        // Count how many vertices of triangle 2 are inside triangle 1
-        let mut u_inside_count = 0;
-        if Self::point_in_triangle_2d(u0x, u0y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) {
-            u_inside_count += 1;
-        }
-        if Self::point_in_triangle_2d(u1x, u1y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) {
-            u_inside_count += 1;
-        }
-        if Self::point_in_triangle_2d(u2x, u2y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) {
-            u_inside_count += 1;
-        }
-        
-        // Only if ALL vertices of one triangle are inside the other, that's significant overlap
-        // This catches cases where one triangle completely contains the other
-        if v_inside_count == 3 || u_inside_count == 3 {
-            return true;
+        // Otherwise they only count as duplicates if they overlap by most of the smaller one,
+        // so neighbors that just share an edge or a corner are kept. The projection scales
+        // areas by the normal's component along the dropped axis.
+        let overlap = Self::overlap_area_2d(
+            [(v0x, v0y), (v1x, v1y), (v2x, v2y)],
+            [(u0x, u0y), (u1x, u1y), (u2x, u2y)],
+        );
+        overlap / abs_normal[max_axis] >= overlap_threshold
+    }
+
+    /// Area shared by two triangles in 2D, by clipping the first against each edge of the second
+    fn overlap_area_2d(subject: [(f64, f64); 3], clip: [(f64, f64); 3]) -> f64 {
+        let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| {
+            (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+        };
+        let area = |polygon: &[(f64, f64)]| {
+            (1..polygon.len().saturating_sub(1))
+                .map(|k| cross(polygon[0], polygon[k], polygon[k + 1]))
+                .sum::<f64>()
+                / 2.0
+        };
+
+        // Walk the clip triangle counter-clockwise so its inside is to the left of each edge
+        let mut clip = clip;
+        if area(&clip) < 0.0 {
+            clip.swap(1, 2);
         }
-        
-        // If 2+ vertices are inside AND edges intersect, that's also significant overlap
-        // Require both conditions to be more conservative
-        if (v_inside_count >= 2 || u_inside_count >= 2) {
-            // Check if edges also intersect to confirm significant overlap
-            if Self::edge_intersects_triangle_2d(v0x, v0y, v1x, v1y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-               Self::edge_intersects_triangle_2d(v1x, v1y, v2x, v2y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-               Self::edge_intersects_triangle_2d(v2x, v2y, v0x, v0y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-               Self::edge_intersects_triangle_2d(u0x, u0y, u1x, u1y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) ||
-               Self::edge_intersects_triangle_2d(u1x, u1y, u2x, u2y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) ||
-               Self::edge_intersects_triangle_2d(u2x, u2y, u0x, u0y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) {
-                return true;
+
+        let mut polygon = subject.to_vec();
+        for k in 0..3 {
+            let (a, b) = (clip[k], clip[(k + 1) % 3]);
+            let input = std::mem::take(&mut polygon);
+            for i in 0..input.len() {
+                let (p, q) = (input[i], input[(i + 1) % input.len()]);
+                let (dp, dq) = (cross(a, b, p), cross(a, b, q));
+                if dp >= 0.0 {
+                    polygon.push(p);
+                }
+                if (dp >= 0.0) != (dq >= 0.0) {
+                    let t = dp / (dp - dq);
+                    polygon.push((p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t));
+                }
             }
-        }
-        
-        // Check if edges intersect (indicates overlap)
-        let mut has_edge_intersection = false;
-        if Self::edge_intersects_triangle_2d(v0x, v0y, v1x, v1y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-           Self::edge_intersects_triangle_2d(v1x, v1y, v2x, v2y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-           Self::edge_intersects_triangle_2d(v2x, v2y, v0x, v0y, u0x, u0y, u1x, u1y, u2x, u2y, epsilon) ||
-           Self::edge_intersects_triangle_2d(u0x, u0y, u1x, u1y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) ||
-           Self::edge_intersects_triangle_2d(u1x, u1y, u2x, u2y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) ||
-           Self::edge_intersects_triangle_2d(u2x, u2y, u0x, u0y, v0x, v0y, v1x, v1y, v2x, v2y, epsilon) {
-            has_edge_intersection = true;
-        }
-        
-        // For edge-only intersections, if centroids are extremely close, consider it overlap
-        // (centroid distance already calculated above)
-        if has_edge_intersection {
-            // If centroids are closer than 1% of average edge length, consider it significant overlap
-            // Very conservative - only extremely close centroids with edge intersections
-            if centroid_dist < min_edge_length * 0.01 && min_edge_length > epsilon {
-                return true;
+            if polygon.len() < 3 {
+                return 0.0;
             }
         }
-        
-        false
+        area(&polygon).abs()
     }
-    
-    /// Robust point-in-triangle test in 2D
-    fn point_in_triangle_2d(
-        px: f64, py: f64,
-        v0x: f64, v0y: f64,
-        v1x: f64, v1y: f64,
-        v2x: f64, v2y: f64,
-        epsilon: f64,
-    ) -> bool {
-        // Use barycentric coordinates
-        let denom = (v1y - v2y) * (v0x - v2x) + (v2x - v1x) * (v0y - v2y);
-        if denom.abs() < epsilon {
-            return false; // Degenerate triangle
+
+    /// Check that every triangle index refers to an existing vertex
+    pub fn check_triangle_indices(&self) -> Result<()> {
+        let vertex_count = self.vertices.len();
+        let mut invalid = self
+            .triangles
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.indices.iter().any(|&idx| idx >= vertex_count));
+
+        if let Some((first, triangle)) = invalid.next() {
+            anyhow::bail!(
+                "{} triangle(s) reference missing vertices (first: triangle {} with indices {:?}, mesh has {} vertices)",
+                1 + invalid.count(),
+                first,
+                triangle.indices,
+                vertex_count
+            );
         }
-        
-        let a = ((v1y - v2y) * (px - v2x) + (v2x - v1x) * (py - v2y)) / denom;
-        let b = ((v2y - v0y) * (px - v2x) + (v0x - v2x) * (py - v2y)) / denom;
-        let c = 1.0 - a - b;
-        
-        // Point is inside if all barycentric coordinates are non-negative
-        a >= -epsilon && b >= -epsilon && c >= -epsilon
+
+        Ok(())
     }
-    
-    /// Check if an edge intersects a triangle in 2D
-    fn edge_intersects_triangle_2d(
-        e0x: f64, e0y: f64,
-        e1x: f64, e1y: f64,
-        v0x: f64, v0y: f64,
-        v1x: f64, v1y: f64,
-        v2x: f64, v2y: f64,
-        epsilon: f64,
-    ) -> bool {
-        // Check if edge intersects any of the triangle's edges
-        // Edge 0-1 of triangle
-        if Self::segments_intersect_2d(e0x, e0y, e1x, e1y, v0x, v0y, v1x, v1y, epsilon) {
-            return true;
-        }
-        // Edge 1-2 of triangle
-        if Self::segments_intersect_2d(e0x, e0y, e1x, e1y, v1x, v1y, v2x, v2y, epsilon) {
-            return true;
-        }
-        // Edge 2-0 of triangle
-        if Self::segments_intersect_2d(e0x, e0y, e1x, e1y, v2x, v2y, v0x, v0y, epsilon) {
-            return true;
-        }
-        false
+
+    /// Remove triangles whose indices refer to missing vertices
+    /// Returns the number of triangles removed
+    pub fn remove_invalid_triangles(&mut self) -> usize {
+        let vertex_count = self.vertices.len();
+        let before = self.triangles.len();
+        self.triangles
+            .retain(|t| t.indices.iter().all(|&idx| idx < vertex_count));
+        before - self.triangles.len()
     }
-    
-    /// Check if two 2D line segments intersect
-    fn segments_intersect_2d(
-        p0x: f64, p0y: f64,
-        p1x: f64, p1y: f64,
-        q0x: f64, q0y: f64,
-        q1x: f64, q1y: f64,
-        epsilon: f64,
-    ) -> bool {
-        // Use cross product to check if segments intersect
-        let d1 = (q1x - q0x) * (p0y - q0y) - (q1y - q0y) * (p0x - q0x);
-        let d2 = (q1x - q0x) * (p1y - q0y) - (q1y - q0y) * (p1x - q0x);
-        let d3 = (p1x - p0x) * (q0y - p0y) - (p1y - p0y) * (q0x - p0x);
-        let d4 = (p1x - p0x) * (q1y - p0y) - (p1y - p0y) * (q1x - p0x);
-        
-        // Check if segments are on opposite sides of each other
-        if (d1 * d2 < -epsilon) && (d3 * d4 < -epsilon) {
-            return true;
-        }
-        
-        // Check for collinear cases (segments overlap)
-        if d1.abs() < epsilon && d2.abs() < epsilon {
-            // Segments are collinear, check if they overlap
-            let t0 = if (p1x - p0x).abs() > epsilon {
-                (q0x - p0x) / (p1x - p0x)
-            } else if (p1y - p0y).abs() > epsilon {
-                (q0y - p0y) / (p1y - p0y)
-            } else {
-                return false; // Degenerate segment
-            };
-            
-            let t1 = if (p1x - p0x).abs() > epsilon {
-                (q1x - p0x) / (p1x - p0x)
-            } else if (p1y - p0y).abs() > epsilon {
-                (q1y - p0y) / (p1y - p0y)
-            } else {
-                return false;
-            };
-            
-            let t_min = t0.min(t1);
-            let t_max = t0.max(t1);
-            
-            // Check if intervals [0,1] and [t_min, t_max] overlap
-            return t_max >= -epsilon && t_min <= 1.0 + epsilon;
+
+    /// Remove triangles with less than `min_area` area or with near-collinear corners,
+    /// then drop the vertices no longer referenced
+    /// Unlike `remove_duplicate_triangles`, this looks at positions rather than indices,
+    /// so it also catches the needle-thin slivers left by triangle splitting
+    /// Returns the number of triangles removed
+    pub fn remove_degenerate_triangles(&mut self, min_area: f64) -> usize {
+        // Height over the longest edge below which the corners count as collinear
+        const COLLINEAR_RATIO: f64 = 1e-6;
+
+        let before = self.triangles.len();
+        let vertices = &self.vertices;
+        self.triangles.retain(|t| {
+            let [a, b, c] = t.indices.map(|i| vertices[i].position);
+            let doubled_area = (b - a).cross(&(c - a)).norm();
+            let longest = (b - a).norm().max((c - b).norm()).max((a - c).norm());
+            doubled_area >= 2.0 * min_area
+                && longest > 0.0
+                && doubled_area / longest >= COLLINEAR_RATIO * longest
+        });
+        let removed = before - self.triangles.len();
+        if removed > 0 {
+            self.remove_orphaned_vertices();
         }
-        
-        false
+        removed
     }
 
     /// Remove orphaned vertices (vertices not referenced by any triangle)
@@ -741,35 +904,89 @@ impl Mesh {
     }
 
     /// Recompute vertex normals from triangle geometry
-    /// This calculates face normals and averages them at shared vertices
+    /// This calculates face normals and averages them at shared vertices,
+    /// weighting each face by its area
     pub fn recompute_normals(&mut self) {
+        self.recompute_normals_weighted(NormalWeighting::Area);
+    }
+
+    /// Recompute vertex normals, weighting each face's contribution according to `mode`
+    ///
+    /// Large meshes are processed in parallel: per-corner contributions are computed per triangle,
+    /// then each vertex sums its faces in triangle order, so the result matches the serial pass exactly.
+    pub fn recompute_normals_weighted(&mut self, mode: NormalWeighting) {
         if self.vertices.is_empty() || self.triangles.is_empty() {
             return;
         }
 
+        if self.triangles.len() < PARALLEL_NORMALS_THRESHOLD {
+            self.recompute_normals_serial(mode);
+            return;
+        }
+
+        let vertices = &self.vertices;
+        let contributions: Vec<Option<[Vector3<f64>; 3]>> = self
+            .triangles
+            .par_iter()
+            .map(|triangle| corner_contributions(vertices, triangle, mode))
+            .collect();
+
+        // Vertex -> (triangle, corner) incidences, in triangle order (CSR layout)
+        let mut offsets = vec![0usize; self.vertices.len() + 1];
+        for (triangle, contribution) in self.triangles.iter().zip(&contributions) {
+            if contribution.is_some() {
+                for &idx in &triangle.indices {
+                    offsets[idx + 1] += 1;
+                }
+            }
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+        let mut incident = vec![(0usize, 0usize); offsets[self.vertices.len()]];
+        let mut cursor = offsets.clone();
+        for (t, (triangle, contribution)) in self.triangles.iter().zip(&contributions).enumerate() {
+            if contribution.is_some() {
+                for (corner, &idx) in triangle.indices.iter().enumerate() {
+                    incident[cursor[idx]] = (t, corner);
+                    cursor[idx] += 1;
+                }
+            }
+        }
+
+        // Each vertex owns its accumulator, so no synchronization is needed
+        self.vertices
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(i, vertex)| {
+                let faces = &incident[offsets[i]..offsets[i + 1]];
+                if faces.is_empty() {
+                    vertex.normal = Vector3::new(0.0, 0.0, 1.0);
+                    return;
+                }
+
+                let mut sum = Vector3::zeros();
+                for &(t, corner) in faces {
+                    if let Some(contribution) = &contributions[t] {
+                        sum += contribution[corner];
+                    }
+                }
+                vertex.normal = sum.normalize();
+            });
+    }
+
+    /// Single-threaded normal recomputation, used for small meshes
+    fn recompute_normals_serial(&mut self, mode: NormalWeighting) {
         // Initialize normal accumulators for each vertex
         let mut normal_sums: Vec<Vector3<f64>> = vec![Vector3::zeros(); self.vertices.len()];
         let mut normal_counts: Vec<u32> = vec![0; self.vertices.len()];
 
         // Calculate face normals and accumulate at vertices
         for triangle in &self.triangles {
-            let v0 = &self.vertices[triangle.indices[0]];
-            let v1 = &self.vertices[triangle.indices[1]];
-            let v2 = &self.vertices[triangle.indices[2]];
-
-            // Calculate face normal using cross product
-            let edge1 = v1.position - v0.position;
-            let edge2 = v2.position - v0.position;
-            let face_normal = edge1.cross(&edge2);
-
             // Only add if triangle has non-zero area
-            let area = face_normal.norm();
-            if area > 1e-10 {
-                let normalized_face_normal = face_normal / area;
-
-                // Add to all three vertices (weighted by area for better quality)
-                for &idx in &triangle.indices {
-                    normal_sums[idx] += normalized_face_normal * area;
+            if let Some(contribution) = corner_contributions(&self.vertices, triangle, mode) {
+                for (corner, &idx) in triangle.indices.iter().enumerate() {
+                    normal_sums[idx] += contribution[corner];
                     normal_counts[idx] += 1;
                 }
             }
@@ -786,6 +1003,142 @@ impl Mesh {
             }
         }
     }
+
+    /// Recompute vertex normals, keeping edges sharper than `crease_angle_deg` hard
+    ///
+    /// Each corner averages (area-weighted) only the faces around its vertex whose normal is
+    /// within the crease angle of its own face. Vertices whose corners end up with different
+    /// normals are split, so smooth regions stay shared and creases get per-face normals.
+    /// `None` or an angle of 180° or more averages every incident face, exactly like
+    /// [`Self::recompute_normals`].
+    pub fn recompute_normals_with_crease(&mut self, crease_angle_deg: Option<f64>) {
+        let min_cos = match crease_angle_deg {
+            Some(angle) if angle < 180.0 => angle.to_radians().cos(),
+            _ => return self.recompute_normals(),
+        };
+        if self.vertices.is_empty() || self.triangles.is_empty() {
+            return;
+        }
+
+        let contributions: Vec<Option<[Vector3<f64>; 3]>> = self
+            .triangles
+            .iter()
+            .map(|triangle| corner_contributions(&self.vertices, triangle, NormalWeighting::Area))
+            .collect();
+        let face_normal = |t: usize| contributions[t].map(|c| c[0].normalize());
+
+        let mut incident: Vec<Vec<(usize, usize)>> = vec![Vec::new(); self.vertices.len()];
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            if contributions[t].is_some() {
+                for (corner, &idx) in triangle.indices.iter().enumerate() {
+                    incident[idx].push((t, corner));
+                }
+            }
+        }
+
+        for (v, corners) in incident.iter().enumerate() {
+            // Normals already assigned around this vertex and the vertex index carrying each
+            let mut groups: Vec<(Vector3<f64>, usize)> = Vec::new();
+
+            for &(t, corner) in corners {
+                let own = face_normal(t).unwrap();
+                let sum: Vector3<f64> = corners
+                    .iter()
+                    .filter(|&&(other, _)| face_normal(other).unwrap().dot(&own) >= min_cos)
+                    .map(|&(other, other_corner)| contributions[other].unwrap()[other_corner])
+                    .sum();
+                let normal = sum.normalize();
+
+                let target = match groups.iter().find(|(n, _)| n.dot(&normal) > 1.0 - 1e-9) {
+                    Some(&(_, target)) => target,
+                    None if groups.is_empty() => {
+                        self.vertices[v].normal = normal;
+                        groups.push((normal, v));
+                        v
+                    }
+                    None => {
+                        let mut split = self.vertices[v];
+                        split.normal = normal;
+                        self.vertices.push(split);
+                        groups.push((normal, self.vertices.len() - 1));
+                        self.vertices.len() - 1
+                    }
+                };
+                self.triangles[t].indices[corner] = target;
+            }
+
+            if corners.is_empty() {
+                self.vertices[v].normal = Vector3::new(0.0, 0.0, 1.0);
+            }
+        }
+    }
+}
+
+/// Passes run by [`Mesh::cleanup`], in the order listed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CleanupOptions {
+    /// Merge vertices closer than this; zero or negative disables welding
+    pub weld_epsilon: f64,
+    /// Drop coplanar triangles that overlap a triangle from the other operand, or that repeat
+    /// an earlier triangle's corners when both come from the same operand
+    pub remove_coplanar_duplicates: bool,
+    /// Drop degenerate triangles and repeats of the same index triple
+    pub remove_duplicate_triangles: bool,
+    /// Drop vertices no triangle refers to
+    pub remove_orphaned_vertices: bool,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        Self {
+            weld_epsilon: 1e-6,
+            remove_coplanar_duplicates: true,
+            remove_duplicate_triangles: true,
+            remove_orphaned_vertices: true,
+        }
+    }
+}
+
+/// How face normals are weighted when averaged into vertex normals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalWeighting {
+    /// Weight by triangle area (default)
+    #[default]
+    Area,
+    /// Weight by the interior angle of the triangle at the vertex; keeps sharp corners
+    /// from being pulled toward large neighboring faces
+    Angle,
+    /// Every incident face counts equally
+    Uniform,
+}
+
+/// Weighted face normal contributed to each corner of a triangle, or `None` if it is degenerate
+fn corner_contributions(
+    vertices: &[Vertex],
+    triangle: &Triangle,
+    mode: NormalWeighting,
+) -> Option<[Vector3<f64>; 3]> {
+    let p0 = vertices[triangle.indices[0]].position;
+    let p1 = vertices[triangle.indices[1]].position;
+    let p2 = vertices[triangle.indices[2]].position;
+
+    // Calculate face normal using cross product
+    let face_normal = (p1 - p0).cross(&(p2 - p0));
+    let area = face_normal.norm();
+    if area <= 1e-10 {
+        return None;
+    }
+    let unit_normal = face_normal / area;
+
+    Some(match mode {
+        NormalWeighting::Area => [unit_normal * area; 3],
+        NormalWeighting::Uniform => [unit_normal; 3],
+        NormalWeighting::Angle => [
+            unit_normal * (p1 - p0).angle(&(p2 - p0)),
+            unit_normal * (p2 - p1).angle(&(p0 - p1)),
+            unit_normal * (p0 - p2).angle(&(p1 - p2)),
+        ],
+    })
 }
 
 impl Default for Mesh {
@@ -831,4 +1184,360 @@ mod tests {
             norm > 0.9 && norm < 1.1 // Should be approximately unit length
         }));
     }
+
+    /// Reference first-match scan used to check the spatial hash welding
+    fn brute_force_weld_count(vertices: &[Vertex], epsilon: f64) -> usize {
+        let mut kept: Vec<Point3<f64>> = Vec::new();
+        for vertex in vertices {
+            if !kept.iter().any(|p| (vertex.position - p).norm() < epsilon) {
+                kept.push(vertex.position);
+            }
+        }
+        vertices.len() - kept.len()
+    }
+
+    #[test]
+    fn test_weld_vertices_epsilon_scale() {
+        let mut mesh = Mesh::new();
+        let normal = Vector3::z();
+        // Two vertices within epsilon across a cell boundary, one just outside
+        mesh.add_vertex(Vertex::new(Point3::new(0.0, 0.0, 0.0), normal));
+        mesh.add_vertex(Vertex::new(Point3::new(-0.4e-6, 0.0, 0.0), normal));
+        mesh.add_vertex(Vertex::new(Point3::new(1.5e-6, 0.0, 0.0), normal));
+        mesh.add_triangle(Triangle::new([0, 1, 2]));
+
+        let expected = brute_force_weld_count(&mesh.vertices, 1e-6);
+        let removed = mesh.weld_vertices(1e-6);
+
+        assert_eq!(removed, expected);
+        assert_eq!(removed, 1);
+        assert_eq!(mesh.triangles[0].indices, [0, 0, 1]);
+    }
+
+    #[test]
+    fn test_weld_vertices_large_mesh() {
+        let epsilon = 1e-3;
+        let normal = Vector3::z();
+        let mut mesh = Mesh::new();
+
+        // 500 distinct grid points, each repeated 100 times with sub-epsilon jitter
+        for copy in 0..100 {
+            let jitter = (copy as f64 - 50.0) * 2e-6;
+            for i in 0..20 {
+                for j in 0..25 {
+                    let position = Point3::new(i as f64 + jitter, j as f64 - jitter, jitter);
+                    mesh.add_vertex(Vertex::new(position, normal));
+                }
+            }
+        }
+        for i in (0..mesh.vertices.len() - 2).step_by(3) {
+            mesh.add_triangle(Triangle::new([i, i + 1, i + 2]));
+        }
+        assert_eq!(mesh.vertices.len(), 50_000);
+
+        let expected = brute_force_weld_count(&mesh.vertices, epsilon);
+
+        let removed = mesh.weld_vertices(epsilon);
+
+        assert_eq!(removed, expected);
+        assert_eq!(mesh.vertices.len(), 500);
+        assert!(mesh
+            .triangles
+            .iter()
+            .all(|t| t.indices.iter().all(|&idx| idx < mesh.vertices.len())));
+    }
+
+    #[test]
+    fn test_parallel_normals_match_serial() {
+        let mesh = Primitive::sphere(10.0, 256).to_mesh();
+        assert!(mesh.triangle_count() >= PARALLEL_NORMALS_THRESHOLD);
+
+        let mut parallel = mesh.clone();
+        parallel.recompute_normals();
+
+        let mut serial = mesh;
+        serial.recompute_normals_serial(NormalWeighting::Area);
+
+        for (p, s) in parallel.vertices.iter().zip(&serial.vertices) {
+            assert!((p.normal - s.normal).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_check_triangle_indices() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        assert!(mesh.check_triangle_indices().is_ok());
+
+        mesh.add_triangle(Triangle::new([0, 1, mesh.vertices.len() + 5]));
+        let err = mesh.check_triangle_indices().unwrap_err();
+        assert!(err.to_string().contains("1 triangle(s)"), "{}", err);
+
+        assert_eq!(mesh.remove_invalid_triangles(), 1);
+        assert!(mesh.check_triangle_indices().is_ok());
+    }
+
+    #[test]
+    fn test_remove_degenerate_triangles() {
+        let mut mesh = Mesh::new();
+        let normal = Vector3::z();
+        for p in [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(5.0, 0.0, 0.0),
+            Point3::new(15.0, 0.0, 0.0),
+            Point3::new(10.0, 1e-8, 0.0),
+        ] {
+            mesh.add_vertex(Vertex::new(p, normal));
+        }
+        mesh.add_triangle(Triangle::new([0, 1, 2]));
+        // Needle: ten units long, a hundred-millionth tall
+        mesh.add_triangle(Triangle::new([3, 4, 5]));
+
+        assert_eq!(mesh.remove_degenerate_triangles(1e-12), 1);
+        assert_eq!(mesh.triangle_count(), 1);
+        assert_eq!(mesh.vertex_count(), 3);
+        assert_eq!(mesh.triangles[0].indices, [0, 1, 2]);
+
+        // The area threshold alone also removes small but well-shaped triangles
+        assert_eq!(mesh.remove_degenerate_triangles(1.0), 1);
+        assert_eq!(mesh.vertex_count(), 0);
+    }
+
+    #[test]
+    fn test_remove_coplanar_duplicates_keeps_edge_neighbors() {
+        let mut mesh = Mesh::new();
+        let normal = Vector3::z();
+        for p in [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(0.05, 0.0, 0.0),
+            Point3::new(0.95, 0.0, 0.0),
+            Point3::new(0.95, 0.9, 0.0),
+        ] {
+            mesh.add_vertex(Vertex::new(p, normal));
+        }
+        // From A, then from B: a neighbor across the edge 1-2, and a copy that almost covers
+        // the first triangle
+        mesh.add_triangle(Triangle::new([0, 1, 2]));
+        mesh.add_triangle(Triangle::new([1, 3, 2]));
+        mesh.add_triangle(Triangle::new([4, 5, 6]));
+
+        assert_eq!(mesh.remove_coplanar_duplicates(&[0, 1, 1], None), 1);
+        let kept: Vec<_> = mesh.triangles.iter().map(|t| t.indices).collect();
+        assert_eq!(kept, vec![[0, 1, 2], [1, 3, 2]]);
+    }
+
+    #[test]
+    fn test_normal_weighting_modes() {
+        // Cube faces are flat, so every mode recovers the axis-aligned face normals
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let mut area = cube.clone();
+        area.recompute_normals_weighted(NormalWeighting::Area);
+        let mut angle = cube;
+        angle.recompute_normals_weighted(NormalWeighting::Angle);
+        for (a, b) in area.vertices.iter().zip(&angle.vertices) {
+            assert!((a.normal - b.normal).norm() < 1e-12);
+            assert!((b.normal.abs().max() - 1.0).abs() < 1e-12);
+        }
+
+        // A fan around a shared corner: a small face on +z and a large face on +y,
+        // both with a right angle at the corner
+        let mut fan = Mesh::new();
+        let normal = Vector3::zeros();
+        let corner = fan.add_vertex(Vertex::new(Point3::origin(), normal));
+        let a = fan.add_vertex(Vertex::new(Point3::new(1.0, 0.0, 0.0), normal));
+        let b = fan.add_vertex(Vertex::new(Point3::new(0.0, 1.0, 0.0), normal));
+        let c = fan.add_vertex(Vertex::new(Point3::new(0.0, 0.0, 10.0), normal));
+        let d = fan.add_vertex(Vertex::new(Point3::new(10.0, 0.0, 0.0), normal));
+        fan.add_triangle(Triangle::new([corner, a, b]));
+        fan.add_triangle(Triangle::new([corner, c, d]));
+
+        let mut area = fan.clone();
+        area.recompute_normals_weighted(NormalWeighting::Area);
+        let mut angle = fan.clone();
+        angle.recompute_normals_weighted(NormalWeighting::Angle);
+        let mut uniform = fan;
+        uniform.recompute_normals_weighted(NormalWeighting::Uniform);
+
+        let expected = Vector3::new(0.0, 1.0, 1.0).normalize();
+        assert!((angle.vertices[corner].normal - expected).norm() < 1e-12);
+        assert!((uniform.vertices[corner].normal - expected).norm() < 1e-12);
+        assert!((area.vertices[corner].normal - expected).norm() > 0.5);
+    }
+
+    #[test]
+    fn test_centroid_and_inertia_of_cube() {
+        let side = 10.0;
+        let density = 2.0;
+        let mesh = Primitive::cube(Vector3::new(side, side, side), true).to_mesh();
+
+        let centroid = mesh.centroid().unwrap();
+        assert!(centroid.coords.norm() < 1e-9);
+
+        let inertia = mesh.inertia_tensor(density).unwrap();
+        let mass = density * side.powi(3);
+        let expected = mass / 6.0 * side * side;
+        for i in 0..3 {
+            for j in 0..3 {
+                let target = if i == j { expected } else { 0.0 };
+                assert!(
+                    (inertia[(i, j)] - target).abs() < 1e-6 * expected,
+                    "I[{}][{}] = {}, expected {}",
+                    i,
+                    j,
+                    inertia[(i, j)],
+                    target
+                );
+            }
+        }
+
+        // Off-center cube keeps the same inertia about its own centroid
+        let mut moved = mesh.clone();
+        moved.transform(&Matrix4::new_translation(&Vector3::new(5.0, -3.0, 7.0)));
+        assert!((moved.centroid().unwrap() - Point3::new(5.0, -3.0, 7.0)).norm() < 1e-9);
+        assert!((moved.inertia_tensor(density).unwrap() - inertia).norm() < 1e-6 * expected);
+    }
+
+    #[test]
+    fn test_mass_properties_reject_open_mesh() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        mesh.triangles.pop();
+
+        assert!(mesh.centroid().is_err());
+        assert!(mesh.inertia_tensor(1.0).is_err());
+    }
+
+    /// Two coincident triangles with their own vertices, then one overlapping them by half
+    fn overlapping_triangles() -> Mesh {
+        let mut mesh = Mesh::new();
+        let corners = [[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]];
+        let half = [[0.0, 0.0], [10.0, 0.0], [0.0, 5.0]];
+        for [x, y] in corners.into_iter().chain(corners).chain(half) {
+            mesh.add_vertex(Vertex::new(Point3::new(x, y, 0.0), Vector3::z()));
+        }
+        mesh.add_triangle(Triangle::new([0, 1, 2]));
+        mesh.add_triangle(Triangle::new([4, 5, 3]));
+        mesh.add_triangle(Triangle::new([6, 7, 8]));
+        mesh
+    }
+
+    const COPLANAR_ONLY: CleanupOptions = CleanupOptions {
+        weld_epsilon: 0.0,
+        remove_coplanar_duplicates: true,
+        remove_duplicate_triangles: false,
+        remove_orphaned_vertices: false,
+    };
+
+    #[test]
+    fn test_cleanup_drops_only_coincident_same_source_triangles() {
+        let mut mesh = overlapping_triangles();
+        assert_eq!(mesh.cleanup(&COPLANAR_ONLY), 1);
+
+        let kept: Vec<[usize; 3]> = mesh.triangles.iter().map(|t| t.indices).collect();
+        assert_eq!(kept, vec![[0, 1, 2], [6, 7, 8]]);
+    }
+
+    #[test]
+    fn test_cleanup_with_sources_prefers_mesh_a() {
+        let mut mesh = overlapping_triangles();
+        mesh.triangles.truncate(2);
+
+        // The later copy comes from mesh A, so it replaces the earlier one from mesh B
+        assert_eq!(mesh.cleanup_with_sources(&COPLANAR_ONLY, &[1, 0]), 1);
+        assert_eq!(mesh.triangles[0].indices, [4, 5, 3]);
+    }
+
+    #[test]
+    fn test_fill_holes_closes_missing_triangle() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.weld_vertices(1e-6);
+        assert!(super::super::is_closed(&mesh));
+
+        let removed = mesh.triangles.remove(0).indices;
+        mesh.recompute_normals();
+        assert!(!super::super::is_closed(&mesh));
+
+        assert_eq!(mesh.fill_holes(8), 1);
+        assert_eq!(mesh.triangle_count(), 12);
+
+        // Normals along the patched rim account for the new triangle
+        let mut expected = mesh.clone();
+        expected.recompute_normals();
+        for (vertex, fresh) in mesh.vertices.iter().zip(&expected.vertices) {
+            assert!((vertex.normal - fresh.normal).norm() < 1e-12);
+        }
+
+        // The patch keeps the original winding (same cyclic order)
+        let patch = mesh.triangles[11].indices;
+        let offset = patch.iter().position(|&i| i == removed[0]).unwrap();
+        assert_eq!(patch[(offset + 1) % 3], removed[1]);
+        assert!(super::super::is_closed(&mesh));
+        assert!(mesh.centroid().is_ok());
+    }
+
+    #[test]
+    fn test_fill_holes_skips_large_holes() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.weld_vertices(1e-6);
+
+        // Removing both triangles of a face leaves a four-edge hole
+        mesh.triangles.drain(0..2);
+        assert_eq!(mesh.fill_holes(3), 0);
+        assert_eq!(mesh.triangle_count(), 10);
+
+        assert_eq!(mesh.fill_holes(4), 1);
+        assert!(super::super::is_closed(&mesh));
+    }
+
+    #[test]
+    fn test_recompute_normals_with_crease() -> Result<()> {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let center = Vector3::new(10.0, 5.0, 5.0);
+        let mut sphere = Primitive::sphere(4.0, 24).to_mesh();
+        sphere.transform(&Matrix4::new_translation(&center));
+        let mut mesh = cube.boolean_operation(&sphere, BooleanOp::Union)?;
+        mesh.weld_vertices(1e-6);
+
+        // No crease angle is plain averaging
+        let mut averaged = mesh.clone();
+        averaged.recompute_normals_with_crease(None);
+        let mut expected = mesh.clone();
+        expected.recompute_normals();
+        assert_eq!(averaged.vertices.len(), expected.vertices.len());
+        assert!(averaged
+            .vertices
+            .iter()
+            .zip(&expected.vertices)
+            .all(|(a, b)| a.normal == b.normal));
+
+        mesh.recompute_normals_with_crease(Some(30.0));
+        assert!(mesh.vertices.len() > averaged.vertices.len());
+
+        // Cube corners split into one vertex per face, each with that face's normal
+        let corner: Vec<_> = mesh
+            .vertices
+            .iter()
+            .filter(|v| v.position == Point3::origin())
+            .map(|v| v.normal)
+            .collect();
+        assert_eq!(corner.len(), 3);
+        assert!(corner.iter().all(|n| n.amax() == 1.0 && n.sum() == -1.0));
+
+        // The sphere cap stays smooth: normals point away from its center
+        let on_cap = |p: &Point3<f64>| p.x > 10.0 + 1e-6;
+        for triangle in &mesh.triangles {
+            for &idx in &triangle.indices {
+                let v = &mesh.vertices[idx];
+                if on_cap(&v.position) {
+                    let radial = (v.position.coords - center).normalize();
+                    assert!(v.normal.dot(&radial) > 0.999, "{:?}", v);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
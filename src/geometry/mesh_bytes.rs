@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Compact binary mesh encoding for caches and FFI
+//!
+//! The layout is [`MESH_BYTES_MAGIC`], a little-endian `u32` [`MESH_BYTES_VERSION`], then the
+//! bincode-encoded mesh. Indices are stored as `u64` so 32- and 64-bit hosts (e.g. wasm and
+//! native) read each other's bytes.
+
+use super::{Mesh, Triangle, Vertex};
+use anyhow::{bail, Context, Result};
+use nalgebra::{Point3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Leading bytes of every encoded mesh
+pub const MESH_BYTES_MAGIC: [u8; 4] = *b"PFMB";
+
+/// Encoding version, bumped whenever the layout changes
+pub const MESH_BYTES_VERSION: u32 = 1;
+
+const HEADER_LEN: usize = MESH_BYTES_MAGIC.len() + 4;
+
+/// Wire form of [`Vertex`]; the serde attributes on `Vertex` suit self-describing formats
+/// like JSON but not bincode, which needs every field present
+#[derive(Serialize, Deserialize)]
+struct VertexRecord {
+    position: [f64; 3],
+    normal: [f64; 3],
+    color: Option<[f32; 4]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MeshRecord {
+    vertices: Vec<VertexRecord>,
+    triangles: Vec<[u64; 3]>,
+}
+
+impl Mesh {
+    /// Encode as magic, version, and bincode payload; positions round-trip exactly
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let record = MeshRecord {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|v| VertexRecord {
+                    position: v.position.coords.into(),
+                    normal: v.normal.into(),
+                    color: v.color,
+                })
+                .collect(),
+            triangles: self
+                .triangles
+                .iter()
+                .map(|t| t.indices.map(|i| i as u64))
+                .collect(),
+        };
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(&MESH_BYTES_MAGIC);
+        bytes.extend_from_slice(&MESH_BYTES_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, &record).context("Failed to encode mesh")?;
+        Ok(bytes)
+    }
+
+    /// Decode bytes written by [`Mesh::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Mesh> {
+        if bytes.len() < HEADER_LEN || bytes[..4] != MESH_BYTES_MAGIC {
+            bail!("Not an encoded mesh: missing magic header");
+        }
+        let version = u32::from_le_bytes(bytes[4..HEADER_LEN].try_into().unwrap());
+        if version != MESH_BYTES_VERSION {
+            bail!(
+                "Unsupported mesh encoding version {} (expected {})",
+                version,
+                MESH_BYTES_VERSION
+            );
+        }
+
+        let record: MeshRecord =
+            bincode::deserialize(&bytes[HEADER_LEN..]).context("Failed to decode mesh")?;
+
+        let mut mesh = Mesh::with_capacity(record.vertices.len(), record.triangles.len());
+        for v in record.vertices {
+            let mut vertex = Vertex::new(Point3::from(v.position), Vector3::from(v.normal));
+            vertex.color = v.color;
+            mesh.add_vertex(vertex);
+        }
+        for indices in record.triangles {
+            mesh.add_triangle(Triangle::new(indices.map(|i| i as usize)));
+        }
+        mesh.check_triangle_indices()?;
+
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+
+    #[test]
+    fn test_mesh_bytes_round_trip() -> Result<()> {
+        let mut sphere = Primitive::sphere(7.3, 24).to_mesh();
+        sphere.vertices[0].color = Some([0.25, 0.5, 1.0, 1.0]);
+
+        let bytes = sphere.to_bytes()?;
+        assert_eq!(bytes[..4], MESH_BYTES_MAGIC);
+        assert!(Mesh::from_bytes(&bytes)?.approx_eq(&sphere, 0.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mesh_bytes_rejects_bad_headers() {
+        let bytes = Primitive::cube(Vector3::new(1.0, 1.0, 1.0), false)
+            .to_mesh()
+            .to_bytes()
+            .unwrap();
+
+        assert!(Mesh::from_bytes(b"PF").is_err());
+        assert!(Mesh::from_bytes(&bytes[..HEADER_LEN + 3]).is_err());
+
+        let mut future = bytes.clone();
+        future[4] = 2;
+        let error = Mesh::from_bytes(&future).unwrap_err();
+        assert!(error.to_string().contains("version 2"), "{}", error);
+    }
+}
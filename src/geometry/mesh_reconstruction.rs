@@ -4,7 +4,7 @@
 //! Mesh reconstruction and validation
 //! Handles vertex welding, topology validation, and Euler characteristic checks
 
-use super::{halfedge::HalfEdgeMesh, Mesh, Vertex};
+use super::{halfedge::HalfEdgeMesh, mesh_utils::split_t_junctions, Mesh, Triangle, Vertex};
 use nalgebra::{Point3, Vector3};
 use std::collections::HashMap;
 
@@ -34,7 +34,6 @@ pub fn reconstruct_manifold_mesh(he_mesh: &HalfEdgeMesh) -> Mesh {
             continue;
         }
         
-        use super::Triangle;
         mesh.add_triangle(Triangle::new([v0, v1, v2]));
     }
     
@@ -47,6 +46,70 @@ pub fn reconstruct_manifold_mesh(he_mesh: &HalfEdgeMesh) -> Mesh {
     mesh
 }
 
+/// Distance within which a vertex counts as lying on another triangle's edge
+const T_JUNCTION_EPSILON: f64 = 1e-6;
+
+/// Rebuild an already welded triangle soup with consistent winding
+///
+/// Collapsed triangles and pairs of coincident triangles wound against each other (a
+/// zero-thickness sheet) are dropped, and triangles are split at T-junctions so edges cut on
+/// one side only meet their neighbors again. The rest go through a half-edge structure so each
+/// closed patch can be flood-fill oriented (see [`HalfEdgeMesh::orient_faces`]), and vertices
+/// no longer referenced by any triangle are removed.
+pub fn reconstruct_oriented_mesh(mesh: &Mesh) -> Mesh {
+    let mut soup = mesh.clone();
+    soup.triangles.retain(|t| {
+        t.indices[0] != t.indices[1] && t.indices[1] != t.indices[2] && t.indices[0] != t.indices[2]
+    });
+    cancel_opposed_triangles(&mut soup);
+    split_t_junctions(&mut soup, T_JUNCTION_EPSILON);
+
+    let mut he_mesh = HalfEdgeMesh::from_mesh(&soup);
+    he_mesh.orient_faces();
+
+    // The half-edge mesh keeps the vertex order but only positions, so copy the colors back
+    let mut result = he_mesh.to_mesh();
+    for (vertex, source) in result.vertices.iter_mut().zip(&soup.vertices) {
+        vertex.color = source.color;
+    }
+    result.remove_orphaned_vertices();
+    result
+}
+
+/// Remove coincident triangles in pairs of opposite winding
+fn cancel_opposed_triangles(mesh: &mut Mesh) {
+    // Rotate each triangle to start at its smallest index, so both windings of the same
+    // vertices share the first index and differ only in the order of the other two
+    let canonical = |indices: [usize; 3]| {
+        let start = (0..3).min_by_key(|&k| indices[k]).unwrap();
+        [indices[start], indices[(start + 1) % 3], indices[(start + 2) % 3]]
+    };
+
+    let mut by_vertices: HashMap<[usize; 3], Vec<usize>> = HashMap::new();
+    for (idx, triangle) in mesh.triangles.iter().enumerate() {
+        let [a, b, c] = canonical(triangle.indices);
+        by_vertices.entry([a, b.min(c), b.max(c)]).or_default().push(idx);
+    }
+
+    let mut remove = vec![false; mesh.triangles.len()];
+    for group in by_vertices.values().filter(|g| g.len() > 1) {
+        let (forward, backward): (Vec<usize>, Vec<usize>) = group.iter().partition(|&&idx| {
+            let [_, b, c] = canonical(mesh.triangles[idx].indices);
+            b < c
+        });
+        for (&f, &b) in forward.iter().zip(&backward) {
+            remove[f] = true;
+            remove[b] = true;
+        }
+    }
+
+    let mut idx = 0;
+    mesh.triangles.retain(|_| {
+        idx += 1;
+        !remove[idx - 1]
+    });
+}
+
 /// Weld duplicate vertices within epsilon distance
 /// Returns (vertex_map, welded_vertices)
 fn weld_vertices(vertices: &[Point3<f64>]) -> (Vec<usize>, Vec<Point3<f64>>) {
@@ -172,5 +235,18 @@ mod tests {
         assert_eq!(vertex_map[0], vertex_map[1]);
         assert!(welded.len() <= 2); // Should have at most 2 unique vertices
     }
-}
 
+    #[test]
+    fn test_reconstruct_oriented_mesh_drops_collapsed_fragments() {
+        let mut mesh = Primitive::cube(nalgebra::Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.weld_vertices(1e-6);
+        mesh.triangles[0].indices.swap(0, 2);
+        let stray = mesh.add_vertex(Vertex::new(Point3::new(50.0, 0.0, 0.0), Vector3::z()));
+        mesh.add_triangle(crate::geometry::Triangle::new([stray, stray, 0]));
+
+        let reconstructed = reconstruct_oriented_mesh(&mesh);
+        assert_eq!(reconstructed.vertex_count(), 8);
+        assert_eq!(reconstructed.triangle_count(), 12);
+        assert!(crate::geometry::validate_winding_order(&reconstructed));
+    }
+}
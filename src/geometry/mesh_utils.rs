@@ -4,6 +4,10 @@
 //! Mesh validation and repair utilities
 
 use super::Mesh;
+use crate::utils::PointHasher;
+use anyhow::{bail, Result};
+use nalgebra::{Point3, Vector3};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Edge representation for connectivity checking
@@ -107,6 +111,95 @@ pub struct MeshValidation {
     pub boundary_edge_count: usize,
 }
 
+impl MeshValidation {
+    /// Locate topology defects, reporting each offending edge by its vertex indices
+    ///
+    /// Coincident vertices are treated as one (primitives emit separate vertices per face),
+    /// and edges are reported using the first vertex at each position.
+    pub fn diagnose(mesh: &Mesh) -> ManifoldReport {
+        const DIAGNOSE_WELD_EPSILON: f64 = 1e-6;
+
+        let (welded, representatives) = mesh.weld_map(DIAGNOSE_WELD_EPSILON);
+        let vertex_count = mesh.vertices.len();
+
+        let mut report = ManifoldReport::default();
+        let mut edge_counts: HashMap<Edge, u32> = HashMap::new();
+        let mut seen_triangles: HashMap<[usize; 3], usize> = HashMap::new();
+
+        for triangle in &mesh.triangles {
+            if triangle.indices.iter().any(|&idx| idx >= vertex_count) {
+                report.invalid_triangle_count += 1;
+                continue;
+            }
+
+            let [a, b, c] = triangle.indices.map(|idx| representatives[welded[idx]]);
+            if a == b || b == c || a == c {
+                report.degenerate_triangle_count += 1;
+                continue;
+            }
+
+            let mut key = [a, b, c];
+            key.sort_unstable();
+            let seen = seen_triangles.entry(key).or_insert(0);
+            *seen += 1;
+            if *seen > 1 {
+                report.duplicate_triangle_count += 1;
+            }
+
+            for edge in [Edge::new(a, b), Edge::new(b, c), Edge::new(c, a)] {
+                *edge_counts.entry(edge).or_insert(0) += 1;
+            }
+        }
+
+        for (edge, &count) in &edge_counts {
+            if count == 1 {
+                report.boundary_edges.push([edge.v0, edge.v1]);
+            } else if count > 2 {
+                report.non_manifold_edges.push([edge.v0, edge.v1]);
+            }
+        }
+        report.boundary_edges.sort_unstable();
+        report.non_manifold_edges.sort_unstable();
+        report.edge_count = edge_counts.len();
+        report.boundary_edge_count = report.boundary_edges.len();
+        report.non_manifold_edge_count = report.non_manifold_edges.len();
+
+        report
+    }
+}
+
+/// Where a mesh fails to be a closed 2-manifold
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifoldReport {
+    /// Number of distinct edges after merging coincident vertices
+    pub edge_count: usize,
+    /// Edges used by exactly one triangle (holes)
+    pub boundary_edge_count: usize,
+    /// Edges shared by more than two triangles
+    pub non_manifold_edge_count: usize,
+    /// Triangles covering the same three vertices as an earlier one
+    pub duplicate_triangle_count: usize,
+    /// Triangles with two corners at the same position
+    pub degenerate_triangle_count: usize,
+    /// Triangles referencing missing vertices
+    pub invalid_triangle_count: usize,
+    /// Vertex index pairs of the boundary edges
+    pub boundary_edges: Vec<[usize; 2]>,
+    /// Vertex index pairs of the non-manifold edges
+    pub non_manifold_edges: Vec<[usize; 2]>,
+}
+
+impl ManifoldReport {
+    /// True if every edge is shared by exactly two triangles and nothing is duplicated or broken
+    pub fn is_watertight(&self) -> bool {
+        self.edge_count > 0
+            && self.boundary_edge_count == 0
+            && self.non_manifold_edge_count == 0
+            && self.duplicate_triangle_count == 0
+            && self.invalid_triangle_count == 0
+    }
+}
+
 /// Find all boundary edges (edges shared by exactly 1 triangle)
 pub fn find_boundary_edges(mesh: &Mesh) -> std::collections::HashSet<Edge> {
     let mut edge_counts: HashMap<Edge, u32> = HashMap::new();
@@ -231,11 +324,407 @@ pub fn validate_mesh(mesh: &Mesh) -> MeshValidation {
     }
 }
 
+/// Copy of the mesh with coincident vertices welded and collapsed triangles removed
+///
+/// Primitives emit separate vertices per face, so index-based topology checks need this first.
+pub(crate) fn welded_for_topology(mesh: &Mesh) -> Mesh {
+    const TOPOLOGY_WELD_EPSILON: f64 = 1e-6;
+
+    let mut welded = mesh.as_indexed(TOPOLOGY_WELD_EPSILON);
+    welded.triangles.retain(|t| {
+        t.indices[0] != t.indices[1] && t.indices[1] != t.indices[2] && t.indices[0] != t.indices[2]
+    });
+    welded
+}
+
+/// Close boundary loops of at most `max_boundary_len` edges with a triangle fan
+///
+/// Loops are traced against the winding of the triangles around them, so the new triangles
+/// keep the surface orientation. Vertex normals are recomputed when any hole is filled.
+/// Returns the number of holes filled.
+pub(crate) fn fill_boundary_holes(mesh: &mut Mesh, max_boundary_len: usize) -> usize {
+    let boundary = find_boundary_edges(mesh);
+    if boundary.is_empty() {
+        return 0;
+    }
+
+    // Boundary edges run opposite to the triangle that owns them when walking around the hole
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut starts: Vec<(usize, usize)> = Vec::new();
+    for triangle in &mesh.triangles {
+        for k in 0..3 {
+            let (a, b) = (triangle.indices[k], triangle.indices[(k + 1) % 3]);
+            if boundary.contains(&Edge::new(a, b)) {
+                outgoing.entry(b).or_default().push(a);
+                starts.push((b, a));
+            }
+        }
+    }
+
+    let mut used: std::collections::HashSet<(usize, usize)> = std::collections::HashSet::new();
+    let mut filled = 0;
+
+    for &(start, second) in &starts {
+        if used.contains(&(start, second)) {
+            continue;
+        }
+
+        // Walk the loop until it returns to the start vertex
+        let mut hole = vec![start];
+        used.insert((start, second));
+        let mut current = second;
+        let mut closed = false;
+        while hole.len() <= max_boundary_len {
+            if current == start {
+                closed = true;
+                break;
+            }
+            hole.push(current);
+            let next = outgoing
+                .get(&current)
+                .and_then(|targets| targets.iter().find(|&&t| !used.contains(&(current, t))));
+            match next {
+                Some(&next) => {
+                    used.insert((current, next));
+                    current = next;
+                }
+                None => break,
+            }
+        }
+
+        if !closed || hole.len() < 3 {
+            continue;
+        }
+
+        for i in 1..hole.len() - 1 {
+            mesh.triangles
+                .push(super::Triangle::new([hole[0], hole[i], hole[i + 1]]));
+        }
+        filled += 1;
+    }
+
+    if filled > 0 {
+        mesh.recompute_normals();
+    }
+    filled
+}
+
+/// Flip triangles so each closed edge-connected component winds consistently and faces outward
+///
+/// [`HalfEdgeMesh::orient_faces`](super::halfedge::HalfEdgeMesh::orient_faces) makes each
+/// closed component agree with itself, and components enclosing a negative volume are then
+/// turned inside out. Components with boundary or non-manifold edges are left as they are.
+/// Adjacency is by index, so weld coincident vertices first. Returns the number of triangles
+/// flipped.
+pub(crate) fn fix_winding_order(mesh: &mut Mesh) -> usize {
+    use super::analytics::signed_volume_of;
+    use super::halfedge::HalfEdgeMesh;
+
+    let vertex_count = mesh.vertices.len();
+    if mesh
+        .triangles
+        .iter()
+        .any(|t| t.indices.iter().any(|&i| i >= vertex_count))
+    {
+        return 0;
+    }
+
+    let mut he_mesh = HalfEdgeMesh::from_mesh(mesh);
+    he_mesh.orient_faces();
+
+    let original: Vec<[usize; 3]> = mesh.triangles.iter().map(|t| t.indices).collect();
+    for (triangle, face) in mesh.triangles.iter_mut().zip(&he_mesh.faces) {
+        triangle.indices = face.map(|v| v as usize);
+    }
+    for component in he_mesh.closed_components() {
+        if signed_volume_of(mesh, component.iter().map(|&face| &mesh.triangles[face])) < 0.0 {
+            for face in component {
+                mesh.triangles[face].indices.swap(1, 2);
+            }
+        }
+    }
+
+    mesh.triangles
+        .iter()
+        .zip(&original)
+        .filter(|(triangle, indices)| triangle.indices != **indices)
+        .count()
+}
+
+/// Find pairs of non-adjacent triangles that intersect each other
+///
+/// Triangles sharing a vertex position are adjacent and never reported. Candidate pairs
+/// come from a BVH over triangle bounds and are confirmed with the exact triangle test.
+pub(crate) fn find_self_intersections(mesh: &Mesh) -> Vec<(usize, usize)> {
+    use super::bvh::BVH;
+    use super::triangle_intersection::triangle_triangle_intersection;
+    use super::BoundingBox;
+
+    const ADJACENCY_EPSILON: f64 = 1e-9;
+
+    let vertex_count = mesh.vertices.len();
+    let (welded, _) = mesh.weld_map(ADJACENCY_EPSILON);
+
+    let corners = |t: &super::Triangle| t.indices.map(|i| mesh.vertices[i].position);
+    let valid: Vec<bool> = mesh
+        .triangles
+        .iter()
+        .map(|t| t.indices.iter().all(|&i| i < vertex_count))
+        .collect();
+
+    let bounds: Vec<(usize, BoundingBox)> = mesh
+        .triangles
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| valid[*idx])
+        .map(|(idx, t)| {
+            let mut bbox = BoundingBox::empty();
+            for p in corners(t) {
+                bbox.expand_to_include(&p);
+            }
+            (idx, bbox)
+        })
+        .collect();
+    if bounds.is_empty() {
+        return Vec::new();
+    }
+    let bvh = BVH::build(bounds.clone());
+
+    let mut pairs = Vec::new();
+    for (i, bbox) in &bounds {
+        let tri_i = &mesh.triangles[*i];
+        let shared_i = tri_i.indices.map(|v| welded[v]);
+
+        for j in bvh.query_triangles(bbox) {
+            if j <= *i {
+                continue;
+            }
+            let tri_j = &mesh.triangles[j];
+            if tri_j.indices.iter().any(|&v| shared_i.contains(&welded[v])) {
+                continue;
+            }
+
+            if triangle_triangle_intersection(&corners(tri_i), &corners(tri_j)).intersects {
+                pairs.push((*i, j));
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Settings for [`remove_self_intersections`]
+#[derive(Debug, Clone, Copy)]
+pub struct SelfIntersectionConfig {
+    /// How far to either side of each surface piece the inside test probes, as a fraction of
+    /// the bounding box diagonal; keep it below the thinnest wall
+    pub probe_distance: f64,
+    /// Fail if intersections remain after healing instead of returning the healed mesh
+    pub require_clean: bool,
+}
+
+impl Default for SelfIntersectionConfig {
+    fn default() -> Self {
+        Self {
+            probe_distance: 1e-6,
+            require_clean: true,
+        }
+    }
+}
+
+/// Resolve self-intersections by rebuilding the outer surface of the region the mesh encloses
+///
+/// Intersecting triangles are split where they cross, and only the pieces with the solid on
+/// their inner side and empty space on their outer side are kept, as a union of the mesh with
+/// itself would. The result is welded, with T-junctions split so neighbors share vertices.
+/// A mesh without self-intersections is returned unchanged.
+pub fn remove_self_intersections(mesh: &Mesh, config: &SelfIntersectionConfig) -> Result<Mesh> {
+    /// Distance within which a vertex counts as lying on another triangle's edge
+    const T_JUNCTION_EPSILON: f64 = 1e-7;
+
+    if find_self_intersections(mesh).is_empty() {
+        return Ok(mesh.clone());
+    }
+
+    let diagonal = mesh.bounding_box().size().norm().max(1.0);
+    let mut healed = super::exact_csg::exact_self_union(mesh, config.probe_distance * diagonal);
+
+    // Cut points snapped to neighboring grid points would otherwise leave slivers between them
+    healed.weld_vertices(T_JUNCTION_EPSILON);
+    healed.triangles.retain(|t| {
+        t.indices[0] != t.indices[1] && t.indices[1] != t.indices[2] && t.indices[0] != t.indices[2]
+    });
+    healed.remove_orphaned_vertices();
+    split_t_junctions(&mut healed, T_JUNCTION_EPSILON);
+    healed.recompute_normals();
+
+    let remaining = find_self_intersections(&healed).len();
+    if remaining > 0 && config.require_clean {
+        bail!(
+            "{} pairs of triangles still intersect after removing self-intersections",
+            remaining
+        );
+    }
+    Ok(healed)
+}
+
+/// Split triangles with other vertices on their edges, so that neighbors share those vertices
+///
+/// Each affected triangle becomes a fan around its centroid through its corners and the
+/// vertices found on its edges. Returns the number of triangles split.
+pub(crate) fn split_t_junctions(mesh: &mut Mesh, epsilon: f64) -> usize {
+    use super::bvh::BVH;
+    use super::{BoundingBox, Triangle, Vertex};
+
+    let margin = Vector3::repeat(epsilon);
+    let points = BVH::build(
+        mesh.vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, BoundingBox::new(v.position - margin, v.position + margin)))
+            .collect(),
+    );
+
+    let mut split = 0;
+    let mut triangles = Vec::with_capacity(mesh.triangles.len());
+    for triangle in std::mem::take(&mut mesh.triangles) {
+        let mut boundary = Vec::new();
+        for k in 0..3 {
+            let (a, b) = (triangle.indices[k], triangle.indices[(k + 1) % 3]);
+            let (p, q) = (mesh.vertices[a].position, mesh.vertices[b].position);
+            let edge = q - p;
+            let length_squared = edge.norm_squared();
+
+            let mut bbox = BoundingBox::empty();
+            bbox.expand_to_include(&p);
+            bbox.expand_to_include(&q);
+            let mut on_edge: Vec<(f64, usize)> = points
+                .query_triangles(&BoundingBox::new(bbox.min - margin, bbox.max + margin))
+                .into_iter()
+                .filter(|&v| !triangle.indices.contains(&v))
+                .filter_map(|v| {
+                    let offset = mesh.vertices[v].position - p;
+                    let t = offset.dot(&edge) / length_squared;
+                    let inside = t > 0.0 && t < 1.0 && (offset - edge * t).norm() < epsilon;
+                    inside.then_some((t, v))
+                })
+                .collect();
+            on_edge.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+            boundary.push(a);
+            boundary.extend(on_edge.into_iter().map(|(_, v)| v));
+        }
+
+        if boundary.len() == 3 {
+            triangles.push(triangle);
+            continue;
+        }
+        let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position);
+        let centroid = Point3::from((a.coords + b.coords + c.coords) / 3.0);
+        let normal = (b - a).cross(&(c - a)).normalize();
+        // The center takes the mean color of the corners that have one
+        let colors: Vec<[f32; 4]> =
+            triangle.indices.iter().filter_map(|&i| mesh.vertices[i].color).collect();
+        let color = (!colors.is_empty()).then(|| {
+            std::array::from_fn(|k| colors.iter().map(|c| c[k]).sum::<f32>() / colors.len() as f32)
+        });
+        let center = mesh.add_vertex(Vertex {
+            color,
+            ..Vertex::new(centroid, normal)
+        });
+        for k in 0..boundary.len() {
+            let next = boundary[(k + 1) % boundary.len()];
+            triangles.push(Triangle::new([center, boundary[k], next]));
+        }
+        split += 1;
+    }
+
+    mesh.triangles = triangles;
+    split
+}
+
+/// Offset a mesh along its vertex normals (inflate for positive `distance`, deflate for negative)
+///
+/// Each vertex moves along the area-weighted normal of the faces around it. Vertices that share a
+/// position (e.g. the per-face vertices of a cube) are moved together so the surface stays closed.
+/// Large offsets can fold concave regions over themselves; use [`find_offset_fold_overs`] to flag them.
+pub fn offset_mesh(mesh: &Mesh, distance: f64) -> Mesh {
+    const POSITION_EPSILON: f64 = 1e-9;
+
+    let mut result = mesh.clone();
+    if mesh.vertices.is_empty() || mesh.triangles.is_empty() || distance == 0.0 {
+        return result;
+    }
+
+    // Group coincident vertices so they share a single offset direction
+    let mut groups = PointHasher::new(POSITION_EPSILON);
+    let vertex_group: Vec<usize> = mesh
+        .vertices
+        .iter()
+        .map(|v| groups.insert(&v.position))
+        .collect();
+
+    // Accumulate area-weighted face normals per group (cross product length = 2 * area)
+    let mut group_normals: Vec<Vector3<f64>> = vec![Vector3::zeros(); groups.len()];
+    for triangle in &mesh.triangles {
+        let v0 = &mesh.vertices[triangle.indices[0]].position;
+        let v1 = &mesh.vertices[triangle.indices[1]].position;
+        let v2 = &mesh.vertices[triangle.indices[2]].position;
+        let weighted_normal = (v1 - v0).cross(&(v2 - v0));
+
+        for &idx in &triangle.indices {
+            group_normals[vertex_group[idx]] += weighted_normal;
+        }
+    }
+
+    for (vertex, &group) in result.vertices.iter_mut().zip(&vertex_group) {
+        let normal = group_normals[group];
+        let length = normal.norm();
+        if length > 1e-12 {
+            vertex.position += normal / length * distance;
+        }
+    }
+
+    result.recompute_normals();
+    result
+}
+
+/// Find triangles whose orientation flipped during an offset
+///
+/// A face whose normal points against its original direction has been folded through the
+/// opposite surface, which signals a self-intersection. Returns the indices of those triangles
+/// so callers can reject the offset or clean it up before further CSG.
+pub fn find_offset_fold_overs(original: &Mesh, offset: &Mesh) -> Vec<usize> {
+    original
+        .triangles
+        .iter()
+        .zip(&offset.triangles)
+        .enumerate()
+        .filter_map(|(i, (before, after))| {
+            let n_before = face_normal(original, before.indices);
+            let n_after = face_normal(offset, after.indices);
+            if n_before.dot(&n_after) < 0.0 {
+                Some(i)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn face_normal(mesh: &Mesh, indices: [usize; 3]) -> Vector3<f64> {
+    let v0 = &mesh.vertices[indices[0]].position;
+    let v1 = &mesh.vertices[indices[1]].position;
+    let v2 = &mesh.vertices[indices[2]].position;
+    (v1 - v0).cross(&(v2 - v0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::geometry::Primitive;
-    use nalgebra::Vector3;
+    use nalgebra::{Point3, Vector3};
 
     #[test]
     fn test_cube_is_manifold() {
@@ -297,5 +786,162 @@ mod tests {
         assert!(validation.is_closed, "Cylinder mesh should be closed");
         assert!(validation.has_valid_winding, "Cylinder mesh should have valid winding order");
     }
-}
 
+    #[test]
+    fn test_fix_winding_order_restores_half_flipped_cube() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.weld_vertices(1e-6);
+
+        let half = mesh.triangle_count() / 2;
+        for triangle in &mut mesh.triangles[..half] {
+            triangle.indices.swap(1, 2);
+        }
+        assert_eq!(mesh.fix_winding_order(), half);
+        assert!(validate_winding_order(&mesh));
+
+        // Every directed edge is walked once, and the enclosed volume is positive again
+        let mut directed = std::collections::HashSet::new();
+        for triangle in &mesh.triangles {
+            for k in 0..3 {
+                assert!(directed.insert((triangle.indices[k], triangle.indices[(k + 1) % 3])));
+            }
+        }
+        assert!((crate::geometry::signed_volume(&mesh) - 1000.0).abs() < 1e-9);
+        assert_eq!(mesh.fix_winding_order(), 0);
+    }
+
+    #[test]
+    fn test_offset_sphere_grows_radius() {
+        let radius = 10.0;
+        let distance = 1.5;
+        let sphere = Primitive::sphere(radius, 32).to_mesh();
+        let inflated = offset_mesh(&sphere, distance);
+
+        let mean_radius = inflated
+            .vertices
+            .iter()
+            .map(|v| v.position.coords.norm())
+            .sum::<f64>()
+            / inflated.vertex_count() as f64;
+
+        assert!(
+            (mean_radius - (radius + distance)).abs() < 0.05,
+            "Mean radius {} should be close to {}",
+            mean_radius,
+            radius + distance
+        );
+        assert!(find_offset_fold_overs(&sphere, &inflated).is_empty());
+    }
+
+    #[test]
+    fn test_offset_cube_stays_closed() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let deflated = offset_mesh(&cube, -1.0);
+        let bbox = deflated.bounding_box();
+
+        // Corner vertices move along the averaged corner normal, so every face moves inward
+        assert!(bbox.size().x < 10.0);
+        assert_eq!(deflated.triangle_count(), cube.triangle_count());
+    }
+
+    #[test]
+    fn test_diagnose_cube_with_missing_triangle() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        assert!(MeshValidation::diagnose(&mesh).is_watertight());
+
+        let removed = mesh.triangles.pop().unwrap();
+        let report = MeshValidation::diagnose(&mesh);
+
+        assert!(!report.is_watertight());
+        assert_eq!(report.boundary_edge_count, 3);
+        assert_eq!(report.non_manifold_edge_count, 0);
+        assert_eq!(report.duplicate_triangle_count, 0);
+
+        // Reported edges lie on the removed triangle
+        let corners: Vec<_> = removed
+            .indices
+            .iter()
+            .map(|&i| mesh.vertices[i].position)
+            .collect();
+        for [a, b] in &report.boundary_edges {
+            for idx in [a, b] {
+                let p = mesh.vertices[*idx].position;
+                assert!(corners.iter().any(|c| (c - p).norm() < 1e-9));
+            }
+        }
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: ManifoldReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn test_diagnose_duplicate_and_non_manifold() {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let duplicate = mesh.triangles[0];
+        mesh.triangles.push(duplicate);
+
+        let report = MeshValidation::diagnose(&mesh);
+        assert_eq!(report.duplicate_triangle_count, 1);
+        assert_eq!(report.non_manifold_edge_count, 3);
+        assert_eq!(report.boundary_edge_count, 0);
+    }
+
+    #[test]
+    fn test_self_intersections_of_interpenetrating_tetrahedra() {
+        use crate::geometry::{Triangle, Vertex};
+
+        let tetrahedron = |offset: Vector3<f64>| {
+            let mut mesh = Mesh::new();
+            for p in [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]] {
+                let position = Point3::new(p[0], p[1], p[2]) + offset;
+                mesh.add_vertex(Vertex::new(position, Vector3::z()));
+            }
+            for face in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]] {
+                mesh.add_triangle(Triangle::new(face));
+            }
+            mesh
+        };
+
+        let single = tetrahedron(Vector3::zeros());
+        assert!(find_self_intersections(&single).is_empty());
+
+        let mut merged = single.clone();
+        merged.merge(&tetrahedron(Vector3::new(1.0, 1.0, 1.0)));
+        let pairs = find_self_intersections(&merged);
+        assert!(!pairs.is_empty());
+        // Every pair mixes one triangle from each tetrahedron
+        assert!(pairs.iter().all(|&(a, b)| a < 4 && b >= 4));
+
+        // Per-face vertices of a primitive still count as adjacent
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        assert!(cube.self_intersections().is_empty());
+    }
+
+    #[test]
+    fn test_remove_self_intersections_of_overlapping_cubes() -> Result<()> {
+        use crate::geometry::analyze;
+        use nalgebra::Matrix4;
+
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let config = SelfIntersectionConfig::default();
+
+        // A clean mesh passes through unchanged
+        let unchanged = remove_self_intersections(&cube, &config)?;
+        assert!(unchanged.approx_eq(&cube, 0.0));
+
+        let mut shifted = cube.clone();
+        shifted.transform(&Matrix4::new_translation(&Vector3::new(5.0, 5.0, 5.0)));
+        let mut merged = cube.clone();
+        merged.merge(&shifted);
+        assert!(!find_self_intersections(&merged).is_empty());
+
+        let healed = remove_self_intersections(&merged, &config)?;
+        assert!(find_self_intersections(&healed).is_empty());
+        assert!((analyze(&healed).volume - 1875.0).abs() < 1e-6);
+        let validation = healed.validate();
+        assert!(validation.is_closed && validation.is_manifold);
+
+        Ok(())
+    }
+}
@@ -6,12 +6,22 @@
 pub mod analytics;
 mod bbox;
 mod boolean;
+mod boolean_chain;
+pub mod clearance;
+mod decimate;
+mod exact_csg;
 mod mesh;
+mod mesh_bytes;
 mod mesh_utils;
 mod parallel_boolean;
+pub mod predicates;
 mod primitives;
+mod raycast;
 mod csg;
 mod robust_csg;
+mod shape2d;
+mod slice;
+mod subdivision;
 mod halfedge;
 mod robust_predicates;
 mod bvh;
@@ -20,14 +30,37 @@ mod triangle_splitting;
 mod classification;
 mod mesh_reconstruction;
 
-pub use analytics::{analyze, GeometryStats};
+pub use analytics::{
+    analyze, analyze_printability, analyze_topology, signed_volume, GeometryStats,
+    PrintabilityStats, TopologyStats,
+};
 pub use bbox::BoundingBox;
 pub use boolean::{BooleanOp, BooleanQuality};
-pub use csg::{csg_difference, csg_intersection, csg_union};
-pub use mesh::{Mesh, Triangle, Vertex};
-pub use mesh_utils::{is_closed, is_manifold, validate_mesh, validate_winding_order, MeshValidation};
+pub use boolean_chain::MeshBuilder;
+pub use csg::{csg_difference, csg_intersection, csg_union, difference_with_cut};
+pub use mesh::{CleanupOptions, Mesh, NormalWeighting, Triangle, Vertex};
+pub use mesh_bytes::{MESH_BYTES_MAGIC, MESH_BYTES_VERSION};
+pub use mesh_utils::{
+    find_offset_fold_overs, is_closed, is_manifold, offset_mesh, remove_self_intersections,
+    validate_mesh, validate_winding_order, ManifoldReport, MeshValidation,
+    SelfIntersectionConfig,
+};
+pub(crate) use mesh_utils::{
+    fill_boundary_holes, find_self_intersections, fix_winding_order, welded_for_topology,
+};
 pub use parallel_boolean::{
-    batch_process_meshes, ParallelBooleanExecutor, ThreadSafeMesh, ThreadSafeMeshOps,
+    batch_difference, batch_difference_with_quality, batch_intersection,
+    batch_intersection_with_quality, batch_process_meshes, batch_union, batch_union_with_quality,
+    ParallelBooleanExecutor, ThreadSafeMesh, ThreadSafeMeshOps,
+};
+pub use primitives::{
+    facet_count, Primitive, SphereTessellation, DEFAULT_FA, DEFAULT_FS, MAX_ICOSPHERE_SUBDIVISIONS,
+};
+pub use raycast::RayHit;
+pub use shape2d::Shape2D;
+pub use triangle_intersection::{
+    triangle_triangle_intersection, IntersectionResult, IntersectionType,
+};
+pub use robust_csg::{
+    robust_difference, robust_intersection, robust_union, robust_union_core_with_config, CsgConfig,
 };
-pub use primitives::Primitive;
-pub use robust_csg::{robust_difference, robust_intersection, robust_union};
@@ -3,7 +3,7 @@
 
 //! Parallel boolean operations using rayon
 
-use super::{BooleanOp, Mesh};
+use super::{BooleanOp, BooleanQuality, Mesh};
 use anyhow::Result;
 use rayon::prelude::*;
 use std::sync::{Arc, RwLock};
@@ -64,6 +64,30 @@ impl ParallelBooleanExecutor {
         Self::execute_parallel(meshes, BooleanOp::Intersection)
     }
 
+    /// Combine operands pairwise as a balanced tree, evaluating the halves in parallel
+    ///
+    /// The tree is `log n` deep instead of the `n` of a left fold. Operand order is kept,
+    /// so the result matches the fold for associative operations.
+    pub fn reduce_balanced(
+        operands: &[&Mesh],
+        op: &BooleanOp,
+        quality: BooleanQuality,
+    ) -> Result<Mesh> {
+        match operands {
+            [] => Ok(Mesh::empty()),
+            [single] => Ok((*single).clone()),
+            [a, b] => a.boolean_operation_with_quality(b, op.clone(), quality),
+            _ => {
+                let (left, right) = operands.split_at(operands.len() / 2);
+                let (left, right) = rayon::join(
+                    || Self::reduce_balanced(left, op, quality),
+                    || Self::reduce_balanced(right, op, quality),
+                );
+                left?.boolean_operation_with_quality(&right?, op.clone(), quality)
+            }
+        }
+    }
+
     /// Transform multiple meshes in parallel
     pub fn transform_parallel(meshes: Vec<Mesh>, transform: nalgebra::Matrix4<f64>) -> Vec<Mesh> {
         meshes
@@ -97,6 +121,48 @@ impl ThreadSafeMeshOps for ThreadSafeMesh {
     }
 }
 
+/// Union of all meshes, reduced as a balanced tree
+pub fn batch_union(meshes: &[Mesh]) -> Result<Mesh> {
+    batch_union_with_quality(meshes, BooleanQuality::Robust)
+}
+
+/// Union of all meshes at the given quality, reduced as a balanced tree
+pub fn batch_union_with_quality(meshes: &[Mesh], quality: BooleanQuality) -> Result<Mesh> {
+    let operands: Vec<&Mesh> = meshes.iter().collect();
+    ParallelBooleanExecutor::reduce_balanced(&operands, &BooleanOp::Union, quality)
+}
+
+/// Intersection of all meshes, reduced as a balanced tree
+pub fn batch_intersection(meshes: &[Mesh]) -> Result<Mesh> {
+    batch_intersection_with_quality(meshes, BooleanQuality::Robust)
+}
+
+/// Intersection of all meshes at the given quality, reduced as a balanced tree
+pub fn batch_intersection_with_quality(meshes: &[Mesh], quality: BooleanQuality) -> Result<Mesh> {
+    let operands: Vec<&Mesh> = meshes.iter().collect();
+    ParallelBooleanExecutor::reduce_balanced(&operands, &BooleanOp::Intersection, quality)
+}
+
+/// First mesh minus all the others
+pub fn batch_difference(meshes: &[Mesh]) -> Result<Mesh> {
+    batch_difference_with_quality(meshes, BooleanQuality::Robust)
+}
+
+/// First mesh minus all the others at the given quality
+///
+/// The subtrahends are unioned as a balanced tree and removed in a single difference.
+pub fn batch_difference_with_quality(meshes: &[Mesh], quality: BooleanQuality) -> Result<Mesh> {
+    let Some((first, rest)) = meshes.split_first() else {
+        return Ok(Mesh::empty());
+    };
+    if rest.is_empty() {
+        return Ok(first.clone());
+    }
+
+    let subtrahend = batch_union_with_quality(rest, quality)?;
+    first.boolean_operation_with_quality(&subtrahend, BooleanOp::Difference, quality)
+}
+
 /// Batch process meshes in parallel
 pub fn batch_process_meshes<F>(meshes: Vec<Mesh>, processor: F) -> Vec<Mesh>
 where
@@ -124,6 +190,64 @@ mod tests {
         assert!(result.unwrap().vertex_count() > 0);
     }
 
+    fn cube_grid() -> Vec<Mesh> {
+        // 2x2x2 grid of overlapping cubes covering [0, 16]^3
+        (0..8)
+            .map(|i| {
+                let offset = Vector3::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64);
+                let mut cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+                cube.transform(&nalgebra::Matrix4::new_translation(&(offset * 6.0)));
+                cube
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_union_matches_sequential() -> Result<()> {
+        // Exact quality, so regrouping the operands cannot change the result
+        let quality = BooleanQuality::Exact;
+        let cubes = cube_grid();
+
+        let mut sequential = cubes[0].clone();
+        for cube in &cubes[1..] {
+            sequential =
+                sequential.boolean_operation_with_quality(cube, BooleanOp::Union, quality)?;
+        }
+        let batched = batch_union_with_quality(&cubes, quality)?;
+
+        let (batched, sequential) = (
+            crate::geometry::analyze(&batched),
+            crate::geometry::analyze(&sequential),
+        );
+        assert!(
+            (batched.volume - 4096.0).abs() < 1e-6,
+            "volume {}",
+            batched.volume
+        );
+        assert!((batched.volume - sequential.volume).abs() < 1e-6);
+        for (b, s) in batched.bbox.iter().zip(&sequential.bbox) {
+            assert!((b - s).abs() < 1e-6);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_difference_and_intersection() -> Result<()> {
+        let quality = BooleanQuality::Exact;
+        let cubes = cube_grid();
+
+        // Only the corner of the first cube that no other cube reaches is left: [0, 6]^3
+        let difference = batch_difference_with_quality(&cubes, quality)?;
+        assert!((crate::geometry::analyze(&difference).volume - 216.0).abs() < 1e-6);
+
+        // All eight cubes share [6, 10]^3
+        let intersection = batch_intersection_with_quality(&cubes, quality)?;
+        assert!((crate::geometry::analyze(&intersection).volume - 64.0).abs() < 1e-6);
+
+        Ok(())
+    }
+
     #[test]
     fn test_parallel_transform() {
         let meshes = vec![
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Exact geometric predicates
+//!
+//! [`orient2d`], [`orient3d`], [`incircle`], and [`insphere`] use Shewchuk's adaptive
+//! arithmetic: a fast `f64` evaluation whose error bound is checked, refined with exact
+//! expansions only when the bound does not settle the sign. The **sign** of each result is
+//! exact for all finite inputs, so collinear, coplanar, cocircular, and cospherical inputs
+//! return exactly zero and nearly-degenerate ones never come out with the wrong sign. The
+//! magnitude is only an approximation of the determinant and should not be used as a
+//! distance.
+//!
+//! The tolerance-based plane classification used by the CSG pipeline is re-exported here
+//! too; it treats points within a fixed epsilon of the plane as on it, and is not exact.
+
+use nalgebra::{Point2, Point3};
+use robust::{Coord, Coord3D};
+
+pub use super::robust_predicates::{classify_point_plane, PlaneClassification};
+
+fn coord(p: &Point2<f64>) -> Coord<f64> {
+    Coord { x: p.x, y: p.y }
+}
+
+fn coord3(p: &Point3<f64>) -> Coord3D<f64> {
+    Coord3D {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
+
+/// Positive when `a`, `b`, `c` turn counterclockwise, negative when clockwise, and zero
+/// when they are collinear
+pub fn orient2d(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>) -> f64 {
+    robust::orient2d(coord(a), coord(b), coord(c))
+}
+
+/// Positive when `d` is on the side of plane `a`, `b`, `c` that its normal
+/// `(b - a) × (c - a)` points to, negative on the other side, and zero when coplanar
+///
+/// This matches the sign of `(b - a) · ((c - a) × (d - a))`, i.e. six times the signed
+/// volume of the tetrahedron. Note that it is the opposite of Shewchuk's convention.
+pub fn orient3d(a: &Point3<f64>, b: &Point3<f64>, c: &Point3<f64>, d: &Point3<f64>) -> f64 {
+    -robust::orient3d(coord3(a), coord3(b), coord3(c), coord3(d))
+}
+
+/// Positive when `d` is strictly inside the circle through `a`, `b`, `c`, negative when
+/// outside, and zero when the four points are cocircular or `a`, `b`, `c` are collinear
+///
+/// Unlike the raw determinant, the sign does not depend on the winding of `a`, `b`, `c`.
+pub fn incircle(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>, d: &Point2<f64>) -> f64 {
+    let det = robust::incircle(coord(a), coord(b), coord(c), coord(d));
+    let orientation = orient2d(a, b, c);
+    if orientation == 0.0 {
+        0.0
+    } else {
+        det * orientation.signum()
+    }
+}
+
+/// Positive when `e` is strictly inside the sphere through `a`, `b`, `c`, `d`, negative when
+/// outside, and zero when the five points are cospherical or `a`, `b`, `c`, `d` coplanar
+///
+/// As with [`incircle`], the sign does not depend on the order of `a`, `b`, `c`, `d`.
+pub fn insphere(
+    a: &Point3<f64>,
+    b: &Point3<f64>,
+    c: &Point3<f64>,
+    d: &Point3<f64>,
+    e: &Point3<f64>,
+) -> f64 {
+    let det = robust::insphere(coord3(a), coord3(b), coord3(c), coord3(d), coord3(e));
+    let orientation = robust::orient3d(coord3(a), coord3(b), coord3(c), coord3(d));
+    if orientation == 0.0 {
+        0.0
+    } else {
+        det * orientation.signum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d_near_collinear() {
+        // `c` is a few ulps off the line through `a` and `b`, to its left
+        let a = Point2::new(12.0, 12.0);
+        let b = Point2::new(24.0, 24.0);
+        let c = Point2::new(0.5000000000000046, 0.5000000000000053);
+
+        let naive = (a.x - c.x) * (b.y - c.y) - (a.y - c.y) * (b.x - c.x);
+        assert!(naive < 0.0, "the f64 determinant rounds to the wrong side");
+        assert!(orient2d(&a, &b, &c) > 0.0);
+        assert!(orient2d(&b, &a, &c) < 0.0);
+
+        let on_line = Point2::new(0.5, 0.5);
+        assert_eq!(orient2d(&a, &b, &on_line), 0.0);
+    }
+
+    #[test]
+    fn test_orient3d_matches_signed_volume() {
+        let a = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+
+        assert!(orient3d(&a, &b, &c, &Point3::new(0.3, 0.3, 1.0)) > 0.0);
+        assert!(orient3d(&a, &b, &c, &Point3::new(0.3, 0.3, -1e-300)) < 0.0);
+        assert_eq!(orient3d(&a, &b, &c, &Point3::new(5.0, -7.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_incircle_and_insphere_ignore_winding() {
+        let a = Point2::new(1.0, 0.0);
+        let b = Point2::new(0.0, 1.0);
+        let c = Point2::new(-1.0, 0.0);
+        let inside = Point2::new(0.0, 0.5);
+        let on = Point2::new(0.0, -1.0);
+
+        assert!(incircle(&a, &b, &c, &inside) > 0.0);
+        assert!(incircle(&c, &b, &a, &inside) > 0.0);
+        assert!(incircle(&a, &b, &c, &Point2::new(0.0, 2.0)) < 0.0);
+        assert_eq!(incircle(&a, &b, &c, &on), 0.0);
+
+        let a = Point3::new(1.0, 0.0, 0.0);
+        let b = Point3::new(0.0, 1.0, 0.0);
+        let c = Point3::new(-1.0, 0.0, 0.0);
+        let d = Point3::new(0.0, 0.0, 1.0);
+        let center = Point3::origin();
+
+        assert!(insphere(&a, &b, &c, &d, &center) > 0.0);
+        assert!(insphere(&b, &a, &c, &d, &center) > 0.0);
+        assert!(insphere(&a, &b, &c, &d, &Point3::new(0.0, 0.0, 2.0)) < 0.0);
+        assert_eq!(insphere(&a, &b, &c, &d, &Point3::new(0.0, 0.0, -1.0)), 0.0);
+    }
+}
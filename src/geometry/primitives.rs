@@ -4,15 +4,66 @@
 //! Geometric primitives generator
 
 use super::{Mesh, Triangle, Vertex};
-use nalgebra::{Point3, Vector3};
+use anyhow::{bail, Result};
+use nalgebra::{Point2, Point3, Vector2, Vector3};
+use std::collections::HashMap;
 use std::f64::consts::PI;
 
+/// Minimum number of fragments for curved primitives, matching OpenSCAD's `$fn` floor
+pub const MIN_FRAGMENTS: u32 = 3;
+
+/// OpenSCAD's default `$fa`, the minimum angle in degrees per fragment
+pub const DEFAULT_FA: f64 = 12.0;
+
+/// OpenSCAD's default `$fs`, the minimum fragment length
+pub const DEFAULT_FS: f64 = 2.0;
+
+/// Most subdivision passes an icosphere may have; each one quadruples the triangle count
+pub const MAX_ICOSPHERE_SUBDIVISIONS: u32 = 8;
+
+/// Radii below this get the minimum fragment count, as in OpenSCAD's `GRID_FINE`
+const GRID_FINE: f64 = 0.000_000_953_674_316_406_25;
+
+/// Smallest `$fa`/`$fs` OpenSCAD accepts
+const MIN_FA_FS: f64 = 0.01;
+
 /// Geometric primitives
+///
+/// Parameters follow OpenSCAD's handling:
+/// - `$fn` below 3 is raised to 3; `$fn = 0` derives the count from the default `$fa`/`$fs`
+/// - a cube with a non-positive size component, a sphere with a non-positive radius,
+///   or a cylinder/cone with a non-positive height, a negative radius, or both radii zero
+///   produces a warning and empty geometry instead of a mesh
+/// - a rectangular frustum or pyramid with a non-positive base size or height, or a negative
+///   top size, is treated the same way
+/// - so is a torus whose tube radius is not positive or not smaller than its major radius
+/// - so is a polyhedron with a face of fewer than three points or one referring to a missing point
+/// - and an icosphere with a non-positive radius or more than [`MAX_ICOSPHERE_SUBDIVISIONS`]
 pub enum Primitive {
     Cube { size: Vector3<f64>, center: bool },
     Sphere { r: f64, fn_: u32 },
+    /// Geodesic sphere: an icosahedron with each face split into four `subdivisions` times
+    Icosphere { r: f64, subdivisions: u32 },
     Cylinder { h: f64, r: f64, fn_: u32 },
     Cone { h: f64, r1: f64, r2: f64, fn_: u32 },
+    /// Rectangular base on z=0 tapering to a rectangle centered above it at z=h
+    FrustumRect { base: Vector2<f64>, top: Vector2<f64>, h: f64, center: bool },
+    /// Ring around the Z axis, centered on the origin; `major_r` is the distance from the axis
+    /// to the center of the tube and `minor_r` the tube radius
+    Torus { major_r: f64, minor_r: f64, major_segments: u32, minor_segments: u32 },
+    /// Faces are lists of indices into `points`, ordered clockwise seen from outside as in
+    /// OpenSCAD's `polyhedron()`
+    Polyhedron { points: Vec<Point3<f64>>, faces: Vec<Vec<usize>> },
+}
+
+/// How a sphere is broken into triangles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SphereTessellation {
+    /// Rings of latitude and longitude, with `$fn` segments each way (default)
+    #[default]
+    LatLong,
+    /// Subdivided icosahedron with near-uniform triangles; `$fn` picks the subdivision level
+    Ico,
 }
 
 impl Primitive {
@@ -21,12 +72,35 @@ impl Primitive {
     }
 
     pub fn sphere(r: f64, fn_: u32) -> Self {
-        let segments = if fn_ > 0 { fn_ } else { 32 };
+        let segments = facet_count(r, fn_ as f64, DEFAULT_FA, DEFAULT_FS);
         Self::Sphere { r, fn_: segments }
     }
 
+    /// Geodesic sphere with `20 * 4^subdivisions` triangles of nearly equal size
+    pub fn icosphere(r: f64, subdivisions: u32) -> Self {
+        Self::Icosphere { r, subdivisions }
+    }
+
+    /// Sphere tessellated as chosen, with about `fn_` segments around the equator
+    ///
+    /// For [`SphereTessellation::Ico`] the subdivision level is the smallest one giving at
+    /// least that many equator segments (an icosahedron has five, each level doubles them),
+    /// capped at [`MAX_ICOSPHERE_SUBDIVISIONS`].
+    pub fn sphere_with_tessellation(r: f64, fn_: u32, tessellation: SphereTessellation) -> Self {
+        match tessellation {
+            SphereTessellation::LatLong => Self::sphere(r, fn_),
+            SphereTessellation::Ico => {
+                let segments = facet_count(r, fn_ as f64, DEFAULT_FA, DEFAULT_FS);
+                let subdivisions = (0..MAX_ICOSPHERE_SUBDIVISIONS)
+                    .find(|&level| 5 << level >= segments)
+                    .unwrap_or(MAX_ICOSPHERE_SUBDIVISIONS);
+                Self::icosphere(r, subdivisions)
+            }
+        }
+    }
+
     pub fn cylinder(h: f64, r: f64, fn_: u32) -> Self {
-        let segments = if fn_ > 0 { fn_ } else { 32 };
+        let segments = facet_count(r, fn_ as f64, DEFAULT_FA, DEFAULT_FS);
         Self::Cylinder {
             h,
             r,
@@ -35,7 +109,7 @@ impl Primitive {
     }
 
     pub fn cone(h: f64, r1: f64, r2: f64, fn_: u32) -> Self {
-        let segments = if fn_ > 0 { fn_ } else { 32 };
+        let segments = facet_count(r1.max(r2), fn_ as f64, DEFAULT_FA, DEFAULT_FS);
         Self::Cone {
             h,
             r1,
@@ -44,16 +118,215 @@ impl Primitive {
         }
     }
 
+    /// Torus around the Z axis with `major_segments` steps around the ring and
+    /// `minor_segments` around the tube
+    ///
+    /// Segment counts of zero are derived from the default `$fa`/`$fs`, each for its own circle.
+    pub fn torus(major_r: f64, minor_r: f64, major_segments: u32, minor_segments: u32) -> Self {
+        Self::Torus {
+            major_r,
+            minor_r,
+            major_segments: facet_count(
+                major_r + minor_r,
+                major_segments as f64,
+                DEFAULT_FA,
+                DEFAULT_FS,
+            ),
+            minor_segments: facet_count(minor_r, minor_segments as f64, DEFAULT_FA, DEFAULT_FS),
+        }
+    }
+
+    /// Solid bounded by `faces`, each a planar polygon of indices into `points`
+    ///
+    /// Faces wind clockwise seen from outside, as in OpenSCAD; they are reversed when meshing
+    /// so the triangles face outward.
+    pub fn polyhedron(points: Vec<Point3<f64>>, faces: Vec<Vec<usize>>) -> Self {
+        Self::Polyhedron { points, faces }
+    }
+
+    /// Pyramid from a rectangular `base` on z=0 to a point `apex_height` above its center
+    ///
+    /// With `center` the base centroid sits at the origin, otherwise the base corner does.
+    pub fn pyramid(base: Vector2<f64>, apex_height: f64, center: bool) -> Self {
+        Self::FrustumRect {
+            base,
+            top: Vector2::zeros(),
+            h: apex_height,
+            center,
+        }
+    }
+
+    pub fn square_pyramid(size: f64, apex_height: f64, center: bool) -> Self {
+        Self::pyramid(Vector2::new(size, size), apex_height, center)
+    }
+
+    /// Rectangular frustum; a zero `top` gives a pyramid, and one zero side gives a ridge
+    pub fn frustum_rect(
+        base: Vector2<f64>,
+        top: Vector2<f64>,
+        height: f64,
+        center: bool,
+    ) -> Self {
+        Self::FrustumRect {
+            base,
+            top,
+            h: height,
+            center,
+        }
+    }
+
+    /// Check parameters against OpenSCAD's rules for producing geometry
+    pub fn validate(&self) -> Result<()> {
+        let finite = |values: &[f64]| values.iter().all(|v| v.is_finite());
+
+        match self {
+            Self::Cube { size, .. } => {
+                if !finite(size.as_slice()) || size.iter().any(|&v| v <= 0.0) {
+                    bail!(
+                        "cube(size=[{}, {}, {}]) must have positive dimensions",
+                        size.x,
+                        size.y,
+                        size.z
+                    );
+                }
+            }
+            Self::Sphere { r, .. } => {
+                if !finite(&[*r]) || *r <= 0.0 {
+                    bail!("sphere(r={}) must have a positive radius", r);
+                }
+            }
+            Self::Icosphere { r, subdivisions } => {
+                if !finite(&[*r]) || *r <= 0.0 {
+                    bail!("icosphere(r={}) must have a positive radius", r);
+                }
+                if *subdivisions > MAX_ICOSPHERE_SUBDIVISIONS {
+                    bail!(
+                        "icosphere(subdivisions={}) exceeds the limit of {}",
+                        subdivisions,
+                        MAX_ICOSPHERE_SUBDIVISIONS
+                    );
+                }
+            }
+            Self::Cylinder { h, r, .. } => {
+                if !finite(&[*h, *r]) || *h <= 0.0 || *r <= 0.0 {
+                    bail!(
+                        "cylinder(h={}, r={}) must have a positive height and radius",
+                        h,
+                        r
+                    );
+                }
+            }
+            Self::Cone { h, r1, r2, .. } => {
+                if !finite(&[*h, *r1, *r2])
+                    || *h <= 0.0
+                    || *r1 < 0.0
+                    || *r2 < 0.0
+                    || (*r1 == 0.0 && *r2 == 0.0)
+                {
+                    bail!(
+                        "cylinder(h={}, r1={}, r2={}) must have a positive height and non-negative radii, not both zero",
+                        h,
+                        r1,
+                        r2
+                    );
+                }
+            }
+            Self::FrustumRect { base, top, h, .. } => {
+                if !finite(&[base.x, base.y, top.x, top.y, *h])
+                    || base.iter().any(|&v| v <= 0.0)
+                    || top.iter().any(|&v| v < 0.0)
+                    || *h <= 0.0
+                {
+                    bail!(
+                        "frustum(base=[{}, {}], top=[{}, {}], h={}) must have a positive base and height and a non-negative top",
+                        base.x,
+                        base.y,
+                        top.x,
+                        top.y,
+                        h
+                    );
+                }
+            }
+            Self::Torus {
+                major_r, minor_r, ..
+            } => {
+                if !finite(&[*major_r, *minor_r]) || *minor_r <= 0.0 || *minor_r >= *major_r {
+                    bail!(
+                        "torus(major_r={}, minor_r={}) must have a positive tube radius smaller than its major radius",
+                        major_r,
+                        minor_r
+                    );
+                }
+            }
+            Self::Polyhedron { points, faces } => {
+                if !points.iter().all(|p| finite(p.coords.as_slice())) {
+                    bail!("polyhedron() points must be finite");
+                }
+                for (i, face) in faces.iter().enumerate() {
+                    if face.len() < 3 {
+                        bail!("polyhedron() face {} has {} points, needs at least 3", i, face.len());
+                    }
+                    if let Some(&index) = face.iter().find(|&&index| index >= points.len()) {
+                        bail!(
+                            "polyhedron() face {} refers to point {}, but there are only {} points",
+                            i,
+                            index,
+                            points.len()
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tessellate the primitive; invalid parameters yield an empty mesh, as in OpenSCAD
     pub fn to_mesh(&self) -> Mesh {
+        if let Err(e) = self.validate() {
+            eprintln!("Warning: {}, object ignored", e);
+            return Mesh::empty();
+        }
+
         match self {
             Self::Cube { size, center } => generate_cube_mesh(*size, *center),
             Self::Sphere { r, fn_ } => generate_sphere_mesh(*r, *fn_),
+            Self::Icosphere { r, subdivisions } => generate_icosphere_mesh(*r, *subdivisions),
             Self::Cylinder { h, r, fn_ } => generate_cylinder_mesh(*h, *r, *fn_),
             Self::Cone { h, r1, r2, fn_ } => generate_cone_mesh(*h, *r1, *r2, *fn_),
+            Self::FrustumRect {
+                base,
+                top,
+                h,
+                center,
+            } => generate_frustum_rect_mesh(*base, *top, *h, *center),
+            Self::Torus {
+                major_r,
+                minor_r,
+                major_segments,
+                minor_segments,
+            } => generate_torus_mesh(*major_r, *minor_r, *major_segments, *minor_segments),
+            Self::Polyhedron { points, faces } => generate_polyhedron_mesh(points, faces),
         }
     }
 }
 
+/// Number of fragments for a circle of radius `r`, following OpenSCAD's `get_fragments_from_r`
+///
+/// A positive `fn_` wins (raised to at least 3). Otherwise each fragment spans at most `fa`
+/// degrees and is at most `fs` long, with a floor of 5 fragments. Like OpenSCAD, `fa` and `fs`
+/// are clamped to at least 0.01.
+pub fn facet_count(r: f64, fn_: f64, fa: f64, fs: f64) -> u32 {
+    if r < GRID_FINE {
+        return MIN_FRAGMENTS;
+    }
+    if fn_ > 0.0 {
+        return (fn_ as u32).max(MIN_FRAGMENTS);
+    }
+    let (fa, fs) = (fa.max(MIN_FA_FS), fs.max(MIN_FA_FS));
+    (360.0 / fa).min(r * 2.0 * PI / fs).max(5.0).ceil() as u32
+}
+
 fn generate_cube_mesh(size: Vector3<f64>, center: bool) -> Mesh {
     let mut mesh = Mesh::new();
 
@@ -145,15 +418,16 @@ fn generate_sphere_mesh(radius: f64, segments: u32) -> Mesh {
             let first = i * (slices + 1) + j;
             let second = first + slices + 1;
 
+            // Counter-clockwise when viewed from outside so face normals point outward
             mesh.add_triangle(Triangle::new([
                 first as usize,
-                second as usize,
                 (first + 1) as usize,
+                second as usize,
             ]));
             mesh.add_triangle(Triangle::new([
                 second as usize,
-                (second + 1) as usize,
                 (first + 1) as usize,
+                (second + 1) as usize,
             ]));
         }
     }
@@ -161,6 +435,81 @@ fn generate_sphere_mesh(radius: f64, segments: u32) -> Mesh {
     mesh
 }
 
+fn generate_icosphere_mesh(radius: f64, subdivisions: u32) -> Mesh {
+    // Icosahedron corners are the cyclic permutations of (0, +-1, +-phi)
+    let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+    let mut directions: Vec<Vector3<f64>> = [
+        (-1.0, phi, 0.0),
+        (1.0, phi, 0.0),
+        (-1.0, -phi, 0.0),
+        (1.0, -phi, 0.0),
+        (0.0, -1.0, phi),
+        (0.0, 1.0, phi),
+        (0.0, -1.0, -phi),
+        (0.0, 1.0, -phi),
+        (phi, 0.0, -1.0),
+        (phi, 0.0, 1.0),
+        (-phi, 0.0, -1.0),
+        (-phi, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| Vector3::new(x, y, z).normalize())
+    .collect();
+
+    // Counter-clockwise when viewed from outside
+    let mut faces: Vec<[usize; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // Each edge gets one midpoint, shared by the two faces on either side
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize, directions: &mut Vec<Vector3<f64>>| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                directions.push((directions[a] + directions[b]).normalize());
+                directions.len() - 1
+            })
+        };
+
+        let mut split = Vec::with_capacity(faces.len() * 4);
+        for [a, b, c] in faces {
+            let ab = midpoint(a, b, &mut directions);
+            let bc = midpoint(b, c, &mut directions);
+            let ca = midpoint(c, a, &mut directions);
+            split.extend([[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+        }
+        faces = split;
+    }
+
+    let mut mesh = Mesh::with_capacity(directions.len(), faces.len());
+    for direction in directions {
+        mesh.add_vertex(Vertex::new(Point3::from(direction * radius), direction));
+    }
+    for face in faces {
+        mesh.add_triangle(Triangle::new(face));
+    }
+    mesh
+}
+
 fn generate_cylinder_mesh(height: f64, radius: f64, segments: u32) -> Mesh {
     generate_cone_mesh(height, radius, radius, segments)
 }
@@ -243,8 +592,8 @@ fn generate_cone_mesh(height: f64, r1: f64, r2: f64, segments: u32) -> Mesh {
         let tn = top_indices[next as usize];
 
         // Add two triangles per segment
-        mesh.add_triangle(Triangle::new([bi, ti, bn]));
-        mesh.add_triangle(Triangle::new([ti, tn, bn]));
+        mesh.add_triangle(Triangle::new([bi, bn, ti]));
+        mesh.add_triangle(Triangle::new([ti, bn, tn]));
     }
 
     // Recompute normals to properly average at shared vertices
@@ -252,10 +601,167 @@ fn generate_cone_mesh(height: f64, r1: f64, r2: f64, segments: u32) -> Mesh {
     mesh
 }
 
+fn generate_frustum_rect_mesh(
+    base: Vector2<f64>,
+    top: Vector2<f64>,
+    h: f64,
+    center: bool,
+) -> Mesh {
+    let mut mesh = Mesh::new();
+    let mid = if center { Vector2::zeros() } else { base / 2.0 };
+
+    // Corners counter-clockwise seen from above
+    let corners = |size: Vector2<f64>, z: f64| {
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(sx, sy)| {
+            Point3::new(mid.x + sx * size.x / 2.0, mid.y + sy * size.y / 2.0, z)
+        })
+    };
+
+    let mut add =
+        |position: Point3<f64>| mesh.add_vertex(Vertex::new(position, Vector3::zeros()));
+    let b = corners(base, 0.0).map(&mut add);
+
+    // Coincident top corners share a vertex, so a zero-size top collapses to an apex or ridge
+    let top_corners = corners(top, h);
+    let mut t = Vec::with_capacity(4);
+    for (i, corner) in top_corners.iter().enumerate() {
+        let shared = top_corners[..i].iter().position(|c| c == corner);
+        t.push(match shared {
+            Some(j) => t[j],
+            None => add(*corner),
+        });
+    }
+
+    let mut faces = vec![[b[0], b[2], b[1]], [b[0], b[3], b[2]]];
+    faces.extend([[t[0], t[1], t[2]], [t[0], t[2], t[3]]]);
+    for i in 0..4 {
+        let next = (i + 1) % 4;
+        faces.extend([[b[i], b[next], t[next]], [b[i], t[next], t[i]]]);
+    }
+
+    // Faces that lost an edge to the collapsed top are dropped
+    for face in faces {
+        if face[0] != face[1] && face[1] != face[2] && face[2] != face[0] {
+            mesh.add_triangle(Triangle::new(face));
+        }
+    }
+
+    mesh.recompute_normals();
+    mesh
+}
+
+fn generate_torus_mesh(
+    major_r: f64,
+    minor_r: f64,
+    major_segments: u32,
+    minor_segments: u32,
+) -> Mesh {
+    let (rings, sides) = (major_segments as usize, minor_segments as usize);
+    let mut mesh = Mesh::with_capacity(rings * sides, 2 * rings * sides);
+
+    // One vertex per (ring, side) pair; indices wrap so the surface closes in both directions
+    for i in 0..rings {
+        let theta = 2.0 * PI * i as f64 / rings as f64;
+        let radial = Vector3::new(theta.cos(), theta.sin(), 0.0);
+        for j in 0..sides {
+            let phi = 2.0 * PI * j as f64 / sides as f64;
+            let normal = radial * phi.cos() + Vector3::z() * phi.sin();
+            let position = Point3::from(radial * major_r + normal * minor_r);
+            mesh.add_vertex(Vertex::new(position, normal));
+        }
+    }
+
+    let index = |i: usize, j: usize| (i % rings) * sides + j % sides;
+    for i in 0..rings {
+        for j in 0..sides {
+            // Counter-clockwise when viewed from outside the tube
+            let (a, b) = (index(i, j), index(i + 1, j));
+            let (c, d) = (index(i + 1, j + 1), index(i, j + 1));
+            mesh.add_triangle(Triangle::new([a, b, c]));
+            mesh.add_triangle(Triangle::new([a, c, d]));
+        }
+    }
+
+    mesh
+}
+
+fn generate_polyhedron_mesh(points: &[Point3<f64>], faces: &[Vec<usize>]) -> Mesh {
+    let mut mesh = Mesh::with_capacity(points.len(), faces.len());
+    for p in points {
+        mesh.add_vertex(Vertex::new(*p, Vector3::zeros()));
+    }
+
+    for face in faces {
+        // Reverse OpenSCAD's clockwise order so the face is counterclockwise from outside
+        let ring: Vec<usize> = face.iter().rev().copied().collect();
+        for triangle in triangulate_face(&ring, points) {
+            mesh.add_triangle(Triangle::new(triangle));
+        }
+    }
+
+    // A directed edge used twice means two neighboring faces disagree on which side is out
+    let mut directed = std::collections::HashSet::new();
+    let consistent = mesh.triangles.iter().all(|t| {
+        (0..3).all(|k| directed.insert((t.indices[k], t.indices[(k + 1) % 3])))
+    });
+    if !consistent {
+        eprintln!("Warning: polyhedron() faces are not all wound the same way; some face inward");
+    }
+
+    mesh.recompute_normals();
+    mesh
+}
+
+/// Triangulate a counterclockwise face of `points`: fans for convex faces, ear clipping
+/// in the face's plane otherwise
+fn triangulate_face(ring: &[usize], points: &[Point3<f64>]) -> Vec<[usize; 3]> {
+    // Newell's method gives a normal that is robust to collinear corners
+    let mut normal = Vector3::zeros();
+    for (k, &i) in ring.iter().enumerate() {
+        let (p, q) = (points[i], points[ring[(k + 1) % ring.len()]]);
+        normal += Vector3::new(
+            (p.y - q.y) * (p.z + q.z),
+            (p.z - q.z) * (p.x + q.x),
+            (p.x - q.x) * (p.y + q.y),
+        );
+    }
+    if normal.norm() == 0.0 {
+        return Vec::new();
+    }
+    let normal = normal.normalize();
+
+    let convex = (0..ring.len()).all(|k| {
+        let (a, b, c) = (
+            points[ring[k]],
+            points[ring[(k + 1) % ring.len()]],
+            points[ring[(k + 2) % ring.len()]],
+        );
+        (b - a).cross(&(c - b)).dot(&normal) >= 0.0
+    });
+    if convex {
+        return (1..ring.len() - 1).map(|k| [ring[0], ring[k], ring[k + 1]]).collect();
+    }
+
+    // Project into the face's plane with u x v = normal, so the ring stays counterclockwise
+    let axis = Vector3::ith(normal.iamin(), 1.0);
+    let u = normal.cross(&axis).normalize();
+    let v = normal.cross(&u);
+    let origin = points[ring[0]];
+    let flat: Vec<Point2<f64>> = ring
+        .iter()
+        .map(|&i| Point2::new((points[i] - origin).dot(&u), (points[i] - origin).dot(&v)))
+        .collect();
+    super::shape2d::ear_clip((0..ring.len()).collect(), &flat)
+        .into_iter()
+        .map(|t| t.map(|k| ring[k]))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::geometry::mesh_utils::{is_closed, is_manifold};
+    use crate::geometry::signed_volume;
     use nalgebra::Vector3;
 
     #[test]
@@ -273,6 +779,7 @@ mod tests {
         let mesh = generate_cylinder_mesh(10.0, 5.0, 32);
         assert!(is_manifold(&mesh), "Cylinder mesh should be manifold");
         assert!(is_closed(&mesh), "Cylinder mesh should be closed");
+        assert!(signed_volume(&mesh) > 0.0, "Cylinder mesh should face outward");
         assert!(mesh.vertex_count() > 0);
         assert!(mesh.triangle_count() > 0);
     }
@@ -282,10 +789,28 @@ mod tests {
         let mesh = generate_cone_mesh(10.0, 5.0, 3.0, 32);
         assert!(is_manifold(&mesh), "Cone mesh should be manifold");
         assert!(is_closed(&mesh), "Cone mesh should be closed");
+        assert!(signed_volume(&mesh) > 0.0, "Cone mesh should face outward");
         assert!(mesh.vertex_count() > 0);
         assert!(mesh.triangle_count() > 0);
     }
 
+    #[test]
+    fn test_sphere_faces_outward() {
+        let mesh = generate_sphere_mesh(5.0, 16);
+        assert!(signed_volume(&mesh) > 0.0, "Sphere mesh should face outward");
+
+        // Every non-degenerate face normal agrees with the direction from the center
+        for triangle in &mesh.triangles {
+            let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position);
+            let normal = (b - a).cross(&(c - a));
+            if normal.norm() < 1e-9 {
+                continue;
+            }
+            let center = (a.coords + b.coords + c.coords) / 3.0;
+            assert!(normal.dot(&center) > 0.0);
+        }
+    }
+
     #[test]
     fn test_cylinder_vertex_reuse() {
         // Verify that vertices are reused (no duplicates at same position)
@@ -301,4 +826,227 @@ mod tests {
             "Cylinder should have exactly {} vertices (2 centers + {} rim vertices)", 
             expected_vertices, 16 * 2);
     }
+
+    #[test]
+    fn test_square_pyramid() {
+        let mesh = Primitive::square_pyramid(10.0, 6.0, true).to_mesh();
+        assert_eq!(mesh.vertex_count(), 5);
+        assert_eq!(mesh.triangle_count(), 6);
+        assert!(is_manifold(&mesh) && is_closed(&mesh));
+
+        let bbox = mesh.bounding_box();
+        assert_eq!(bbox.min, Point3::new(-5.0, -5.0, 0.0));
+        assert_eq!(bbox.max, Point3::new(5.0, 5.0, 6.0));
+
+        // Two base triangles face down, each side is a single outward-facing triangle
+        let face_normals: Vec<Vector3<f64>> = mesh
+            .triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.indices.map(|i| mesh.vertices[i].position);
+                (b - a).cross(&(c - a)).normalize()
+            })
+            .collect();
+        assert_eq!(face_normals.iter().filter(|n| n.z < -0.999).count(), 2);
+        let slant = Vector3::new(0.0, -6.0, 5.0).normalize();
+        assert!(face_normals.iter().any(|n| (n - slant).norm() < 1e-12));
+        let volume = crate::geometry::analyze(&mesh).volume;
+        assert!((volume - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frustum_rect() {
+        let frustum =
+            Primitive::frustum_rect(Vector2::new(8.0, 4.0), Vector2::new(4.0, 2.0), 3.0, false)
+                .to_mesh();
+        assert_eq!(frustum.triangle_count(), 12);
+        assert!(is_manifold(&frustum) && is_closed(&frustum));
+        let top_center = Point3::new(4.0, 2.0, 3.0);
+        assert!(frustum
+            .vertices
+            .iter()
+            .filter(|v| v.position.z == 3.0)
+            .all(|v| (v.position - top_center).abs() == Vector3::new(2.0, 1.0, 0.0)));
+
+        // A top with one zero side becomes a ridge, like a roof
+        let roof =
+            Primitive::frustum_rect(Vector2::new(8.0, 4.0), Vector2::new(4.0, 0.0), 3.0, true)
+                .to_mesh();
+        assert_eq!(roof.vertex_count(), 6);
+        assert_eq!(roof.triangle_count(), 8);
+        assert!(is_manifold(&roof) && is_closed(&roof));
+
+        assert!(Primitive::frustum_rect(Vector2::new(8.0, 0.0), Vector2::zeros(), 3.0, true)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_torus_bounds_and_topology() {
+        let (major_r, minor_r) = (10.0, 2.5);
+        let mesh = Primitive::torus(major_r, minor_r, 48, 16).to_mesh();
+        let bbox = mesh.bounding_box();
+
+        let size = bbox.size();
+        assert!((size.x - 2.0 * (major_r + minor_r)).abs() < 1e-9);
+        assert!((size.y - 2.0 * (major_r + minor_r)).abs() < 1e-9);
+        assert!((size.z - 2.0 * minor_r).abs() < 1e-9);
+        assert!((bbox.center() - Point3::origin()).norm() < 1e-9);
+
+        assert_eq!(mesh.vertex_count(), 48 * 16);
+        assert_eq!(mesh.triangle_count(), 2 * 48 * 16);
+        assert!(is_manifold(&mesh));
+        assert!(is_closed(&mesh));
+
+        // Normals are smooth and face away from the tube's center circle
+        for vertex in &mesh.vertices {
+            let p = vertex.position;
+            let ring = Vector3::new(p.x, p.y, 0.0).normalize() * major_r;
+            let outward = (p.coords - ring).normalize();
+            assert!((vertex.normal - outward).norm() < 1e-9);
+        }
+        // Face winding agrees with the vertex normals
+        for t in &mesh.triangles {
+            let [a, b, c] = t.indices.map(|i| mesh.vertices[i].position);
+            let face = (b - a).cross(&(c - a));
+            assert!(face.dot(&mesh.vertices[t.indices[0]].normal) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_polyhedron_tetrahedron() {
+        let points = vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(10.0, 0.0, 0.0),
+            Point3::new(0.0, 10.0, 0.0),
+            Point3::new(0.0, 0.0, 10.0),
+        ];
+        // Clockwise seen from outside, as OpenSCAD expects
+        let faces = vec![vec![0, 1, 2], vec![0, 3, 1], vec![0, 2, 3], vec![1, 3, 2]];
+        let mesh = Primitive::polyhedron(points, faces).to_mesh();
+
+        assert_eq!(mesh.triangle_count(), 4);
+        assert!((signed_volume(&mesh) - 1000.0 / 6.0).abs() < 1e-9);
+        assert!(is_closed(&mesh) && is_manifold(&mesh));
+    }
+
+    #[test]
+    fn test_polyhedron_triangulates_concave_faces() {
+        // L-shaped prism: two concave hexagonal caps and six quad walls
+        let outline = [(0.0, 0.0), (4.0, 0.0), (4.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0)];
+        let mut points: Vec<Point3<f64>> =
+            outline.iter().map(|&(x, y)| Point3::new(x, y, 0.0)).collect();
+        points.extend(outline.iter().map(|&(x, y)| Point3::new(x, y, 2.0)));
+
+        // The outline is counterclockwise from above, so the bottom lists it as is
+        let mut faces = vec![(0..6).collect::<Vec<_>>(), (6..12).rev().collect()];
+        for i in 0..6 {
+            let next = (i + 1) % 6;
+            faces.push(vec![i, i + 6, next + 6, next]);
+        }
+        let mesh = Primitive::polyhedron(points, faces).to_mesh();
+
+        assert_eq!(mesh.triangle_count(), 2 * 4 + 6 * 2);
+        assert!((signed_volume(&mesh) - 2.0 * 6.0).abs() < 1e-9);
+        assert!(is_closed(&mesh) && is_manifold(&mesh));
+    }
+
+    #[test]
+    fn test_icosphere_counts_and_radius() {
+        let radius = 10.0;
+        for subdivisions in 0..4 {
+            let mesh = Primitive::icosphere(radius, subdivisions).to_mesh();
+            assert_eq!(mesh.triangle_count(), 20 * 4_usize.pow(subdivisions));
+            assert_eq!(mesh.vertex_count(), 10 * 4_usize.pow(subdivisions) + 2);
+            assert!(is_closed(&mesh) && is_manifold(&mesh));
+            assert!(signed_volume(&mesh) > 0.0);
+
+            for vertex in &mesh.vertices {
+                assert!((vertex.position.coords.norm() - radius).abs() < 1e-9);
+            }
+        }
+
+        // Faces are close to uniform, unlike the slivers of a lat/long sphere near its poles
+        let area = |mesh: &Mesh, t: &Triangle| {
+            let [a, b, c] = t.indices.map(|i| mesh.vertices[i].position);
+            (b - a).cross(&(c - a)).norm()
+        };
+        let spread = |mesh: &Mesh| {
+            let areas: Vec<f64> = mesh.triangles.iter().map(|t| area(mesh, t)).collect();
+            let max = areas.iter().cloned().fold(0.0, f64::max);
+            let min = areas.iter().cloned().filter(|&a| a > 1e-9).fold(f64::INFINITY, f64::min);
+            max / min
+        };
+        let ico = Primitive::icosphere(radius, 3).to_mesh();
+        let lat_long = Primitive::sphere(radius, 40).to_mesh();
+        assert!(spread(&ico) < 2.0);
+        assert!(spread(&lat_long) > spread(&ico));
+
+        // Equator segments choose the subdivision level
+        match Primitive::sphere_with_tessellation(radius, 20, SphereTessellation::Ico) {
+            Primitive::Icosphere { subdivisions, .. } => assert_eq!(subdivisions, 2),
+            _ => panic!("Expected an icosphere"),
+        }
+        assert!(matches!(
+            Primitive::sphere_with_tessellation(radius, 20, SphereTessellation::default()),
+            Primitive::Sphere { fn_: 20, .. }
+        ));
+    }
+
+    #[test]
+    fn test_fn_clamped_to_minimum() {
+        match Primitive::sphere(5.0, 1) {
+            Primitive::Sphere { fn_, .. } => assert_eq!(fn_, MIN_FRAGMENTS),
+            _ => unreachable!(),
+        }
+        match Primitive::cylinder(5.0, 1.0, 0) {
+            Primitive::Cylinder { fn_, .. } => assert_eq!(fn_, 5),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_facet_count_from_fa_fs() {
+        let small = facet_count(1.0, 0.0, DEFAULT_FA, DEFAULT_FS);
+        let large = facet_count(50.0, 0.0, DEFAULT_FA, DEFAULT_FS);
+        assert_eq!(small, 5);
+        assert_eq!(large, 30);
+        assert!(large > small);
+
+        // $fs limits mid-sized circles, $fa large ones
+        assert_eq!(facet_count(5.0, 0.0, DEFAULT_FA, DEFAULT_FS), 16);
+        assert_eq!(facet_count(50.0, 0.0, 6.0, 1.0), 60);
+
+        // An explicit $fn overrides both
+        assert_eq!(facet_count(50.0, 7.0, DEFAULT_FA, DEFAULT_FS), 7);
+        assert_eq!(facet_count(1.0, 64.0, 1.0, 0.01), 64);
+        assert_eq!(facet_count(0.0, 64.0, DEFAULT_FA, DEFAULT_FS), MIN_FRAGMENTS);
+    }
+
+    #[test]
+    fn test_invalid_parameters_produce_empty_mesh() {
+        let invalid = [
+            Primitive::cube(Vector3::new(10.0, -1.0, 10.0), false),
+            Primitive::sphere(-5.0, 16),
+            Primitive::sphere(0.0, 16),
+            Primitive::icosphere(0.0, 2),
+            Primitive::icosphere(1.0, MAX_ICOSPHERE_SUBDIVISIONS + 1),
+            Primitive::cylinder(-2.0, 1.0, 16),
+            Primitive::cylinder(2.0, -1.0, 16),
+            Primitive::cone(2.0, 0.0, 0.0, 16),
+            Primitive::torus(5.0, 0.0, 16, 8),
+            Primitive::torus(2.0, 3.0, 16, 8),
+            Primitive::polyhedron(vec![Point3::origin(); 3], vec![vec![0, 1, 3]]),
+            Primitive::polyhedron(vec![Point3::origin(); 3], vec![vec![0, 1]]),
+        ];
+        for primitive in &invalid {
+            assert!(primitive.validate().is_err());
+            assert_eq!(primitive.to_mesh().triangle_count(), 0);
+        }
+
+        // A cone tapering to a point is valid
+        let cone = Primitive::cone(2.0, 1.0, 0.0, 16);
+        assert!(cone.validate().is_ok());
+        assert!(cone.to_mesh().triangle_count() > 0);
+    }
 }
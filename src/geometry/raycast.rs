@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Ray queries against a mesh, for picking and support generation
+
+use super::bvh::BVH;
+use super::robust_csg::ray_triangle_hit;
+use super::{BoundingBox, Mesh};
+use nalgebra::{Point3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Nearest intersection of a ray with a mesh
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RayHit {
+    /// Index of the triangle hit
+    pub triangle: usize,
+    /// Weights of the triangle's three corners, in index order, at the hit point
+    pub barycentric: [f64; 3],
+    /// Distance from the ray origin to the hit point
+    pub distance: f64,
+}
+
+impl RayHit {
+    /// Position of the hit on `mesh`, interpolated from the triangle's corners
+    pub fn point(&self, mesh: &Mesh) -> Point3<f64> {
+        let corners = mesh.triangles[self.triangle]
+            .indices
+            .map(|i| mesh.vertices[i].position);
+        Point3::from(
+            corners
+                .iter()
+                .zip(self.barycentric)
+                .map(|(c, w)| c.coords * w)
+                .sum::<Vector3<f64>>(),
+        )
+    }
+}
+
+/// First triangle of `mesh` hit by the ray from `origin` along `direction`
+///
+/// Back faces count, so a ray starting inside a closed mesh hits its far side. A ray through
+/// an edge or vertex shared by several triangles reports the lowest triangle index among them.
+/// `direction` need not be normalized; a zero direction hits nothing.
+pub(crate) fn raycast(
+    mesh: &Mesh,
+    origin: &Point3<f64>,
+    direction: &Vector3<f64>,
+) -> Option<RayHit> {
+    let length = direction.norm();
+    if length == 0.0 || !length.is_finite() {
+        return None;
+    }
+    let direction = direction / length;
+
+    let vertex_count = mesh.vertices.len();
+    let corners = |idx: usize| {
+        mesh.triangles[idx]
+            .indices
+            .map(|i| mesh.vertices[i].position)
+    };
+    let bounds = mesh
+        .triangles
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.indices.iter().all(|&i| i < vertex_count))
+        .map(|(idx, _)| {
+            let mut bbox = BoundingBox::empty();
+            for p in corners(idx) {
+                bbox.expand_to_include(&p);
+            }
+            (idx, bbox)
+        })
+        .collect();
+    let bvh = BVH::build(bounds);
+
+    let hit = |idx: usize| {
+        let [a, b, c] = corners(idx);
+        ray_triangle_hit(origin, &direction, &a, &b, &c)
+    };
+    let (triangle, _) = bvh.raycast(origin, &direction, |idx| hit(idx).map(|(t, _, _)| t))?;
+    let (distance, u, v) = hit(triangle)?;
+    Some(RayHit {
+        triangle,
+        barycentric: [1.0 - u - v, u, v],
+        distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+
+    #[test]
+    fn test_raycast_down_onto_cube() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let origin = Point3::new(3.0, 4.0, 25.0);
+
+        let hit = cube
+            .raycast(&origin, &-Vector3::z())
+            .expect("ray should hit the top face");
+        assert!((hit.distance - 15.0).abs() < 1e-9);
+        let point = hit.point(&cube);
+        assert!((point - Point3::new(3.0, 4.0, 10.0)).norm() < 1e-9);
+        assert!((hit.barycentric.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // The length of the direction doesn't change the distance
+        let scaled = cube
+            .raycast(&origin, &Vector3::new(0.0, 0.0, -4.0))
+            .unwrap();
+        assert_eq!(scaled.triangle, hit.triangle);
+        assert!((scaled.distance - 15.0).abs() < 1e-9);
+
+        assert!(cube.raycast(&origin, &Vector3::z()).is_none());
+        assert!(cube
+            .raycast(&Point3::new(20.0, 4.0, 25.0), &-Vector3::z())
+            .is_none());
+        assert!(cube.raycast(&origin, &Vector3::zeros()).is_none());
+    }
+
+    #[test]
+    fn test_raycast_through_shared_edge_picks_lowest_index() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+
+        // Aim at every point of the top face along its diagonal, where two triangles meet
+        let top: Vec<usize> = (0..cube.triangle_count())
+            .filter(|&i| {
+                cube.triangles[i]
+                    .indices
+                    .iter()
+                    .all(|&v| cube.vertices[v].position.z == 10.0)
+            })
+            .collect();
+        assert_eq!(top.len(), 2);
+        let [a, b, c] = cube.triangles[top[0]]
+            .indices
+            .map(|i| cube.vertices[i].position);
+        let shared: Vec<_> = [a, b, c]
+            .into_iter()
+            .filter(|p| {
+                cube.triangles[top[1]]
+                    .indices
+                    .iter()
+                    .any(|&i| cube.vertices[i].position == *p)
+            })
+            .collect();
+        assert_eq!(shared.len(), 2);
+
+        let midpoint = Point3::from((shared[0].coords + shared[1].coords) / 2.0);
+        let hit = cube
+            .raycast(&(midpoint + Vector3::z() * 5.0), &-Vector3::z())
+            .unwrap();
+        assert_eq!(hit.triangle, top[0].min(top[1]));
+        assert!((hit.distance - 5.0).abs() < 1e-9);
+    }
+}
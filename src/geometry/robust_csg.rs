@@ -5,15 +5,25 @@
 //! This provides a fallback for cases where BSP tree CSG fails
 
 use super::{
-    classification::{classify_face_fragment, Classification},
+    classification::{
+        boundary_normal, classify_face_fragment, classify_split_fragment, Classification, Solid,
+    },
     triangle_intersection::{triangle_triangle_intersection, IntersectionResult, IntersectionType},
-    triangle_splitting::{split_triangle_by_segment, split_triangle_by_plane},
+    triangle_splitting::{split_triangle_by_planes, PlaneCut, TriangleFragment},
     mesh_utils::{find_boundary_edges, build_edge_counts, classify_triangle_by_edges, TriangleClassification},
-    Mesh, Triangle,
+    CleanupOptions, Mesh, Triangle,
 };
 use anyhow::Result;
 use nalgebra::{Point3, Vector3};
 
+/// Debug options for the robust CSG pipeline
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsgConfig {
+    /// Keep fragments classified as inside the other operand and skip the cleanup passes,
+    /// so the raw split output can be inspected when geometry goes missing
+    pub keep_internal_faces: bool,
+}
+
 /// Perform robust CSG union using improved classification
 /// This properly removes internal faces between overlapping meshes
 /// 
@@ -153,6 +163,12 @@ pub fn robust_union(a: &Mesh, b: &Mesh) -> Result<Mesh> {
 /// 5. Keeps fragments: A outside B, B outside A
 /// 6. Reconstructs manifold mesh
 pub fn robust_union_core(a: &Mesh, b: &Mesh) -> Result<Mesh> {
+    robust_union_core_with_config(a, b, &CsgConfig::default())
+}
+
+/// Robust union core with debug options
+/// See [`CsgConfig`]; the default configuration matches [`robust_union_core`]
+pub fn robust_union_core_with_config(a: &Mesh, b: &Mesh, config: &CsgConfig) -> Result<Mesh> {
     use super::{bvh::BVH, BoundingBox};
     
     // DEBUG: This is the robust union core being called
@@ -253,6 +269,10 @@ pub fn robust_union_core(a: &Mesh, b: &Mesh) -> Result<Mesh> {
         }
     }
     
+    // Index both meshes for the inside/outside tests of every kept triangle
+    let solid_a = Solid::new(a);
+    let solid_b = Solid::new(b);
+
     // Build result mesh
     let mut result = Mesh::new();
     // Track which mesh each triangle came from (0 = mesh A, 1 = mesh B)
@@ -268,120 +288,23 @@ pub fn robust_union_core(a: &Mesh, b: &Mesh) -> Result<Mesh> {
         
         // Check if this triangle has intersections
         if let Some(intersection_list) = intersections_a.get(&tri_idx) {
-            // Triangle has intersections - split it
-            let mut all_intersection_points = Vec::new();
-            let mut has_coplanar = false;
-            
-            // Collect all intersection points from all intersecting triangles
-            for (_other_tri_idx, intersection_result) in intersection_list {
-                match intersection_result.intersection_type {
-                    IntersectionType::Point => {
-                        // Single point intersection
-                        all_intersection_points.extend(intersection_result.intersection_points.iter().cloned());
-                    }
-                    IntersectionType::Segment => {
-                        // Segment intersection - add both endpoints
-                        all_intersection_points.extend(intersection_result.intersection_points.iter().cloned());
-                    }
-                    IntersectionType::Coplanar => {
-                        // Coplanar case - handle separately
-                        has_coplanar = true;
-                    }
-                    IntersectionType::None => {}
-                }
-            }
-            
-            // Deduplicate intersection points
-            all_intersection_points = deduplicate_intersection_points(&all_intersection_points);
-            
-            // Split the triangle
-            let split_result = if has_coplanar {
-                // For coplanar case, use plane-based splitting
-                // Compute plane from the coplanar triangle
-                if let Some((other_idx, _)) = intersection_list.first() {
-                    let other_tri = &b.triangles[*other_idx];
-                    let ov0 = &b.vertices[other_tri.indices[0]].position;
-                    let ov1 = &b.vertices[other_tri.indices[1]].position;
-                    let ov2 = &b.vertices[other_tri.indices[2]].position;
-                    
-                    let edge1 = ov1 - ov0;
-                    let edge2 = ov2 - ov0;
-                    let plane_normal = edge1.cross(&edge2).normalize();
-                    let plane_d = plane_normal.dot(&ov0.coords);
-                    
-                    split_triangle_by_plane(tri, &a.vertices, &plane_normal, plane_d)
-                } else {
-                    // Fallback: no splitting
-                    split_triangle_by_segment(tri, &a.vertices, &[])
-                }
-            } else if all_intersection_points.len() <= 2 {
-                // Normal splitting with intersection points
-                split_triangle_by_segment(tri, &a.vertices, &all_intersection_points)
-            } else {
-                // Multiple intersection points - use first two (simplified)
-                split_triangle_by_segment(tri, &a.vertices, &all_intersection_points[..2.min(all_intersection_points.len())])
-            };
-            
-            // Classify and keep fragments that are outside or on boundary
-            // For union, we keep fragments that are outside the other mesh
-            // OnBoundary fragments are also kept as they're part of the union surface
-            let mut kept_fragments = 0;
-            for fragment in &split_result.fragments {
-                let frag_vertices = [
-                    fragment.vertices[0].position,
-                    fragment.vertices[1].position,
-                    fragment.vertices[2].position,
-                ];
-                let classification = classify_face_fragment(&frag_vertices, b);
-                
-                // For union, keep Outside and OnBoundary triangles
-                // Duplicate coplanar triangles will be removed by remove_duplicate_triangles()
-                let should_keep = match classification {
-                    Classification::Outside => true,
-                    Classification::OnBoundary => true, // Keep boundary triangles - duplicates will be removed
-                    Classification::Inside => false,
-                };
-                
-                if should_keep {
-                    // Add fragment vertices directly to result mesh
-                    let i0 = result.add_vertex(fragment.vertices[0]);
-                    let i1 = result.add_vertex(fragment.vertices[1]);
-                    let i2 = result.add_vertex(fragment.vertices[2]);
-                    result.add_triangle(Triangle::new([i0, i1, i2]));
-                    triangle_mesh_source.push(0); // From mesh A
-                    kept_fragments += 1;
-                }
-            }
-            
-            // Fallback: if no fragments were kept, always re-check the original triangle
-            // This handles cases where:
-            // 1. Splitting failed or didn't actually split (fragments.len() == 1)
-            // 2. All fragments were incorrectly classified as Inside
-            // 3. Classification of fragments was wrong but original triangle should be kept
-            if kept_fragments == 0 {
-                // Re-check the original triangle - classification might have been wrong
-                let classification = classify_face_fragment(&face_vertices, b);
-                let should_keep = match classification {
-                    Classification::Outside => true,
-                    Classification::OnBoundary => true, // Keep boundary triangles
-                    Classification::Inside => false,
-                };
-                if should_keep {
-                    let i0 = result.add_vertex(*v0);
-                    let i1 = result.add_vertex(*v1);
-                    let i2 = result.add_vertex(*v2);
-                    result.add_triangle(Triangle::new([i0, i1, i2]));
-                    triangle_mesh_source.push(0); // From mesh A
-                }
+            // Triangle has intersections - split it and keep the fragments outside B
+            // Coplanar overlaps are kept from A only
+            for fragment in union_fragments(tri, a, &solid_b, intersection_list, true, config) {
+                let i0 = result.add_vertex(fragment.vertices[0]);
+                let i1 = result.add_vertex(fragment.vertices[1]);
+                let i2 = result.add_vertex(fragment.vertices[2]);
+                result.add_triangle(Triangle::new([i0, i1, i2]));
+                triangle_mesh_source.push(0); // From mesh A
             }
         } else {
             // Non-intersecting triangle - use robust classification
-            let classification = classify_face_fragment(&face_vertices, b);
+            let classification = classify_face_fragment(&face_vertices, &solid_b);
             // For union, keep Outside and OnBoundary triangles
             let should_keep = match classification {
                 Classification::Outside => true,
                 Classification::OnBoundary => true, // Keep boundary triangles
-                Classification::Inside => false,
+                Classification::Inside => config.keep_internal_faces,
             };
             if should_keep {
                 let i0 = result.add_vertex(*v0);
@@ -403,113 +326,22 @@ pub fn robust_union_core(a: &Mesh, b: &Mesh) -> Result<Mesh> {
         
         // Check if this triangle has intersections
         if let Some(intersection_list) = intersections_b.get(&tri_idx) {
-            // Triangle has intersections - split it
-            let mut all_intersection_points = Vec::new();
-            let mut has_coplanar = false;
-            
-            // Collect all intersection points from all intersecting triangles
-            for (_other_tri_idx, intersection_result) in intersection_list {
-                match intersection_result.intersection_type {
-                    IntersectionType::Point => {
-                        all_intersection_points.extend(intersection_result.intersection_points.iter().cloned());
-                    }
-                    IntersectionType::Segment => {
-                        all_intersection_points.extend(intersection_result.intersection_points.iter().cloned());
-                    }
-                    IntersectionType::Coplanar => {
-                        has_coplanar = true;
-                    }
-                    IntersectionType::None => {}
-                }
-            }
-            
-            // Deduplicate intersection points
-            all_intersection_points = deduplicate_intersection_points(&all_intersection_points);
-            
-            // Split the triangle
-            let split_result = if has_coplanar {
-                // For coplanar case, use plane-based splitting
-                if let Some((other_idx, _)) = intersection_list.first() {
-                    let other_tri = &a.triangles[*other_idx];
-                    let ov0 = &a.vertices[other_tri.indices[0]].position;
-                    let ov1 = &a.vertices[other_tri.indices[1]].position;
-                    let ov2 = &a.vertices[other_tri.indices[2]].position;
-                    
-                    let edge1 = ov1 - ov0;
-                    let edge2 = ov2 - ov0;
-                    let plane_normal = edge1.cross(&edge2).normalize();
-                    let plane_d = plane_normal.dot(&ov0.coords);
-                    
-                    split_triangle_by_plane(tri, &b.vertices, &plane_normal, plane_d)
-                } else {
-                    split_triangle_by_segment(tri, &b.vertices, &[])
-                }
-            } else if all_intersection_points.len() <= 2 {
-                split_triangle_by_segment(tri, &b.vertices, &all_intersection_points)
-            } else {
-                split_triangle_by_segment(tri, &b.vertices, &all_intersection_points[..2.min(all_intersection_points.len())])
-            };
-            
-            // Classify and keep fragments that are outside or on boundary
-            // For union, we keep fragments that are outside the other mesh
-            // OnBoundary fragments are also kept as they're part of the union surface
-            let mut kept_fragments = 0;
-            for fragment in &split_result.fragments {
-                let frag_vertices = [
-                    fragment.vertices[0].position,
-                    fragment.vertices[1].position,
-                    fragment.vertices[2].position,
-                ];
-                let classification = classify_face_fragment(&frag_vertices, a);
-                
-                // For union, keep Outside and OnBoundary triangles
-                // Duplicate coplanar triangles will be removed by remove_duplicate_triangles()
-                let should_keep = match classification {
-                    Classification::Outside => true,
-                    Classification::OnBoundary => true, // Keep boundary triangles - duplicates will be removed
-                    Classification::Inside => false,
-                };
-                
-                if should_keep {
-                    // Add fragment vertices directly to result mesh
-                    let i0 = result.add_vertex(fragment.vertices[0]);
-                    let i1 = result.add_vertex(fragment.vertices[1]);
-                    let i2 = result.add_vertex(fragment.vertices[2]);
-                    result.add_triangle(Triangle::new([i0, i1, i2]));
-                    triangle_mesh_source.push(1); // From mesh B
-                    kept_fragments += 1;
-                }
-            }
-            
-            // Fallback: if no fragments were kept, always re-check the original triangle
-            // This handles cases where:
-            // 1. Splitting failed or didn't actually split (fragments.len() == 1)
-            // 2. All fragments were incorrectly classified as Inside
-            // 3. Classification of fragments was wrong but original triangle should be kept
-            if kept_fragments == 0 {
-                // Re-check the original triangle - classification might have been wrong
-                let classification = classify_face_fragment(&face_vertices, a);
-                let should_keep = match classification {
-                    Classification::Outside => true,
-                    Classification::OnBoundary => true, // Keep boundary triangles
-                    Classification::Inside => false,
-                };
-                if should_keep {
-                    let i0 = result.add_vertex(*v0);
-                    let i1 = result.add_vertex(*v1);
-                    let i2 = result.add_vertex(*v2);
-                    result.add_triangle(Triangle::new([i0, i1, i2]));
-                    triangle_mesh_source.push(1); // From mesh B
-                }
+            // Triangle has intersections - split it and keep the fragments outside A
+            for fragment in union_fragments(tri, b, &solid_a, intersection_list, false, config) {
+                let i0 = result.add_vertex(fragment.vertices[0]);
+                let i1 = result.add_vertex(fragment.vertices[1]);
+                let i2 = result.add_vertex(fragment.vertices[2]);
+                result.add_triangle(Triangle::new([i0, i1, i2]));
+                triangle_mesh_source.push(1); // From mesh B
             }
         } else {
             // Non-intersecting triangle - use robust classification
-            let classification = classify_face_fragment(&face_vertices, a);
+            let classification = classify_face_fragment(&face_vertices, &solid_a);
             // For union, keep Outside and OnBoundary triangles
             let should_keep = match classification {
                 Classification::Outside => true,
                 Classification::OnBoundary => true, // Keep boundary triangles
-                Classification::Inside => false,
+                Classification::Inside => config.keep_internal_faces,
             };
             if should_keep {
                 let i0 = result.add_vertex(*v0);
@@ -525,63 +357,106 @@ pub fn robust_union_core(a: &Mesh, b: &Mesh) -> Result<Mesh> {
     // This fixes artifacts from duplicate vertices, overlapping triangles, and z-fighting
     const WELD_EPSILON: f64 = 1e-6;
     
-    // Step 1: Weld vertices (remove duplicate vertices at intersection points)
-    result.weld_vertices(WELD_EPSILON);
-    
-    // Step 2: Remove exact duplicate triangles (same indices)
-    // Note: This only removes exact duplicates, not coplanar overlaps
-    // TEMPORARILY DISABLED to debug missing faces
-    // TODO: Re-enable and check if it's removing legitimate triangles
-    // result.remove_duplicate_triangles();
-    
-    // Step 3: Remove coplanar overlapping triangles with z-fighting prevention
-    // Use mesh source tracking to prefer mesh A for coplanar boundaries
-    // Only removes triangles with significant overlap (>= 50% of smaller triangle)
-    // This prevents removing triangles that just touch at edges
-    // Sync triangle_mesh_source length with result.triangles length
-    if triangle_mesh_source.len() != result.triangles.len() {
-        // Adjust triangle_mesh_source to match current triangle count
-        triangle_mesh_source.truncate(result.triangles.len());
-        while triangle_mesh_source.len() < result.triangles.len() {
-            triangle_mesh_source.push(0); // Default to mesh A for any new triangles
+    // Debug output keeps every fragment exactly as classified, so skip the merging steps
+    if config.keep_internal_faces {
+        result.recompute_normals();
+        return Ok(result);
+    }
+
+    // Step 1: Drop any triangle the splitting left pointing at a missing vertex
+    if let Err(e) = result.check_triangle_indices() {
+        eprintln!("Warning: robust union produced malformed triangles, skipping them: {}", e);
+        let vertex_count = result.vertices.len();
+        let mut kept_sources = Vec::with_capacity(result.triangles.len());
+        for (triangle, &source) in result.triangles.iter().zip(&triangle_mesh_source) {
+            if triangle.indices.iter().all(|&i| i < vertex_count) {
+                kept_sources.push(source);
+            }
         }
+        result.remove_invalid_triangles();
+        triangle_mesh_source = kept_sources;
     }
-    
-    // Now remove coplanar duplicates
-    // TEMPORARILY DISABLED to debug artifacts
-    // TODO: Re-enable with better overlap detection
-    // if triangle_mesh_source.len() == result.triangles.len() {
-    //     result.remove_coplanar_duplicates(&triangle_mesh_source, None);
-    // }
-    
-    // Step 4: Remove orphaned vertices (vertices not referenced by any triangle)
-    result.remove_orphaned_vertices();
-    
-    // Step 5: Recompute normals after all cleanup (vertices may have changed)
+
+    // Step 2: Weld, then drop coplanar overlaps (preferring mesh A), exact duplicates and
+    // orphaned vertices
+    let cleanup = CleanupOptions {
+        weld_epsilon: WELD_EPSILON,
+        ..CleanupOptions::default()
+    };
+    result.cleanup_with_sources(&cleanup, &triangle_mesh_source);
+
+    // Step 3: Rebuild through half-edges so fragments split from either side share one
+    // winding, dropping collapsed triangles and unreferenced vertices
+    let mut result = super::mesh_reconstruction::reconstruct_oriented_mesh(&result);
+
+    // Step 4: Recompute normals after all cleanup (vertices may have changed)
     result.recompute_normals();
     
     Ok(result)
 }
 
-/// Deduplicate intersection points within epsilon distance
-fn deduplicate_intersection_points(points: &[Point3<f64>]) -> Vec<Point3<f64>> {
-    const EPS: f64 = 1e-9;
-    let mut result: Vec<Point3<f64>> = Vec::new();
-    
-    for &point in points {
-        let mut is_duplicate = false;
-        for &existing in &result {
-            if (point - existing).norm() < EPS {
-                is_duplicate = true;
-                break;
+/// Split a triangle of `mesh` against the triangles of `other` it intersects, returning the
+/// fragments a union keeps
+///
+/// The triangle is cut along the plane of each crossing triangle and along the edges of each
+/// coplanar one, near that triangle only, so no fragment straddles the other surface and its
+/// centroid tells where it lies. A fragment lying on the other surface survives only where
+/// both surfaces face the same way (opposed faces meet inside the union), and only if
+/// `keep_shared` is set, so a shared face is emitted once.
+fn union_fragments(
+    tri: &Triangle,
+    mesh: &Mesh,
+    other: &Solid,
+    intersections: &[(usize, IntersectionResult)],
+    keep_shared: bool,
+    config: &CsgConfig,
+) -> Vec<TriangleFragment> {
+    let corners = |m: &Mesh, t: &Triangle| t.indices.map(|i| m.vertices[i].position);
+    let face = corners(mesh, tri);
+    let face_normal = (face[1] - face[0]).cross(&(face[2] - face[0]));
+
+    let mut cuts = Vec::new();
+    for (other_idx, intersection) in intersections {
+        let source = corners(other.mesh(), &other.mesh().triangles[*other_idx]);
+        let [p0, p1, p2] = source;
+        // Zero-area triangles (e.g. at sphere poles) have no plane, and a NaN cut drops every piece
+        let Some(normal) = (p1 - p0).cross(&(p2 - p0)).try_normalize(1e-12) else {
+            continue;
+        };
+        match intersection.intersection_type {
+            IntersectionType::Coplanar => {
+                for (from, to) in [(p0, p1), (p1, p2), (p2, p0)] {
+                    let edge_normal = normal.cross(&(to - from)).normalize();
+                    let d = edge_normal.dot(&from.coords);
+                    cuts.push(PlaneCut { normal: edge_normal, d, source });
+                }
             }
-        }
-        if !is_duplicate {
-            result.push(point);
+            IntersectionType::Point | IntersectionType::Segment => {
+                let d = normal.dot(&p0.coords);
+                cuts.push(PlaneCut { normal, d, source });
+            }
+            IntersectionType::None => {}
         }
     }
-    
-    result
+
+    split_triangle_by_planes(tri, &mesh.vertices, &cuts)
+        .fragments
+        .into_iter()
+        .filter(|fragment| {
+            let points = fragment.vertices.map(|v| v.position);
+            match classify_split_fragment(&points, other) {
+                Classification::Outside => true,
+                Classification::Inside => config.keep_internal_faces,
+                Classification::OnBoundary => {
+                    let centroid =
+                        Point3::from((points[0].coords + points[1].coords + points[2].coords) / 3.0);
+                    keep_shared
+                        && boundary_normal(&centroid, other)
+                            .is_some_and(|normal| normal.dot(&face_normal) > 0.0)
+                }
+            }
+        })
+        .collect()
 }
 
 /// Find the normal of the nearest surface to a point (within epsilon distance)
@@ -808,10 +683,18 @@ fn mesh_difference_winding(a: &Mesh, b: &Mesh) -> Result<Mesh> {
     Ok(result)
 }
 
+/// Direction of the parity rays used by the point-in-solid tests
+///
+/// An axis-aligned ray from a point at the center of an axis-aligned box passes exactly through
+/// the diagonal each face quad is split along. Both triangles of the face report the hit, the
+/// crossing count comes out even, and the point is classified as outside. Tilting the ray
+/// slightly off the axes keeps it clear of edges and vertices on the grid-aligned geometry most
+/// models are built from.
+pub(crate) const PARITY_RAY: Vector3<f64> = Vector3::new(1.0, 0.0137, 0.0071);
+
 /// Check if a point is inside a mesh using ray casting
-fn is_point_inside_mesh(point: &Point3<f64>, mesh: &Mesh) -> bool {
-    // Ray casting algorithm: cast ray in +X direction and count intersections
-    let ray_dir = Vector3::new(1.0, 0.0, 0.0);
+pub(crate) fn is_point_inside_mesh(point: &Point3<f64>, mesh: &Mesh) -> bool {
+    // Ray casting algorithm: cast a ray along PARITY_RAY and count intersections
     let mut intersection_count = 0;
 
     for tri in &mesh.triangles {
@@ -819,7 +702,7 @@ fn is_point_inside_mesh(point: &Point3<f64>, mesh: &Mesh) -> bool {
         let v1 = &mesh.vertices[tri.indices[1]].position;
         let v2 = &mesh.vertices[tri.indices[2]].position;
 
-        if ray_intersects_triangle(point, &ray_dir, v0, v1, v2) {
+        if ray_intersects_triangle(point, &PARITY_RAY, v0, v1, v2) {
             intersection_count += 1;
         }
     }
@@ -836,6 +719,20 @@ fn ray_intersects_triangle(
     v1: &Point3<f64>,
     v2: &Point3<f64>,
 ) -> bool {
+    ray_triangle_hit(origin, direction, v0, v1, v2).is_some()
+}
+
+/// Möller-Trumbore ray/triangle test, returning `(t, u, v)` for a forward hit
+///
+/// The hit point is `origin + t * direction`, and `(u, v)` are the barycentric weights of
+/// `v1` and `v2`. Hits on an edge count for both triangles sharing it.
+pub(crate) fn ray_triangle_hit(
+    origin: &Point3<f64>,
+    direction: &Vector3<f64>,
+    v0: &Point3<f64>,
+    v1: &Point3<f64>,
+    v2: &Point3<f64>,
+) -> Option<(f64, f64, f64)> {
     const EPSILON: f64 = 0.000001;
 
     let edge1 = v1 - v0;
@@ -844,7 +741,7 @@ fn ray_intersects_triangle(
     let a = edge1.dot(&h);
 
     if a > -EPSILON && a < EPSILON {
-        return false; // Ray parallel to triangle
+        return None; // Ray parallel to triangle
     }
 
     let f = 1.0 / a;
@@ -852,18 +749,18 @@ fn ray_intersects_triangle(
     let u = f * s.dot(&h);
 
     if !(0.0..=1.0).contains(&u) {
-        return false;
+        return None;
     }
 
     let q = s.cross(&edge1);
     let v = f * direction.dot(&q);
 
     if v < 0.0 || u + v > 1.0 {
-        return false;
+        return None;
     }
 
     let t = f * edge2.dot(&q);
-    t > EPSILON // Only count forward intersections
+    (t > EPSILON).then_some((t, u, v)) // Only count forward intersections
 }
 
 /// Perform robust CSG intersection
@@ -921,6 +818,28 @@ mod tests {
     use crate::geometry::Primitive;
     use nalgebra::Vector3;
 
+    #[test]
+    fn test_point_inside_mesh_ray_misses_face_diagonals() {
+        // The center of a centered cube lines up with every face diagonal along the axes
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+
+        assert!(is_point_inside_mesh(&Point3::origin(), &cube));
+        assert!(is_point_inside_mesh(&Point3::new(0.0, 0.0, 2.5), &cube));
+        assert!(!is_point_inside_mesh(&Point3::new(-10.0, 0.0, 0.0), &cube));
+        assert!(!is_point_inside_mesh(&Point3::new(0.0, 10.0, 0.0), &cube));
+    }
+
+    #[test]
+    fn test_union_of_disjoint_cubes_in_a_row_keeps_every_face() {
+        // Fragment centroids of each cube line up with the other cube's face diagonals along +X
+        let mesh_a = Primitive::cube(Vector3::new(5.0, 5.0, 5.0), false).to_mesh();
+        let mut mesh_b = mesh_a.clone();
+        mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(10.0, 0.0, 0.0)));
+
+        let mesh = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert_eq!(mesh.triangle_count(), 24);
+    }
+
     #[test]
     fn test_robust_union() {
         let mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
@@ -932,6 +851,58 @@ mod tests {
         assert!(mesh.vertex_count() > 0);
     }
 
+    #[test]
+    fn test_union_of_overlapping_cubes_winds_outward() {
+        let mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mut mesh_b = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(5.0, 3.0, 2.0)));
+
+        let mesh = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert!(crate::geometry::validate_winding_order(&mesh));
+        let validation = crate::geometry::validate_mesh(&mesh);
+        assert!(validation.is_closed && validation.is_manifold);
+
+        // Consistent winding runs every shared edge once in each direction
+        let mut directed_edges = std::collections::HashSet::new();
+        for t in &mesh.triangles {
+            for k in 0..3 {
+                let edge = (t.indices[k], t.indices[(k + 1) % 3]);
+                assert!(directed_edges.insert(edge), "edge {:?} wound twice", edge);
+            }
+        }
+
+        // Outward winding encloses the union's volume with a positive sign
+        let signed_volume = crate::geometry::signed_volume(&mesh);
+        assert!((signed_volume - (2000.0 - 5.0 * 7.0 * 8.0)).abs() < 1e-6, "{}", signed_volume);
+    }
+
+    #[test]
+    fn test_union_ignores_zero_area_triangles_at_sphere_poles() {
+        // A pole triangle of B crosses a face of A; cutting along it used to drop the whole face
+        let mut mesh_a = Primitive::sphere(3.53, 16).to_mesh();
+        mesh_a.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(-1.79, 3.28, -1.72)));
+        let mut mesh_b = Primitive::sphere(6.11, 7).to_mesh();
+        mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(-1.32, 0.49, 1.91)));
+
+        let mesh = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert!(crate::geometry::validate_mesh(&mesh).is_closed);
+    }
+
+    #[test]
+    fn test_union_keeps_vertex_colors() {
+        const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+        let mut mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mut mesh_b = mesh_a.clone();
+        mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(5.0, 3.0, 2.0)));
+        for vertex in mesh_a.vertices.iter_mut().chain(mesh_b.vertices.iter_mut()) {
+            vertex.color = Some(RED);
+        }
+
+        // Split points, T-junction centers and the rebuilt mesh all carry the color over
+        let mesh = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert!(mesh.vertices.iter().all(|v| v.color == Some(RED)));
+    }
+
     #[test]
     fn test_curved_surface_detection() {
         let sphere = Primitive::sphere(10.0, 16).to_mesh();
@@ -940,4 +911,53 @@ mod tests {
         let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
         assert!(!has_curved_surfaces(&cube));
     }
+
+    #[test]
+    fn test_keep_internal_faces() {
+        let mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mut mesh_b = Primitive::cube(Vector3::new(4.0, 4.0, 4.0), false).to_mesh();
+        // B straddles A's x = 10 face, so its x = 8 face is buried inside A
+        mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(8.0, 3.0, 2.5)));
+
+        // Triangles lying on B's x = 8 face, which is buried inside A
+        let internal_count = |mesh: &Mesh| {
+            mesh.triangles
+                .iter()
+                .filter(|t| {
+                    t.indices
+                        .iter()
+                        .all(|&i| (mesh.vertices[i].position.x - 8.0).abs() < 1e-9)
+                })
+                .count()
+        };
+
+        let filtered = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert_eq!(internal_count(&filtered), 0);
+
+        let config = CsgConfig {
+            keep_internal_faces: true,
+        };
+        let raw = robust_union_core_with_config(&mesh_a, &mesh_b, &config).unwrap();
+        assert_eq!(internal_count(&raw), 2);
+        // B's x = 8 face, the strips of its sides inside A and the patch of A's x = 10 face
+        // inside B: 4 x 4 + 4 x (2 x 4) + 4 x 4
+        let area = |mesh: &Mesh| crate::geometry::analyze(mesh).surface_area;
+        assert!((area(&raw) - area(&filtered) - 64.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_union_of_identical_cubes_has_no_duplicates() {
+        let mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let mesh_b = mesh_a.clone();
+
+        let result = robust_union_core(&mesh_a, &mesh_b).unwrap();
+        assert_eq!(result.triangle_count(), mesh_a.triangle_count());
+
+        let mut seen = std::collections::HashSet::new();
+        for triangle in &result.triangles {
+            let mut key = triangle.indices;
+            key.sort_unstable();
+            assert!(seen.insert(key), "duplicate triangle {:?}", triangle.indices);
+        }
+    }
 }
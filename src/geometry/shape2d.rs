@@ -0,0 +1,599 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Planar shapes for 2D operations such as `offset()`
+//!
+//! A [`Shape2D`] is a set of simple closed contours in the XY plane. Outer boundaries wind
+//! counterclockwise and holes clockwise, so material is always on the left of each edge.
+//! Contours are kept side by side rather than merged, so overlapping children of a 2D
+//! operation stay overlapping.
+
+use super::slice;
+use super::{facet_count, BoundingBox, Mesh, Triangle, Vertex, DEFAULT_FA, DEFAULT_FS};
+use nalgebra::{Matrix4, Point2, Point3, Vector2, Vector3};
+use std::f64::consts::PI;
+
+/// Lengths and areas below this are treated as zero
+const EPSILON: f64 = 1e-9;
+
+/// z component of the cross product of two planar vectors
+fn cross(a: Vector2<f64>, b: Vector2<f64>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Signed area of a closed contour, positive when counterclockwise
+fn signed_area(contour: &[Point2<f64>]) -> f64 {
+    let n = contour.len();
+    (0..n)
+        .map(|i| cross(contour[i].coords, contour[(i + 1) % n].coords))
+        .sum::<f64>()
+        / 2.0
+}
+
+/// How offset edges are joined where they separate
+#[derive(Debug, Clone, Copy)]
+enum Join {
+    /// Arc around the original corner, with this many fragments per full turn
+    Round(u32),
+    /// Straight cut between the displaced edge ends
+    Chamfer,
+    /// Extend both edges until they meet
+    Miter,
+}
+
+/// Collection of closed planar contours
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Shape2D {
+    pub contours: Vec<Vec<Point2<f64>>>,
+}
+
+impl Shape2D {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rectangle with a corner at the origin, or centered on it
+    ///
+    /// Non-positive sizes produce a warning and an empty shape, as for
+    /// [`Primitive`](super::Primitive).
+    pub fn square(size: Vector2<f64>, center: bool) -> Self {
+        if size.iter().any(|&v| v <= 0.0 || !v.is_finite()) {
+            eprintln!(
+                "Warning: square(size=[{}, {}]) must have positive dimensions",
+                size.x, size.y
+            );
+            return Self::new();
+        }
+
+        let min = if center {
+            -size / 2.0
+        } else {
+            Vector2::zeros()
+        };
+        let max = min + size;
+        Self {
+            contours: vec![vec![
+                Point2::new(min.x, min.y),
+                Point2::new(max.x, min.y),
+                Point2::new(max.x, max.y),
+                Point2::new(min.x, max.y),
+            ]],
+        }
+    }
+
+    /// Regular polygon approximating a circle, starting at `(r, 0)` like OpenSCAD
+    pub fn circle(r: f64, fn_: u32) -> Self {
+        if r <= 0.0 || !r.is_finite() {
+            eprintln!("Warning: circle(r={}) must have a positive radius", r);
+            return Self::new();
+        }
+
+        let segments = facet_count(r, fn_ as f64, DEFAULT_FA, DEFAULT_FS);
+        let contour = (0..segments)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / segments as f64;
+                Point2::new(r * angle.cos(), r * angle.sin())
+            })
+            .collect();
+        Self {
+            contours: vec![contour],
+        }
+    }
+
+    /// Simple polygon through `points` in order, as drawn by `polygon()`
+    ///
+    /// Either winding is accepted. Fewer than three points, or points enclosing no area,
+    /// produce a warning and an empty shape.
+    pub fn polygon(points: &[Vector2<f64>]) -> Self {
+        let mut contour: Vec<Point2<f64>> = points.iter().map(|&p| Point2::from(p)).collect();
+        let area = signed_area(&contour);
+        if contour.len() < 3 || area.abs() <= EPSILON || area.is_nan() {
+            eprintln!("Warning: polygon() needs at least three points enclosing an area");
+            return Self::new();
+        }
+
+        if area < 0.0 {
+            contour.reverse();
+        }
+        Self {
+            contours: vec![contour],
+        }
+    }
+
+    /// Outline of `mesh` seen from +z, as drawn by `projection()`
+    pub fn projection(mesh: &Mesh) -> Self {
+        let outline = slice::silhouette(mesh);
+        if outline.open > 0 {
+            eprintln!(
+                "Warning: projection() dropped {} open outline(s); the mesh is not closed",
+                outline.open
+            );
+        }
+        Self {
+            contours: outline.loops,
+        }
+    }
+
+    /// Section of `mesh` through the z=0 plane, as drawn by `projection(cut=true)`
+    pub fn cross_section(mesh: &Mesh) -> Self {
+        Self {
+            contours: mesh.slice(0.0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+
+    /// Add the contours of `other` alongside these
+    pub fn extend(&mut self, other: Shape2D) {
+        self.contours.extend(other.contours);
+    }
+
+    /// Enclosed area, with holes subtracted
+    pub fn area(&self) -> f64 {
+        self.contours.iter().map(|c| signed_area(c)).sum()
+    }
+
+    /// Bounds of all contours, flat on z=0
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bbox = BoundingBox::empty();
+        for p in self.contours.iter().flatten() {
+            bbox.expand_to_include(&Point3::new(p.x, p.y, 0.0));
+        }
+        bbox
+    }
+
+    /// Apply the XY part of `matrix`, keeping outer contours counterclockwise
+    pub fn transform(&mut self, matrix: &Matrix4<f64>) {
+        let flips = matrix[(0, 0)] * matrix[(1, 1)] - matrix[(0, 1)] * matrix[(1, 0)] < 0.0;
+        for contour in &mut self.contours {
+            for p in contour.iter_mut() {
+                let moved = matrix.transform_point(&Point3::new(p.x, p.y, 0.0));
+                *p = Point2::new(moved.x, moved.y);
+            }
+            if flips {
+                contour.reverse();
+            }
+        }
+    }
+
+    /// Move every edge `r` outward (inward when negative), rounding the corners that open up
+    ///
+    /// `fn_` is the fragment count for a full turn, resolved from `r` as for [`Shape2D::circle`].
+    pub fn offset_round(&self, r: f64, fn_: u32) -> Self {
+        let fragments = facet_count(r.abs(), fn_ as f64, DEFAULT_FA, DEFAULT_FS);
+        self.offset(r, Join::Round(fragments))
+    }
+
+    /// Move every edge `delta` outward (inward when negative), keeping corners sharp or,
+    /// with `chamfer`, cutting them off
+    pub fn offset_delta(&self, delta: f64, chamfer: bool) -> Self {
+        self.offset(delta, if chamfer { Join::Chamfer } else { Join::Miter })
+    }
+
+    fn offset(&self, distance: f64, join: Join) -> Self {
+        if distance == 0.0 {
+            return self.clone();
+        }
+        Self {
+            contours: self
+                .contours
+                .iter()
+                .filter_map(|c| offset_contour(c, distance, join))
+                .collect(),
+        }
+    }
+
+    /// Flat mesh of the shape on z=0, facing +z, with holes cut out
+    pub fn to_mesh(&self) -> Mesh {
+        let points: Vec<Point2<f64>> = self.contours.iter().flatten().copied().collect();
+        let mut mesh = Mesh::with_capacity(points.len(), points.len());
+        for p in &points {
+            mesh.add_vertex(Vertex::new(Point3::new(p.x, p.y, 0.0), Vector3::z()));
+        }
+
+        // Contours as index rings into `points`, split into outer boundaries and holes
+        let mut rings = Vec::new();
+        let mut start = 0;
+        for contour in &self.contours {
+            rings.push((start..start + contour.len()).collect::<Vec<usize>>());
+            start += contour.len();
+        }
+        let (outers, holes): (Vec<_>, Vec<_>) = rings
+            .into_iter()
+            .filter(|ring| ring.len() >= 3)
+            .partition(|ring| ring_area(ring, &points) > 0.0);
+
+        // Each hole belongs to the smallest outer boundary containing it
+        let mut outer_holes: Vec<Vec<Vec<usize>>> = vec![Vec::new(); outers.len()];
+        for hole in holes {
+            let probe = points[hole[0]];
+            let owner = outers
+                .iter()
+                .enumerate()
+                .filter(|(_, outer)| point_in_ring(probe, outer, &points))
+                .min_by(|(_, a), (_, b)| ring_area(a, &points).total_cmp(&ring_area(b, &points)))
+                .map(|(i, _)| i);
+            if let Some(i) = owner {
+                outer_holes[i].push(hole);
+            }
+        }
+
+        for (outer, holes) in outers.into_iter().zip(outer_holes) {
+            let ring = bridge_holes(outer, holes, &points);
+            for triangle in ear_clip(ring, &points) {
+                mesh.add_triangle(Triangle::new(triangle));
+            }
+        }
+
+        mesh
+    }
+}
+
+/// One edge of a contour being offset
+#[derive(Clone, Copy)]
+struct OffsetEdge {
+    /// Original endpoints
+    start: Point2<f64>,
+    end: Point2<f64>,
+    dir: Vector2<f64>,
+    /// Unit normal pointing away from the material
+    normal: Vector2<f64>,
+}
+
+/// Offset one contour, or `None` if it shrinks away entirely
+///
+/// Edges that would flip direction after offsetting (e.g. the short sides of a thin rectangle
+/// shrunk past its half-width) are removed one at a time, and their neighbors extended to
+/// meet, so large negative offsets don't leave self-intersecting loops behind.
+fn offset_contour(contour: &[Point2<f64>], distance: f64, join: Join) -> Option<Vec<Point2<f64>>> {
+    let n = contour.len();
+    let mut edges: Vec<OffsetEdge> = (0..n)
+        .filter_map(|i| {
+            let (start, end) = (contour[i], contour[(i + 1) % n]);
+            let edge = end - start;
+            let length = edge.norm();
+            (length > EPSILON).then(|| {
+                let dir = edge / length;
+                OffsetEdge {
+                    start,
+                    end,
+                    dir,
+                    normal: Vector2::new(dir.y, -dir.x),
+                }
+            })
+        })
+        .collect();
+
+    let corners = loop {
+        if edges.len() < 3 {
+            return None;
+        }
+
+        let corners: Vec<Vec<Point2<f64>>> = (0..edges.len())
+            .map(|i| offset_corner(&edges[i], &edges[(i + 1) % edges.len()], distance, join))
+            .collect();
+
+        // How far each displaced edge runs along its original direction
+        let m = edges.len();
+        let worst = (0..m)
+            .map(|i| {
+                let from = *corners[(i + m - 1) % m].last().unwrap();
+                let to = corners[i][0];
+                (i, (to - from).dot(&edges[i].dir))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match worst {
+            Some((i, run)) if run < -EPSILON => {
+                edges.remove(i);
+            }
+            _ => break corners,
+        }
+    };
+
+    let mut result: Vec<Point2<f64>> = Vec::new();
+    for p in corners.into_iter().flatten() {
+        if result.last().is_none_or(|last| (p - last).norm() > EPSILON) {
+            result.push(p);
+        }
+    }
+    while result.len() > 1 && (result[0] - result[result.len() - 1]).norm() <= EPSILON {
+        result.pop();
+    }
+
+    // A contour that turned inside out has collapsed
+    let (before, after) = (signed_area(contour), signed_area(&result));
+    (result.len() >= 3 && after.abs() > EPSILON && before.signum() == after.signum())
+        .then_some(result)
+}
+
+/// Points where the displaced edge `a` hands over to the displaced edge `b`
+fn offset_corner(a: &OffsetEdge, b: &OffsetEdge, distance: f64, join: Join) -> Vec<Point2<f64>> {
+    let turn = cross(a.dir, b.dir);
+    let a_end = a.end + a.normal * distance;
+    let b_start = b.start + b.normal * distance;
+
+    // Edges that were adjacent separate at corners turning away from the offset side
+    let adjacent = a.end == b.start;
+    let opens = adjacent && turn * distance > EPSILON;
+
+    match join {
+        Join::Round(fragments) if opens => {
+            let sweep = turn.atan2(a.dir.dot(&b.dir));
+            let steps = ((fragments as f64 * sweep.abs() / (2.0 * PI)).ceil() as usize).max(1);
+            let mut points = vec![a_end];
+            for k in 1..steps {
+                let (sin, cos) = (sweep * k as f64 / steps as f64).sin_cos();
+                let normal = Vector2::new(
+                    a.normal.x * cos - a.normal.y * sin,
+                    a.normal.x * sin + a.normal.y * cos,
+                );
+                points.push(a.end + normal * distance);
+            }
+            points.push(b_start);
+            points
+        }
+        Join::Chamfer if opens => vec![a_end, b_start],
+        _ => {
+            // Intersect the displaced lines; nearly parallel edges just continue
+            let a_origin = a.start + a.normal * distance;
+            if turn.abs() <= EPSILON {
+                return if adjacent {
+                    vec![a_end]
+                } else {
+                    vec![a_end, b_start]
+                };
+            }
+            let t = cross(b_start - a_origin, b.dir) / turn;
+            vec![a_origin + a.dir * t]
+        }
+    }
+}
+
+fn ring_area(ring: &[usize], points: &[Point2<f64>]) -> f64 {
+    let contour: Vec<Point2<f64>> = ring.iter().map(|&i| points[i]).collect();
+    signed_area(&contour)
+}
+
+/// Even-odd point-in-polygon test
+fn point_in_ring(p: Point2<f64>, ring: &[usize], points: &[Point2<f64>]) -> bool {
+    let mut inside = false;
+    for i in 0..ring.len() {
+        let (a, b) = (points[ring[i]], points[ring[(i + 1) % ring.len()]]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Whether segments `p1-p2` and `q1-q2` cross at a point interior to both
+fn segments_cross(p1: Point2<f64>, p2: Point2<f64>, q1: Point2<f64>, q2: Point2<f64>) -> bool {
+    let d1 = cross(p2 - p1, q1 - p1);
+    let d2 = cross(p2 - p1, q2 - p1);
+    let d3 = cross(q2 - q1, p1 - q1);
+    let d4 = cross(q2 - q1, p2 - q1);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Splice holes into `outer` through zero-width bridges, giving one ring to ear-clip
+fn bridge_holes(
+    mut outer: Vec<usize>,
+    mut holes: Vec<Vec<usize>>,
+    points: &[Point2<f64>],
+) -> Vec<usize> {
+    // Rightmost holes first, so each bridge can reach the boundary built so far
+    let max_x = |ring: &Vec<usize>| {
+        ring.iter()
+            .map(|&i| points[i].x)
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+
+    for (h, hole) in holes.iter().enumerate() {
+        let m = (0..hole.len())
+            .max_by(|&a, &b| points[hole[a]].x.total_cmp(&points[hole[b]].x))
+            .unwrap();
+        let from = points[hole[m]];
+
+        // Closest outer vertex whose bridge crosses no edge
+        let blocked = |to: Point2<f64>| {
+            std::iter::once(&outer)
+                .chain(&holes[h..])
+                .flat_map(|ring| {
+                    (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()]))
+                })
+                .any(|(a, b)| segments_cross(from, to, points[a], points[b]))
+        };
+        let mut candidates: Vec<usize> = (0..outer.len()).collect();
+        candidates.sort_by(|&a, &b| {
+            (points[outer[a]] - from)
+                .norm()
+                .total_cmp(&(points[outer[b]] - from).norm())
+        });
+        let Some(v) = candidates.into_iter().find(|&v| !blocked(points[outer[v]])) else {
+            continue;
+        };
+
+        let mut spliced = outer[..=v].to_vec();
+        spliced.extend(hole[m..].iter().chain(&hole[..=m]));
+        spliced.extend(&outer[v..]);
+        outer = spliced;
+    }
+
+    outer
+}
+
+/// Triangulate a counterclockwise ring by ear clipping
+pub(super) fn ear_clip(mut ring: Vec<usize>, points: &[Point2<f64>]) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::new();
+
+    while ring.len() > 3 {
+        let n = ring.len();
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (
+                points[ring[(i + n - 1) % n]],
+                points[ring[i]],
+                points[ring[(i + 1) % n]],
+            );
+            if cross(b - a, c - b) <= EPSILON {
+                return false;
+            }
+            // Bridge endpoints repeat positions, so compare by position rather than index
+            !ring.iter().any(|&j| {
+                let p = points[j];
+                p != a
+                    && p != b
+                    && p != c
+                    && cross(b - a, p - a) >= 0.0
+                    && cross(c - b, p - b) >= 0.0
+                    && cross(a - c, p - c) >= 0.0
+            })
+        });
+
+        match ear {
+            Some(i) => {
+                triangles.push([ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]);
+                ring.remove(i);
+            }
+            None => {
+                // Only degenerate (collinear) corners remain to be dropped, or nothing can be
+                let flat = (0..n).find(|&i| {
+                    let (a, b, c) = (
+                        points[ring[(i + n - 1) % n]],
+                        points[ring[i]],
+                        points[ring[(i + 1) % n]],
+                    );
+                    cross(b - a, c - b).abs() <= EPSILON
+                });
+                match flat {
+                    Some(i) => {
+                        ring.remove(i);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if ring.len() == 3 && ring_area(&ring, points) > EPSILON {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(shape: &Shape2D) -> Vector2<f64> {
+        let size = shape.bounding_box().size();
+        Vector2::new(size.x, size.y)
+    }
+
+    #[test]
+    fn test_polygon_winds_counterclockwise() {
+        let clockwise = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(0.0, 4.0),
+            Vector2::new(3.0, 0.0),
+        ];
+        let triangle = Shape2D::polygon(&clockwise);
+        assert!((triangle.area() - 6.0).abs() < 1e-12);
+        assert_eq!(triangle.to_mesh().triangle_count(), 1);
+
+        assert!(Shape2D::polygon(&clockwise[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_offset_square_outward() {
+        let square = Shape2D::square(Vector2::new(10.0, 10.0), false);
+
+        let mitered = square.offset_delta(2.0, false);
+        assert_eq!(mitered.contours[0].len(), 4);
+        assert_eq!(size(&mitered), Vector2::new(14.0, 14.0));
+        assert!((mitered.area() - 196.0).abs() < 1e-9);
+
+        // Chamfers cut each corner with a 45 degree edge
+        let chamfered = square.offset_delta(2.0, true);
+        assert_eq!(chamfered.contours[0].len(), 8);
+        assert!((chamfered.area() - (196.0 - 4.0 * 2.0)).abs() < 1e-9);
+
+        // Rounded corners stay within the mitered outline but keep the extents
+        let rounded = square.offset_round(2.0, 32);
+        assert!((size(&rounded) - Vector2::new(14.0, 14.0)).norm() < 1e-9);
+        let exact = 100.0 + 4.0 * 20.0 + PI * 4.0;
+        assert!(rounded.area() < exact && rounded.area() > exact - 1.0);
+    }
+
+    #[test]
+    fn test_offset_inward_collapses_cleanly() {
+        let bar = Shape2D::square(Vector2::new(10.0, 2.0), false);
+        assert_eq!(size(&bar.offset_delta(-0.5, false)), Vector2::new(9.0, 1.0));
+        assert!(bar.offset_delta(-1.5, false).is_empty());
+        assert!(bar.offset_round(-1.5, 16).is_empty());
+
+        // The notch of an L closes up without leaving a self-intersecting loop
+        let l_shape = Shape2D {
+            contours: vec![vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(6.0, 0.0),
+                Point2::new(6.0, 2.0),
+                Point2::new(2.0, 2.0),
+                Point2::new(2.0, 6.0),
+                Point2::new(0.0, 6.0),
+            ]],
+        };
+        let thinner = l_shape.offset_delta(-0.5, false);
+        assert_eq!(thinner.contours[0].len(), 6);
+        assert!((thinner.area() - (20.0 - 0.5 * 24.0 + 0.25 * 4.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_mesh_cuts_holes() {
+        let mut frame = Shape2D::square(Vector2::new(10.0, 10.0), true);
+        let mut hole = Shape2D::square(Vector2::new(4.0, 4.0), true)
+            .contours
+            .remove(0);
+        hole.reverse();
+        frame.contours.push(hole);
+        assert!((frame.area() - 84.0).abs() < 1e-9);
+
+        let mesh = frame.to_mesh();
+        let area: f64 = mesh
+            .triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.indices.map(|i| mesh.vertices[i].position);
+                (b - a).cross(&(c - a)).z / 2.0
+            })
+            .sum();
+        assert!((area - 84.0).abs() < 1e-9);
+        assert!(mesh.triangles.iter().all(|t| {
+            let [a, b, c] = t.indices.map(|i| mesh.vertices[i].position);
+            (b - a).cross(&(c - a)).z > 0.0
+        }));
+    }
+}
@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Planar cross-sections and silhouettes of meshes
+//!
+//! Both produce directed boundary segments which are stitched into loops. Loops wind like
+//! [`Shape2D`](super::Shape2D) contours: counterclockwise around material, seen from +z.
+
+use super::triangle_splitting::edge_plane_intersection;
+use super::Mesh;
+use crate::utils::PointHasher;
+use nalgebra::{Point2, Point3, Vector3};
+use std::collections::HashMap;
+
+/// Distance within which segment endpoints are treated as the same point
+const WELD_EPSILON: f64 = 1e-9;
+
+/// Closed loops, plus the number of chains that could not be closed
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Contours {
+    pub loops: Vec<Vec<Point2<f64>>>,
+    pub open: usize,
+}
+
+/// Intersect `mesh` with the plane at height `z`
+///
+/// Vertices on the plane (within [`WELD_EPSILON`]) count as above it, so faces lying in the
+/// plane contribute nothing, every crossing is counted once, and seam vertices duplicated
+/// with rounding noise are classified alike.
+pub(crate) fn cross_section(mesh: &Mesh, z: f64) -> Contours {
+    let normal = Vector3::z();
+    let mut points = PointHasher::new(WELD_EPSILON);
+    let mut segments = Vec::new();
+
+    let snap = |p: Point3<f64>| {
+        if (p.z - z).abs() < WELD_EPSILON {
+            Point3::new(p.x, p.y, z)
+        } else {
+            p
+        }
+    };
+
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.indices.map(|i| snap(mesh.vertices[i].position));
+        let crossings: Vec<Point3<f64>> = [(a, b), (b, c), (c, a)]
+            .into_iter()
+            .filter(|(p, q)| (p.z >= z) != (q.z >= z))
+            .filter_map(|(p, q)| edge_plane_intersection(&p, &q, &normal, z))
+            .collect();
+        let [start, end] = crossings[..] else {
+            continue;
+        };
+
+        // Material lies on the inner side of the face, so walk with it on the left
+        let face_normal = (b - a).cross(&(c - a));
+        let forward = Vector3::new(-face_normal.y, face_normal.x, 0.0);
+        let (start, end) = if (end - start).dot(&forward) >= 0.0 {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        segments.push((
+            points.insert(&Point3::new(start.x, start.y, z)),
+            points.insert(&Point3::new(end.x, end.y, z)),
+        ));
+    }
+
+    stitch(&segments, points.points())
+}
+
+impl Mesh {
+    /// Closed contours where the plane at height `z` cuts the mesh, e.g. for a layer preview
+    ///
+    /// Outer contours wind counterclockwise seen from +z and holes clockwise. Chains that do
+    /// not close up, as from open or non-manifold meshes, are dropped with a warning.
+    pub fn slice(&self, z: f64) -> Vec<Vec<Point2<f64>>> {
+        let section = cross_section(self, z);
+        if section.open > 0 {
+            eprintln!(
+                "Warning: Slice at z={} dropped {} open contour(s); the mesh is not closed",
+                z, section.open
+            );
+        }
+        section.loops
+    }
+}
+
+/// Outline of `mesh` seen from +z, traced around its upward-facing faces
+///
+/// This is the exact silhouette when each vertical line meets the upper surface at most once
+/// (spheres, boxes, extrusions). Otherwise overlapping patches come out as separate loops.
+pub(crate) fn silhouette(mesh: &Mesh) -> Contours {
+    let mut welded = PointHasher::new(WELD_EPSILON);
+    let ids: Vec<usize> = mesh
+        .vertices
+        .iter()
+        .map(|v| welded.insert(&v.position))
+        .collect();
+
+    // Directed edges of upward-facing triangles; an edge is on the outline unless the
+    // neighboring upward face runs it the other way
+    let mut edges: HashMap<(usize, usize), i32> = HashMap::new();
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position);
+        let face_normal = (b - a).cross(&(c - a));
+        if face_normal.z <= WELD_EPSILON * face_normal.norm() {
+            continue;
+        }
+        let [a, b, c] = triangle.indices.map(|i| ids[i]);
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            if from != to {
+                *edges.entry((from, to)).or_default() += 1;
+                *edges.entry((to, from)).or_default() -= 1;
+            }
+        }
+    }
+
+    let mut segments: Vec<(usize, usize)> = edges
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .flat_map(|(edge, count)| std::iter::repeat_n(edge, count as usize))
+        .collect();
+    segments.sort_unstable();
+
+    // Project before stitching, so points stacked vertically share an id
+    let mut projected = PointHasher::new(WELD_EPSILON);
+    let flat: Vec<usize> = welded
+        .points()
+        .iter()
+        .map(|p| projected.insert(&Point3::new(p.x, p.y, 0.0)))
+        .collect();
+    let segments: Vec<(usize, usize)> = segments
+        .into_iter()
+        .map(|(a, b)| (flat[a], flat[b]))
+        .collect();
+
+    stitch(&segments, projected.points())
+}
+
+/// Chain directed segments between point ids into loops
+fn stitch(segments: &[(usize, usize)], points: &[Point3<f64>]) -> Contours {
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &(from, to)) in segments.iter().enumerate() {
+        if from != to {
+            outgoing.entry(from).or_default().push(i);
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut contours = Contours::default();
+    for first in 0..segments.len() {
+        let (start, mut current) = segments[first];
+        if used[first] || start == current {
+            continue;
+        }
+        used[first] = true;
+
+        let mut chain = vec![start];
+        let closed = loop {
+            if current == start {
+                break true;
+            }
+            let next = outgoing
+                .get(&current)
+                .and_then(|candidates| candidates.iter().copied().find(|&s| !used[s]));
+            let Some(next) = next else {
+                break false;
+            };
+            used[next] = true;
+            chain.push(current);
+            current = segments[next].1;
+        };
+
+        if !closed {
+            contours.open += 1;
+        } else if chain.len() >= 3 {
+            contours.loops.push(
+                chain
+                    .iter()
+                    .map(|&i| Point2::new(points[i].x, points[i].y))
+                    .collect(),
+            );
+        }
+    }
+
+    contours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+
+    fn signed_area(contour: &[Point2<f64>]) -> f64 {
+        let n = contour.len();
+        (0..n)
+            .map(|i| {
+                let (p, q) = (contour[i], contour[(i + 1) % n]);
+                p.x * q.y - p.y * q.x
+            })
+            .sum::<f64>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_cross_section_of_cube() {
+        let cube = Primitive::cube(Vector3::new(10.0, 6.0, 4.0), true).to_mesh();
+        let section = cross_section(&cube, 0.0);
+
+        assert_eq!(section.open, 0);
+        assert_eq!(section.loops.len(), 1);
+        assert!((signed_area(&section.loops[0]) - 60.0).abs() < 1e-9);
+        assert!(cross_section(&cube, 3.0).loops.is_empty());
+    }
+
+    #[test]
+    fn test_slice_centered_cube() {
+        let mut cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let loops = cube.slice(0.0);
+
+        assert_eq!(loops.len(), 1);
+        let contour = &loops[0];
+        let perimeter: f64 = (0..contour.len())
+            .map(|i| (contour[(i + 1) % contour.len()] - contour[i]).norm())
+            .sum();
+        assert!((perimeter - 40.0).abs() < 1e-9);
+        assert!((signed_area(contour) - 100.0).abs() < 1e-9);
+        for p in contour {
+            assert!((p.x.abs().max(p.y.abs()) - 5.0).abs() < 1e-9);
+        }
+
+        // Without a side face the section no longer closes
+        let side = cube
+            .triangles
+            .iter()
+            .position(|t| t.indices.iter().all(|&i| cube.vertices[i].position.x > 4.9))
+            .unwrap();
+        cube.triangles.remove(side);
+        let section = cross_section(&cube, 0.0);
+        assert_eq!(section.open, 1);
+        assert!(cube.slice(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_silhouette_of_cylinder() {
+        let mesh = Primitive::cylinder(10.0, 5.0, 24).to_mesh();
+        let outline = silhouette(&mesh);
+
+        assert_eq!(outline.open, 0);
+        assert_eq!(outline.loops.len(), 1);
+        assert_eq!(outline.loops[0].len(), 24);
+        assert!(signed_area(&outline.loops[0]) > 0.0);
+    }
+}
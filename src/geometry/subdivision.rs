@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Loop subdivision surfaces
+
+use super::halfedge::HalfEdgeMesh;
+use super::{welded_for_topology, Mesh, Triangle, Vertex};
+use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
+
+/// Apply `iterations` passes of Loop subdivision
+///
+/// Every pass splits each triangle into four. Interior vertices move by the Loop weights,
+/// while boundary edges use the cubic B-spline boundary rule so open borders stay in place.
+pub(crate) fn subdivide_loop(mesh: &Mesh, iterations: usize) -> Mesh {
+    if iterations == 0 || mesh.triangles.is_empty() {
+        return mesh.clone();
+    }
+
+    let mut current = welded_for_topology(mesh);
+    for _ in 0..iterations {
+        current = subdivide_once(&current);
+    }
+    current.recompute_normals();
+    current
+}
+
+fn subdivide_once(mesh: &Mesh) -> Mesh {
+    let he_mesh = HalfEdgeMesh::from_mesh(mesh);
+    let positions = &he_mesh.vertices;
+    let origin = |h: usize| he_mesh.half_edges[he_mesh.half_edges[h].prev].vertex;
+
+    let mut result = Mesh::with_capacity(
+        positions.len() + he_mesh.edges.len(),
+        he_mesh.faces.len() * 4,
+    );
+
+    // Collect neighbors, marking boundary neighbors separately for the boundary rule
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+    let mut boundary_neighbors: Vec<Vec<usize>> = vec![Vec::new(); positions.len()];
+    for edge in &he_mesh.edges {
+        let (a, b) = (
+            origin(edge.half_edge_a),
+            he_mesh.half_edges[edge.half_edge_a].vertex,
+        );
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+        if edge.half_edge_b.is_none() {
+            boundary_neighbors[a].push(b);
+            boundary_neighbors[b].push(a);
+        }
+    }
+
+    // Reposition original vertices
+    for (v, position) in positions.iter().enumerate() {
+        let new_position = if !boundary_neighbors[v].is_empty() {
+            match boundary_neighbors[v].as_slice() {
+                [b0, b1] => Point3::from(
+                    position.coords * 0.75
+                        + (positions[*b0].coords + positions[*b1].coords) * 0.125,
+                ),
+                // Corners of non-manifold borders stay put
+                _ => *position,
+            }
+        } else if neighbors[v].is_empty() {
+            *position
+        } else {
+            let n = neighbors[v].len() as f64;
+            let beta = loop_beta(n);
+            let sum: Vector3<f64> = neighbors[v].iter().map(|&u| positions[u].coords).sum();
+            Point3::from(position.coords * (1.0 - n * beta) + sum * beta)
+        };
+        result.add_vertex(Vertex::new(new_position, Vector3::zeros()));
+    }
+
+    // Insert one vertex per edge
+    let mut edge_points: HashMap<(usize, usize), usize> = HashMap::new();
+    for edge in &he_mesh.edges {
+        let h = edge.half_edge_a;
+        let (a, b) = (origin(h), he_mesh.half_edges[h].vertex);
+        let (pa, pb) = (positions[a].coords, positions[b].coords);
+
+        let point = match edge.half_edge_b {
+            Some(twin) => {
+                let c = he_mesh.half_edges[he_mesh.half_edges[h].next].vertex;
+                let d = he_mesh.half_edges[he_mesh.half_edges[twin].next].vertex;
+                (pa + pb) * 0.375 + (positions[c].coords + positions[d].coords) * 0.125
+            }
+            None => (pa + pb) * 0.5,
+        };
+
+        let index = result.add_vertex(Vertex::new(Point3::from(point), Vector3::zeros()));
+        edge_points.insert((a.min(b), a.max(b)), index);
+    }
+
+    // Split each face into four, keeping the original winding
+    let edge_point = |a: usize, b: usize| edge_points[&(a.min(b), a.max(b))];
+    for face in &he_mesh.faces {
+        let [v0, v1, v2] = face.map(|v| v as usize);
+        let (e01, e12, e20) = (edge_point(v0, v1), edge_point(v1, v2), edge_point(v2, v0));
+        result.add_triangle(Triangle::new([v0, e01, e20]));
+        result.add_triangle(Triangle::new([v1, e12, e01]));
+        result.add_triangle(Triangle::new([v2, e20, e12]));
+        result.add_triangle(Triangle::new([e01, e12, e20]));
+    }
+
+    result
+}
+
+/// Loop's weight for each neighbor of an interior vertex with valence `n`
+fn loop_beta(n: f64) -> f64 {
+    let c = 0.375 + 0.25 * (2.0 * std::f64::consts::PI / n).cos();
+    (0.625 - c * c) / n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+
+    #[test]
+    fn test_subdivide_cube() {
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let smooth = subdivide_loop(&cube, 1);
+
+        assert_eq!(smooth.triangle_count(), cube.triangle_count() * 4);
+
+        let before = cube.centroid().unwrap();
+        let after = smooth.centroid().unwrap();
+        assert!(
+            (after - before).norm() < 1e-6,
+            "centroid moved to {:?}",
+            after
+        );
+
+        // Smoothing pulls the corners in
+        let corner = Point3::new(10.0, 10.0, 10.0);
+        assert!(smooth
+            .vertices
+            .iter()
+            .all(|v| (v.position - corner).norm() > 0.5));
+        assert!(crate::geometry::analyze(&smooth).volume < 1000.0);
+        assert!(smooth
+            .vertices
+            .iter()
+            .all(|v| (v.normal.norm() - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_subdivide_keeps_open_boundary() {
+        let mut open_box = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let vertices = open_box.vertices.clone();
+        open_box
+            .triangles
+            .retain(|t| t.indices.iter().any(|&i| vertices[i].position.z < 10.0));
+
+        let smooth = subdivide_loop(&open_box, 2);
+        assert_eq!(smooth.triangle_count(), open_box.triangle_count() * 16);
+
+        // The rim is refined along the boundary curve and never sinks below the opening
+        let top = smooth.bounding_box().max.z;
+        assert!((top - 10.0).abs() < 1e-9);
+    }
+}
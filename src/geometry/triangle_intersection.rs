@@ -6,6 +6,7 @@
 
 use super::robust_predicates::{classify_point_plane, PlaneClassification};
 use nalgebra::{Point3, Vector3};
+use crate::utils::PointHasher;
 
 /// Result of triangle-triangle intersection test
 #[derive(Debug, Clone)]
@@ -107,19 +108,48 @@ fn are_triangles_coplanar(
     true
 }
 
+/// Distance below which coplanar triangles count as touching rather than overlapping
+const COPLANAR_TOUCH_EPSILON: f64 = 1e-9;
+
 /// Handle coplanar triangle intersection
+///
+/// Coplanar triangles intersect when their interiors overlap; touching along an edge or at a
+/// corner, as at a T-junction, does not count. The test looks for a separating axis among the
+/// edge normals of both triangles, projected onto the plane's dominant axes.
 fn handle_coplanar_triangles(
     tri_a: &[Point3<f64>; 3],
     tri_b: &[Point3<f64>; 3],
 ) -> IntersectionResult {
-    // Check if triangles overlap in 2D
-    // Project to 2D plane and test overlap
-    
-    // For now, use simple bounding box test
-    let bbox_a = compute_triangle_bbox(tri_a);
-    let bbox_b = compute_triangle_bbox(tri_b);
-    
-    let overlaps = bboxes_overlap(&bbox_a, &bbox_b);
+    let normal = (tri_a[1] - tri_a[0]).cross(&(tri_a[2] - tri_a[0]));
+    let abs_normal = normal.map(|x| x.abs());
+    let project = |p: &Point3<f64>| -> (f64, f64) {
+        if abs_normal.x > abs_normal.y && abs_normal.x > abs_normal.z {
+            (p.y, p.z)
+        } else if abs_normal.y > abs_normal.z {
+            (p.x, p.z)
+        } else {
+            (p.x, p.y)
+        }
+    };
+    let a = tri_a.map(|p| project(&p));
+    let b = tri_b.map(|p| project(&p));
+
+    let extent = |triangle: &[(f64, f64); 3], axis: (f64, f64)| {
+        let values = triangle.map(|(x, y)| x * axis.0 + y * axis.1);
+        (values.iter().copied().fold(f64::INFINITY, f64::min),
+         values.iter().copied().fold(f64::NEG_INFINITY, f64::max))
+    };
+    let separated = [a, b].iter().any(|triangle| {
+        (0..3).any(|i| {
+            let (p, q) = (triangle[i], triangle[(i + 1) % 3]);
+            let axis = (p.1 - q.1, q.0 - p.0);
+            let epsilon = COPLANAR_TOUCH_EPSILON * axis.0.hypot(axis.1);
+            let (min_a, max_a) = extent(&a, axis);
+            let (min_b, max_b) = extent(&b, axis);
+            max_a <= min_b + epsilon || max_b <= min_a + epsilon
+        })
+    });
+    let overlaps = !separated;
     
     IntersectionResult {
         intersects: overlaps,
@@ -208,55 +238,13 @@ fn point_in_triangle_2d(
     a >= 0.0 && b >= 0.0 && c >= 0.0
 }
 
-/// Compute bounding box of triangle
-fn compute_triangle_bbox(triangle: &[Point3<f64>; 3]) -> (Point3<f64>, Point3<f64>) {
-    let mut min = triangle[0];
-    let mut max = triangle[0];
-    
-    for &vertex in triangle.iter().skip(1) {
-        min.x = min.x.min(vertex.x);
-        min.y = min.y.min(vertex.y);
-        min.z = min.z.min(vertex.z);
-        max.x = max.x.max(vertex.x);
-        max.y = max.y.max(vertex.y);
-        max.z = max.z.max(vertex.z);
-    }
-    
-    (min, max)
-}
-
-/// Check if two bounding boxes overlap
-fn bboxes_overlap(
-    bbox_a: &(Point3<f64>, Point3<f64>),
-    bbox_b: &(Point3<f64>, Point3<f64>),
-) -> bool {
-    bbox_a.0.x <= bbox_b.1.x
-        && bbox_a.1.x >= bbox_b.0.x
-        && bbox_a.0.y <= bbox_b.1.y
-        && bbox_a.1.y >= bbox_b.0.y
-        && bbox_a.0.z <= bbox_b.1.z
-        && bbox_a.1.z >= bbox_b.0.z
-}
-
 /// Deduplicate points within epsilon distance
 fn deduplicate_points(points: &[Point3<f64>]) -> Vec<Point3<f64>> {
-    const EPS: f64 = 1e-9;
-    let mut result: Vec<Point3<f64>> = Vec::new();
-    
-    for &point in points {
-        let mut is_duplicate = false;
-        for &existing in &result {
-            if (point - existing).norm() < EPS {
-                is_duplicate = true;
-                break;
-            }
-        }
-        if !is_duplicate {
-            result.push(point);
-        }
+    let mut unique = PointHasher::new(1e-9);
+    for point in points {
+        unique.insert(point);
     }
-    
-    result
+    unique.into_points()
 }
 
 #[cfg(test)]
@@ -297,5 +285,37 @@ mod tests {
         // Should intersect (coplanar case)
         assert!(result.intersects || result.intersection_type == IntersectionType::Coplanar);
     }
-}
 
+    #[test]
+    fn test_coplanar_triangles_touching_do_not_intersect() {
+        // Bounding boxes overlap, but the triangles are apart
+        let tri_a = [
+            Point3::new(0.0, 0.0, 10.0),
+            Point3::new(5.0, 0.0, 10.0),
+            Point3::new(5.0, 5.0, 10.0),
+        ];
+        let tri_b = [
+            Point3::new(2.5, 5.0, 10.0),
+            Point3::new(5.0, 10.0, 10.0),
+            Point3::new(0.0, 10.0, 10.0),
+        ];
+        assert!(!triangle_triangle_intersection(&tri_a, &tri_b).intersects);
+
+        // Sharing part of an edge without a shared vertex, as at a T-junction
+        let tri_c = [
+            Point3::new(1.0, 0.0, 10.0),
+            Point3::new(4.0, 0.0, 10.0),
+            Point3::new(2.5, -3.0, 10.0),
+        ];
+        assert!(!triangle_triangle_intersection(&tri_a, &tri_c).intersects);
+
+        let tri_d = [
+            Point3::new(1.0, -1.0, 10.0),
+            Point3::new(4.0, -1.0, 10.0),
+            Point3::new(2.5, 0.5, 10.0),
+        ];
+        let result = triangle_triangle_intersection(&tri_a, &tri_d);
+        assert!(result.intersects);
+        assert_eq!(result.intersection_type, IntersectionType::Coplanar);
+    }
+}
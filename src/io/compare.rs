@@ -3,8 +3,14 @@
 
 //! Mesh comparison utilities for I/O equivalence testing
 
+use crate::evaluation::{compare_mesh, compare_meshes_with_options, CompareOptions, Comparison};
 use crate::geometry::Mesh;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Distance within which vertices are merged before comparing against a reference file
+const REFERENCE_WELD_EPSILON: f64 = 1e-5;
 
 /// Result of mesh comparison
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +71,39 @@ pub fn compare_meshes(mesh_a: &Mesh, mesh_b: &Mesh, tolerance: f32) -> MeshCompa
     comparison
 }
 
+/// Render SCAD `source` and compare it against a stored reference mesh (`.stl` or `.obj`)
+///
+/// Runs the same checks as a comparison against OpenSCAD, and additionally requires the
+/// volume, surface area, and bounding box deltas of [`compare_mesh`] to be within threshold.
+/// Both meshes are welded first so a reference written as a triangle soup still matches.
+pub fn compare_to_reference(
+    source: &str,
+    reference_path: &Path,
+    options: &CompareOptions,
+) -> Result<Comparison> {
+    let extension = reference_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    let reference = match extension.as_deref() {
+        Some("stl") => super::import_stl(reference_path)?,
+        Some("obj") => super::import_obj(reference_path)?,
+        _ => bail!(
+            "Unsupported reference file {:?}: expected .stl or .obj",
+            reference_path
+        ),
+    };
+    let rendered = crate::render(source).context("Failed to render SCAD source")?;
+
+    let rendered = rendered.as_indexed(REFERENCE_WELD_EPSILON);
+    let reference = reference.as_indexed(REFERENCE_WELD_EPSILON);
+
+    let mut comparison = compare_meshes_with_options(&rendered, &reference, options);
+    comparison.passed &= compare_mesh(&reference, &rendered)?.passed;
+
+    Ok(comparison)
+}
+
 /// Compare triangle count with percentage tolerance
 #[allow(dead_code)]
 pub fn compare_triangle_count_with_tolerance(
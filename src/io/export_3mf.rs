@@ -3,22 +3,69 @@
 
 //! 3MF (3D Manufacturing Format) exporter
 
-use crate::geometry::Mesh;
-use anyhow::{Context, Result};
-use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use super::metadata::{generator, ModelMetadata};
+use super::units::Unit;
+use crate::geometry::{welded_for_topology, Mesh};
+use anyhow::{bail, Context, Result};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{Cursor, Write as IoWrite};
 use zip::write::{ExtendedFileOptions, FileOptions, ZipWriter};
 use zip::CompressionMethod;
 
 /// Export mesh to 3MF format
+///
+/// The mesh must be closed, manifold, and consistently oriented; slicers reject anything else.
+/// Use [`export_unvalidated`] to write the file regardless.
 pub fn export(mesh: &Mesh, path: &str) -> Result<()> {
+    validate_for_3mf(mesh)?;
+    export_unvalidated(mesh, path)
+}
+
+/// Settings for [`export_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct ThreeMfOptions {
+    pub metadata: ModelMetadata,
+    /// Unit of the model; coordinates are converted from millimeters
+    pub unit: Unit,
+    /// Write the file even if the mesh is not printable
+    pub skip_validation: bool,
+}
+
+/// Export mesh to 3MF format with package metadata, validated like [`export`]
+pub fn export_with_metadata(mesh: &Mesh, path: &str, metadata: &ModelMetadata) -> Result<()> {
+    let options = ThreeMfOptions {
+        metadata: metadata.clone(),
+        ..Default::default()
+    };
+    export_with_options(mesh, path, &options)
+}
+
+/// Export mesh to 3MF format without checking that it is printable
+pub fn export_unvalidated(mesh: &Mesh, path: &str) -> Result<()> {
+    let options = ThreeMfOptions {
+        skip_validation: true,
+        ..Default::default()
+    };
+    export_with_options(mesh, path, &options)
+}
+
+/// Export mesh to 3MF format with metadata, unit, and validation chosen by `options`
+pub fn export_with_options(mesh: &Mesh, path: &str, options: &ThreeMfOptions) -> Result<()> {
+    if !options.skip_validation {
+        validate_for_3mf(mesh)?;
+    }
+    write_3mf(mesh, path, options)
+}
+
+fn write_3mf(mesh: &Mesh, path: &str, settings: &ThreeMfOptions) -> Result<()> {
     let file = File::create(path).context("Failed to create 3MF file")?;
     let mut zip = ZipWriter::new(file);
 
     // Create 3D/3dmodel.model file
-    let model_xml = generate_3dmodel_xml(mesh)?;
+    let model_xml = generate_3dmodel_xml(mesh, settings)?;
 
     let options: FileOptions<ExtendedFileOptions> =
         FileOptions::default().compression_method(CompressionMethod::Deflated);
@@ -40,7 +87,67 @@ pub fn export(mesh: &Mesh, path: &str) -> Result<()> {
     Ok(())
 }
 
-fn generate_3dmodel_xml(mesh: &Mesh) -> Result<String> {
+/// Check that the mesh forms a valid 3MF object, as [`export`] does before writing
+///
+/// There is no beam-lattice check: [`Mesh`] holds only triangles, so the model is never written
+/// with the beam lattice extension and has no beams to reject.
+pub fn validate_for_3mf(mesh: &Mesh) -> Result<()> {
+    let vertex_count = mesh.vertices.len();
+    for (i, triangle) in mesh.triangles.iter().enumerate() {
+        if let Some(&index) = triangle.indices.iter().find(|&&idx| idx >= vertex_count) {
+            bail!(
+                "Invalid 3MF mesh: triangle {} references vertex {} but the mesh has {} vertices",
+                i,
+                index,
+                vertex_count
+            );
+        }
+    }
+
+    // Primitives emit per-face vertices, so check topology on a welded copy.
+    // Triangles that collapse when welded (e.g. at sphere poles) carry no area and are skipped.
+    let welded = welded_for_topology(mesh);
+
+    if welded.triangles.is_empty() {
+        bail!("Invalid 3MF mesh: mesh has no triangles");
+    }
+
+    let validation = welded.validate();
+    if !validation.is_manifold {
+        bail!("Invalid 3MF mesh: mesh is non-manifold (an edge is shared by more than two triangles)");
+    }
+    if !validation.is_closed {
+        bail!(
+            "Invalid 3MF mesh: mesh is not closed ({} boundary edges)",
+            validation.boundary_edge_count
+        );
+    }
+
+    // Each directed edge of a consistently oriented closed mesh appears exactly once
+    let mut directed_edges = HashSet::new();
+    for triangle in &welded.triangles {
+        for k in 0..3 {
+            let edge = (triangle.indices[k], triangle.indices[(k + 1) % 3]);
+            if !directed_edges.insert(edge) {
+                bail!(
+                    "Invalid 3MF mesh: inconsistent triangle orientation at edge {}-{}",
+                    edge.0,
+                    edge.1
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_3dmodel_xml(mesh: &Mesh, options: &ThreeMfOptions) -> Result<String> {
+    let metadata = &options.metadata;
+    let colors = corner_colors(mesh);
+    let per_corner = colors
+        .as_ref()
+        .is_some_and(|(_, corners)| corners.iter().any(|c| c[0] != c[1] || c[1] != c[2]));
+
     let mut writer = Writer::new(Cursor::new(Vec::new()));
 
     // XML declaration
@@ -48,21 +155,70 @@ fn generate_3dmodel_xml(mesh: &Mesh) -> Result<String> {
 
     // Root element
     let mut model = BytesStart::new("model");
-    model.push_attribute(("unit", "millimeter"));
+    model.push_attribute(("unit", options.unit.threemf_name()));
     model.push_attribute(("xml:lang", "en-US"));
     model.push_attribute((
         "xmlns",
         "http://schemas.microsoft.com/3dmanufacturing/core/2015/02",
     ));
+    if per_corner {
+        model.push_attribute(("xmlns:m", MATERIAL_NAMESPACE));
+    }
     writer.write_event(Event::Start(model))?;
 
+    // Metadata, using the names defined by the core specification
+    let application = generator();
+    let entries = [
+        ("Title", metadata.title.as_deref()),
+        ("Designer", metadata.designer.as_deref()),
+        ("Description", metadata.description.as_deref()),
+        ("CreationDate", metadata.creation_date.as_deref()),
+        ("Application", Some(application.as_str())),
+    ];
+    for (name, value) in entries {
+        let Some(value) = value else { continue };
+        let mut element = BytesStart::new("metadata");
+        element.push_attribute(("name", name));
+        writer.write_event(Event::Start(element))?;
+        writer.write_event(Event::Text(BytesText::new(value)))?;
+        writer.write_event(Event::End(BytesEnd::new("metadata")))?;
+    }
+
     // Resources
     writer.write_event(Event::Start(BytesStart::new("resources")))?;
 
+    // One base material per distinct color when every triangle is a single color, otherwise
+    // a color group from the materials extension, so corners can differ
+    if let Some((palette, _)) = &colors {
+        let (group_tag, entry_tag) = if per_corner {
+            ("m:colorgroup", "m:color")
+        } else {
+            ("basematerials", "base")
+        };
+        let mut group = BytesStart::new(group_tag);
+        group.push_attribute(("id", MATERIALS_ID));
+        writer.write_event(Event::Start(group))?;
+        for (i, rgba) in palette.iter().enumerate() {
+            let mut entry = BytesStart::new(entry_tag);
+            if per_corner {
+                entry.push_attribute(("color", hex_color(rgba).as_str()));
+            } else {
+                entry.push_attribute(("name", format!("color{}", i).as_str()));
+                entry.push_attribute(("displaycolor", hex_color(rgba).as_str()));
+            }
+            writer.write_event(Event::Empty(entry))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(group_tag)))?;
+    }
+
     // Object
     let mut object = BytesStart::new("object");
     object.push_attribute(("id", "1"));
     object.push_attribute(("type", "model"));
+    if let Some((_, corners)) = &colors {
+        object.push_attribute(("pid", MATERIALS_ID));
+        object.push_attribute(("pindex", corners[0][0].to_string().as_str()));
+    }
     writer.write_event(Event::Start(object))?;
 
     // Mesh
@@ -70,22 +226,31 @@ fn generate_3dmodel_xml(mesh: &Mesh) -> Result<String> {
 
     // Vertices
     writer.write_event(Event::Start(BytesStart::new("vertices")))?;
+    let coordinate = |value: f64| options.unit.from_millimeters(value).to_string();
     for vertex in &mesh.vertices {
         let mut v = BytesStart::new("vertex");
-        v.push_attribute(("x", vertex.position.x.to_string().as_str()));
-        v.push_attribute(("y", vertex.position.y.to_string().as_str()));
-        v.push_attribute(("z", vertex.position.z.to_string().as_str()));
+        v.push_attribute(("x", coordinate(vertex.position.x).as_str()));
+        v.push_attribute(("y", coordinate(vertex.position.y).as_str()));
+        v.push_attribute(("z", coordinate(vertex.position.z).as_str()));
         writer.write_event(Event::Empty(v))?;
     }
     writer.write_event(Event::End(BytesEnd::new("vertices")))?;
 
     // Triangles
     writer.write_event(Event::Start(BytesStart::new("triangles")))?;
-    for triangle in &mesh.triangles {
+    for (i, triangle) in mesh.triangles.iter().enumerate() {
         let mut t = BytesStart::new("triangle");
         t.push_attribute(("v1", triangle.indices[0].to_string().as_str()));
         t.push_attribute(("v2", triangle.indices[1].to_string().as_str()));
         t.push_attribute(("v3", triangle.indices[2].to_string().as_str()));
+        if let Some((_, corners)) = &colors {
+            t.push_attribute(("pid", MATERIALS_ID));
+            t.push_attribute(("p1", corners[i][0].to_string().as_str()));
+            if per_corner {
+                t.push_attribute(("p2", corners[i][1].to_string().as_str()));
+                t.push_attribute(("p3", corners[i][2].to_string().as_str()));
+            }
+        }
         writer.write_event(Event::Empty(t))?;
     }
     writer.write_event(Event::End(BytesEnd::new("triangles")))?;
@@ -113,6 +278,61 @@ fn generate_3dmodel_xml(mesh: &Mesh) -> Result<String> {
     Ok(String::from_utf8(result)?)
 }
 
+/// Resource ID of the base material or color group; the object is always 1
+const MATERIALS_ID: &str = "2";
+
+/// Namespace of the 3MF materials and properties extension, which defines color groups
+const MATERIAL_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/material/2015/02";
+
+/// Distinct colors, and each triangle corner's index into them
+type CornerColors = (Vec<[f32; 4]>, Vec<[usize; 3]>);
+
+/// Colors of every triangle corner, or `None` for an uncolored mesh
+///
+/// Uncolored corners take the color of the triangle's first colored vertex, and triangles
+/// without any colored vertex get white.
+fn corner_colors(mesh: &Mesh) -> Option<CornerColors> {
+    if !mesh.has_colors() || mesh.triangles.is_empty() {
+        return None;
+    }
+
+    let mut colors: Vec<[f32; 4]> = Vec::new();
+    let mut index_of = |rgba: [f32; 4]| {
+        colors.iter().position(|c| *c == rgba).unwrap_or_else(|| {
+            colors.push(rgba);
+            colors.len() - 1
+        })
+    };
+    let corners = mesh
+        .triangles
+        .iter()
+        .map(|triangle| {
+            let fallback = triangle
+                .indices
+                .iter()
+                .find_map(|&i| mesh.vertices[i].color)
+                .unwrap_or([1.0; 4]);
+            triangle
+                .indices
+                .map(|i| index_of(mesh.vertices[i].color.unwrap_or(fallback)))
+        })
+        .collect();
+
+    Some((colors, corners))
+}
+
+/// Format RGBA as the `#RRGGBBAA` sRGB value 3MF expects
+fn hex_color(rgba: &[f32; 4]) -> String {
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02X}{:02X}{:02X}{:02X}",
+        byte(rgba[0]),
+        byte(rgba[1]),
+        byte(rgba[2]),
+        byte(rgba[3])
+    )
+}
+
 fn generate_content_types_xml() -> String {
     r#"<?xml version="1.0" encoding="UTF-8"?>
 <Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
@@ -133,10 +353,18 @@ fn generate_rels_xml() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::geometry::Primitive;
-    use nalgebra::Vector3;
+    use crate::geometry::{Primitive, Triangle, Vertex};
+    use nalgebra::{Point3, Vector3};
     use tempfile::NamedTempFile;
 
+    /// Unzip the model part of a 3MF package
+    fn read_model(path: &str) -> Result<String> {
+        let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+        let mut xml = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("3D/3dmodel.model")?, &mut xml)?;
+        Ok(xml)
+    }
+
     #[test]
     fn test_export_3mf() -> Result<()> {
         let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
@@ -152,4 +380,130 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_export_3mf_rejects_invalid_mesh() -> Result<()> {
+        let file = NamedTempFile::with_suffix(".3mf")?;
+        let path = file.path().to_str().unwrap();
+
+        // Three triangles sharing one edge is non-manifold
+        let mut mesh = Mesh::new();
+        let normal = Vector3::z();
+        for (x, y, z) in [
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, -1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        ] {
+            mesh.add_vertex(Vertex::new(Point3::new(x, y, z), normal));
+        }
+        mesh.add_triangle(Triangle::new([0, 1, 2]));
+        mesh.add_triangle(Triangle::new([1, 0, 3]));
+        mesh.add_triangle(Triangle::new([0, 1, 4]));
+
+        let err = export(&mesh, path).unwrap_err();
+        assert!(err.to_string().contains("non-manifold"), "{}", err);
+
+        // Forcing the export still writes the file
+        export_unvalidated(&mesh, path)?;
+        assert!(std::fs::metadata(path)?.len() > 0);
+
+        // A closed primitive passes validation
+        let cube = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        export(&cube, path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_colored_mesh_writes_base_materials() -> Result<()> {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let options = ThreeMfOptions::default();
+        assert!(!generate_3dmodel_xml(&mesh, &options)?.contains("basematerials"));
+
+        mesh.apply_color([1.0, 0.0, 0.0, 1.0]);
+        let xml = generate_3dmodel_xml(&mesh, &options)?;
+        assert!(xml.contains(r##"<base name="color0" displaycolor="#FF0000FF"/>"##));
+        assert_eq!(
+            xml.matches(r#"pid="2" p1="0""#).count(),
+            mesh.triangle_count()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_metadata_written_to_model_xml() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let file = NamedTempFile::with_suffix(".3mf")?;
+        let path = file.path().to_str().unwrap();
+
+        let metadata = ModelMetadata {
+            title: Some("Bracket <rev B>".to_string()),
+            designer: Some("Ada Lovelace".to_string()),
+            creation_date: Some("2025-06-01".to_string()),
+            ..Default::default()
+        };
+        export_with_metadata(&mesh, path, &metadata)?;
+
+        let xml = read_model(path)?;
+        assert!(xml.contains(r#"<metadata name="Title">Bracket &lt;rev B&gt;</metadata>"#));
+        assert!(xml.contains(r#"<metadata name="Designer">Ada Lovelace</metadata>"#));
+        assert!(xml.contains(r#"<metadata name="CreationDate">2025-06-01</metadata>"#));
+        assert!(!xml.contains(r#"name="Description""#));
+        assert!(xml.find("<metadata").unwrap() < xml.find("<resources>").unwrap());
+
+        // Without metadata only the generator is recorded
+        export(&mesh, path)?;
+        let xml = read_model(path)?;
+        assert_eq!(xml.matches("<metadata ").count(), 1);
+        assert!(xml.contains(&format!(
+            r#"<metadata name="Application">{}</metadata>"#,
+            generator()
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_color_groups_metadata_and_unit() -> Result<()> {
+        let mut mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        mesh.apply_color([0.0, 0.0, 1.0, 1.0]);
+        let file = NamedTempFile::with_suffix(".3mf")?;
+        let path = file.path().to_str().unwrap();
+
+        let options = ThreeMfOptions {
+            metadata: ModelMetadata {
+                title: Some("Blue cube".to_string()),
+                ..Default::default()
+            },
+            unit: Unit::Centimeter,
+            ..Default::default()
+        };
+        export_with_options(&mesh, path, &options)?;
+
+        let xml = read_model(path)?;
+        assert!(xml.contains(r#"unit="centimeter""#));
+        assert!(xml.contains(r#"<vertex x="1" y="1" z="1"/>"#));
+        assert!(xml.contains(r##"<base name="color0" displaycolor="#0000FFFF"/>"##));
+        assert!(xml.contains(r#"<metadata name="Title">Blue cube</metadata>"#));
+        assert!(xml.contains(&format!(
+            r#"<metadata name="Application">{}</metadata>"#,
+            generator()
+        )));
+
+        // Corners of different colors need a color group instead of whole-triangle materials
+        mesh.vertices[0].color = Some([1.0, 0.0, 0.0, 1.0]);
+        export_with_options(&mesh, path, &ThreeMfOptions::default())?;
+        let xml = read_model(path)?;
+        assert!(xml.contains(r#"unit="millimeter""#));
+        assert!(xml.contains(MATERIAL_NAMESPACE));
+        assert!(!xml.contains("basematerials"));
+        assert!(xml.contains(r##"<m:color color="#0000FFFF"/>"##));
+        assert!(xml.contains(r##"<m:color color="#FF0000FF"/>"##));
+        assert_eq!(xml.matches(" p3=").count(), mesh.triangle_count());
+
+        Ok(())
+    }
 }
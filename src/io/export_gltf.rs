@@ -4,109 +4,248 @@
 //! GLTF/GLB exporter
 
 use crate::geometry::Mesh;
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use nalgebra::Vector3;
 use serde_json::json;
-use std::fs::File;
-use std::io::Write;
-
-/// Export mesh to GLTF or GLB format
-pub fn export(mesh: &Mesh, path: &str) -> Result<()> {
-    if path.ends_with(".glb") {
-        export_glb(mesh, path)
-    } else {
-        export_gltf_separate(mesh, path)
-    }
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How vertex attributes are laid out in the binary buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttributeLayout {
+    /// POSITION and NORMAL interleaved in one strided bufferView
+    Interleaved,
+    /// POSITION and NORMAL in their own tightly packed bufferViews
+    #[default]
+    Separate,
 }
 
-/// Export mesh to GLB (binary GLTF)
-fn export_glb(mesh: &Mesh, path: &str) -> Result<()> {
-    let (gltf_json_val, buffer_data) = create_gltf_json(mesh)?;
-
-    let json_string = serde_json::to_string(&gltf_json_val)?;
-    let mut json_offset = json_string.len();
-    align_to_multiple_of_four(&mut json_offset);
-    let json_padding = json_offset - json_string.len();
+/// glTF 2.0 container to write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GltfFormat {
+    /// `.gltf` JSON document referencing an external `.bin` buffer next to it
+    Json,
+    /// `.glb` binary container with the JSON and buffer packed into one file
+    Binary,
+}
 
-    let mut buffer_offset = buffer_data.len();
-    align_to_multiple_of_four(&mut buffer_offset);
-    let buffer_padding = buffer_offset - buffer_data.len();
+impl GltfFormat {
+    /// Binary for a `.glb` extension (any case), JSON otherwise
+    pub fn from_path(path: &str) -> Self {
+        let is_glb = Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("glb"));
+        if is_glb {
+            GltfFormat::Binary
+        } else {
+            GltfFormat::Json
+        }
+    }
+}
 
-    let total_length = 12 + 8 + json_offset + 8 + buffer_offset;
+/// GLB header magic, "glTF" read as a little-endian u32
+const GLB_MAGIC: u32 = 0x46546C67;
+/// Version of the binary container format
+const GLB_VERSION: u32 = 2;
+const CHUNK_TYPE_JSON: u32 = 0x4E4F534A;
+const CHUNK_TYPE_BIN: u32 = 0x004E4942;
 
-    let mut file = File::create(path)?;
+/// Export mesh to GLTF or GLB format, chosen by the file extension
+pub fn export(mesh: &Mesh, path: &str) -> Result<()> {
+    export_with_layout(mesh, path, AttributeLayout::default())
+}
 
-    // GLB header
-    file.write_all(&0x46546C67u32.to_le_bytes())?; // magic: "glTF"
-    file.write_all(&2u32.to_le_bytes())?; // version
-    file.write_all(&(total_length as u32).to_le_bytes())?;
+/// Export mesh to GLTF or GLB format, chosen by the file extension, with the given vertex
+/// attribute layout
+pub fn export_with_layout(mesh: &Mesh, path: &str, layout: AttributeLayout) -> Result<()> {
+    export_with_format(mesh, path, GltfFormat::from_path(path), layout)
+}
 
-    // JSON chunk
-    file.write_all(&(json_offset as u32).to_le_bytes())?;
-    file.write_all(&0x4E4F534Au32.to_le_bytes())?; // type: "JSON"
-    file.write_all(json_string.as_bytes())?;
-    for _ in 0..json_padding {
-        file.write_all(b" ")?;
+/// Export mesh in an explicit container `format`, whatever the file extension
+pub fn export_with_format(
+    mesh: &Mesh,
+    path: &str,
+    format: GltfFormat,
+    layout: AttributeLayout,
+) -> Result<()> {
+    match format {
+        GltfFormat::Binary => export_glb(mesh, path, layout),
+        GltfFormat::Json => export_gltf_separate(mesh, path, layout),
     }
+}
+
+/// Export mesh to GLB (binary GLTF)
+fn export_glb(mesh: &Mesh, path: &str, layout: AttributeLayout) -> Result<()> {
+    let (gltf_json_val, buffer_data) = create_gltf_json(mesh, layout, None)?;
+    let glb = encode_glb(&serde_json::to_vec(&gltf_json_val)?, &buffer_data)?;
+    std::fs::write(path, glb).with_context(|| format!("Failed to write GLB file: {}", path))
+}
 
-    // BIN chunk
-    file.write_all(&(buffer_offset as u32).to_le_bytes())?;
-    file.write_all(&0x004E4942u32.to_le_bytes())?; // type: "BIN\0"
-    file.write_all(&buffer_data)?;
-    for _ in 0..buffer_padding {
-        file.write_all(&[0])?;
+/// Pack a JSON document and its binary buffer into a GLB container
+///
+/// The file is a 12-byte header (magic, version, total length) followed by the JSON chunk,
+/// padded with spaces, and the BIN chunk, padded with zeros, so both start 4-byte aligned.
+/// An empty buffer gets no BIN chunk.
+fn encode_glb(json: &[u8], buffer: &[u8]) -> Result<Vec<u8>> {
+    let mut json_length = json.len();
+    align_to_multiple_of_four(&mut json_length);
+    let mut buffer_length = buffer.len();
+    align_to_multiple_of_four(&mut buffer_length);
+
+    let bin_chunk_length = if buffer.is_empty() {
+        0
+    } else {
+        8 + buffer_length
+    };
+    let total_length = 12 + 8 + json_length + bin_chunk_length;
+    let total_length = u32::try_from(total_length).map_err(|_| {
+        anyhow!(
+            "GLB output of {} bytes exceeds the 4 GiB limit",
+            total_length
+        )
+    })?;
+
+    let mut glb = Vec::with_capacity(total_length as usize);
+    glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+    glb.extend_from_slice(&total_length.to_le_bytes());
+
+    glb.extend_from_slice(&(json_length as u32).to_le_bytes());
+    glb.extend_from_slice(&CHUNK_TYPE_JSON.to_le_bytes());
+    glb.extend_from_slice(json);
+    glb.resize(20 + json_length, b' ');
+
+    if !buffer.is_empty() {
+        glb.extend_from_slice(&(buffer_length as u32).to_le_bytes());
+        glb.extend_from_slice(&CHUNK_TYPE_BIN.to_le_bytes());
+        glb.extend_from_slice(buffer);
+        glb.resize(total_length as usize, 0);
     }
 
-    Ok(())
+    Ok(glb)
 }
 
 /// Export mesh to GLTF with separate .bin file
-fn export_gltf_separate(mesh: &Mesh, path: &str) -> Result<()> {
-    let (gltf_json_val, buffer_data) = create_gltf_json(mesh)?;
+fn export_gltf_separate(mesh: &Mesh, path: &str, layout: AttributeLayout) -> Result<()> {
+    let bin_path = Path::new(path).with_extension("bin");
+    let bin_uri = bin_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("data.bin")
+        .to_string();
+
+    let (gltf_json_val, buffer_data) = create_gltf_json(mesh, layout, Some(&bin_uri))?;
 
     // Write .gltf JSON file
     let json_string = serde_json::to_string_pretty(&gltf_json_val)?;
     std::fs::write(path, json_string)?;
 
     // Write .bin file
-    let bin_path = path.replace(".gltf", ".bin");
     std::fs::write(bin_path, buffer_data)?;
 
     Ok(())
 }
 
-fn create_gltf_json(mesh: &Mesh) -> Result<(serde_json::Value, Vec<u8>)> {
-    let mut buffer_data = Vec::new();
+/// Build the GLTF document and its binary buffer
+///
+/// `uri` names the external buffer file; GLB embeds the buffer and passes `None`.
+fn create_gltf_json(
+    mesh: &Mesh,
+    layout: AttributeLayout,
+    uri: Option<&str>,
+) -> Result<(serde_json::Value, Vec<u8>)> {
+    const VEC3_SIZE: usize = 3 * std::mem::size_of::<f32>();
 
-    // Write positions
-    let position_offset = buffer_data.len();
-    let (min_pos, max_pos) = calculate_bounds(mesh);
-    for vertex in &mesh.vertices {
-        buffer_data.extend_from_slice(&vertex.position.x.to_le_bytes());
-        buffer_data.extend_from_slice(&vertex.position.y.to_le_bytes());
-        buffer_data.extend_from_slice(&vertex.position.z.to_le_bytes());
-    }
-    let position_length = buffer_data.len() - position_offset;
-
-    // Write normals
-    let normal_offset = buffer_data.len();
-    for vertex in &mesh.vertices {
-        buffer_data.extend_from_slice(&vertex.normal.x.to_le_bytes());
-        buffer_data.extend_from_slice(&vertex.normal.y.to_le_bytes());
-        buffer_data.extend_from_slice(&vertex.normal.z.to_le_bytes());
-    }
-    let normal_length = buffer_data.len() - normal_offset;
+    let mut buffer_data = Vec::new();
+    let (vertices, indices) = indexed_vertices(mesh);
+    let (min_pos, max_pos) = calculate_bounds(&vertices);
+
+    let push_vec3 = |buffer: &mut Vec<u8>, v: [f32; 3]| {
+        for component in v {
+            buffer.extend_from_slice(&component.to_le_bytes());
+        }
+    };
+
+    // Write vertex attributes, recording (bufferView, byteOffset) for POSITION and NORMAL
+    let mut buffer_views = Vec::new();
+    let (position_view, normal_view) = match layout {
+        AttributeLayout::Interleaved => {
+            for vertex in &vertices {
+                push_vec3(&mut buffer_data, vertex.position);
+                push_vec3(&mut buffer_data, vertex.normal);
+            }
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": 0,
+                "byteLength": buffer_data.len(),
+                "byteStride": 2 * VEC3_SIZE,
+                "target": 34962
+            }));
+            ((0, 0), (0, VEC3_SIZE))
+        }
+        AttributeLayout::Separate => {
+            for vertex in &vertices {
+                push_vec3(&mut buffer_data, vertex.position);
+            }
+            let normal_offset = buffer_data.len();
+            for vertex in &vertices {
+                push_vec3(&mut buffer_data, vertex.normal);
+            }
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": 0,
+                "byteLength": normal_offset,
+                "target": 34962
+            }));
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": normal_offset,
+                "byteLength": buffer_data.len() - normal_offset,
+                "target": 34962
+            }));
+            ((0, 0), (1, 0))
+        }
+    };
 
     // Write indices
     let indices_offset = buffer_data.len();
-    for triangle in &mesh.triangles {
-        buffer_data.extend_from_slice(&(triangle.indices[0] as u32).to_le_bytes());
-        buffer_data.extend_from_slice(&(triangle.indices[1] as u32).to_le_bytes());
-        buffer_data.extend_from_slice(&(triangle.indices[2] as u32).to_le_bytes());
+    for index in &indices {
+        buffer_data.extend_from_slice(&index.to_le_bytes());
+    }
+    let indices_view = buffer_views.len();
+    buffer_views.push(json!({
+        "buffer": 0,
+        "byteOffset": indices_offset,
+        "byteLength": buffer_data.len() - indices_offset,
+        "target": 34963
+    }));
+
+    let mut buffer = json!({ "byteLength": buffer_data.len() });
+    if let Some(uri) = uri {
+        buffer["uri"] = json!(uri);
     }
-    let indices_length = buffer_data.len() - indices_offset;
+
+    // Vertex colors go in their own bufferView after the indices; uncolored vertices are white.
+    // COLOR_0 is linear, while `color()` values are sRGB like the rest of OpenSCAD.
+    let colors_view = mesh.has_colors().then(|| {
+        let colors_offset = buffer_data.len();
+        for vertex in &vertices {
+            let [r, g, b, a] = vertex.color.unwrap_or([1.0; 4]);
+            for component in [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a] {
+                buffer_data.extend_from_slice(&component.to_le_bytes());
+            }
+        }
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": colors_offset,
+            "byteLength": buffer_data.len() - colors_offset,
+            "target": 34962
+        }));
+        buffer_views.len() - 1
+    });
 
     // Build GLTF JSON
-    let gltf = json!({
+    let mut gltf = json!({
         "asset": {
             "generator": "Polyframe Kernel",
             "version": "2.0"
@@ -138,71 +277,136 @@ fn create_gltf_json(mesh: &Mesh) -> Result<(serde_json::Value, Vec<u8>)> {
         ],
         "accessors": [
             {
-                "bufferView": 0,
-                "byteOffset": 0,
+                "bufferView": position_view.0,
+                "byteOffset": position_view.1,
                 "componentType": 5126,
-                "count": mesh.vertices.len(),
+                "count": vertices.len(),
                 "type": "VEC3",
                 "min": [min_pos[0], min_pos[1], min_pos[2]],
                 "max": [max_pos[0], max_pos[1], max_pos[2]]
             },
             {
-                "bufferView": 1,
-                "byteOffset": 0,
+                "bufferView": normal_view.0,
+                "byteOffset": normal_view.1,
                 "componentType": 5126,
-                "count": mesh.vertices.len(),
+                "count": vertices.len(),
                 "type": "VEC3"
             },
             {
-                "bufferView": 2,
+                "bufferView": indices_view,
                 "byteOffset": 0,
                 "componentType": 5125,
-                "count": mesh.triangles.len() * 3,
+                "count": indices.len(),
                 "type": "SCALAR"
             }
         ],
-        "bufferViews": [
-            {
-                "buffer": 0,
-                "byteOffset": position_offset,
-                "byteLength": position_length,
-                "target": 34962
-            },
-            {
-                "buffer": 0,
-                "byteOffset": normal_offset,
-                "byteLength": normal_length,
-                "target": 34962
+        "bufferViews": buffer_views,
+        "buffers": [buffer]
+    });
+
+    if let Some(colors_view) = colors_view {
+        let translucent = vertices.iter().any(|v| v.color.is_some_and(|c| c[3] < 1.0));
+        gltf["accessors"].as_array_mut().unwrap().push(json!({
+            "bufferView": colors_view,
+            "byteOffset": 0,
+            "componentType": 5126,
+            "count": vertices.len(),
+            "type": "VEC4"
+        }));
+        gltf["meshes"][0]["primitives"][0]["attributes"]["COLOR_0"] = json!(3);
+        gltf["meshes"][0]["primitives"][0]["material"] = json!(0);
+        gltf["materials"] = json!([{
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [1.0, 1.0, 1.0, 1.0],
+                "metallicFactor": 0.0
             },
+            "alphaMode": if translucent { "BLEND" } else { "OPAQUE" }
+        }]);
+    }
+
+    Ok((gltf, buffer_data))
+}
+
+/// One vertex as written to the buffer
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GltfVertex {
+    position: [f32; 3],
+    /// Unit length, as the spec requires for NORMAL
+    normal: [f32; 3],
+    color: Option<[f32; 4]>,
+}
+
+/// Deduplicated vertices and the triangle list indexing them
+///
+/// Triangle corners sharing position, normal, and color after conversion to `f32` are written
+/// once, while corners with different normals, as along hard edges, stay separate so shading
+/// is unchanged. Corners whose normal is zero or not finite use their face normal instead, and
+/// vertices no triangle refers to are left out.
+fn indexed_vertices(mesh: &Mesh) -> (Vec<GltfVertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::with_capacity(mesh.triangles.len() * 3);
+    let mut lookup: HashMap<[u32; 10], u32> = HashMap::new();
+
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.indices.map(|i| mesh.vertices[i].position);
+        let face_normal = (b - a)
+            .cross(&(c - a))
+            .try_normalize(f64::EPSILON)
+            .unwrap_or_else(Vector3::z);
+
+        for &i in &triangle.indices {
+            let source = &mesh.vertices[i];
+            let normal = source
+                .normal
+                .try_normalize(f64::EPSILON)
+                .filter(|n| n.iter().all(|c| c.is_finite()))
+                .unwrap_or(face_normal);
+            let vertex = GltfVertex {
+                position: [source.position.x, source.position.y, source.position.z]
+                    .map(|c| c as f32),
+                normal: [normal.x, normal.y, normal.z].map(|c| c as f32),
+                color: source.color,
+            };
+
+            let mut key = [0u32; 10];
+            let color = vertex.color.unwrap_or([f32::NAN; 4]);
+            for (slot, value) in key
+                .iter_mut()
+                .zip(vertex.position.iter().chain(&vertex.normal).chain(&color))
             {
-                "buffer": 0,
-                "byteOffset": indices_offset,
-                "byteLength": indices_length,
-                "target": 34963
-            }
-        ],
-        "buffers": [
-            {
-                "byteLength": buffer_data.len(),
-                "uri": "data.bin"
+                *slot = value.to_bits();
             }
-        ]
-    });
 
-    Ok((gltf, buffer_data))
+            let index = *lookup.entry(key).or_insert_with(|| {
+                vertices.push(vertex);
+                (vertices.len() - 1) as u32
+            });
+            indices.push(index);
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Decode one sRGB color channel to linear light
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
 }
 
-fn calculate_bounds(mesh: &Mesh) -> ([f32; 3], [f32; 3]) {
-    let mut min = [f32::MAX, f32::MAX, f32::MAX];
-    let mut max = [f32::MIN, f32::MIN, f32::MIN];
-
-    for vertex in &mesh.vertices {
-        min[0] = min[0].min(vertex.position.x as f32);
-        min[1] = min[1].min(vertex.position.y as f32);
-        min[2] = min[2].min(vertex.position.z as f32);
-        max[0] = max[0].max(vertex.position.x as f32);
-        max[1] = max[1].max(vertex.position.y as f32);
-        max[2] = max[2].max(vertex.position.z as f32);
+/// Component-wise bounds of the written positions, for the POSITION accessor's min and max
+fn calculate_bounds(vertices: &[GltfVertex]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+
+    for vertex in vertices {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(vertex.position[axis]);
+            max[axis] = max[axis].max(vertex.position[axis]);
+        }
     }
 
     (min, max)
@@ -239,6 +443,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_glb_header_with_explicit_format() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+
+        // The explicit format wins over an extension that would otherwise mean JSON
+        let file = NamedTempFile::with_suffix(".gltf")?;
+        let path = file.path().to_str().unwrap();
+        export_with_format(&mesh, path, GltfFormat::Binary, AttributeLayout::default())?;
+
+        let content = std::fs::read(path)?;
+        let u32_at = |i: usize| u32::from_le_bytes(content[i..i + 4].try_into().unwrap());
+        assert_eq!(&content[0..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8) as usize, content.len());
+
+        let json_length = u32_at(12) as usize;
+        assert_eq!(&content[16..20], b"JSON");
+        assert_eq!(json_length % 4, 0);
+        let bin_header = 20 + json_length;
+        assert_eq!(&content[bin_header + 4..bin_header + 8], b"BIN\0");
+        assert_eq!(bin_header + 8 + u32_at(bin_header) as usize, content.len());
+
+        let (gltf, bin) = read_glb(path)?;
+        assert!(gltf["buffers"][0]["uri"].is_null());
+        assert!(gltf["buffers"][0]["byteLength"].as_u64().unwrap() as usize <= bin.len());
+
+        assert_eq!(GltfFormat::from_path("model.GLB"), GltfFormat::Binary);
+        assert_eq!(GltfFormat::from_path("model.gltf"), GltfFormat::Json);
+
+        Ok(())
+    }
+
     #[test]
     fn test_export_gltf() -> Result<()> {
         let mesh = Primitive::sphere(5.0, 16).to_mesh();
@@ -255,4 +491,161 @@ mod tests {
 
         Ok(())
     }
+
+    /// Split a GLB file into its JSON document and binary chunk
+    fn read_glb(path: &str) -> Result<(serde_json::Value, Vec<u8>)> {
+        let content = std::fs::read(path)?;
+        let u32_at = |i: usize| u32::from_le_bytes(content[i..i + 4].try_into().unwrap()) as usize;
+
+        let json_len = u32_at(12);
+        let json = serde_json::from_slice(&content[20..20 + json_len])?;
+        let bin_start = 20 + json_len + 8;
+        let bin_len = u32_at(20 + json_len);
+        Ok((json, content[bin_start..bin_start + bin_len].to_vec()))
+    }
+
+    /// Decode a VEC3 float accessor, honoring bufferView stride
+    fn read_vec3_accessor(gltf: &serde_json::Value, bin: &[u8], accessor: usize) -> Vec<[f32; 3]> {
+        let accessor = &gltf["accessors"][accessor];
+        let view = &gltf["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+        let stride = view["byteStride"].as_u64().unwrap_or(12) as usize;
+        let base = view["byteOffset"].as_u64().unwrap() as usize
+            + accessor["byteOffset"].as_u64().unwrap() as usize;
+
+        (0..accessor["count"].as_u64().unwrap() as usize)
+            .map(|i| {
+                let at = base + i * stride;
+                [0, 1, 2].map(|c| {
+                    f32::from_le_bytes(bin[at + c * 4..at + c * 4 + 4].try_into().unwrap())
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_colored_cube_exports_vertex_colors() -> Result<()> {
+        let ast = crate::io::parse_scad("color([0.2, 0.4, 0.6, 0.5]) cube(10);")?;
+        let mesh = crate::ast::Evaluator::new().evaluate(&ast)?;
+
+        let file = NamedTempFile::with_suffix(".glb")?;
+        let path = file.path().to_str().unwrap();
+        export(&mesh, path)?;
+
+        let (gltf, bin) = read_glb(path)?;
+        let primitive = &gltf["meshes"][0]["primitives"][0];
+        assert_eq!(primitive["attributes"]["COLOR_0"], 3);
+        assert_eq!(
+            gltf["materials"][primitive["material"].as_u64().unwrap() as usize]["alphaMode"],
+            "BLEND"
+        );
+
+        let accessor = &gltf["accessors"][3];
+        assert_eq!(accessor["type"], "VEC4");
+        assert_eq!(accessor["count"], gltf["accessors"][0]["count"]);
+        let view = &gltf["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let colors: Vec<f32> = bin[offset..offset + view["byteLength"].as_u64().unwrap() as usize]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        // RGB is decoded from sRGB to linear, alpha passes through
+        let linear = [0.033_105, 0.132_868, 0.318_547, 0.5];
+        for color in colors.chunks_exact(4) {
+            for (component, expected) in color.iter().zip(linear) {
+                assert!((component - expected).abs() < 1e-5, "{:?}", color);
+            }
+        }
+
+        // Uncolored meshes don't gain a color attribute
+        export(
+            &Primitive::cube(Vector3::new(1.0, 1.0, 1.0), false).to_mesh(),
+            path,
+        )?;
+        let (gltf, _) = read_glb(path)?;
+        assert!(gltf["meshes"][0]["primitives"][0]["attributes"]["COLOR_0"].is_null());
+        assert!(gltf["materials"].is_null());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attribute_layouts_decode_identically() -> Result<()> {
+        let mesh = Primitive::sphere(5.0, 16).to_mesh();
+
+        let separate_file = NamedTempFile::with_suffix(".glb")?;
+        let interleaved_file = NamedTempFile::with_suffix(".glb")?;
+        let separate_path = separate_file.path().to_str().unwrap();
+        let interleaved_path = interleaved_file.path().to_str().unwrap();
+
+        export_with_layout(&mesh, separate_path, AttributeLayout::Separate)?;
+        export_with_layout(&mesh, interleaved_path, AttributeLayout::Interleaved)?;
+
+        let (separate, separate_bin) = read_glb(separate_path)?;
+        let (interleaved, interleaved_bin) = read_glb(interleaved_path)?;
+
+        assert_eq!(separate["bufferViews"].as_array().unwrap().len(), 3);
+        assert_eq!(interleaved["bufferViews"].as_array().unwrap().len(), 2);
+        assert_eq!(interleaved["bufferViews"][0]["byteStride"], 24);
+        assert_eq!(separate_bin.len(), interleaved_bin.len());
+
+        for accessor in [0, 1] {
+            let a = read_vec3_accessor(&separate, &separate_bin, accessor);
+            let b = read_vec3_accessor(&interleaved, &interleaved_bin, accessor);
+            assert_eq!(a.len(), separate["accessors"][accessor]["count"]);
+            assert_eq!(a, b);
+        }
+
+        let positions = read_vec3_accessor(&separate, &separate_bin, 0);
+        for decoded in &positions {
+            assert!(mesh.vertices.iter().any(|v| {
+                decoded[0] == v.position.x as f32 && decoded[2] == v.position.z as f32
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_indexed_primitive_with_unit_normals() -> Result<()> {
+        let mesh = Primitive::sphere(5.0, 16).to_mesh();
+        // Triangle soup: every corner its own vertex, as boolean results often come out
+        let mut soup = Mesh::new();
+        for triangle in &mesh.triangles {
+            let corners = triangle.indices.map(|i| soup.add_vertex(mesh.vertices[i]));
+            soup.add_triangle(crate::geometry::Triangle::new(corners));
+        }
+        soup.vertices[0].normal = Vector3::zeros();
+        soup.vertices[1].normal *= 3.0;
+
+        let file = NamedTempFile::with_suffix(".glb")?;
+        let path = file.path().to_str().unwrap();
+        export(&soup, path)?;
+
+        let (gltf, bin) = read_glb(path)?;
+        let primitive = &gltf["meshes"][0]["primitives"][0];
+        let normal = primitive["attributes"]["NORMAL"].as_u64().unwrap() as usize;
+        let indices = &gltf["accessors"][primitive["indices"].as_u64().unwrap() as usize];
+        assert_eq!(indices["count"], soup.triangle_count() * 3);
+        assert_eq!(indices["type"], "SCALAR");
+
+        let position =
+            &gltf["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+        let vertex_count = position["count"].as_u64().unwrap() as usize;
+        assert!(vertex_count < soup.vertex_count());
+        assert_eq!(position["min"], json!([-5.0, -5.0, -5.0]));
+        assert_eq!(position["max"], json!([5.0, 5.0, 5.0]));
+
+        let normals = read_vec3_accessor(&gltf, &bin, normal);
+        assert_eq!(normals.len(), vertex_count);
+        for n in normals {
+            let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            assert!(
+                (length - 1.0).abs() < 1e-5,
+                "normal {:?} is not unit length",
+                n
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Wavefront OBJ exporter
+
+use crate::geometry::Mesh;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Attributes referenced by each `f` record
+///
+/// OBJ importers disagree on which face forms they accept, so the exporter lets the caller
+/// pick one. Only attributes written to the file are referenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FaceFormat {
+    /// `f v v v`, positions only
+    VertexOnly,
+    /// `f v//vn v//vn v//vn`, positions and normals
+    #[default]
+    VertexNormal,
+    /// `f v/vt/vn v/vt/vn v/vt/vn`; needs texture coordinates on the mesh
+    VertexUvNormal,
+}
+
+/// Export mesh to OBJ format with the given face record format
+///
+/// Kernel meshes carry no texture coordinates, so [`FaceFormat::VertexUvNormal`] is an error.
+pub fn export(mesh: &Mesh, path: &str, format: FaceFormat) -> Result<()> {
+    if format == FaceFormat::VertexUvNormal {
+        bail!("OBJ face format v/vt/vn needs texture coordinates, which this mesh does not have");
+    }
+
+    let file = File::create(path).context("Failed to create OBJ file")?;
+    let mut writer = BufWriter::new(file);
+    write_obj(mesh, &mut writer, format)?;
+    writer.flush().context("Failed to write OBJ file")?;
+    Ok(())
+}
+
+fn write_obj<W: Write>(mesh: &Mesh, writer: &mut W, format: FaceFormat) -> Result<()> {
+    writeln!(writer, "# Polyframe Kernel Export")?;
+
+    for vertex in &mesh.vertices {
+        let p = vertex.position;
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    if format == FaceFormat::VertexNormal {
+        for vertex in &mesh.vertices {
+            let n = vertex.normal;
+            writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+    }
+
+    // Positions and normals are written in the same order, so both share one 1-based index
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.indices.map(|i| i + 1);
+        match format {
+            FaceFormat::VertexOnly => writeln!(writer, "f {} {} {}", a, b, c)?,
+            FaceFormat::VertexNormal => {
+                writeln!(writer, "f {}//{} {}//{} {}//{}", a, a, b, b, c, c)?
+            }
+            FaceFormat::VertexUvNormal => unreachable!("rejected by export"),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+    use nalgebra::Vector3;
+    use tempfile::NamedTempFile;
+
+    fn face_lines(format: FaceFormat) -> Result<Vec<String>> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+        let file = NamedTempFile::with_suffix(".obj")?;
+        let path = file.path().to_str().unwrap();
+
+        export(&mesh, path, format)?;
+
+        let content = std::fs::read_to_string(path)?;
+        Ok(content
+            .lines()
+            .filter(|line| line.starts_with("f "))
+            .map(str::to_string)
+            .collect())
+    }
+
+    #[test]
+    fn test_face_formats() -> Result<()> {
+        let plain = face_lines(FaceFormat::VertexOnly)?;
+        assert_eq!(plain.len(), 12);
+        assert!(plain.iter().all(|line| !line.contains('/')));
+
+        let with_normals = face_lines(FaceFormat::VertexNormal)?;
+        assert_eq!(with_normals.len(), 12);
+        assert_eq!(with_normals[0], "f 1//1 2//2 3//3");
+        assert!(with_normals
+            .iter()
+            .all(|line| line.matches("//").count() == 3));
+
+        let error = face_lines(FaceFormat::VertexUvNormal).unwrap_err();
+        assert!(error.to_string().contains("texture coordinates"));
+
+        Ok(())
+    }
+}
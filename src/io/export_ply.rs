@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! PLY (Polygon File Format) exporter
+
+use crate::geometry::Mesh;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Export mesh to PLY format
+///
+/// Writes positions and normals per vertex and one `3 i j k` face per triangle.
+/// `binary` selects `binary_little_endian` packing instead of ASCII.
+pub fn export(mesh: &Mesh, path: &str, binary: bool) -> Result<()> {
+    let file = File::create(path).context("Failed to create PLY file")?;
+    let mut writer = BufWriter::new(file);
+
+    writer.write_all(generate_header(mesh, binary).as_bytes())?;
+
+    if binary {
+        write_binary_body(mesh, &mut writer)?;
+    } else {
+        write_ascii_body(mesh, &mut writer)?;
+    }
+
+    writer.flush().context("Failed to write PLY file")?;
+    Ok(())
+}
+
+fn generate_header(mesh: &Mesh, binary: bool) -> String {
+    let format = if binary {
+        "binary_little_endian"
+    } else {
+        "ascii"
+    };
+
+    let mut header = String::new();
+    header.push_str("ply\n");
+    header.push_str(&format!("format {} 1.0\n", format));
+    header.push_str("comment Polyframe Kernel Export\n");
+    header.push_str(&format!("element vertex {}\n", mesh.vertices.len()));
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        header.push_str(&format!("property float {}\n", property));
+    }
+    header.push_str(&format!("element face {}\n", mesh.triangles.len()));
+    header.push_str("property list uchar int vertex_indices\n");
+    header.push_str("end_header\n");
+    header
+}
+
+fn write_ascii_body<W: Write>(mesh: &Mesh, writer: &mut W) -> Result<()> {
+    for vertex in &mesh.vertices {
+        writeln!(
+            writer,
+            "{} {} {} {} {} {}",
+            vertex.position.x as f32,
+            vertex.position.y as f32,
+            vertex.position.z as f32,
+            vertex.normal.x as f32,
+            vertex.normal.y as f32,
+            vertex.normal.z as f32
+        )?;
+    }
+
+    for triangle in &mesh.triangles {
+        writeln!(
+            writer,
+            "3 {} {} {}",
+            triangle.indices[0], triangle.indices[1], triangle.indices[2]
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_binary_body<W: Write>(mesh: &Mesh, writer: &mut W) -> Result<()> {
+    for vertex in &mesh.vertices {
+        for value in [
+            vertex.position.x,
+            vertex.position.y,
+            vertex.position.z,
+            vertex.normal.x,
+            vertex.normal.y,
+            vertex.normal.z,
+        ] {
+            writer.write_all(&(value as f32).to_le_bytes())?;
+        }
+    }
+
+    for triangle in &mesh.triangles {
+        writer.write_all(&[3u8])?;
+        for &index in &triangle.indices {
+            writer.write_all(&(index as i32).to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Primitive;
+    use nalgebra::Vector3;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_export_ply_binary_size() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+
+        let file = NamedTempFile::with_suffix(".ply")?;
+        let path = file.path().to_str().unwrap();
+
+        export(&mesh, path, true)?;
+
+        // Header + 6 floats per vertex + (uchar count + 3 ints) per face
+        let header_len = generate_header(&mesh, true).len();
+        let expected = header_len + mesh.vertex_count() * 6 * 4 + mesh.triangle_count() * (1 + 3 * 4);
+
+        let content = std::fs::read(path)?;
+        assert_eq!(content.len(), expected);
+        assert!(content.starts_with(b"ply\nformat binary_little_endian 1.0\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_ply_ascii() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
+
+        let file = NamedTempFile::with_suffix(".ply")?;
+        let path = file.path().to_str().unwrap();
+
+        export(&mesh, path, false)?;
+
+        let content = std::fs::read_to_string(path)?;
+        assert!(content.contains("element vertex 36"));
+        assert!(content.contains("element face 12"));
+        assert!(content.contains("property float nx"));
+        assert!(content.lines().last().unwrap().starts_with("3 "));
+
+        Ok(())
+    }
+}
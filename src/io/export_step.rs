@@ -1,22 +1,283 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) 2025 Polyframe Inc.
 
-//! STEP exporter (placeholder implementation)
+//! STEP exporter
+//!
+//! Writes an AP214 boundary representation: every face is a planar `ADVANCED_FACE` bounded
+//! by straight `EDGE_CURVE`s shared with its neighbors, collected into a `CLOSED_SHELL` and a
+//! `MANIFOLD_SOLID_BREP`. Coplanar adjacent triangles are merged into one polygonal face, so
+//! flat-sided models come out as CAD tools expect; curved regions stay faceted.
 
-use crate::geometry::Mesh;
-use anyhow::Result;
+use super::units::Unit;
+use crate::geometry::{welded_for_topology, Mesh};
+use anyhow::{Context, Result};
+use nalgebra::{Point3, Vector3};
+use std::collections::{HashMap, HashSet, VecDeque};
 
-/// Export mesh to STEP format (AP214 or AP242)
-///
-/// Note: Full STEP export requires complex CAD data structures.
-/// This is a placeholder that exports basic B-Rep representation.
+/// Largest distance from a face's plane at which a triangle still counts as coplanar
+const PLANE_EPSILON: f64 = 1e-6;
+/// Smallest cosine between two normals that still counts as parallel
+const NORMAL_COS_EPSILON: f64 = 1.0 - 1e-9;
+
+/// How mesh triangles become STEP faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepMode {
+    /// Merge coplanar edge-adjacent triangles into polygonal faces
+    #[default]
+    Brep,
+    /// One face per triangle
+    Faceted,
+}
+
+/// Settings for [`export_with_options`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StepOptions {
+    pub mode: StepMode,
+    /// Length unit of the file; coordinates are converted from millimeters
+    pub unit: Unit,
+}
+
+/// Export mesh to STEP format (AP214), merging coplanar triangles into faces
 pub fn export(mesh: &Mesh, path: &str) -> Result<()> {
-    let step_content = generate_step_content(mesh);
-    std::fs::write(path, step_content)?;
-    Ok(())
+    export_with_options(mesh, path, &StepOptions::default())
+}
+
+/// Export mesh to STEP format (AP214) with the given face mode
+pub fn export_with_mode(mesh: &Mesh, path: &str, mode: StepMode) -> Result<()> {
+    let options = StepOptions {
+        mode,
+        ..Default::default()
+    };
+    export_with_options(mesh, path, &options)
+}
+
+/// Export mesh to STEP format (AP214) with the face mode and unit chosen by `options`
+///
+/// A closed manifold mesh becomes a `MANIFOLD_SOLID_BREP`. Anything else is written as an
+/// `OPEN_SHELL` surface model, with a warning, since it does not bound a solid.
+pub fn export_with_options(mesh: &Mesh, path: &str, options: &StepOptions) -> Result<()> {
+    let step_content = generate_step_content(mesh, options);
+    std::fs::write(path, step_content)
+        .with_context(|| format!("Failed to write STEP file: {}", path))
+}
+
+/// A planar face bounded by loops of welded vertex indices
+struct PlanarFace {
+    origin: Point3<f64>,
+    normal: Vector3<f64>,
+    /// Outer boundary first, counterclockwise around the normal, then any holes
+    loops: Vec<Vec<usize>>,
+}
+
+/// Group triangles into planar faces and trace each face's boundary loops
+fn planar_faces(mesh: &Mesh, mode: StepMode) -> Vec<PlanarFace> {
+    let position = |i: usize| mesh.vertices[i].position;
+    let normals: Vec<Option<Vector3<f64>>> = mesh
+        .triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.indices.map(position);
+            (b - a).cross(&(c - a)).try_normalize(f64::EPSILON)
+        })
+        .collect();
+
+    let mut edge_triangles: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (i, triangle) in mesh.triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (triangle.indices[k], triangle.indices[(k + 1) % 3]);
+            edge_triangles
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push(i);
+        }
+    }
+
+    let mut assigned = vec![false; mesh.triangles.len()];
+    let mut faces = Vec::new();
+    for seed in 0..mesh.triangles.len() {
+        let Some(normal) = normals[seed] else {
+            continue;
+        };
+        if assigned[seed] {
+            continue;
+        }
+        assigned[seed] = true;
+        let origin = position(mesh.triangles[seed].indices[0]);
+
+        // Grow the face against the seed's plane, so small bends don't accumulate
+        let mut region = vec![seed];
+        let mut queue = VecDeque::from([seed]);
+        while let Some(current) = queue.pop_front() {
+            if mode == StepMode::Faceted {
+                break;
+            }
+            let indices = mesh.triangles[current].indices;
+            for k in 0..3 {
+                let (a, b) = (indices[k], indices[(k + 1) % 3]);
+                for &neighbor in &edge_triangles[&(a.min(b), a.max(b))] {
+                    let coplanar = normals[neighbor].is_some_and(|n| {
+                        n.dot(&normal) >= NORMAL_COS_EPSILON
+                            && mesh.triangles[neighbor].indices.iter().all(|&v| {
+                                (position(v) - origin).dot(&normal).abs() <= PLANE_EPSILON
+                            })
+                    });
+                    if coplanar && !assigned[neighbor] {
+                        assigned[neighbor] = true;
+                        region.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut loops = boundary_loops(mesh, &region);
+        let area = |l: &Vec<usize>| loop_area(mesh, l, &normal);
+        loops.sort_by(|a, b| area(b).total_cmp(&area(a)));
+        faces.push(PlanarFace {
+            origin,
+            normal,
+            loops,
+        });
+    }
+
+    faces
+}
+
+/// Loops of the region's directed edges that no other triangle of the region runs backwards
+fn boundary_loops(mesh: &Mesh, region: &[usize]) -> Vec<Vec<usize>> {
+    let directed: HashSet<(usize, usize)> = region
+        .iter()
+        .flat_map(|&t| {
+            let [a, b, c] = mesh.triangles[t].indices;
+            [(a, b), (b, c), (c, a)]
+        })
+        .collect();
+
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut boundary: Vec<(usize, usize)> = directed
+        .iter()
+        .copied()
+        .filter(|&(a, b)| !directed.contains(&(b, a)))
+        .collect();
+    boundary.sort_unstable();
+    for &(a, b) in &boundary {
+        outgoing.entry(a).or_default().push(b);
+    }
+
+    let mut loops = Vec::new();
+    for &(start, _) in &boundary {
+        while outgoing.get(&start).is_some_and(|next| !next.is_empty()) {
+            let mut chain = vec![start];
+            let mut current = start;
+            while let Some(next) = outgoing.get_mut(&current).and_then(|next| next.pop()) {
+                if next == start {
+                    break;
+                }
+                chain.push(next);
+                current = next;
+            }
+            if chain.len() >= 3 {
+                loops.push(chain);
+            }
+        }
+    }
+    loops
+}
+
+/// Signed area of a loop, positive when it winds counterclockwise around `normal`
+fn loop_area(mesh: &Mesh, vertices: &[usize], normal: &Vector3<f64>) -> f64 {
+    let n = vertices.len();
+    let newell: Vector3<f64> = (0..n)
+        .map(|i| {
+            let p = mesh.vertices[vertices[i]].position.coords;
+            let q = mesh.vertices[vertices[(i + 1) % n]].position.coords;
+            p.cross(&q)
+        })
+        .sum();
+    newell.dot(normal) / 2.0
+}
+
+/// Format a STEP real, which always carries a decimal point (`1.`, `0.5`, `1.E-7`)
+fn real(value: f64) -> String {
+    let value = if value == 0.0 { 0.0 } else { value };
+    let text = format!("{:?}", value);
+    match text.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{}E{}", mantissa, exponent)
+        }
+        Some((mantissa, exponent)) => format!("{}.E{}", mantissa, exponent),
+        None => text.trim_end_matches('0').to_string(),
+    }
+}
+
+fn triple(v: [f64; 3]) -> String {
+    format!("({},{},{})", real(v[0]), real(v[1]), real(v[2]))
+}
+
+/// Numbered entity instances of the DATA section
+struct EntityWriter {
+    output: String,
+    next_id: usize,
+    /// Length unit the file's coordinates are written in
+    unit: Unit,
+}
+
+impl EntityWriter {
+    fn add(&mut self, entity: &str) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.output.push_str(&format!("#{}={};\n", id, entity));
+        id
+    }
+
+    fn direction(&mut self, v: &Vector3<f64>) -> usize {
+        self.add(&format!("DIRECTION('',{})", triple([v.x, v.y, v.z])))
+    }
+
+    fn point(&mut self, p: &Point3<f64>) -> usize {
+        let p = p / self.unit.millimeters();
+        self.add(&format!("CARTESIAN_POINT('',{})", triple([p.x, p.y, p.z])))
+    }
+
+    /// The file's length unit; inches are defined by conversion from millimeters
+    fn length_unit(&mut self) -> usize {
+        match self.unit {
+            Unit::Millimeter => self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))"),
+            Unit::Centimeter => self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.CENTI.,.METRE.))"),
+            Unit::Meter => self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT($,.METRE.))"),
+            Unit::Inch => {
+                let millimeter = self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))");
+                let measure = self.add(&format!(
+                    "LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{})",
+                    real(Unit::Inch.millimeters()),
+                    millimeter
+                ));
+                let exponents = self.add("DIMENSIONAL_EXPONENTS(1.,0.,0.,0.,0.,0.,0.)");
+                self.add(&format!(
+                    "(CONVERSION_BASED_UNIT('INCH',#{})LENGTH_UNIT()NAMED_UNIT(#{}))",
+                    measure, exponents
+                ))
+            }
+        }
+    }
+}
+
+fn refs(ids: &[usize]) -> String {
+    let refs: Vec<String> = ids.iter().map(|id| format!("#{}", id)).collect();
+    format!("({})", refs.join(","))
 }
 
-fn generate_step_content(mesh: &Mesh) -> String {
+fn generate_step_content(mesh: &Mesh, options: &StepOptions) -> String {
+    let mesh = welded_for_topology(mesh);
+    let validation = mesh.validate();
+    let solid = validation.is_closed && validation.is_manifold;
+    if !solid && !mesh.triangles.is_empty() {
+        eprintln!(
+            "Warning: STEP export of a mesh that is not closed and manifold; \
+             writing an open surface model instead of a solid"
+        );
+    }
+
     let mut output = String::new();
 
     // STEP header
@@ -26,59 +287,155 @@ fn generate_step_content(mesh: &Mesh) -> String {
     output.push_str("FILE_NAME('mesh.step','");
     output.push_str(&chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string());
     output.push_str("',('Polyframe'),('Polyframe Inc.'),'Polyframe Kernel','','');\n");
-    output.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\n");
+    output.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));\n");
     output.push_str("ENDSEC;\n");
 
-    // DATA section
-    output.push_str("DATA;\n");
+    let mut w = EntityWriter {
+        output,
+        next_id: 1,
+        unit: options.unit,
+    };
+    w.output.push_str("DATA;\n");
 
-    let mut entity_id = 1;
+    // Product structure and representation context, in the chosen length unit and radians
+    let application = w.add("APPLICATION_CONTEXT('automotive_design')");
+    w.add(&format!(
+        "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+        application
+    ));
+    let product_context = w.add(&format!(
+        "PRODUCT_CONTEXT('',#{},'mechanical')",
+        application
+    ));
+    let product = w.add(&format!("PRODUCT('mesh','mesh','',(#{}))", product_context));
+    let formation = w.add(&format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+    let definition_context = w.add(&format!(
+        "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+        application
+    ));
+    let definition = w.add(&format!(
+        "PRODUCT_DEFINITION('design','',#{},#{})",
+        formation, definition_context
+    ));
+    let shape = w.add(&format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+    let length_unit = w.length_unit();
+    let angle_unit = w.add("(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))");
+    let solid_angle_unit = w.add("(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())");
+    let uncertainty = w.add(&format!(
+        "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{},'distance_accuracy_value',\
+         'confusion accuracy')",
+        real(options.unit.from_millimeters(PLANE_EPSILON)),
+        length_unit
+    ));
+    let context = w.add(&format!(
+        "(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{}))\
+         GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{}))REPRESENTATION_CONTEXT('',''))",
+        uncertainty, length_unit, angle_unit, solid_angle_unit
+    ));
 
-    // Cartesian points for vertices
-    let vertex_ids: Vec<usize> = mesh
-        .vertices
-        .iter()
-        .map(|v| {
-            let id = entity_id;
-            entity_id += 1;
-            output.push_str(&format!(
-                "#{}=CARTESIAN_POINT('',({}.,{}.,{}.));\n",
-                id, v.position.x, v.position.y, v.position.z
-            ));
-            id
-        })
-        .collect();
+    // Vertices and edges are shared between the faces that meet there
+    let mut vertex_ids: HashMap<usize, usize> = HashMap::new();
+    let mut edge_ids: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut face_ids = Vec::new();
 
-    // Directions for normals
-    let _normal_ids: Vec<usize> = mesh
-        .vertices
-        .iter()
-        .map(|v| {
-            let id = entity_id;
-            entity_id += 1;
-            output.push_str(&format!(
-                "#{}=DIRECTION('',({}.,{}.,{}.));\n",
-                id, v.normal.x, v.normal.y, v.normal.z
-            ));
-            id
-        })
-        .collect();
-
-    // Triangular faces
-    for triangle in &mesh.triangles {
-        let face_id = entity_id;
-        entity_id += 1;
+    for face in planar_faces(&mesh, options.mode) {
+        let mut bound_ids = Vec::new();
+        for (loop_index, vertices) in face.loops.iter().enumerate() {
+            let mut oriented = Vec::new();
+            for k in 0..vertices.len() {
+                let (a, b) = (vertices[k], vertices[(k + 1) % vertices.len()]);
+                let key = (a.min(b), a.max(b));
+                let edge = match edge_ids.get(&key) {
+                    Some(&edge) => edge,
+                    None => {
+                        let [start, end] = [key.0, key.1].map(|v| {
+                            *vertex_ids.entry(v).or_insert_with(|| {
+                                let point = w.point(&mesh.vertices[v].position);
+                                w.add(&format!("VERTEX_POINT('',#{})", point))
+                            })
+                        });
+                        let from = mesh.vertices[key.0].position;
+                        let span = mesh.vertices[key.1].position - from;
+                        let origin = w.point(&from);
+                        let direction = w.direction(&span.normalize());
+                        let vector = w.add(&format!(
+                            "VECTOR('',#{},{})",
+                            direction,
+                            real(options.unit.from_millimeters(span.norm()))
+                        ));
+                        let line = w.add(&format!("LINE('',#{},#{})", origin, vector));
+                        let edge =
+                            w.add(&format!("EDGE_CURVE('',#{},#{},#{},.T.)", start, end, line));
+                        edge_ids.insert(key, edge);
+                        edge
+                    }
+                };
+                let same_sense = if (a, b) == key { ".T." } else { ".F." };
+                oriented.push(w.add(&format!("ORIENTED_EDGE('',*,*,#{},{})", edge, same_sense)));
+            }
+            let edge_loop = w.add(&format!("EDGE_LOOP('',{})", refs(&oriented)));
+            let bound = if loop_index == 0 {
+                "FACE_OUTER_BOUND"
+            } else {
+                "FACE_BOUND"
+            };
+            bound_ids.push(w.add(&format!("{}('',#{},.T.)", bound, edge_loop)));
+        }
+        if bound_ids.is_empty() {
+            continue;
+        }
 
-        let v0 = vertex_ids[triangle.indices[0]];
-        let v1 = vertex_ids[triangle.indices[1]];
-        let v2 = vertex_ids[triangle.indices[2]];
-
-        output.push_str(&format!(
-            "#{}=FACE_OUTER_BOUND('',(#{},#{},#{}));\n",
-            face_id, v0, v1, v2
+        // Any in-plane direction will do as the plane's reference axis
+        let n = face.normal;
+        let helper = if n.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        let reference = n.cross(&helper).normalize();
+        let origin = w.point(&face.origin);
+        let axis = w.direction(&n);
+        let ref_direction = w.direction(&reference);
+        let placement = w.add(&format!(
+            "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+            origin, axis, ref_direction
         ));
+        let plane = w.add(&format!("PLANE('',#{})", placement));
+        face_ids.push(w.add(&format!(
+            "ADVANCED_FACE('',{},#{},.T.)",
+            refs(&bound_ids),
+            plane
+        )));
     }
 
+    let world_origin = w.point(&Point3::origin());
+    let world_z = w.direction(&Vector3::z());
+    let world_x = w.direction(&Vector3::x());
+    let world = w.add(&format!(
+        "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+        world_origin, world_z, world_x
+    ));
+    let representation = if solid {
+        let shell = w.add(&format!("CLOSED_SHELL('',{})", refs(&face_ids)));
+        let brep = w.add(&format!("MANIFOLD_SOLID_BREP('',#{})", shell));
+        w.add(&format!(
+            "ADVANCED_BREP_SHAPE_REPRESENTATION('',(#{},#{}),#{})",
+            brep, world, context
+        ))
+    } else {
+        let shell = w.add(&format!("OPEN_SHELL('',{})", refs(&face_ids)));
+        let model = w.add(&format!("SHELL_BASED_SURFACE_MODEL('',(#{}))", shell));
+        w.add(&format!(
+            "MANIFOLD_SURFACE_SHAPE_REPRESENTATION('',(#{},#{}),#{})",
+            model, world, context
+        ))
+    };
+    w.add(&format!(
+        "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+        shape, representation
+    ));
+
+    let mut output = w.output;
     output.push_str("ENDSEC;\n");
     output.push_str("END-ISO-10303-21;\n");
 
@@ -92,6 +449,10 @@ mod tests {
     use nalgebra::Vector3;
     use tempfile::NamedTempFile;
 
+    fn count(content: &str, entity: &str) -> usize {
+        content.matches(&format!("={}(", entity)).count()
+    }
+
     #[test]
     fn test_export_step() -> Result<()> {
         let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), true).to_mesh();
@@ -108,4 +469,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cube_exports_six_planar_faces() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 20.0, 30.0), false).to_mesh();
+
+        let file = NamedTempFile::with_suffix(".step")?;
+        let path = file.path().to_str().unwrap();
+        export(&mesh, path)?;
+
+        let content = std::fs::read_to_string(path)?;
+        assert_eq!(count(&content, "ADVANCED_FACE"), 6);
+        assert_eq!(count(&content, "PLANE"), 6);
+        assert_eq!(count(&content, "FACE_OUTER_BOUND"), 6);
+        assert_eq!(count(&content, "EDGE_CURVE"), 12);
+        assert_eq!(count(&content, "VERTEX_POINT"), 8);
+        assert_eq!(count(&content, "ORIENTED_EDGE"), 24);
+        assert_eq!(count(&content, "CLOSED_SHELL"), 1);
+        assert_eq!(count(&content, "MANIFOLD_SOLID_BREP"), 1);
+        assert!(content.contains("CARTESIAN_POINT('',(10.,20.,30.))"));
+
+        // Faceted mode keeps the triangulation
+        export_with_mode(&mesh, path, StepMode::Faceted)?;
+        let content = std::fs::read_to_string(path)?;
+        assert_eq!(count(&content, "ADVANCED_FACE"), 12);
+        assert_eq!(count(&content, "EDGE_CURVE"), 18);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_faces_with_holes_and_open_meshes() {
+        // A cube with a cube-shaped notch cut into its top leaves an L-shaped top face
+        let ast = crate::io::parse_scad(
+            "difference() { cube([10, 10, 10]); translate([5, 5, 5]) cube([10, 10, 10]); }",
+        )
+        .unwrap();
+        let mesh = crate::ast::Evaluator::new().evaluate(&ast).unwrap();
+        let content = generate_step_content(&mesh, &StepOptions::default());
+        assert_eq!(count(&content, "ADVANCED_FACE"), 9);
+        assert_eq!(count(&content, "CLOSED_SHELL"), 1);
+
+        // Removing a face leaves an open surface model
+        let mut open = Primitive::cube(Vector3::new(1.0, 1.0, 1.0), false).to_mesh();
+        open.triangles.truncate(10);
+        let content = generate_step_content(&open, &StepOptions::default());
+        assert_eq!(count(&content, "OPEN_SHELL"), 1);
+        assert_eq!(count(&content, "MANIFOLD_SOLID_BREP"), 0);
+        assert_eq!(count(&content, "ADVANCED_FACE"), 5);
+    }
+
+    #[test]
+    fn test_step_reals() {
+        assert_eq!(real(10.0), "10.");
+        assert_eq!(real(-0.0), "0.");
+        assert_eq!(real(0.25), "0.25");
+        assert_eq!(real(1e-7), "1.E-7");
+        assert_eq!(real(1.5e20), "1.5E20");
+    }
 }
@@ -5,10 +5,118 @@
 
 use crate::geometry::Mesh;
 use anyhow::{Context, Result};
+use nalgebra::{Point3, Vector3};
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
 use std::path::Path;
 
+/// Receiver for triangles produced incrementally, see [`crate::render_streaming`]
+pub trait TriangleSink {
+    /// Accept one triangle with its facet normal
+    fn triangle(&mut self, vertices: [Point3<f64>; 3], normal: Vector3<f64>) -> Result<()>;
+
+    /// Feed every triangle of `mesh`, with the same normals [`export_stl`] writes
+    fn mesh(&mut self, mesh: &Mesh) -> Result<()> {
+        for tri in &mesh.triangles {
+            let [v0, v1, v2] = tri.indices.map(|i| &mesh.vertices[i]);
+            let normal = (v0.normal + v1.normal + v2.normal) / 3.0;
+            self.triangle([v0.position, v1.position, v2.position], normal)?;
+        }
+        Ok(())
+    }
+}
+
+/// STL file written one triangle at a time, so the whole mesh never has to be in memory
+///
+/// Like [`export_stl`], paths ending in `.stl` get binary STL and anything else ASCII. The
+/// binary triangle count is patched into the header by [`StlStreamWriter::finish`], which
+/// must be called to complete the file. [`StlStreamWriter::new`] streams to any seekable
+/// writer instead, such as a `Cursor<Vec<u8>>` for STL bytes in memory.
+pub struct StlStreamWriter<W: Write + Seek = BufWriter<File>> {
+    writer: W,
+    binary: bool,
+    count: u32,
+}
+
+impl StlStreamWriter {
+    pub fn create(path: &str) -> Result<Self> {
+        let file = File::create(path).context("Failed to create STL file")?;
+        Self::new(BufWriter::new(file), path.ends_with(".stl"))
+    }
+}
+
+impl<W: Write + Seek> StlStreamWriter<W> {
+    pub fn new(mut writer: W, binary: bool) -> Result<Self> {
+        if binary {
+            // 80-byte header, then a triangle count filled in by `finish`
+            writer.write_all(&[0; 84])?;
+        } else {
+            writeln!(writer, "solid mesh")?;
+        }
+        Ok(Self {
+            writer,
+            binary,
+            count: 0,
+        })
+    }
+
+    /// Complete the file and return the number of triangles written
+    pub fn finish(mut self) -> Result<u32> {
+        self.complete()?;
+        Ok(self.count)
+    }
+
+    /// Complete the file and hand back the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.complete()?;
+        Ok(self.writer)
+    }
+
+    fn complete(&mut self) -> Result<()> {
+        if self.binary {
+            self.writer.seek(SeekFrom::Start(80))?;
+            self.writer.write_all(&self.count.to_le_bytes())?;
+            self.writer.seek(SeekFrom::End(0))?;
+        } else {
+            writeln!(self.writer, "endsolid mesh")?;
+        }
+        self.writer.flush().context("Failed to write STL file")
+    }
+}
+
+impl<W: Write + Seek> TriangleSink for StlStreamWriter<W> {
+    fn triangle(&mut self, vertices: [Point3<f64>; 3], normal: Vector3<f64>) -> Result<()> {
+        if self.binary {
+            let mut record = Vec::with_capacity(50);
+            let points = [Point3::from(normal)].into_iter().chain(vertices);
+            for p in points {
+                for value in [p.x, p.y, p.z] {
+                    record.extend_from_slice(&(value as f32).to_le_bytes());
+                }
+            }
+            record.extend_from_slice(&[0, 0]);
+            self.writer.write_all(&record)?;
+        } else {
+            writeln!(self.writer, "  facet normal {} {} {}", normal.x, normal.y, normal.z)?;
+            writeln!(self.writer, "    outer loop")?;
+            for v in vertices {
+                writeln!(self.writer, "      vertex {} {} {}", v.x, v.y, v.z)?;
+            }
+            writeln!(self.writer, "    endloop")?;
+            writeln!(self.writer, "  endfacet")?;
+        }
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Encode a mesh as STL in memory, binary or ASCII
+pub fn stl_bytes(mesh: &Mesh, binary: bool) -> Result<Vec<u8>> {
+    let mut writer = StlStreamWriter::new(Cursor::new(Vec::new()), binary)?;
+    writer.mesh(mesh)?;
+    Ok(writer.into_inner()?.into_inner())
+}
+
 /// Export mesh to STL format
 pub fn export_stl(mesh: &Mesh, path: &str) -> Result<()> {
     let file_path = Path::new(path);
@@ -116,4 +224,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_stream_writer_matches_export() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 20.0, 30.0), true).to_mesh();
+        let exported = NamedTempFile::with_suffix(".stl")?;
+        let streamed = NamedTempFile::with_suffix(".stl")?;
+
+        export_stl(&mesh, exported.path().to_str().unwrap())?;
+        let mut writer = StlStreamWriter::create(streamed.path().to_str().unwrap())?;
+        writer.mesh(&mesh)?;
+        writer.mesh(&mesh)?;
+        assert_eq!(writer.finish()?, 24);
+
+        let exported = std::fs::read(exported.path())?;
+        let streamed = std::fs::read(streamed.path())?;
+        assert_eq!(streamed.len(), 84 + 24 * 50);
+        assert_eq!(&streamed[84..84 + 12 * 50], &exported[84..]);
+
+        let triangles = stl_io::create_stl_reader(&mut std::io::Cursor::new(streamed))?.count();
+        assert_eq!(triangles, 24);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stl_bytes_parse_back() -> Result<()> {
+        let mesh = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+
+        let binary = stl_bytes(&mesh, true)?;
+        assert_eq!(binary.len(), 84 + 12 * 50);
+        assert_eq!(stl_io::create_stl_reader(&mut Cursor::new(binary))?.count(), 12);
+
+        let ascii = stl_bytes(&mesh, false)?;
+        assert!(ascii.starts_with(b"solid mesh\n"));
+        assert_eq!(stl_io::create_stl_reader(&mut Cursor::new(ascii))?.count(), 12);
+
+        Ok(())
+    }
 }
@@ -1,17 +1,143 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) 2025 Polyframe Inc.
 
-//! SCAD file importer
+//! SCAD, STL, and OBJ file importers
 
 use crate::ast::Node;
-use anyhow::{Context, Result};
-use std::fs;
+use crate::geometry::{Mesh, Triangle, Vertex};
+use anyhow::{bail, Context, Result};
+use nalgebra::{Point3, Vector3};
+use std::fs::{self, File};
+use std::path::Path;
 
 /// Import a .scad file and parse it into an AST
+///
+/// `import()` paths inside the file resolve relative to the file's directory.
 pub fn import_scad_file(path: &str) -> Result<Node> {
     let source = fs::read_to_string(path).context(format!("Failed to read SCAD file: {}", path))?;
+    let options = super::ParseOptions {
+        base_dir: Path::new(path).parent().map(Path::to_path_buf),
+        ..Default::default()
+    };
 
-    super::parse_scad(&source).context(format!("Failed to parse SCAD file: {}", path))
+    super::parse_scad_with_options(&source, &options)
+        .context(format!("Failed to parse SCAD file: {}", path))
+}
+
+/// Load the mesh behind an `import()` node, picking the importer by file extension
+///
+/// STL soups are welded so the result is an indexed mesh like the primitives.
+pub(crate) fn import_mesh(path: &Path) -> Result<Mesh> {
+    if !path.is_file() {
+        bail!("Imported file not found: {}", path.display());
+    }
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("stl") => {
+            let mut mesh = import_stl(path)?;
+            mesh.deduplicate_vertices(1e-6);
+            mesh.recompute_normals();
+            Ok(mesh)
+        }
+        Some("obj") => import_obj(path),
+        _ => bail!("Unsupported import format: {}", path.display()),
+    }
+}
+
+/// Import an STL file as a triangle soup
+///
+/// Every facet gets its own three vertices carrying the facet normal, matching the file
+/// exactly. Call [`Mesh::deduplicate_vertices`] to turn the soup into an indexed mesh.
+pub fn import_stl(path: impl AsRef<Path>) -> Result<Mesh> {
+    let path = path.as_ref();
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open STL file: {:?}", path))?;
+    let stl = stl_io::read_stl(&mut file).context("Failed to read STL file")?;
+
+    let mut mesh = Mesh::with_capacity(stl.faces.len() * 3, stl.faces.len());
+    for face in &stl.faces {
+        let normal = Vector3::new(
+            face.normal[0] as f64,
+            face.normal[1] as f64,
+            face.normal[2] as f64,
+        );
+        let indices = face.vertices.map(|v| {
+            let p = &stl.vertices[v];
+            mesh.add_vertex(Vertex::new(
+                Point3::new(p[0] as f64, p[1] as f64, p[2] as f64),
+                normal,
+            ))
+        });
+        mesh.add_triangle(Triangle::new(indices));
+    }
+
+    Ok(mesh)
+}
+
+/// Import a Wavefront OBJ file as an indexed mesh
+///
+/// Only vertex positions and faces are read; polygons are fan-triangulated, and texture or
+/// normal references in face entries (`v/vt/vn`) are ignored. Normals are recomputed.
+pub fn import_obj(path: impl AsRef<Path>) -> Result<Mesh> {
+    let path = path.as_ref();
+    let source =
+        fs::read_to_string(path).with_context(|| format!("Failed to read OBJ file: {:?}", path))?;
+
+    let mut mesh = Mesh::new();
+    for (line_number, line) in source.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+        let context = || format!("Invalid OBJ line {}: {}", line_number + 1, line);
+
+        match fields.next() {
+            Some("v") => {
+                let coords: Vec<f64> = fields
+                    .take(3)
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .with_context(context)?;
+                if coords.len() != 3 {
+                    bail!(context());
+                }
+                mesh.add_vertex(Vertex::new(
+                    Point3::new(coords[0], coords[1], coords[2]),
+                    Vector3::zeros(),
+                ));
+            }
+            Some("f") => {
+                // Indices are 1-based, or negative to count back from the latest vertex
+                let vertex_count = mesh.vertex_count() as i64;
+                let indices: Vec<usize> = fields
+                    .map(|entry| {
+                        let index: i64 = entry.split('/').next().unwrap_or("").parse()?;
+                        let resolved = if index < 0 {
+                            vertex_count + index
+                        } else {
+                            index - 1
+                        };
+                        if !(0..vertex_count).contains(&resolved) {
+                            bail!("vertex index {} out of range", index);
+                        }
+                        Ok(resolved as usize)
+                    })
+                    .collect::<Result<_>>()
+                    .with_context(context)?;
+                if indices.len() < 3 {
+                    bail!(context());
+                }
+                for k in 1..indices.len() - 1 {
+                    mesh.add_triangle(Triangle::new([indices[0], indices[k], indices[k + 1]]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    mesh.recompute_normals();
+    Ok(mesh)
 }
 
 #[cfg(test)]
@@ -30,4 +156,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_import_stl_deduplicates_to_indexed() -> Result<()> {
+        let cube =
+            crate::geometry::Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        let file = NamedTempFile::with_suffix(".stl")?;
+        super::super::export_stl(&cube, file.path().to_str().unwrap())?;
+
+        let mut mesh = import_stl(file.path())?;
+        assert_eq!(mesh.vertex_count(), 36);
+        assert_eq!(mesh.triangle_count(), 12);
+
+        assert_eq!(mesh.deduplicate_vertices(1e-6), 28);
+        assert_eq!(mesh.vertex_count(), 8);
+        assert_eq!(mesh.triangle_count(), 12);
+
+        // Idempotent
+        let before: Vec<_> = mesh.vertices.iter().map(|v| v.position).collect();
+        assert_eq!(mesh.deduplicate_vertices(1e-6), 0);
+        assert!(mesh.vertices.iter().map(|v| v.position).eq(before));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scad_import_resolves_relative_to_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let part =
+            crate::geometry::Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+        super::super::export_stl(&part, dir.path().join("part.stl").to_str().unwrap())?;
+        let scad = dir.path().join("model.scad");
+        fs::write(
+            &scad,
+            "import(\"part.stl\");\ntranslate([20, 0, 0]) cube([5, 5, 5]);\n",
+        )?;
+
+        let ast = import_scad_file(scad.to_str().unwrap())?;
+        let mesh = crate::render_node(&ast)?;
+        assert_eq!(mesh.triangle_count(), 24);
+        let bbox = mesh.bounding_box();
+        assert!((bbox.min.x - 0.0).abs() < 1e-6 && (bbox.max.x - 25.0).abs() < 1e-6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scad_import_missing_file_reports_absolute_path() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let scad = dir.path().join("model.scad");
+        fs::write(&scad, "import(\"missing.stl\");\n")?;
+
+        let ast = import_scad_file(scad.to_str().unwrap())?;
+        let err = crate::render_node(&ast).unwrap_err();
+        let expected = std::path::absolute(dir.path().join("missing.stl"))?;
+        assert!(format!("{:#}", err).contains(&expected.display().to_string()));
+
+        Ok(())
+    }
 }
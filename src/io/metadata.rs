@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Package metadata written by exporters that support it
+
+/// Descriptive fields for an exported model, for provenance in asset repositories
+///
+/// Every field is optional; unset fields are left out of the file. The generating
+/// application is always recorded, see [`generator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelMetadata {
+    pub title: Option<String>,
+    /// Author of the model
+    pub designer: Option<String>,
+    pub description: Option<String>,
+    /// Date the model was created, preferably ISO 8601 (`YYYY-MM-DD`)
+    pub creation_date: Option<String>,
+}
+
+/// Name and version of this kernel, as recorded in exported files
+pub(crate) fn generator() -> String {
+    format!("Polyframe {}", env!("CARGO_PKG_VERSION"))
+}
@@ -3,18 +3,41 @@
 
 //! I/O module - parsing, importing, and exporting
 
+mod colors;
 mod compare;
 mod exporter;
 mod importer;
+mod metadata;
 mod parser;
+mod units;
 mod export_3mf;
 mod export_gltf;
+mod export_obj;
+mod export_ply;
 mod export_step;
 
-pub use compare::{compare_meshes, MeshComparison};
-pub use export_3mf::export as export_3mf;
-pub use export_gltf::export as export_gltf;
-pub use export_step::export as export_step;
-pub use exporter::export_stl;
-pub use importer::import_scad_file;
-pub use parser::parse_scad;
+pub use compare::{compare_meshes, compare_to_reference, MeshComparison};
+pub use export_3mf::{
+    export as export_3mf, export_unvalidated as export_3mf_unvalidated,
+    export_with_metadata as export_3mf_with_metadata,
+    export_with_options as export_3mf_with_options, validate_for_3mf as validate_3mf,
+    ThreeMfOptions,
+};
+pub use export_gltf::{
+    export as export_gltf, export_with_format as export_gltf_with_format,
+    export_with_layout as export_gltf_with_layout, AttributeLayout, GltfFormat,
+};
+pub use export_obj::{export as export_obj, FaceFormat};
+pub use export_ply::export as export_ply;
+pub use export_step::{
+    export as export_step, export_with_mode as export_step_with_mode,
+    export_with_options as export_step_with_options, StepMode, StepOptions,
+};
+pub use exporter::{export_stl, stl_bytes, StlStreamWriter, TriangleSink};
+pub use importer::{import_obj, import_scad_file, import_stl};
+pub(crate) use importer::import_mesh;
+pub use metadata::ModelMetadata;
+pub use parser::{
+    parse_scad, parse_scad_with_options, AssertionFailed, ParseError, ParseOptions,
+};
+pub use units::Unit;
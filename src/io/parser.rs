@@ -3,88 +3,302 @@
 
 //! OpenSCAD parser using pest
 
-use crate::ast::{Node, NodeKind, TransformOp, Vec3};
-use anyhow::{anyhow, Context, Result};
-use nalgebra::Vector3;
+use super::colors::parse_color;
+use crate::ast::{Node, NodeKind, Span, TransformOp, Vec2, Vec3};
+use crate::geometry::{facet_count, DEFAULT_FA, DEFAULT_FS};
+use anyhow::{anyhow, bail, Context, Result};
+use nalgebra::{Point3, Vector3};
+use pest::error::LineColLocation;
 use pest::Parser;
 use pest_derive::Parser;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[grammar = "io/scad.pest"]
 struct ScadParser;
 
+/// Options controlling what the parser records on each node
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Capture the source span of the statement that produced each node
+    pub spans: bool,
+    /// Directory relative `import()` paths resolve against; the working directory if unset
+    pub base_dir: Option<PathBuf>,
+}
+
+/// Syntax error in SCAD source, positioned for editors to underline
+///
+/// [`parse_scad`] returns it inside an [`anyhow::Error`]; get it back with
+/// `error.downcast_ref::<ParseError>()`. Errors found after the syntax check, such as a
+/// call to an unknown module, are not positioned and come back as plain errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// What the parser expected, e.g. "expected expr"
+    pub message: String,
+    /// 1-based line of the offending token
+    pub line: usize,
+    /// 1-based column of the offending token, in characters
+    pub column: usize,
+    /// Source line containing the error
+    pub snippet: String,
+}
+
+impl ParseError {
+    fn from_pest(error: pest::error::Error<Rule>) -> Self {
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(pos) | LineColLocation::Span(pos, _) => pos,
+        };
+        Self {
+            message: error.variant.message().into_owned(),
+            line,
+            column,
+            snippet: error.line().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse SCAD source at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A false `assert()` condition, which stops the parse
+///
+/// Like [`ParseError`], it is returned inside an [`anyhow::Error`] and can be downcast, so
+/// callers can tell a model rejecting its own parameters apart from a kernel failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssertionFailed {
+    /// Source text of the condition, e.g. `width > 0`
+    pub expression: String,
+    /// The assertion's message, if it has one
+    pub message: Option<String>,
+    /// 1-based line of the `assert`
+    pub line: usize,
+    /// 1-based column of the `assert`
+    pub column: usize,
+}
+
+impl fmt::Display for AssertionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Assertion '{}' failed at line {}, column {}",
+            self.expression, self.line, self.column
+        )?;
+        match &self.message {
+            Some(message) => write!(f, ": {}", message),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for AssertionFailed {}
+
 /// Parse OpenSCAD source code into an AST
 pub fn parse_scad(source: &str) -> Result<Node> {
-    let mut pairs =
-        ScadParser::parse(Rule::program, source).context("Failed to parse SCAD source")?;
+    parse_scad_with_options(source, &ParseOptions::default())
+}
+
+/// Parse OpenSCAD source code into an AST with explicit parser options
+pub fn parse_scad_with_options(source: &str, options: &ParseOptions) -> Result<Node> {
+    let mut pairs = ScadParser::parse(Rule::program, source).map_err(ParseError::from_pest)?;
 
     let mut statements = Vec::new();
+    let mut program_span = None;
 
     // Get the program node and iterate over its children
     if let Some(program) = pairs.next() {
-        for pair in program.into_inner() {
-            match pair.as_rule() {
-                Rule::statement => {
-                    if let Some(node) = parse_statement(pair)? {
-                        statements.push(node);
-                    }
-                }
-                Rule::EOI => {}
-                _ => {}
-            }
-        }
+        program_span = span_of(&program, options);
+        statements = parse_statements(program.into_inner(), &Scope::new(options))?;
     }
 
     // If single statement, return it directly
     if statements.len() == 1 {
         Ok(statements.into_iter().next().unwrap())
     } else if statements.is_empty() {
-        Ok(Node::new(NodeKind::Empty))
+        Ok(Node::new(NodeKind::Empty).with_span(program_span))
     } else {
         // Multiple statements become a union
-        Ok(Node::new(NodeKind::Union(statements)))
+        Ok(Node::new(NodeKind::Union(statements)).with_span(program_span))
+    }
+}
+
+/// Capture the span of a pair when spans are enabled
+fn span_of(pair: &pest::iterators::Pair<Rule>, options: &ParseOptions) -> Option<Span> {
+    if !options.spans {
+        return None;
     }
+
+    let span = pair.as_span();
+    let (line, col) = span.start_pos().line_col();
+    Some(Span {
+        start: span.start(),
+        end: span.end(),
+        line,
+        col,
+    })
 }
 
-fn parse_statement(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
+/// Give synthetic nodes (e.g. the translate wrapping a centered cylinder) the span
+/// of the statement that produced them. Nested statements already carry their own span.
+fn fill_missing_spans(node: &mut Node, span: Span) {
+    if node.span.is_some() {
+        return;
+    }
+    node.span = Some(span);
+
+    match &mut node.kind {
+        NodeKind::Union(children)
+        | NodeKind::Difference(children)
+        | NodeKind::Intersection(children)
+        | NodeKind::Transform { children, .. }
+        | NodeKind::Resize { children, .. }
+        | NodeKind::Offset { children, .. }
+        | NodeKind::Projection { children, .. }
+        | NodeKind::Color { children, .. } => {
+            for child in children.iter_mut() {
+                fill_missing_spans(child, span);
+            }
+        }
+        NodeKind::If {
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            for child in then_branch.iter_mut().chain(else_branch) {
+                fill_missing_spans(child, span);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Deepest chain of nested module calls, so runaway recursion errors instead of overflowing
+const MAX_MODULE_DEPTH: usize = 100;
+
+/// Variables and modules visible to the statements being parsed
+///
+/// A module call sees the caller's scope plus its bound parameters.
+#[derive(Clone)]
+struct Scope<'a, 'i> {
+    options: &'a ParseOptions,
+    variables: HashMap<String, Value>,
+    modules: HashMap<String, Module<'i>>,
+    /// Number of module calls being expanded
+    depth: usize,
+}
+
+impl<'a> Scope<'a, '_> {
+    fn new(options: &'a ParseOptions) -> Self {
+        Self {
+            options,
+            variables: HashMap::new(),
+            modules: HashMap::new(),
+            depth: 0,
+        }
+    }
+}
+
+/// A user-defined module: its parameters with optional defaults, and its unparsed body
+#[derive(Clone)]
+struct Module<'i> {
+    params: Vec<(String, Option<pest::iterators::Pair<'i, Rule>>)>,
+    body: pest::iterators::Pair<'i, Rule>,
+}
+
+impl<'i> Module<'i> {
+    /// Read a `module_def` into its name and definition
+    fn from_pair(pair: pest::iterators::Pair<'i, Rule>) -> (String, Self) {
+        let mut name = String::new();
+        let mut params = Vec::new();
+        let mut body = None;
+
+        for part in pair.into_inner() {
+            match part.as_rule() {
+                Rule::ident => name = part.as_str().to_string(),
+                Rule::module_params => {
+                    for param in part.into_inner() {
+                        let mut inner = param.into_inner();
+                        let param_name = inner.next().unwrap().as_str().to_string();
+                        params.push((param_name, inner.next()));
+                    }
+                }
+                Rule::block_or_stmt => body = Some(part),
+                _ => {}
+            }
+        }
+
+        (name, Self { params, body: body.unwrap() })
+    }
+}
+
+fn parse_statement<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let span = span_of(&pair, scope.options);
     let inner = pair
         .into_inner()
         .next()
         .ok_or_else(|| anyhow!("Empty statement"))?;
 
-    match inner.as_rule() {
-        Rule::primitive_stmt => parse_primitive(inner),
-        Rule::transform_stmt => parse_transform(inner),
-        Rule::boolean_stmt => parse_boolean(inner),
-        Rule::module_call => Ok(Some(Node::new(NodeKind::Empty))), // Ignore for now
-        _ => Ok(None),
-    }
+    let node = match inner.as_rule() {
+        Rule::primitive_stmt => parse_primitive(inner, scope)?,
+        Rule::transform_stmt => parse_transform(inner, scope)?,
+        Rule::boolean_stmt => parse_boolean(inner, scope)?,
+        Rule::module_call => parse_module_call(inner, scope)?,
+        Rule::if_stmt => parse_if(inner, scope)?,
+        Rule::echo_stmt => parse_echo(inner, scope)?,
+        Rule::assert_stmt => parse_assert(inner, scope)?,
+        Rule::import_stmt => parse_import(inner, scope)?,
+        // Definitions and assignments are applied by the enclosing statement list
+        _ => None,
+    };
+
+    Ok(node.map(|mut node| {
+        if let Some(span) = span {
+            fill_missing_spans(&mut node, span);
+        }
+        node
+    }))
 }
 
-fn parse_primitive(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
+fn parse_primitive<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
     let inner = pair.into_inner().next().unwrap();
 
     match inner.as_rule() {
         Rule::cube_stmt => {
-            let params = parse_params(inner)?;
+            let params = parse_params(inner, scope)?;
             let size = params
-                .get_vector("size")
-                .or_else(|| params.get_positional_vector(0))
+                .get_vector("size")?
+                .or(params.get_positional_vector(0)?)
                 .unwrap_or(Vector3::new(1.0, 1.0, 1.0));
             let center = params.get_boolean("center").unwrap_or(false);
             Ok(Some(Node::new(NodeKind::Cube { size, center })))
         }
         Rule::sphere_stmt => {
-            let params = parse_params(inner)?;
+            let params = parse_params(inner, scope)?;
             let r = params
                 .get_number("r")
                 .or_else(|| params.get_positional_number(0))
                 .unwrap_or(1.0) as f64;
-            let fn_ = params.get_number("$fn").map(|v| v as u32).unwrap_or(32);
+            let fn_ = params.get_fragments(r);
             Ok(Some(Node::new(NodeKind::Sphere { r, fn_ })))
         }
         Rule::cylinder_stmt => {
-            let params = parse_params(inner)?;
+            let params = parse_params(inner, scope)?;
             let h = params
                 .get_number("h")
                 .or_else(|| params.get_positional_number(0))
@@ -111,7 +325,7 @@ fn parse_primitive(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
                 .unwrap_or(r as f32)) as f64;
             
             let center = params.get_boolean("center").unwrap_or(false);
-            let fn_ = params.get_number("$fn").map(|v| v as u32).unwrap_or(32);
+            let fn_ = params.get_fragments(r1.max(r2));
             
             // If r1 != r2, use Cone node; otherwise use Cylinder
             if (r1 - r2).abs() > 1e-6 {
@@ -138,22 +352,151 @@ fn parse_primitive(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
                 Ok(Some(node))
             }
         }
+        Rule::torus_stmt => {
+            // A user-defined `torus` module takes precedence over the built-in
+            if scope.modules.contains_key("torus") {
+                let at = inner.as_span().start_pos().line_col();
+                return call_module("torus", inner.into_inner().next(), at, scope);
+            }
+            let params = parse_params(inner, scope)?;
+            let major_r = params
+                .get_number("major_r")
+                .or_else(|| params.get_positional_number(0))
+                .unwrap_or(2.0) as f64;
+            let minor_r = params
+                .get_number("minor_r")
+                .or_else(|| params.get_positional_number(1))
+                .unwrap_or(1.0) as f64;
+            let major_fn = params.get_fragments(major_r + minor_r);
+            let minor_fn = params.get_fragments(minor_r);
+            Ok(Some(Node::new(NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            })))
+        }
+        Rule::square_stmt => {
+            let params = parse_params(inner, scope)?;
+            let size = match params.get_vector("size")?.or(params.get_positional_vector(0)?) {
+                Some(size) => Vec2::new(size.x, size.y),
+                None => {
+                    let side = params
+                        .get_number("size")
+                        .or_else(|| params.get_positional_number(0))
+                        .unwrap_or(1.0) as f64;
+                    Vec2::new(side, side)
+                }
+            };
+            let center = params.get_boolean("center").unwrap_or(false);
+            Ok(Some(Node::new(NodeKind::Square { size, center })))
+        }
+        Rule::circle_stmt => {
+            let params = parse_params(inner, scope)?;
+            let r = params
+                .get_number("r")
+                .or_else(|| params.get_number("d").map(|d| d / 2.0))
+                .or_else(|| params.get_positional_number(0))
+                .unwrap_or(1.0) as f64;
+            let fn_ = params.get_fragments(r);
+            Ok(Some(Node::new(NodeKind::Circle { r, fn_ })))
+        }
+        Rule::polygon_stmt => {
+            let (line, col) = inner.as_span().start_pos().line_col();
+            let params = parse_params(inner, scope)?;
+            if params.named.contains_key("paths") || params.positional.len() > 1 {
+                eprintln!("Warning: polygon() paths are not supported; using the points in order");
+            }
+
+            let points = match params.named.get("points").or(params.positional.first()) {
+                Some(Value::Matrix(rows)) if rows.iter().all(|row| row.len() == 2) => rows
+                    .iter()
+                    .map(|row| Vec2::new(row[0] as f64, row[1] as f64))
+                    .collect(),
+                Some(Value::Vector(v)) if v.is_empty() => Vec::new(),
+                None | Some(Value::Undef) => Vec::new(),
+                Some(other) => bail!(
+                    "polygon() points must be a list of [x, y] pairs at line {}, column {}, got {}",
+                    line,
+                    col,
+                    other
+                ),
+            };
+            Ok(Some(Node::new(NodeKind::Polygon { points })))
+        }
+        Rule::polyhedron_stmt => {
+            let (line, col) = inner.as_span().start_pos().line_col();
+            let params = parse_params(inner, scope)?;
+
+            let points = match params.named.get("points").or(params.positional.first()) {
+                Some(Value::Matrix(rows)) if rows.iter().all(|row| row.len() == 3) => rows
+                    .iter()
+                    .map(|row| Point3::new(row[0] as f64, row[1] as f64, row[2] as f64))
+                    .collect(),
+                Some(Value::Vector(v)) if v.is_empty() => Vec::new(),
+                None | Some(Value::Undef) => Vec::new(),
+                Some(other) => bail!(
+                    "polyhedron() points must be a list of [x, y, z] triples at line {}, column {}, got {}",
+                    line,
+                    col,
+                    other
+                ),
+            };
+
+            // `triangles` is the name older OpenSCAD versions used for `faces`
+            let faces_value = params
+                .named
+                .get("faces")
+                .or(params.named.get("triangles"))
+                .or(params.positional.get(1));
+            let faces = match faces_value {
+                Some(Value::Matrix(rows)) => rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|&i| (i >= 0.0 && i.fract() == 0.0).then_some(i as usize))
+                            .collect::<Option<Vec<usize>>>()
+                    })
+                    .collect::<Option<Vec<_>>>(),
+                Some(Value::Vector(v)) if v.is_empty() => Some(Vec::new()),
+                None | Some(Value::Undef) => Some(Vec::new()),
+                _ => None,
+            };
+            let Some(faces) = faces else {
+                bail!(
+                    "polyhedron() faces must be lists of point indices at line {}, column {}, got {}",
+                    line,
+                    col,
+                    faces_value.unwrap_or(&Value::Undef)
+                );
+            };
+
+            let convexity = params.get_number("convexity").unwrap_or(1.0).max(1.0) as u32;
+            Ok(Some(Node::new(NodeKind::Polyhedron {
+                points,
+                faces,
+                convexity,
+            })))
+        }
         _ => Ok(None),
     }
 }
 
-fn parse_transform(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
+fn parse_transform<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
     let inner = pair.into_inner().next().unwrap();
 
     match inner.as_rule() {
         Rule::translate_stmt => {
             let mut inner_pairs = inner.into_inner();
-            let params = parse_params_from_list(inner_pairs.next().unwrap())?;
-            let children = parse_block_or_stmt(inner_pairs.next().unwrap())?;
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
 
             let v = params
-                .get_vector("v")
-                .or_else(|| params.get_positional_vector(0))
+                .get_vector("v")?
+                .or(params.get_positional_vector(0)?)
                 .unwrap_or(Vector3::zeros());
 
             Ok(Some(Node::new(NodeKind::Transform {
@@ -163,27 +506,36 @@ fn parse_transform(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
         }
         Rule::rotate_stmt => {
             let mut inner_pairs = inner.into_inner();
-            let params = parse_params_from_list(inner_pairs.next().unwrap())?;
-            let children = parse_block_or_stmt(inner_pairs.next().unwrap())?;
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
 
-            let a = params
-                .get_vector("a")
-                .or_else(|| params.get_positional_vector(0))
-                .unwrap_or(Vector3::zeros());
+            // A scalar angle rotates about `v`, or about Z when no axis is given
+            let angle = params.get_number("a").or_else(|| params.get_positional_number(0));
+            let axis = params.get_vector("v")?.or(params.get_positional_vector(1)?);
+            let op = match (angle, axis) {
+                (Some(angle), Some(axis)) => TransformOp::RotateAxis {
+                    angle: angle as f64,
+                    axis,
+                },
+                (Some(angle), None) => TransformOp::Rotate(Vector3::new(0.0, 0.0, angle as f64)),
+                (None, _) => TransformOp::Rotate(
+                    params
+                        .get_vector("a")?
+                        .or(params.get_positional_vector(0)?)
+                        .unwrap_or(Vector3::zeros()),
+                ),
+            };
 
-            Ok(Some(Node::new(NodeKind::Transform {
-                op: TransformOp::Rotate(a),
-                children,
-            })))
+            Ok(Some(Node::new(NodeKind::Transform { op, children })))
         }
         Rule::scale_stmt => {
             let mut inner_pairs = inner.into_inner();
-            let params = parse_params_from_list(inner_pairs.next().unwrap())?;
-            let children = parse_block_or_stmt(inner_pairs.next().unwrap())?;
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
 
             let v = params
-                .get_vector("v")
-                .or_else(|| params.get_positional_vector(0))
+                .get_vector("v")?
+                .or(params.get_positional_vector(0)?)
                 .unwrap_or(Vector3::new(1.0, 1.0, 1.0));
 
             Ok(Some(Node::new(NodeKind::Transform {
@@ -193,12 +545,12 @@ fn parse_transform(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
         }
         Rule::mirror_stmt => {
             let mut inner_pairs = inner.into_inner();
-            let params = parse_params_from_list(inner_pairs.next().unwrap())?;
-            let children = parse_block_or_stmt(inner_pairs.next().unwrap())?;
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
 
             let axis = params
-                .get_vector("v")
-                .or_else(|| params.get_positional_vector(0))
+                .get_vector("v")?
+                .or(params.get_positional_vector(0)?)
                 .unwrap_or(Vector3::new(1.0, 0.0, 0.0));
 
             Ok(Some(Node::new(NodeKind::Transform {
@@ -206,38 +558,404 @@ fn parse_transform(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
                 children,
             })))
         }
+        Rule::multmatrix_stmt => {
+            let mut inner_pairs = inner.into_inner();
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
+
+            // Entries missing from a 3x4 (or smaller) matrix keep their identity values
+            let mut m = nalgebra::Matrix4::identity();
+            let matrix = params.named.get("m").or_else(|| params.positional.first());
+            if let Some(Value::Matrix(rows)) = matrix {
+                for (i, row) in rows.iter().take(4).enumerate() {
+                    for (j, value) in row.iter().take(4).enumerate() {
+                        m[(i, j)] = *value as f64;
+                    }
+                }
+            }
+
+            Ok(Some(Node::new(NodeKind::Transform {
+                op: TransformOp::Multmatrix(m),
+                children,
+            })))
+        }
+        Rule::resize_stmt => {
+            let mut inner_pairs = inner.into_inner();
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
+
+            // Missing components mean "unchanged", unlike other vectors
+            let mut newsize = Vec3::zeros();
+            if let Some(Value::Vector(values)) = params
+                .named
+                .get("newsize")
+                .or_else(|| params.positional.first())
+            {
+                for (axis, value) in values.iter().take(3).enumerate() {
+                    newsize[axis] = *value as f64;
+                }
+            }
+
+            // `auto` is either one flag for every axis or a vector of per-axis flags
+            let auto = match params.named.get("auto").or_else(|| params.positional.get(1)) {
+                Some(Value::Boolean(flag)) => [*flag; 3],
+                Some(Value::Vector(flags)) => {
+                    [0, 1, 2].map(|axis| flags.get(axis).is_some_and(|&f| f != 0.0))
+                }
+                _ => [false; 3],
+            };
+
+            Ok(Some(Node::new(NodeKind::Resize {
+                newsize,
+                auto,
+                children,
+            })))
+        }
+        Rule::color_stmt => {
+            let mut inner_pairs = inner.into_inner();
+            let params = parse_params_from_list(inner_pairs.next().unwrap(), scope)?;
+            let children = parse_block_or_stmt(inner_pairs.next().unwrap(), scope)?;
+
+            let color = params.named.get("c").or_else(|| params.positional.first());
+            let rgba = match color {
+                Some(Value::String(name)) => parse_color(name),
+                Some(Value::Vector(components)) => match *components.as_slice() {
+                    [r, g, b] => Some([r, g, b, 1.0]),
+                    [r, g, b, a] => Some([r, g, b, a]),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            // Like OpenSCAD, an unrecognized color leaves the children uncolored
+            let Some(mut rgba) = rgba else {
+                eprintln!("Warning: color() has an unrecognized color, ignoring it");
+                return Ok(Some(Node::new(NodeKind::Union(children))));
+            };
+            if let Some(alpha) = params
+                .get_number("alpha")
+                .or_else(|| params.get_positional_number(1))
+            {
+                rgba[3] = alpha;
+            }
+
+            Ok(Some(Node::new(NodeKind::Color { rgba, children })))
+        }
+        Rule::offset_stmt => {
+            let mut params = Params::new();
+            let mut children = Vec::new();
+            for part in inner.into_inner() {
+                match part.as_rule() {
+                    Rule::param_list => params = parse_params_from_list(part, scope)?,
+                    Rule::block_or_stmt => children = parse_block_or_stmt(part, scope)?,
+                    _ => {}
+                }
+            }
+
+            // `r` takes precedence; without either, OpenSCAD offsets by `delta = 1`
+            let r = params
+                .get_number("r")
+                .or_else(|| params.get_positional_number(0))
+                .map_or(0.0, f64::from);
+            let delta = if r == 0.0 {
+                params.get_number("delta").map_or(1.0, f64::from)
+            } else {
+                0.0
+            };
+            let chamfer = params.get_boolean("chamfer").unwrap_or(false);
+            let fn_ = params.get_fragments(r.abs());
+
+            Ok(Some(Node::new(NodeKind::Offset {
+                r,
+                delta,
+                chamfer,
+                fn_,
+                children,
+            })))
+        }
+        Rule::projection_stmt => {
+            let mut params = Params::new();
+            let mut children = Vec::new();
+            for part in inner.into_inner() {
+                match part.as_rule() {
+                    Rule::param_list => params = parse_params_from_list(part, scope)?,
+                    Rule::block_or_stmt => children = parse_block_or_stmt(part, scope)?,
+                    _ => {}
+                }
+            }
+
+            let cut = params.get_boolean("cut").unwrap_or(false);
+            Ok(Some(Node::new(NodeKind::Projection { cut, children })))
+        }
         _ => Ok(None),
     }
 }
 
-fn parse_boolean(pair: pest::iterators::Pair<Rule>) -> Result<Option<Node>> {
+fn parse_boolean<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
     let inner = pair.into_inner().next().unwrap();
 
     match inner.as_rule() {
         Rule::union_stmt => {
             let block = inner.into_inner().next().unwrap();
-            let children = parse_block(block)?;
+            let children = parse_block(block, scope)?;
             Ok(Some(Node::new(NodeKind::Union(children))))
         }
         Rule::difference_stmt => {
             let block = inner.into_inner().next().unwrap();
-            let children = parse_block(block)?;
+            let children = parse_block(block, scope)?;
             Ok(Some(Node::new(NodeKind::Difference(children))))
         }
         Rule::intersection_stmt => {
             let block = inner.into_inner().next().unwrap();
-            let children = parse_block(block)?;
+            let children = parse_block(block, scope)?;
             Ok(Some(Node::new(NodeKind::Intersection(children))))
         }
         _ => Ok(None),
     }
 }
 
-fn parse_block(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Node>> {
-    let mut nodes = Vec::new();
+/// Expand a call to a user-defined module by parsing its body with the arguments bound
+fn parse_module_call<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let (line, col) = pair.as_span().start_pos().line_col();
+    let mut inner = pair.into_inner();
+    let name = inner.next().unwrap().as_str();
+    call_module(name, inner.next(), (line, col), scope)
+}
+
+/// Expand a call to the user module `name`, with `args` the call's `param_list` if any
+fn call_module<'i>(
+    name: &str,
+    args: Option<pest::iterators::Pair<'i, Rule>>,
+    (line, col): (usize, usize),
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let Some(module) = scope.modules.get(name) else {
+        eprintln!("Warning: Unknown module `{}`, ignoring", name);
+        return Ok(Some(Node::new(NodeKind::Empty)));
+    };
+    if scope.depth >= MAX_MODULE_DEPTH {
+        bail!(
+            "Module `{}` nested more than {} calls deep at line {}, column {}",
+            name,
+            MAX_MODULE_DEPTH,
+            line,
+            col
+        );
+    }
+
+    // Children passed to the call are dropped; `children()` is not supported
+    let params = match args {
+        Some(list) if list.as_rule() == Rule::param_list => parse_params_from_list(list, scope)?,
+        _ => Params::new(),
+    };
+
+    // Arguments bind by name, then by position, then fall back to the default
+    let mut body_scope = scope.clone();
+    body_scope.depth += 1;
+    for (i, (param, default)) in module.params.iter().enumerate() {
+        let value = match params.named.get(param).or(params.positional.get(i)) {
+            Some(value) => value.clone(),
+            None => match default {
+                Some(expr) => parse_expr(expr.clone(), &body_scope)?,
+                None => Value::Undef,
+            },
+        };
+        body_scope.variables.insert(param.clone(), value);
+    }
+
+    let mut children = parse_block_or_stmt(module.body.clone(), &body_scope)?;
+    Ok(Some(if children.len() == 1 {
+        children.pop().unwrap()
+    } else {
+        Node::new(NodeKind::Union(children))
+    }))
+}
+
+/// Parse `if (condition) ... else ...`, expanding only the selected branch
+///
+/// The other branch is never parsed, so a recursive module guarded by an `if` terminates.
+fn parse_if<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let mut condition = None;
+    let mut branches = Vec::new();
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::expr => condition = Some(part),
+            Rule::block_or_stmt => branches.push(part),
+            _ => {}
+        }
+    }
+
+    let condition = parse_expr(condition.unwrap(), scope)?.is_truthy();
+    let selected = branches.into_iter().nth(if condition { 0 } else { 1 });
+    let children = match selected {
+        Some(branch) => parse_block_or_stmt(branch, scope)?,
+        None => Vec::new(),
+    };
+
+    let (then_branch, else_branch) = if condition {
+        (children, Vec::new())
+    } else {
+        (Vec::new(), children)
+    };
+    Ok(Some(Node::new(NodeKind::If {
+        condition,
+        then_branch,
+        else_branch,
+    })))
+}
+
+/// Parse `echo(...)`, formatting its arguments in call order
+fn parse_echo<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let mut args = Vec::new();
+    let params = pair.into_inner().find(|p| p.as_rule() == Rule::param_list);
+    for param in params.into_iter().flat_map(|list| list.into_inner()) {
+        let mut parts = param.into_inner();
+        let first = parts.next().unwrap();
+        args.push(match parts.next() {
+            Some(expr) => format!("{} = {}", first.as_str(), parse_expr(expr, scope)?),
+            None => parse_expr(first, scope)?.to_string(),
+        });
+    }
+    Ok(Some(Node::new(NodeKind::Echo(args))))
+}
+
+/// Check `assert(condition, message)` while parsing, then expand its children, if any
+///
+/// The condition is evaluated against the enclosing scope, like an `if` condition, so a
+/// false one fails the parse with [`AssertionFailed`]. A missing condition is `undef`.
+fn parse_assert<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let (line, column) = pair.as_span().start_pos().line_col();
+    let (mut condition, mut message, mut body) = (None, None, None);
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::param_list => {
+                for (i, param) in part.into_inner().enumerate() {
+                    let mut parts = param.into_inner();
+                    let first = parts.next().unwrap();
+                    let (name, expr) = match parts.next() {
+                        Some(expr) => (first.as_str(), expr),
+                        None => (["condition", "message"].get(i).copied().unwrap_or(""), first),
+                    };
+                    match name {
+                        "condition" => condition = Some(expr),
+                        "message" => message = Some(expr),
+                        _ => {}
+                    }
+                }
+            }
+            Rule::block_or_stmt => body = Some(part),
+            _ => {}
+        }
+    }
+
+    let holds = match &condition {
+        Some(expr) => parse_expr(expr.clone(), scope)?.is_truthy(),
+        None => false,
+    };
+    if !holds {
+        let message = match message {
+            Some(expr) => Some(match parse_expr(expr, scope)? {
+                Value::String(text) => text,
+                other => other.to_string(),
+            }),
+            None => None,
+        };
+        return Err(AssertionFailed {
+            expression: condition.map_or("undef", |expr| expr.as_str()).to_string(),
+            message,
+            line,
+            column,
+        }
+        .into());
+    }
+
+    let Some(body) = body else {
+        return Ok(None);
+    };
+    let mut children = parse_block_or_stmt(body, scope)?;
+    Ok(match children.len() {
+        0 => None,
+        1 => children.pop(),
+        _ => Some(Node::new(NodeKind::Union(children))),
+    })
+}
+
+/// Parse `import(file)`, resolving the path against [`ParseOptions::base_dir`]
+///
+/// The file is only read when the node is evaluated.
+fn parse_import<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Option<Node>> {
+    let (line, col) = pair.as_span().start_pos().line_col();
+    let params = parse_params(pair, scope)?;
+    let file = match params.named.get("file").or(params.positional.first()) {
+        Some(Value::String(file)) => file,
+        other => bail!(
+            "import() needs a file name at line {}, column {}, got {}",
+            line,
+            col,
+            other.unwrap_or(&Value::Undef)
+        ),
+    };
 
-    for stmt in pair.into_inner() {
-        if let Some(node) = parse_statement(stmt)? {
+    let base_dir = scope.options.base_dir.as_deref().unwrap_or(Path::new(""));
+    let path = std::path::absolute(base_dir.join(file))
+        .with_context(|| format!("Cannot resolve import path {:?}", file))?;
+    let convexity = params.get_number("convexity").unwrap_or(1.0).max(1.0) as u32;
+    Ok(Some(Node::new(NodeKind::Import { path, convexity })))
+}
+
+fn parse_block<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Vec<Node>> {
+    parse_statements(pair.into_inner(), scope)
+}
+
+/// Parse a statement list in a child scope of `scope`
+///
+/// Modules defined anywhere in the list can be called from all of it, as in OpenSCAD.
+/// Variable assignments apply to the statements after them.
+fn parse_statements<'i>(
+    pairs: pest::iterators::Pairs<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Vec<Node>> {
+    let statements: Vec<_> = pairs.filter(|p| p.as_rule() == Rule::statement).collect();
+    let mut scope = scope.clone();
+
+    for stmt in &statements {
+        let inner = stmt.clone().into_inner().next().unwrap();
+        if inner.as_rule() == Rule::module_def {
+            let (name, module) = Module::from_pair(inner);
+            scope.modules.insert(name, module);
+        }
+    }
+
+    let mut nodes = Vec::new();
+    for stmt in statements {
+        let inner = stmt.clone().into_inner().next().unwrap();
+        if inner.as_rule() == Rule::variable_assignment {
+            let mut parts = inner.into_inner();
+            let name = parts.next().unwrap().as_str().to_string();
+            let value = parse_expr(parts.next().unwrap(), &scope)?;
+            scope.variables.insert(name, value);
+        } else if let Some(node) = parse_statement(stmt, &scope)? {
             nodes.push(node);
         }
     }
@@ -245,7 +963,10 @@ fn parse_block(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Node>> {
     Ok(nodes)
 }
 
-fn parse_block_or_stmt(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Node>> {
+fn parse_block_or_stmt<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Vec<Node>> {
     // block_or_stmt is a wrapper rule - unwrap it to get the actual block or statement
     let inner = if pair.as_rule() == Rule::block_or_stmt {
         pair.into_inner().next().unwrap()
@@ -254,9 +975,9 @@ fn parse_block_or_stmt(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Node>> {
     };
 
     match inner.as_rule() {
-        Rule::block => parse_block(inner),
+        Rule::block => parse_block(inner, scope),
         Rule::statement => {
-            if let Some(node) = parse_statement(inner)? {
+            if let Some(node) = parse_statement(inner, scope)? {
                 Ok(vec![node])
             } else {
                 Ok(vec![])
@@ -270,16 +991,154 @@ fn parse_block_or_stmt(pair: pest::iterators::Pair<Rule>) -> Result<Vec<Node>> {
 struct Params {
     named: std::collections::HashMap<String, Value>,
     positional: Vec<Value>,
+    /// Line and column of each argument, for error messages
+    named_at: std::collections::HashMap<String, (usize, usize)>,
+    positional_at: Vec<(usize, usize)>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 enum Value {
     Number(f32),
-    Vector(Vec3),
-    #[allow(dead_code)]
+    /// Raw components; vector parameters read them through [`vec3_from`]
+    Vector(Vec<f32>),
+    /// Vector of numeric vectors, e.g. the rows of a `multmatrix()`
+    Matrix(Vec<Vec<f32>>),
+    /// Any other `[...]` literal, e.g. strings or deeper nesting
+    List(Vec<Value>),
     String(String),
-    #[allow(dead_code)]
     Boolean(bool),
+    /// `undef`, including unknown variables and unbound module parameters
+    Undef,
+}
+
+impl Value {
+    /// OpenSCAD truthiness: `false`, `0`, `""`, `[]`, and `undef` are false
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Number(n) => *n != 0.0,
+            Value::Vector(values) => !values.is_empty(),
+            Value::Matrix(rows) => !rows.is_empty(),
+            Value::List(items) => !items.is_empty(),
+            Value::String(s) => !s.is_empty(),
+            Value::Boolean(b) => *b,
+            Value::Undef => false,
+        }
+    }
+
+    /// Value of a `[...]` literal: all numbers make a vector, all numeric vectors a matrix,
+    /// and anything else a list
+    fn from_items(items: Vec<Value>) -> Value {
+        let numbers: Option<Vec<f32>> = items
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) => Some(*n),
+                Value::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+                _ => None,
+            })
+            .collect();
+        if let Some(values) = numbers {
+            return Value::Vector(values);
+        }
+
+        let rows: Option<Vec<Vec<f32>>> = items
+            .iter()
+            .map(|item| match item {
+                Value::Vector(row) => Some(row.clone()),
+                _ => None,
+            })
+            .collect();
+        match rows {
+            Some(rows) => Value::Matrix(rows),
+            None => Value::List(items),
+        }
+    }
+
+    /// Element `index` of a vector, matrix, list, or string, as read by `value[index]`
+    ///
+    /// Fractional indices round down, like OpenSCAD. Unlike OpenSCAD, which yields `undef`,
+    /// an index outside the value is an error.
+    fn index(&self, index: &Value) -> Result<Value> {
+        let Value::Number(position) = index else {
+            bail!("Index {} is not a number", index);
+        };
+        let len = match self {
+            Value::Vector(values) => values.len(),
+            Value::Matrix(rows) => rows.len(),
+            Value::List(items) => items.len(),
+            Value::String(s) => s.chars().count(),
+            other => bail!("Cannot index {}, which is not a list or string", other),
+        };
+        let i = position.floor();
+        if !(i >= 0.0 && (i as usize) < len) {
+            bail!("Index {} is out of range for {}, which has {} elements", index, self, len);
+        }
+
+        let i = i as usize;
+        Ok(match self {
+            Value::Vector(values) => Value::Number(values[i]),
+            Value::Matrix(rows) => Value::Vector(rows[i].clone()),
+            Value::List(items) => items[i].clone(),
+            Value::String(s) => Value::String(s.chars().nth(i).unwrap().to_string()),
+            _ => unreachable!("checked above"),
+        })
+    }
+}
+
+/// Upper bound on the numbers a range literal expands to
+const MAX_RANGE_ELEMENTS: usize = 1_000_000;
+
+/// Numbers covered by `[start : step : end]`, including `end` when a step lands on it
+///
+/// Ranges are expanded eagerly, so they behave as ordinary vectors once evaluated.
+fn range_values(start: f32, step: f32, end: f32) -> Result<Vec<f32>> {
+    if step == 0.0 || !(start.is_finite() && step.is_finite() && end.is_finite()) {
+        bail!("Range [{}:{}:{}] needs finite bounds and a nonzero step", start, step, end);
+    }
+
+    // Tolerate rounding so that e.g. [0 : 0.1 : 1] still ends at 1
+    let steps = ((end - start) / step + 1e-4).floor();
+    if steps < 0.0 {
+        return Ok(Vec::new());
+    }
+    if steps >= MAX_RANGE_ELEMENTS as f32 {
+        bail!(
+            "Range [{}:{}:{}] has more than {} elements",
+            start,
+            step,
+            end,
+            MAX_RANGE_ELEMENTS
+        );
+    }
+    Ok((0..=steps as usize).map(|i| start + i as f32 * step).collect())
+}
+
+/// Formats values the way OpenSCAD's `echo()` prints them
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+            f.write_str("[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", item)?;
+            }
+            f.write_str("]")
+        }
+
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Vector(values) => list(f, values),
+            Value::Matrix(rows) => {
+                let rows: Vec<_> = rows.iter().map(|row| Value::Vector(row.clone())).collect();
+                list(f, &rows)
+            }
+            Value::List(items) => list(f, items),
+            Value::String(s) => write!(f, "\"{}\"", s),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Undef => f.write_str("undef"),
+        }
+    }
 }
 
 impl Params {
@@ -287,6 +1146,8 @@ impl Params {
         Self {
             named: std::collections::HashMap::new(),
             positional: Vec::new(),
+            named_at: std::collections::HashMap::new(),
+            positional_at: Vec::new(),
         }
     }
 
@@ -297,11 +1158,12 @@ impl Params {
         })
     }
 
-    fn get_vector(&self, name: &str) -> Option<Vec3> {
-        self.named.get(name).and_then(|v| match v {
-            Value::Vector(v) => Some(*v),
-            _ => None,
-        })
+    fn get_vector(&self, name: &str) -> Result<Option<Vec3>> {
+        match self.named.get(name) {
+            Some(Value::Vector(v)) => vec3_at(v, name, self.named_at[name]).map(Some),
+            Some(list @ Value::List(_)) => Err(non_numeric(list, name, self.named_at[name])),
+            _ => Ok(None),
+        }
     }
 
     fn get_boolean(&self, name: &str) -> Option<bool> {
@@ -311,6 +1173,17 @@ impl Params {
         })
     }
 
+    /// Fragment count for radius `r` from `$fn`, `$fa` and `$fs`
+    fn get_fragments(&self, r: f64) -> u32 {
+        let special = |name: &str, default: f64| self.get_number(name).map_or(default, f64::from);
+        facet_count(
+            r,
+            special("$fn", 0.0),
+            special("$fa", DEFAULT_FA),
+            special("$fs", DEFAULT_FS),
+        )
+    }
+
     fn get_positional_number(&self, idx: usize) -> Option<f32> {
         self.positional.get(idx).and_then(|v| match v {
             Value::Number(n) => Some(*n),
@@ -318,27 +1191,39 @@ impl Params {
         })
     }
 
-    fn get_positional_vector(&self, idx: usize) -> Option<Vec3> {
-        self.positional.get(idx).and_then(|v| match v {
-            Value::Vector(v) => Some(*v),
-            _ => None,
-        })
+    fn get_positional_vector(&self, idx: usize) -> Result<Option<Vec3>> {
+        match self.positional.get(idx) {
+            Some(Value::Vector(v)) => {
+                vec3_at(v, "positional argument", self.positional_at[idx]).map(Some)
+            }
+            Some(list @ Value::List(_)) => {
+                Err(non_numeric(list, "positional argument", self.positional_at[idx]))
+            }
+            _ => Ok(None),
+        }
     }
 }
 
-fn parse_params(pair: pest::iterators::Pair<Rule>) -> Result<Params> {
+fn parse_params<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Params> {
     for inner in pair.into_inner() {
         if let Rule::param_list = inner.as_rule() {
-            return parse_params_from_list(inner);
+            return parse_params_from_list(inner, scope);
         }
     }
     Ok(Params::new())
 }
 
-fn parse_params_from_list(pair: pest::iterators::Pair<Rule>) -> Result<Params> {
+fn parse_params_from_list<'i>(
+    pair: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Params> {
     let mut params = Params::new();
 
     for param in pair.into_inner() {
+        let at = param.as_span().start_pos().line_col();
         let mut param_inner = param.into_inner();
         let first = param_inner.next().unwrap();
 
@@ -346,44 +1231,140 @@ fn parse_params_from_list(pair: pest::iterators::Pair<Rule>) -> Result<Params> {
             // Named parameter
             let name = first.as_str().to_string();
             let expr = param_inner.next().unwrap();
-            let value = parse_expr(expr)?;
+            let value = parse_expr(expr, scope)?;
+            params.named_at.insert(name.clone(), at);
             params.named.insert(name, value);
         } else {
             // Positional parameter
-            let value = parse_expr(first)?;
+            let value = parse_expr(first, scope)?;
             params.positional.push(value);
+            params.positional_at.push(at);
         }
     }
 
     Ok(params)
 }
 
-fn parse_expr(pair: pest::iterators::Pair<Rule>) -> Result<Value> {
-    let inner = pair.into_inner().next().unwrap();
+fn parse_expr<'i>(pair: pest::iterators::Pair<'i, Rule>, scope: &Scope<'_, 'i>) -> Result<Value> {
+    match pair.as_rule() {
+        Rule::logic_or | Rule::logic_and => {
+            let is_or = pair.as_rule() == Rule::logic_or;
+            let mut operands = pair.into_inner();
+            let first = parse_expr(operands.next().unwrap(), scope)?;
+            if operands.peek().is_none() {
+                return Ok(first);
+            }
+
+            // Short-circuits like OpenSCAD, so later operands may be left unevaluated
+            let mut result = first.is_truthy();
+            for operand in operands {
+                if result == is_or {
+                    break;
+                }
+                result = parse_expr(operand, scope)?.is_truthy();
+            }
+            Ok(Value::Boolean(result))
+        }
+        Rule::comparison => {
+            let mut parts = pair.into_inner();
+            let lhs = parse_expr(parts.next().unwrap(), scope)?;
+            let Some(op) = parts.next() else {
+                return Ok(lhs);
+            };
+            let rhs = parse_expr(parts.next().unwrap(), scope)?;
+            Ok(Value::Boolean(compare(&lhs, op.as_str(), &rhs)))
+        }
+        Rule::unary => {
+            let mut negations = 0;
+            let mut value = Value::Undef;
+            for part in pair.into_inner() {
+                match part.as_rule() {
+                    Rule::not_op => negations += 1,
+                    _ => value = parse_expr(part, scope)?,
+                }
+            }
+            if negations == 0 {
+                Ok(value)
+            } else {
+                Ok(Value::Boolean(value.is_truthy() == (negations % 2 == 0)))
+            }
+        }
+        Rule::postfix => {
+            let mut parts = pair.into_inner();
+            let mut value = parse_expr(parts.next().unwrap(), scope)?;
+            for index in parts {
+                let (line, col) = index.as_span().start_pos().line_col();
+                let position = parse_expr(index.into_inner().next().unwrap(), scope)?;
+                value = value
+                    .index(&position)
+                    .map_err(|e| anyhow!("{} at line {}, column {}", e, line, col))?;
+            }
+            Ok(value)
+        }
+        Rule::primary => parse_primary(pair.into_inner().next().unwrap(), scope),
+        _ => parse_expr(pair.into_inner().next().unwrap(), scope),
+    }
+}
 
+/// Evaluate a comparison operator; ordering is only defined between numbers or strings
+fn compare(lhs: &Value, op: &str, rhs: &Value) -> bool {
+    let ordering = match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+
+    match op {
+        "==" => lhs == rhs,
+        "!=" => lhs != rhs,
+        "<" => ordering.is_some_and(|o| o.is_lt()),
+        ">" => ordering.is_some_and(|o| o.is_gt()),
+        "<=" => ordering.is_some_and(|o| o.is_le()),
+        ">=" => ordering.is_some_and(|o| o.is_ge()),
+        _ => false,
+    }
+}
+
+fn parse_primary<'i>(
+    inner: pest::iterators::Pair<'i, Rule>,
+    scope: &Scope<'_, 'i>,
+) -> Result<Value> {
     match inner.as_rule() {
         Rule::number => {
             let num: f32 = inner.as_str().parse()?;
             Ok(Value::Number(num))
         }
         Rule::vector => {
-            let mut values = Vec::new();
+            let mut items = Vec::new();
             if let Some(expr_list) = inner.into_inner().next() {
                 for expr in expr_list.into_inner() {
-                    if let Value::Number(n) = parse_expr(expr)? {
-                        values.push(n);
-                    }
+                    items.push(parse_expr(expr, scope)?);
                 }
             }
-
-            let v = match values.len() {
-                1 => Vector3::new(values[0] as f64, values[0] as f64, values[0] as f64),
-                2 => Vector3::new(values[0] as f64, values[1] as f64, 0.0),
-                3 => Vector3::new(values[0] as f64, values[1] as f64, values[2] as f64),
-                _ => Vector3::zeros(),
+            Ok(Value::from_items(items))
+        }
+        Rule::range => {
+            let (line, col) = inner.as_span().start_pos().line_col();
+            let mut bounds = Vec::new();
+            for expr in inner.into_inner() {
+                match parse_expr(expr, scope)? {
+                    Value::Number(n) => bounds.push(n),
+                    other => bail!(
+                        "Non-numeric range bound {} at line {}, column {}",
+                        other,
+                        line,
+                        col
+                    ),
+                }
+            }
+            let (start, step, end) = match bounds[..] {
+                [start, end] => (start, 1.0, end),
+                [start, step, end] => (start, step, end),
+                _ => unreachable!("the grammar allows two or three bounds"),
             };
-
-            Ok(Value::Vector(v))
+            range_values(start, step, end)
+                .map(Value::Vector)
+                .map_err(|e| anyhow!("{} at line {}, column {}", e, line, col))
         }
         Rule::boolean => {
             let b = inner.as_str() == "true";
@@ -393,10 +1374,60 @@ fn parse_expr(pair: pest::iterators::Pair<Rule>) -> Result<Value> {
             let s = inner.into_inner().next().unwrap().as_str().to_string();
             Ok(Value::String(s))
         }
+        Rule::ident => Ok(scope
+            .variables
+            .get(inner.as_str())
+            .cloned()
+            .unwrap_or(Value::Undef)),
+        Rule::expr => parse_expr(inner, scope),
         _ => Ok(Value::Number(0.0)),
     }
 }
 
+/// Interpret vector components as a 3D vector, padding `[x, y]` with zero
+///
+/// `[x]` is uniform. Empty and longer vectors have no sensible reading and yield `None`.
+fn vec3_from(values: &[f32]) -> Option<Vec3> {
+    match values.len() {
+        1 => Some(Vector3::new(values[0] as f64, values[0] as f64, values[0] as f64)),
+        2 => Some(Vector3::new(values[0] as f64, values[1] as f64, 0.0)),
+        3 => Some(Vector3::new(values[0] as f64, values[1] as f64, values[2] as f64)),
+        _ => None,
+    }
+}
+
+/// Error for a list given where the argument `name` needs a numeric vector
+fn non_numeric(list: &Value, name: &str, (line, col): (usize, usize)) -> anyhow::Error {
+    let Value::List(items) = list else {
+        unreachable!("only lists are non-numeric vectors");
+    };
+    let element = items
+        .iter()
+        .find(|item| !matches!(item, Value::Number(_) | Value::Boolean(_)))
+        .unwrap_or(&Value::Undef);
+    anyhow!(
+        "Non-numeric vector element `{}` in {} for {} at line {}, column {}",
+        element,
+        list,
+        name,
+        line,
+        col
+    )
+}
+
+/// [`vec3_from`] for the argument `name` at `(line, col)`, erroring on unusable lengths
+fn vec3_at(values: &[f32], name: &str, (line, col): (usize, usize)) -> Result<Vec3> {
+    vec3_from(values).ok_or_else(|| {
+        anyhow!(
+            "Expected 1 to 3 components for {} at line {}, column {}, got {}",
+            name,
+            line,
+            col,
+            values.len()
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,4 +1515,342 @@ difference() {
         let result = parse_scad("width = 50;\nheight = width;");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_parse_with_spans() {
+        let code = "cube(10);\ntranslate([5, 0, 0]) {\n    sphere(2);\n}\ncylinder(h=4, r=1, center=true);";
+        let options = ParseOptions {
+            spans: true,
+            ..Default::default()
+        };
+        let ast = parse_scad_with_options(code, &options).unwrap();
+
+        let root_span = ast.span.expect("root union should carry the program span");
+        assert_eq!((root_span.line, root_span.col), (1, 1));
+
+        let children = match &ast.kind {
+            NodeKind::Union(children) => children,
+            other => panic!("Expected union, got {:?}", other),
+        };
+        assert_eq!(children.len(), 3);
+
+        let cube = children[0].span.unwrap();
+        assert_eq!(&code[cube.start..cube.end], "cube(10);");
+        assert_eq!((cube.line, cube.col), (1, 1));
+
+        let translate = children[1].span.unwrap();
+        assert_eq!(translate.line, 2);
+        assert!(code[translate.start..translate.end].starts_with("translate"));
+
+        // Nested statement keeps its own location
+        let sphere = match &children[1].kind {
+            NodeKind::Transform { children, .. } => children[0].span.unwrap(),
+            other => panic!("Expected transform, got {:?}", other),
+        };
+        assert_eq!(&code[sphere.start..sphere.end], "sphere(2);");
+        assert_eq!((sphere.line, sphere.col), (3, 5));
+
+        // The centering translate and the cylinder both map back to the cylinder statement
+        let centered = &children[2];
+        let cylinder_span = centered.span.unwrap();
+        assert_eq!(cylinder_span.line, 5);
+        match &centered.kind {
+            NodeKind::Transform { children, .. } => {
+                assert_eq!(children[0].span, Some(cylinder_span));
+            }
+            other => panic!("Expected centering transform, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_color() {
+        let rgba = |code: &str| match parse_scad(code).unwrap().kind {
+            NodeKind::Color { rgba, children } => {
+                assert_eq!(children.len(), 1);
+                rgba
+            }
+            other => panic!("Expected color, got {:?}", other),
+        };
+
+        assert_eq!(rgba("color(\"red\") cube(1);"), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(rgba("color(\"#0000ff\", 0.5) cube(1);"), [0.0, 0.0, 1.0, 0.5]);
+        assert_eq!(rgba("color([0, 0.5, 1]) cube(1);"), [0.0, 0.5, 1.0, 1.0]);
+        assert_eq!(rgba("color(c=[1, 1, 0, 0.25]) { cube(1); }"), [1.0, 1.0, 0.0, 0.25]);
+
+        let unknown = parse_scad("color(\"nope\") cube(1);").unwrap();
+        assert!(matches!(unknown.kind, NodeKind::Union(_)));
+    }
+
+    #[test]
+    fn test_parse_without_spans_by_default() {
+        let ast = parse_scad("cube(10);").unwrap();
+        assert!(ast.span.is_none());
+    }
+
+    #[test]
+    fn test_parse_vector_edge_cases() {
+        let size = |code: &str| match parse_scad(code).unwrap().kind {
+            NodeKind::Cube { size, .. } => size,
+            other => panic!("Expected cube, got {:?}", other),
+        };
+        assert_eq!(size("cube([4]);"), Vector3::new(4.0, 4.0, 4.0));
+        assert_eq!(size("cube([4, 5]);"), Vector3::new(4.0, 5.0, 0.0));
+        assert_eq!(size("cube(size=[4, 5, 6]);"), Vector3::new(4.0, 5.0, 6.0));
+
+        let error = parse_scad("cube([]);").unwrap_err().to_string();
+        assert!(error.contains("got 0"), "{}", error);
+        assert!(error.contains("line 1, column 6"), "{}", error);
+
+        let error = parse_scad("translate(v=[1, 2, 3, 4]) cube(1);").unwrap_err().to_string();
+        assert!(error.contains("for v"), "{}", error);
+
+        // Lists may hold anything, so the error is raised where the vector is used
+        let error = parse_scad("cube([10,\n  undef, 10]);").unwrap_err().to_string();
+        assert!(error.contains("`undef` in [10, undef, 10]"), "{}", error);
+        assert!(error.contains("line 1, column 6"), "{}", error);
+        assert!(parse_scad("cube([10, \"a\", 10]);").is_err());
+        assert!(parse_scad("cube([1, [2, 3]]);").is_err());
+    }
+
+    #[test]
+    fn test_parse_fa_fs_fragments() {
+        let fragments = |code: &str| match parse_scad(code).unwrap().kind {
+            NodeKind::Sphere { fn_, .. } | NodeKind::Cylinder { fn_, .. } => fn_,
+            other => panic!("Expected sphere or cylinder, got {:?}", other),
+        };
+
+        assert!(fragments("sphere(r=40);") > fragments("sphere(r=2);"));
+        assert_eq!(fragments("sphere(r=40);"), 30);
+        assert_eq!(fragments("sphere(r=40, $fa=5);"), 72);
+        assert_eq!(fragments("cylinder(h=1, r=3, $fa=5, $fs=0.5);"), 38);
+        assert_eq!(fragments("sphere(r=40, $fn=12, $fa=1, $fs=0.1);"), 12);
+    }
+
+    #[test]
+    fn test_parse_module_call() {
+        let ast = parse_scad("module tube(h) cylinder(h=h, r=2);\ntube(10);").unwrap();
+        match ast.kind {
+            NodeKind::Cylinder { h, r, .. } => assert_eq!((h, r), (10.0, 2.0)),
+            other => panic!("Expected cylinder, got {:?}", other),
+        }
+
+        // Defaults, named arguments, and calls ahead of the definition
+        let code = "peg(r=3);\nmodule peg(h=5, r=1) { cylinder(h=h, r=r); }";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Cylinder { h, r, .. } => assert_eq!((h, r), (5.0, 3.0)),
+            other => panic!("Expected cylinder, got {:?}", other),
+        }
+
+        // Variables from the calling scope are visible in the body
+        let code = "module block() cube([w, w, w]);\nw = 4;\nblock();";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Cube { size, .. } => assert_eq!(size, Vector3::new(4.0, 4.0, 4.0)),
+            other => panic!("Expected cube, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_conditions() {
+        let taken = |condition: &str| {
+            let code = format!("a = 2; b = \"x\"; if ({}) cube(1);", condition);
+            match parse_scad(&code).unwrap().kind {
+                NodeKind::If {
+                    condition,
+                    then_branch,
+                    else_branch,
+                } => {
+                    assert_eq!(then_branch.len(), usize::from(condition));
+                    assert!(else_branch.is_empty());
+                    condition
+                }
+                other => panic!("Expected if, got {:?}", other),
+            }
+        };
+
+        assert!(taken("a == 2"));
+        assert!(taken("a != 3 && a >= 2"));
+        assert!(taken("a < 1 || a <= 2"));
+        assert!(taken("!(a > 2)"));
+        assert!(taken("b == \"x\" && [1]"));
+        assert!(!taken("undefined"));
+        assert!(!taken("!!0 || \"\""));
+        assert!(!taken("b < 1"));
+
+        // `else` binds to the nearest `if`
+        let code = "if (true) if (false) cube(1); else sphere(1);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::If { then_branch, .. } => match &then_branch[0].kind {
+                NodeKind::If { else_branch, .. } => {
+                    assert!(matches!(else_branch[0].kind, NodeKind::Sphere { .. }));
+                }
+                other => panic!("Expected nested if, got {:?}", other),
+            },
+            other => panic!("Expected if, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_skips_unselected_branch() {
+        // Expanding the false branch would hit the recursion limit
+        let code = "module forever() forever();\nif (false) forever(); else cube(1);";
+        assert!(parse_scad(code).is_ok());
+    }
+
+    #[test]
+    fn test_parse_torus() {
+        match parse_scad("torus(major_r=10, minor_r=2, $fn=24);").unwrap().kind {
+            NodeKind::Torus {
+                major_r,
+                minor_r,
+                major_fn,
+                minor_fn,
+            } => {
+                assert_eq!((major_r, minor_r), (10.0, 2.0));
+                assert_eq!((major_fn, minor_fn), (24, 24));
+            }
+            other => panic!("Expected torus, got {:?}", other),
+        }
+
+        // Positional radii, with fragments derived separately for the ring and the tube
+        match parse_scad("torus(20, 1);").unwrap().kind {
+            NodeKind::Torus {
+                major_fn, minor_fn, ..
+            } => assert!(major_fn > minor_fn),
+            other => panic!("Expected torus, got {:?}", other),
+        }
+
+        // A user module of the same name shadows the extension
+        let code = "module torus(r) sphere(r);\ntorus(3);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Sphere { r, .. } => assert_eq!(r, 3.0),
+            other => panic!("Expected sphere, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_polyhedron() {
+        let code = "polyhedron(points=[[0,0,0], [1,0,0], [0,1,0], [0,0,1]],\n\
+                    faces=[[0,1,2], [0,3,1], [0,2,3], [1,3,2]]);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Polyhedron {
+                points,
+                faces,
+                convexity,
+            } => {
+                assert_eq!(points.len(), 4);
+                assert_eq!(points[3], Point3::new(0.0, 0.0, 1.0));
+                assert_eq!(faces[3], vec![1, 3, 2]);
+                assert_eq!(convexity, 1);
+            }
+            other => panic!("Expected polyhedron, got {:?}", other),
+        }
+
+        // Faces may mix triangles and larger polygons, under the old `triangles` name too
+        let code = "polyhedron([[0,0,0], [1,0,0], [1,1,0], [0,1,0], [0,0,1]],\n\
+                    triangles=[[0,1,2,3], [0,4,1], [1,4,2], [2,4,3], [3,4,0]]);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Polyhedron { faces, .. } => assert_eq!(faces[0].len(), 4),
+            other => panic!("Expected polyhedron, got {:?}", other),
+        }
+
+        let error = parse_scad("polyhedron(points=[[0,0,0]], faces=[[0, -1, 2]]);").unwrap_err();
+        assert!(error.to_string().contains("point indices"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_module_recursion_is_bounded() {
+        let error = parse_scad("module forever() forever();\nforever();").unwrap_err();
+        assert!(error.to_string().contains("nested more than"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_error_reports_position() {
+        let error = parse_scad("cube([10,10)").unwrap_err();
+        let parse_error = error.downcast_ref::<ParseError>().expect("not a ParseError");
+        assert_eq!(parse_error.line, 1);
+        // At the `)` that should have been a `]`
+        assert_eq!(parse_error.column, 12);
+        assert_eq!(parse_error.snippet, "cube([10,10)");
+
+        let error = parse_scad("x = 1;\ncube([10,10);").unwrap_err();
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.line, 2);
+        assert_eq!(parse_error.snippet, "cube([10,10);");
+        assert!(error.to_string().starts_with("Failed to parse SCAD source at line 2"));
+    }
+
+    #[test]
+    fn test_parse_echo_formats_arguments() {
+        let code = "w = 2.5;\necho(\"x\", w, size = [w, 1], ok = !false, missing);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Echo(args) => assert_eq!(
+                args,
+                ["\"x\"", "2.5", "size = [2.5, 1]", "ok = true", "undef"]
+            ),
+            other => panic!("Expected echo, got {:?}", other),
+        }
+
+        // Not mistaken for the keyword
+        assert!(!matches!(parse_scad("echoes();").unwrap().kind, NodeKind::Echo(_)));
+    }
+
+    #[test]
+    fn test_parse_assert() {
+        let error = crate::render("assert(false, \"bad\"); cube(5);").unwrap_err();
+        assert!(error.to_string().contains("bad"), "{}", error);
+
+        let code = "w = -1;\nassert(w > 0, str = 1);";
+        let error = parse_scad(code).unwrap_err();
+        let failed = error.downcast_ref::<AssertionFailed>().expect("not an AssertionFailed");
+        assert_eq!(failed.expression, "w > 0");
+        assert_eq!(failed.message, None);
+        assert_eq!((failed.line, failed.column), (2, 1));
+
+        // A passing assertion yields its children, or nothing
+        assert!(matches!(
+            parse_scad("w = 2;\nassert(w > 0, \"w\") cube(w);").unwrap().kind,
+            NodeKind::Cube { .. }
+        ));
+        assert_eq!(parse_scad("assert(true);").unwrap().kind, NodeKind::Empty);
+    }
+
+    #[test]
+    fn test_parse_polygon_from_list_variable() {
+        let code = "points = [[0, 0], [10, 0], [0, 10]];\npolygon(points);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Polygon { points } => assert_eq!(
+                points,
+                [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(0.0, 10.0)]
+            ),
+            other => panic!("Expected polygon, got {:?}", other),
+        }
+
+        let mesh = crate::render(code).unwrap();
+        assert_eq!(mesh.triangle_count(), 1);
+        assert_eq!(mesh.bounding_box().size(), Vector3::new(10.0, 10.0, 0.0));
+
+        let error = parse_scad("polygon([1, 2, 3]);").unwrap_err();
+        assert!(error.to_string().contains("[x, y] pairs"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_list_indexing_and_ranges() {
+        let code = "items = [\"a\", [1, 2], 3];\n\
+                    echo(items[0], items[1][1], [0:2:6], [0:3][3], \"abc\"[2], items);";
+        match parse_scad(code).unwrap().kind {
+            NodeKind::Echo(args) => assert_eq!(
+                args,
+                ["\"a\"", "2", "[0, 2, 4, 6]", "3", "\"c\"", "[\"a\", [1, 2], 3]"]
+            ),
+            other => panic!("Expected echo, got {:?}", other),
+        }
+
+        let error = parse_scad("v = [1, 2];\necho(v[2]);").unwrap_err();
+        assert!(error.to_string().contains("out of range"), "{}", error);
+        assert!(error.to_string().contains("line 2, column 7"), "{}", error);
+
+        // Lists are not vectors, so geometry rejects them where it needs numbers
+        let error = parse_scad("cube([\"a\", 1, 1]);").unwrap_err();
+        assert!(error.to_string().contains("Non-numeric vector"), "{}", error);
+    }
 }
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Length units for exporters whose formats record them
+
+/// Unit an exported file is written in
+///
+/// The kernel models in millimeters, like OpenSCAD; exporting in another unit converts the
+/// coordinates, so the part keeps its physical size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+    #[default]
+    Millimeter,
+    Centimeter,
+    Inch,
+    Meter,
+}
+
+impl Unit {
+    /// Length of one unit in millimeters
+    pub fn millimeters(self) -> f64 {
+        match self {
+            Unit::Millimeter => 1.0,
+            Unit::Centimeter => 10.0,
+            Unit::Inch => 25.4,
+            Unit::Meter => 1000.0,
+        }
+    }
+
+    /// Convert a length in millimeters to this unit
+    pub fn from_millimeters(self, value: f64) -> f64 {
+        value / self.millimeters()
+    }
+
+    /// Value of the 3MF `unit` attribute
+    pub(crate) fn threemf_name(self) -> &'static str {
+        match self {
+            Unit::Millimeter => "millimeter",
+            Unit::Centimeter => "centimeter",
+            Unit::Inch => "inch",
+            Unit::Meter => "meter",
+        }
+    }
+}
@@ -6,6 +6,8 @@
 use crate::ast::{CacheStats, IncrementalEvaluator, Node, NodeId};
 use crate::geometry::Mesh;
 use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Main kernel for incremental rendering
 pub struct Kernel {
@@ -40,6 +42,13 @@ impl Kernel {
         }
     }
 
+    /// Render a standalone AST without touching the kernel's own AST or cache
+    ///
+    /// Uses the same evaluation path as [`crate::render`], minus the parse step.
+    pub fn render_ast(&self, node: &Node) -> Result<Mesh> {
+        crate::render_node(node)
+    }
+
     /// Update a specific subtree and trigger incremental re-evaluation
     pub fn update_subtree(&mut self, node_id: &NodeId, updated_node: Node) -> Result<Mesh> {
         // Update the evaluator's cache and dependency graph
@@ -77,13 +86,28 @@ impl Kernel {
                     }
                 }
             }
-            crate::ast::NodeKind::Transform { children, .. } => {
+            crate::ast::NodeKind::Transform { children, .. }
+            | crate::ast::NodeKind::Resize { children, .. }
+            | crate::ast::NodeKind::Offset { children, .. }
+            | crate::ast::NodeKind::Projection { children, .. }
+            | crate::ast::NodeKind::Color { children, .. } => {
                 for child in children.iter_mut() {
                     if Self::update_node_in_ast_static(child, target_id, updated_node) {
                         return true;
                     }
                 }
             }
+            crate::ast::NodeKind::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                for child in then_branch.iter_mut().chain(else_branch) {
+                    if Self::update_node_in_ast_static(child, target_id, updated_node) {
+                        return true;
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -95,6 +119,14 @@ impl Kernel {
         self.evaluator.invalidate(node_id);
     }
 
+    /// Mesh of every ID'd node as of the last render, for previewing subtrees
+    ///
+    /// Read straight from the incremental cache, so nothing is re-evaluated; nodes that have
+    /// not been rendered since they were last invalidated are missing from the map.
+    pub fn node_meshes(&self) -> HashMap<NodeId, Arc<Mesh>> {
+        self.evaluator.cached_meshes()
+    }
+
     /// Get cache statistics
     pub fn cache_stats(&self) -> CacheStats {
         self.evaluator.cache_stats()
@@ -164,4 +196,37 @@ mod tests {
         // Meshes should be different
         assert_ne!(mesh1.vertex_count(), mesh2.vertex_count());
     }
+
+    #[test]
+    fn test_node_meshes_after_render() {
+        let cube = |size: f64, id: &str| {
+            Node::with_id(
+                NodeKind::Cube {
+                    size: Vec3::new(size, size, size),
+                    center: true,
+                },
+                id.into(),
+            )
+        };
+        let root = Node::with_id(
+            NodeKind::Difference(vec![cube(10.0, "outer"), cube(5.0, "inner")]),
+            "root".into(),
+        );
+
+        let kernel = Kernel::with_ast(root);
+        assert!(kernel.node_meshes().is_empty());
+        let rendered = kernel.render().unwrap();
+
+        let meshes = kernel.node_meshes();
+        assert_eq!(meshes.len(), 3);
+        assert_eq!(meshes["root"].triangle_count(), rendered.triangle_count());
+        assert_eq!(meshes["outer"].bounding_box().size().x, 10.0);
+        assert_eq!(meshes["inner"].bounding_box().size().x, 5.0);
+        assert!(meshes["root"].triangle_count() > meshes["outer"].triangle_count());
+
+        // Reading the meshes does not evaluate anything
+        let stats = kernel.cache_stats();
+        kernel.node_meshes();
+        assert_eq!(kernel.cache_stats().misses, stats.misses);
+    }
 }
@@ -16,30 +16,67 @@ pub mod kernel;
 pub mod utils;
 pub mod validation;
 
-#[cfg(feature = "wasm")]
+#[cfg(any(feature = "wasm", feature = "napi"))]
 pub mod ffi;
 
 pub use ast::{
-    CacheStats, IncrementalEvaluator, Node, NodeId, NodeKind, ParallelEvaluator, TransformOp,
+    CacheStats, Cancelled, EditImpact, EvalProgress, IncrementalEvaluator, Node, NodeId, NodeKind,
+    ParallelEvaluator, TransformOp,
 };
 pub use geometry::{analyze, GeometryStats, Mesh, Primitive};
-pub use io::{export_3mf, export_gltf, export_step, export_stl, import_scad_file, parse_scad};
+pub use io::{
+    export_3mf, export_gltf, export_ply, export_step, export_stl, import_scad_file, parse_scad,
+    AssertionFailed, ParseError, TriangleSink,
+};
 pub use kernel::Kernel;
 
 use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 /// Main entry point for rendering a SCAD script to a mesh
 pub fn render(source: &str) -> Result<Mesh> {
     let ast = parse_scad(source)?;
-    let evaluator = ast::Evaluator::new();
-    evaluator.evaluate(&ast)
+    render_node(&ast)
+}
+
+/// Render a SCAD script, stopping with a [`Cancelled`] error soon after `cancel` is set
+pub fn render_with_cancellation(source: &str, cancel: Arc<AtomicBool>) -> Result<Mesh> {
+    let ast = parse_scad(source)?;
+    ast::Evaluator::new().with_cancellation(cancel).evaluate(&ast)
 }
 
 /// Render a SCAD file to a mesh
 pub fn render_file(path: &str) -> Result<Mesh> {
     let ast = import_scad_file(path)?;
+    render_node(&ast)
+}
+
+/// Render a SCAD script, passing triangles to `sink` as each top-level child is evaluated
+///
+/// When the program is a union, explicit or the implicit one around several top-level
+/// statements, each child is evaluated and streamed on its own and never merged with the
+/// others, so memory stays bounded by the largest child rather than the whole assembly.
+/// Overlapping children therefore come out as separate shells. Any other program is
+/// rendered whole and streamed once.
+pub fn render_streaming(source: &str, sink: &mut dyn TriangleSink) -> Result<()> {
+    let ast = parse_scad(source)?;
     let evaluator = ast::Evaluator::new();
-    evaluator.evaluate(&ast)
+    match &ast.kind {
+        NodeKind::Union(children) => {
+            for child in children {
+                sink.mesh(&evaluator.evaluate(child)?)?;
+            }
+            Ok(())
+        }
+        _ => sink.mesh(&evaluator.evaluate(&ast)?),
+    }
+}
+
+/// Render an already-parsed AST to a mesh, skipping the parse step
+pub fn render_node(node: &Node) -> Result<Mesh> {
+    let evaluator = ast::Evaluator::new();
+    evaluator.evaluate(node)
 }
 
 #[cfg(test)]
@@ -51,4 +88,67 @@ mod tests {
         let result = render("cube([10, 10, 10]);");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_negative_radius_matches_openscad() {
+        // OpenSCAD warns and renders nothing for a negative radius
+        let mesh = render("sphere(r=-5);").unwrap();
+        assert_eq!(mesh.triangle_count(), 0);
+
+        // ...and the rest of the model is unaffected
+        let mesh = render("union() { cube([10, 10, 10]); sphere(r=-5); }").unwrap();
+        assert_eq!(mesh.triangle_count(), 12);
+    }
+
+    #[test]
+    fn test_render_node_difference() {
+        // difference() { cube(20); translate([10, 10, 20]) sphere(5); }
+        let cube = Node::new(NodeKind::Cube {
+            size: ast::Vec3::new(20.0, 20.0, 20.0),
+            center: false,
+        });
+        let sphere = Node::new(NodeKind::Transform {
+            op: TransformOp::Translate(ast::Vec3::new(10.0, 10.0, 20.0)),
+            children: vec![Node::new(NodeKind::Sphere { r: 5.0, fn_: 16 })],
+        });
+        let node = Node::new(NodeKind::Difference(vec![cube, sphere]));
+
+        let mesh = render_node(&node).unwrap();
+        assert!(mesh.triangle_count() > 0);
+
+        // Same result as going through SCAD text
+        let parsed = render(
+            "difference() { cube([20, 20, 20]); translate([10, 10, 20]) sphere(5, $fn=16); }",
+        )
+        .unwrap();
+        assert_eq!(mesh.triangle_count(), parsed.triangle_count());
+        for (a, b) in mesh.vertices.iter().zip(&parsed.vertices) {
+            assert_eq!(a.position, b.position);
+        }
+    }
+
+    #[test]
+    fn test_streamed_union_matches_render() {
+        use nalgebra::{Point3, Vector3};
+
+        struct Counter(usize);
+        impl TriangleSink for Counter {
+            fn triangle(&mut self, _: [Point3<f64>; 3], _: Vector3<f64>) -> Result<()> {
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        // Stepped along the diagonal, so no ray cast by the union's inside test from one cube
+        // can reach another
+        let cubes: String = (0..100)
+            .map(|i| format!("translate([{0}, {0}, {0}]) cube([5, 5, 5]);\n", i * 10))
+            .collect();
+        let source = format!("union() {{\n{}}}", cubes);
+
+        let mut sink = Counter(0);
+        render_streaming(&source, &mut sink).unwrap();
+        assert_eq!(sink.0, 1200);
+        assert_eq!(sink.0, render(&source).unwrap().triangle_count());
+    }
 }
@@ -4,3 +4,6 @@
 //! Utility modules
 
 pub mod math;
+pub mod point_hash;
+
+pub use point_hash::{quantize_point, PointHasher};
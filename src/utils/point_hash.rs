@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Quantized point hashing shared by vertex welding and deduplication
+//!
+//! Every routine that merges nearby points uses the same convention: the grid cell size is the
+//! merge distance `epsilon`, and a point belongs to the cell nearest to `p / epsilon`. Two
+//! points closer than `epsilon` can still straddle a cell boundary, so lookups go through
+//! [`PointHasher`], which also searches the neighboring cells.
+
+use nalgebra::Point3;
+use std::collections::HashMap;
+
+/// Grid cell of `p` for cell size `epsilon`
+///
+/// Points well inside the same cell (e.g. `epsilon / 2` apart around a lattice point) share a
+/// key; points `2 * epsilon` or more apart on any axis never do.
+pub fn quantize_point(p: &Point3<f64>, epsilon: f64) -> (i64, i64, i64) {
+    (
+        (p.x / epsilon).round() as i64,
+        (p.y / epsilon).round() as i64,
+        (p.z / epsilon).round() as i64,
+    )
+}
+
+/// Assigns each point the index of the first inserted point within `epsilon` of it
+///
+/// Matching is strict (`distance < epsilon`) and always finds the earliest match, so the
+/// result is the same as a linear first-match scan but runs in expected constant time.
+pub struct PointHasher {
+    epsilon: f64,
+    grid: HashMap<(i64, i64, i64), Vec<usize>>,
+    points: Vec<Point3<f64>>,
+}
+
+impl PointHasher {
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            grid: HashMap::new(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Index of the earliest distinct point within `epsilon` of `p`
+    pub fn find(&self, p: &Point3<f64>) -> Option<usize> {
+        let (cx, cy, cz) = quantize_point(p, self.epsilon);
+        let mut best: Option<usize> = None;
+
+        // A point within epsilon is at most one cell away on each axis
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(bucket) = self.grid.get(&(cx + dx, cy + dy, cz + dz)) else {
+                        continue;
+                    };
+                    // Buckets are in insertion order, so the first hit is the earliest
+                    let hit = bucket
+                        .iter()
+                        .take_while(|&&i| best.is_none_or(|b| i < b))
+                        .find(|&&i| (p - self.points[i]).norm() < self.epsilon);
+                    if let Some(&i) = hit {
+                        best = Some(i);
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Index of `p`, adding it as a new distinct point if nothing is within `epsilon`
+    pub fn insert(&mut self, p: &Point3<f64>) -> usize {
+        if let Some(i) = self.find(p) {
+            return i;
+        }
+
+        let i = self.points.len();
+        self.points.push(*p);
+        self.grid
+            .entry(quantize_point(p, self.epsilon))
+            .or_default()
+            .push(i);
+        i
+    }
+
+    /// Number of distinct points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Distinct points, in insertion order
+    pub fn points(&self) -> &[Point3<f64>] {
+        &self.points
+    }
+
+    pub fn into_points(self) -> Vec<Point3<f64>> {
+        self.points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_point() {
+        let epsilon = 1e-6;
+        let p = Point3::new(1.0, -2.0, 3.0);
+
+        let near = p + nalgebra::Vector3::repeat(epsilon / 2.0 * 0.99);
+        assert_eq!(quantize_point(&p, epsilon), quantize_point(&near, epsilon));
+
+        let far = p + nalgebra::Vector3::new(2.0 * epsilon, 0.0, 0.0);
+        assert_ne!(quantize_point(&p, epsilon), quantize_point(&far, epsilon));
+    }
+
+    #[test]
+    fn test_point_hasher_matches_across_cell_boundaries() {
+        let epsilon = 1e-3;
+        let mut hasher = PointHasher::new(epsilon);
+
+        // 0.0005 rounds up while 0.00049 rounds down, yet they are well within epsilon
+        let a = hasher.insert(&Point3::new(0.0005, 0.0, 0.0));
+        let b = hasher.insert(&Point3::new(0.00049, 0.0, 0.0));
+        assert_eq!(a, b);
+
+        let c = hasher.insert(&Point3::new(0.0025, 0.0, 0.0));
+        assert_ne!(a, c);
+        assert_eq!(hasher.len(), 2);
+
+        // The earliest match wins when several are in range
+        let mut hasher = PointHasher::new(epsilon);
+        let first = hasher.insert(&Point3::origin());
+        let second = hasher.insert(&Point3::new(0.0018, 0.0, 0.0));
+        assert_ne!(first, second);
+        assert_eq!(hasher.find(&Point3::new(0.0009, 0.0, 0.0)), Some(first));
+        assert_eq!(hasher.find(&Point3::new(0.0013, 0.0, 0.0)), Some(second));
+        assert_eq!(hasher.find(&Point3::new(0.0, 0.0, 0.0015)), None);
+    }
+}
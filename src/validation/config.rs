@@ -8,7 +8,30 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use super::types::TestSuite;
+use super::types::{TestStatus, TestSuite};
+
+/// Which comparison models keep their preview PNGs and STLs after a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KeepPreviews {
+    /// Keep artifacts for every model
+    All,
+    /// Keep artifacts only for models that failed or errored
+    #[default]
+    FailuresOnly,
+    /// Delete all artifacts once the run is summarized
+    None,
+}
+
+impl KeepPreviews {
+    /// Whether a model finishing with `status` keeps its artifacts
+    pub fn keeps(self, status: &TestStatus) -> bool {
+        match self {
+            KeepPreviews::All => true,
+            KeepPreviews::FailuresOnly => *status != TestStatus::Passed,
+            KeepPreviews::None => false,
+        }
+    }
+}
 
 /// Validation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +54,9 @@ pub struct ValidationConfig {
     pub file_patterns: Vec<String>,
     /// Whether to generate visual diffs
     pub generate_visual_diffs: bool,
+    /// Which models keep their preview artifacts
+    #[serde(default)]
+    pub keep_previews: KeepPreviews,
     /// Whether to stop on first failure
     pub fail_fast: bool,
     /// Verbose output
@@ -54,6 +80,7 @@ impl Default for ValidationConfig {
             filters: Vec::new(),
             file_patterns: Vec::new(),
             generate_visual_diffs: false,
+            keep_previews: KeepPreviews::default(),
             fail_fast: false,
             verbose: false,
         }
@@ -9,7 +9,13 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use crate::evaluation::runner::{
+    run_and_compare_with_cancellation, run_model_task_with_cancellation,
+};
+use crate::io::AssertionFailed;
 
 use super::config::ValidationConfig;
 use super::discovery::TestDiscovery;
@@ -29,6 +35,20 @@ impl ValidationCoordinator {
         Self { config }
     }
 
+    /// Run one test, cancelling it with a "timed out" error if it exceeds `test_timeout`
+    ///
+    /// `test` receives the cancellation flag that is set when the timeout passes.
+    fn run_test<T, F>(&self, test: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(Arc<AtomicBool>) -> Result<T> + Send + 'static,
+    {
+        match self.config.test_timeout {
+            Some(timeout) => crate::evaluation::run_with_timeout(timeout, test),
+            None => test(Arc::default()),
+        }
+    }
+
     /// Run all configured test suites
     pub fn run_all(&self) -> Result<Vec<SuiteResult>> {
         let mut suite_results = Vec::new();
@@ -247,7 +267,10 @@ impl ValidationCoordinator {
                     .map(|task| {
                         let start = Instant::now();
                         let task_name = task.name();
-                        let result = crate::evaluation::run_model_task(task);
+                        let task_owned = task.clone();
+                        let result = self.run_test(move |cancel| {
+                            run_model_task_with_cancellation(&task_owned, cancel)
+                        });
                         let duration = start.elapsed();
 
                         if let Some(ref p) = pb {
@@ -282,7 +305,7 @@ impl ValidationCoordinator {
                                 })
                             }
                             Err(e) => {
-                                let error_msg = format!("{}", e);
+                                let (status, error_msg) = error_outcome(&e);
                                 if self.config.verbose {
                                     println!("  {} {} - Error: {}", "✗".red(), task_name, error_msg);
                                     // Show source code snippet for debugging
@@ -298,7 +321,7 @@ impl ValidationCoordinator {
                                 }
                                 ValidationResult::Evaluation(EvaluationTestResult {
                                     model: task_name,
-                                    status: TestStatus::Error,
+                                    status,
                                     duration,
                                     comparison_passed: false,
                                     error: Some(error_msg),
@@ -321,7 +344,10 @@ impl ValidationCoordinator {
             // Run discovered tests
             for test in eval_tests {
                 let start = Instant::now();
-                let result = crate::evaluation::run_and_compare(&test.path);
+                let path = test.path.clone();
+                let result = self.run_test(move |cancel| {
+                    run_and_compare_with_cancellation(&path, &Default::default(), cancel)
+                });
                 let duration = start.elapsed();
 
                 let validation_result = match result {
@@ -337,14 +363,17 @@ impl ValidationCoordinator {
                         error: None,
                         metrics: Some(eval_result.metrics),
                     }),
-                    Err(e) => ValidationResult::Evaluation(EvaluationTestResult {
-                        model: test.path.display().to_string(),
-                        status: TestStatus::Error,
-                        duration,
-                        comparison_passed: false,
-                        error: Some(e.to_string()),
-                        metrics: None,
-                    }),
+                    Err(e) => {
+                        let (status, error) = error_outcome(&e);
+                        ValidationResult::Evaluation(EvaluationTestResult {
+                            model: test.path.display().to_string(),
+                            status,
+                            duration,
+                            comparison_passed: false,
+                            error: Some(error),
+                            metrics: None,
+                        })
+                    }
                 };
 
                 suite_result.add_result(validation_result);
@@ -387,12 +416,10 @@ impl ValidationCoordinator {
                         let preview = preview_root
                             .as_ref()
                             .map(|root| crate::cli::PreviewConfig::for_input(root, &path));
-                        let result = crate::cli::compare_with_openscad(
-                            &path,
-                            1e-5,
-                            self.config.verbose,
-                            preview,
-                        );
+                        let (input, verbose) = (path.clone(), self.config.verbose);
+                        let result = self.run_test(move |_| {
+                            crate::cli::compare_with_openscad(&input, 1e-5, verbose, preview)
+                        });
                         let duration = start.elapsed();
 
                         let validation_result = match result {
@@ -443,9 +470,10 @@ impl ValidationCoordinator {
                                 if self.config.verbose {
                                     println!("  {} {} - Error: {}", "✗".red(), path.display(), e);
                                 }
+                                let (status, error) = error_outcome(&e);
                                 ValidationResult::Comparison(ComparisonTestResult {
                                     file: path,
-                                    status: TestStatus::Error,
+                                    status,
                                     duration,
                                     comparison_passed: false,
                                     vertex_delta: 0.0,
@@ -456,7 +484,7 @@ impl ValidationCoordinator {
                                     diff_preview: None,
                                     polyframe_stl: None,
                                     openscad_stl: None,
-                                    error: Some(e.to_string()),
+                                    error: Some(error),
                                 })
                             }
                         };
@@ -472,12 +500,10 @@ impl ValidationCoordinator {
                 let preview = preview_root
                     .as_ref()
                     .map(|root| crate::cli::PreviewConfig::for_input(root, &test.path));
-                let result = crate::cli::compare_with_openscad(
-                    &test.path,
-                    1e-5,
-                    self.config.verbose,
-                    preview,
-                );
+                let (input, verbose) = (test.path.clone(), self.config.verbose);
+                let result = self.run_test(move |_| {
+                    crate::cli::compare_with_openscad(&input, 1e-5, verbose, preview)
+                });
                 let duration = start.elapsed();
 
                 let validation_result = match result {
@@ -500,30 +526,48 @@ impl ValidationCoordinator {
                         openscad_stl: comp_result.openscad_stl.clone(),
                         error: None,
                     }),
-                    Err(e) => ValidationResult::Comparison(ComparisonTestResult {
-                        file: test.path.clone(),
-                        status: TestStatus::Error,
-                        duration,
-                        comparison_passed: false,
-                        vertex_delta: 0.0,
-                        triangle_delta: 0.0,
-                        visual_diff_delta: None,
-                        polyframe_preview: None,
-                        openscad_preview: None,
-                        diff_preview: None,
-                        polyframe_stl: None,
-                        openscad_stl: None,
-                        error: Some(e.to_string()),
-                    }),
+                    Err(e) => {
+                        let (status, error) = error_outcome(&e);
+                        ValidationResult::Comparison(ComparisonTestResult {
+                            file: test.path.clone(),
+                            status,
+                            duration,
+                            comparison_passed: false,
+                            vertex_delta: 0.0,
+                            triangle_delta: 0.0,
+                            visual_diff_delta: None,
+                            polyframe_preview: None,
+                            openscad_preview: None,
+                            diff_preview: None,
+                            polyframe_stl: None,
+                            openscad_stl: None,
+                            error: Some(error),
+                        })
+                    }
                 };
 
                 suite_result.add_result(validation_result);
             }
         }
 
+        self.prune_previews(&mut suite_result)?;
         Ok(suite_result)
     }
 
+    /// Delete comparison artifacts that `keep_previews` does not retain
+    fn prune_previews(&self, suite_result: &mut SuiteResult) -> Result<()> {
+        for result in &mut suite_result.results {
+            if let ValidationResult::Comparison(comparison) = result {
+                if !self.config.keep_previews.keeps(&comparison.status) {
+                    comparison.discard_artifacts().with_context(|| {
+                        format!("Failed to remove previews for {}", comparison.file.display())
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Run fuzz tests
     fn run_fuzz_tests(&self) -> Result<SuiteResult> {
         let mut suite_result = SuiteResult::new(TestSuite::Fuzz);
@@ -537,9 +581,13 @@ impl ValidationCoordinator {
             count: 100, // Reasonable default
             max_depth: 5,
             max_primitives: 10,
+            ..Default::default()
         };
 
         let mut fuzzer = crate::evaluation::Fuzzer::new(config);
+        if self.config.verbose {
+            println!("  Fuzz seed: {}", fuzzer.seed());
+        }
         let generated = fuzzer.run();
 
         let mut parse_success = 0;
@@ -552,8 +600,10 @@ impl ValidationCoordinator {
                 Ok(_) => {
                     parse_success += 1;
 
-                    // Test rendering
-                    match crate::render(scad_code) {
+                    // Test rendering; parsing is linear, but CSG on odd inputs can hang
+                    let code = scad_code.clone();
+                    let render = move |cancel| crate::render_with_cancellation(&code, cancel);
+                    match self.run_test(render) {
                         Ok(_) => render_success += 1,
                         Err(e) => {
                             errors.push(format!("{}: Render failed: {}", name, e));
@@ -597,7 +647,10 @@ impl ValidationCoordinator {
 
         for test in reg_tests {
             let start = Instant::now();
-            let result = crate::evaluation::run_and_compare(&test.path);
+            let path = test.path.clone();
+            let result = self.run_test(move |cancel| {
+                run_and_compare_with_cancellation(&path, &Default::default(), cancel)
+            });
             let duration = start.elapsed();
 
             let validation_result = match result {
@@ -612,13 +665,16 @@ impl ValidationCoordinator {
                     error: None,
                     fixed: false, // Would need to track previous state
                 }),
-                Err(e) => ValidationResult::Regression(RegressionTestResult {
-                    file: test.path.clone(),
-                    status: TestStatus::Error,
-                    duration,
-                    error: Some(e.to_string()),
-                    fixed: false,
-                }),
+                Err(e) => {
+                    let (status, error) = error_outcome(&e);
+                    ValidationResult::Regression(RegressionTestResult {
+                        file: test.path.clone(),
+                        status,
+                        duration,
+                        error: Some(error),
+                        fixed: false,
+                    })
+                }
             };
 
             suite_result.add_result(validation_result);
@@ -628,3 +684,118 @@ impl ValidationCoordinator {
     }
 }
 
+/// Status and message for a test whose run returned `error`
+///
+/// A model's own `assert()` failing is a test failure rather than a kernel error, and its
+/// message is reported even when the render wrapped it in context.
+fn error_outcome(error: &anyhow::Error) -> (TestStatus, String) {
+    match error.chain().find_map(|e| e.downcast_ref::<AssertionFailed>()) {
+        Some(assertion) => (TestStatus::Failed, assertion.to_string()),
+        None => (TestStatus::Error, error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::config::KeepPreviews;
+    use std::path::Path;
+    use std::sync::atomic::Ordering;
+
+    fn comparison_with_previews(root: &Path, name: &str, status: TestStatus) -> ValidationResult {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let artifact = |file: &str| {
+            let path = dir.join(file);
+            std::fs::write(&path, b"preview").unwrap();
+            Some(path)
+        };
+
+        ValidationResult::Comparison(ComparisonTestResult {
+            file: PathBuf::from(format!("{}.scad", name)),
+            comparison_passed: status == TestStatus::Passed,
+            status,
+            duration: Duration::from_millis(1),
+            vertex_delta: 0.0,
+            triangle_delta: 0.0,
+            visual_diff_delta: None,
+            polyframe_preview: artifact("polyframe.png"),
+            openscad_preview: artifact("openscad.png"),
+            diff_preview: artifact("diff.png"),
+            polyframe_stl: artifact("polyframe.stl"),
+            openscad_stl: artifact("openscad.stl"),
+            error: None,
+        })
+    }
+
+    #[test]
+    fn test_slow_test_times_out() {
+        let coordinator = ValidationCoordinator::new(ValidationConfig {
+            test_timeout: Some(Duration::from_millis(20)),
+            ..Default::default()
+        });
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let worker_stopped = Arc::clone(&stopped);
+        let error = coordinator
+            .run_test(move |cancel| {
+                while !cancel.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                worker_stopped.store(true, Ordering::Relaxed);
+                Ok(())
+            })
+            .unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+        // The worker was cancelled and joined, not left running
+        assert!(stopped.load(Ordering::Relaxed));
+
+        assert_eq!(coordinator.run_test(|_| Ok(7)).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_failures_only_keeps_failing_previews() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut suite_result = SuiteResult::new(TestSuite::Comparison);
+        for (name, status) in [
+            ("passing", TestStatus::Passed),
+            ("failing", TestStatus::Failed),
+            ("erroring", TestStatus::Error),
+        ] {
+            suite_result.add_result(comparison_with_previews(dir.path(), name, status));
+        }
+
+        let coordinator = ValidationCoordinator::new(ValidationConfig {
+            keep_previews: KeepPreviews::FailuresOnly,
+            ..Default::default()
+        });
+        coordinator.prune_previews(&mut suite_result)?;
+
+        assert!(!dir.path().join("passing").exists());
+        assert_eq!(std::fs::read_dir(dir.path().join("failing"))?.count(), 5);
+        assert_eq!(std::fs::read_dir(dir.path().join("erroring"))?.count(), 5);
+        for result in &suite_result.results {
+            let ValidationResult::Comparison(comparison) = result else {
+                unreachable!();
+            };
+            let kept = comparison.status != TestStatus::Passed;
+            assert_eq!(comparison.polyframe_preview.is_some(), kept);
+            assert_eq!(comparison.openscad_stl.is_some(), kept);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assertion_failures_are_not_errors() {
+        let error = crate::render("assert(1 > 2, \"too small\");")
+            .context("Polyframe execution failed")
+            .unwrap_err();
+        let (status, message) = error_outcome(&error);
+        assert_eq!(status, TestStatus::Failed);
+        assert!(message.contains("too small"), "{}", message);
+
+        let (status, _) = error_outcome(&crate::render("cube([1, 1").unwrap_err());
+        assert_eq!(status, TestStatus::Error);
+    }
+}
@@ -9,13 +9,13 @@ pub mod discovery;
 pub mod reporter;
 pub mod types;
 
-pub use config::ValidationConfig;
+pub use config::{KeepPreviews, ValidationConfig};
 pub use coordinator::ValidationCoordinator;
 pub use discovery::{DiscoveredTest, TestCategory, TestComplexity, TestDiscovery};
 pub use reporter::ValidationReporter;
 pub use types::{
-    ComparisonTestResult, EvaluationTestResult, FuzzTestResult, IntegrationTestResult,
-    RegressionTestResult, SuiteResult, TestStatus, TestSuite, UnitTestResult, ValidationReport,
-    ValidationResult,
+    ComparisonTestResult, EvaluationTestResult, FuzzTestResult, HistoryEntry,
+    IntegrationTestResult, RegressionTestResult, SuiteResult, SuiteSummary, TestStatus, TestSuite,
+    UnitTestResult, ValidationReport, ValidationResult,
 };
 
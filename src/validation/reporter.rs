@@ -3,12 +3,21 @@
 
 //! Unified validation report generator
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use base64::Engine;
 use colored::Colorize;
-use std::fs;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
 use std::path::Path;
 
-use super::types::{TestStatus, ValidationReport};
+use super::types::{HistoryEntry, SuiteResult, TestStatus, ValidationReport, ValidationResult};
+
+/// Number of most recent runs shown in the trend section
+const TREND_RUNS: usize = 20;
+
+/// Preview images up to this size are inlined into the HTML report; larger ones are linked
+const INLINE_IMAGE_LIMIT: u64 = 512 * 1024;
 
 /// Unified validation reporter
 pub struct ValidationReporter;
@@ -21,8 +30,54 @@ impl ValidationReporter {
         Ok(())
     }
 
+    /// Append this run's summary to a JSON Lines history file
+    ///
+    /// Existing lines are never rewritten, so the file can be kept across releases.
+    pub fn append_history(report: &ValidationReport, path: impl AsRef<Path>) -> Result<HistoryEntry> {
+        let entry = HistoryEntry::from(report);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())
+            .with_context(|| format!("Failed to open history file {}", path.as_ref().display()))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// Load every run from a history file, oldest first
+    ///
+    /// A missing file is an empty history; lines that fail to parse are skipped with a warning.
+    pub fn load_history(path: impl AsRef<Path>) -> Result<Vec<HistoryEntry>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let mut history = Vec::new();
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => history.push(entry),
+                Err(e) => eprintln!("Warning: skipping history line {} in {}: {}", line_no + 1, path.display(), e),
+            }
+        }
+        Ok(history)
+    }
+
     /// Write Markdown report
     pub fn write_markdown(report: &ValidationReport, path: impl AsRef<Path>) -> Result<()> {
+        Self::write_markdown_with_history(report, &[], path)
+    }
+
+    /// Write Markdown report with a pass-rate trend section built from `history`
+    pub fn write_markdown_with_history(
+        report: &ValidationReport,
+        history: &[HistoryEntry],
+        path: impl AsRef<Path>,
+    ) -> Result<()> {
         let mut md = String::new();
 
         // Header
@@ -38,10 +93,17 @@ impl ValidationReporter {
         md.push_str(&format!("- **Failed:** {}\n", report.total_failed));
         md.push_str(&format!("- **Errors:** {}\n", report.total_errors));
         md.push_str(&format!("- **Skipped:** {}\n", report.total_skipped));
+        md.push_str(&format!("- **Expected Failures:** {}\n", report.total_xfailed));
+        md.push_str(&format!("- **Unexpected Passes:** {}\n", report.total_xpassed));
         md.push_str(&format!("- **Total Duration:** {:.2}s\n", report.total_duration.as_secs_f64()));
         md.push_str(&format!("- **Compatibility Score:** {:.1}%\n\n", report.compatibility_score));
         md.push_str("---\n\n");
 
+        if !history.is_empty() {
+            md.push_str(&Self::trend_section(history));
+            md.push_str("---\n\n");
+        }
+
         // Suite results
         md.push_str("## Test Suites\n\n");
         for suite_result in &report.suite_results {
@@ -50,11 +112,40 @@ impl ValidationReporter {
             md.push_str(&format!("- **Passed:** {} ({:.1}%)\n", suite_result.passed, suite_result.pass_rate()));
             md.push_str(&format!("- **Failed:** {}\n", suite_result.failed));
             md.push_str(&format!("- **Errors:** {}\n", suite_result.errors));
+            md.push_str(&format!("- **Skipped:** {}\n", suite_result.skipped));
+            md.push_str(&format!("- **Expected Failures:** {}\n", suite_result.xfailed));
+            md.push_str(&format!("- **Unexpected Passes:** {}\n", suite_result.xpassed));
             md.push_str(&format!("- **Duration:** {:.2}s\n\n", suite_result.duration.as_secs_f64()));
 
+            // Unexpected passes mean an expected-failure list is out of date
+            let xpassed: Vec<_> = suite_result.results.iter()
+                .filter(|r| r.status() == TestStatus::Xpass)
+                .collect();
+            if !xpassed.is_empty() {
+                md.push_str("#### Unexpected Passes\n\n");
+                for result in xpassed {
+                    md.push_str(&format!("- `{}`\n", result.label()));
+                }
+                md.push('\n');
+            }
+
+            let skipped: Vec<_> = suite_result.results.iter()
+                .filter_map(|r| match r.status() {
+                    TestStatus::Skipped { reason } => Some((r.label(), reason)),
+                    _ => None,
+                })
+                .collect();
+            if !skipped.is_empty() {
+                md.push_str("#### Skipped Tests\n\n");
+                for (label, reason) in skipped {
+                    md.push_str(&format!("- `{}`: {}\n", label, reason));
+                }
+                md.push('\n');
+            }
+
             // Failed tests
             let failed: Vec<_> = suite_result.results.iter()
-                .filter(|r| matches!(r.status(), TestStatus::Failed | TestStatus::Error))
+                .filter(|r| r.status().is_failure())
                 .collect();
 
             if !failed.is_empty() {
@@ -123,6 +214,236 @@ impl ValidationReporter {
         Ok(())
     }
 
+    /// Write a self-contained HTML report
+    ///
+    /// Shows the summary and a table per suite. Comparison results get their preview and diff
+    /// images, inlined as base64 when small enough so the file can be shared on its own.
+    pub fn write_html(report: &ValidationReport, path: impl AsRef<Path>) -> Result<()> {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>Polyframe Kernel Validation Report</title>\n<style>\n");
+        html.push_str("body { font-family: sans-serif; margin: 2em; }\n");
+        html.push_str("table { border-collapse: collapse; margin-bottom: 1.5em; }\n");
+        html.push_str("th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }\n");
+        html.push_str("img { max-width: 320px; }\n");
+        html.push_str(".passed, .xpass { color: #1a7f37; } .failed, .error { color: #cf222e; }\n");
+        html.push_str(".skipped, .xfail { color: #9a6700; }\n");
+        html.push_str("</style>\n</head>\n<body>\n");
+
+        html.push_str("<h1>Polyframe Kernel Validation Report</h1>\n");
+        html.push_str(&format!("<p>Generated: {}</p>\n", escape_html(&report.timestamp)));
+
+        html.push_str("<h2>Summary</h2>\n<table>\n");
+        let summary = [
+            ("Total Tests", report.total_tests.to_string()),
+            ("Passed", format!("{} ({:.1}%)", report.total_passed, report.overall_pass_rate())),
+            ("Failed", report.total_failed.to_string()),
+            ("Errors", report.total_errors.to_string()),
+            ("Skipped", report.total_skipped.to_string()),
+            ("Expected Failures", report.total_xfailed.to_string()),
+            ("Unexpected Passes", report.total_xpassed.to_string()),
+            ("Duration", format!("{:.2}s", report.total_duration.as_secs_f64())),
+            ("Compatibility Score", format!("{:.1}%", report.compatibility_score)),
+        ];
+        for (label, value) in summary {
+            html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value));
+        }
+        html.push_str("</table>\n");
+
+        for suite_result in &report.suite_results {
+            html.push_str(&format!(
+                "<h2>{}</h2>\n<p>{} passed, {} failed, {} errors, {} skipped ({:.1}%)</p>\n",
+                escape_html(suite_result.suite.as_str()),
+                suite_result.passed,
+                suite_result.failed,
+                suite_result.errors,
+                suite_result.skipped,
+                suite_result.pass_rate()
+            ));
+            html.push_str("<table>\n<tr><th>Test</th><th>Status</th><th>Duration</th><th>Details</th></tr>\n");
+            for result in &suite_result.results {
+                let status = result.status();
+                let (class, label) = match &status {
+                    TestStatus::Passed => ("passed", "passed".to_string()),
+                    TestStatus::Failed => ("failed", "failed".to_string()),
+                    TestStatus::Error => ("error", "error".to_string()),
+                    TestStatus::Skipped { reason } => ("skipped", format!("skipped: {}", reason)),
+                    TestStatus::Xfail => ("xfail", "expected failure".to_string()),
+                    TestStatus::Xpass => ("xpass", "unexpected pass".to_string()),
+                };
+                let details = if status.is_failure() || status == TestStatus::Xfail {
+                    Self::error_text(result).unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td class=\"{}\">{}</td><td>{:.3}s</td><td>{}</td></tr>\n",
+                    escape_html(&result.label()),
+                    class,
+                    escape_html(&label),
+                    result.duration().as_secs_f64(),
+                    escape_html(&details).replace('\n', "<br>")
+                ));
+
+                if let ValidationResult::Comparison(r) = result {
+                    let images = [
+                        ("Polyframe", &r.polyframe_preview),
+                        ("OpenSCAD", &r.openscad_preview),
+                        ("Diff", &r.diff_preview),
+                    ];
+                    let cells: Vec<String> = images
+                        .into_iter()
+                        .filter_map(|(caption, path)| Some((caption, path.as_ref()?)))
+                        .map(|(caption, path)| {
+                            format!(
+                                "<figure><img src=\"{}\" alt=\"{} preview\"><figcaption>{}</figcaption></figure>",
+                                image_source(path),
+                                caption,
+                                caption
+                            )
+                        })
+                        .collect();
+                    if !cells.is_empty() {
+                        html.push_str(&format!("<tr><td colspan=\"4\">{}</td></tr>\n", cells.join("")));
+                    }
+                }
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        fs::write(path.as_ref(), html)
+            .with_context(|| format!("Failed to write HTML report {}", path.as_ref().display()))?;
+        Ok(())
+    }
+
+    /// Write JUnit XML for CI systems
+    ///
+    /// Each suite becomes a `<testsuite>` and each result a `<testcase>`. Failures and errors
+    /// carry the stored error text; skips and expected failures are reported as `<skipped>`.
+    pub fn write_junit(report: &ValidationReport, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = quick_xml::Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+        let mut testsuites = BytesStart::new("testsuites");
+        testsuites.push_attribute(("name", "polyframe-validation"));
+        testsuites.push_attribute(("tests", report.total_tests.to_string().as_str()));
+        testsuites.push_attribute(("failures", report.total_failed.to_string().as_str()));
+        testsuites.push_attribute(("errors", report.total_errors.to_string().as_str()));
+        testsuites.push_attribute(("time", Self::junit_time(report.total_duration).as_str()));
+        writer.write_event(Event::Start(testsuites))?;
+
+        for suite_result in &report.suite_results {
+            Self::write_junit_suite(&mut writer, suite_result)?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("testsuites")))?;
+        fs::write(path.as_ref(), writer.into_inner())
+            .with_context(|| format!("Failed to write JUnit report {}", path.as_ref().display()))?;
+        Ok(())
+    }
+
+    fn write_junit_suite(writer: &mut quick_xml::Writer<Vec<u8>>, suite_result: &SuiteResult) -> Result<()> {
+        let name = suite_result.suite.as_str();
+        let mut testsuite = BytesStart::new("testsuite");
+        testsuite.push_attribute(("name", name));
+        testsuite.push_attribute(("tests", suite_result.total.to_string().as_str()));
+        testsuite.push_attribute(("failures", suite_result.failed.to_string().as_str()));
+        testsuite.push_attribute(("errors", suite_result.errors.to_string().as_str()));
+        let skipped = suite_result.skipped + suite_result.xfailed;
+        testsuite.push_attribute(("skipped", skipped.to_string().as_str()));
+        testsuite.push_attribute(("time", Self::junit_time(suite_result.duration).as_str()));
+        writer.write_event(Event::Start(testsuite))?;
+
+        for result in &suite_result.results {
+            let mut testcase = BytesStart::new("testcase");
+            testcase.push_attribute(("name", result.label().as_str()));
+            testcase.push_attribute(("classname", name));
+            testcase.push_attribute(("time", Self::junit_time(result.duration()).as_str()));
+
+            let (tag, message, detail) = match result.status() {
+                TestStatus::Passed | TestStatus::Xpass => {
+                    writer.write_event(Event::Empty(testcase))?;
+                    continue;
+                }
+                TestStatus::Failed => ("failure", "failed".to_string(), Self::error_text(result)),
+                TestStatus::Error => ("error", "error".to_string(), Self::error_text(result)),
+                TestStatus::Skipped { reason } => ("skipped", reason, None),
+                TestStatus::Xfail => ("skipped", "expected failure".to_string(), Self::error_text(result)),
+            };
+
+            writer.write_event(Event::Start(testcase))?;
+            let mut child = BytesStart::new(tag);
+            child.push_attribute(("message", message.as_str()));
+            match detail {
+                Some(text) => {
+                    writer.write_event(Event::Start(child))?;
+                    writer.write_event(Event::Text(BytesText::new(&text)))?;
+                    writer.write_event(Event::End(BytesEnd::new(tag)))?;
+                }
+                None => writer.write_event(Event::Empty(child))?,
+            }
+            writer.write_event(Event::End(BytesEnd::new("testcase")))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("testsuite")))?;
+        Ok(())
+    }
+
+    /// Stored error text of a result; fuzz failures are listed one per line
+    fn error_text(result: &ValidationResult) -> Option<String> {
+        match result {
+            ValidationResult::Unit(r) => r.error.clone(),
+            ValidationResult::Integration(r) => r.error.clone(),
+            ValidationResult::Evaluation(r) => r.error.clone(),
+            ValidationResult::Comparison(r) => r.error.clone().or_else(|| {
+                Some(format!(
+                    "vertex delta {:.2}%, triangle delta {:.2}%",
+                    r.vertex_delta * 100.0,
+                    r.triangle_delta * 100.0
+                ))
+            }),
+            ValidationResult::Fuzz(r) => Some(r.errors.join("\n")),
+            ValidationResult::Regression(r) => r.error.clone(),
+        }
+    }
+
+    /// Seconds with millisecond precision, as JUnit consumers expect
+    fn junit_time(duration: std::time::Duration) -> String {
+        format!("{:.3}", duration.as_secs_f64())
+    }
+
+    /// Markdown trend of the most recent runs: a sparkline plus a table of scores
+    fn trend_section(history: &[HistoryEntry]) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let recent = &history[history.len().saturating_sub(TREND_RUNS)..];
+        let sparkline: String = recent
+            .iter()
+            .map(|e| BARS[((e.pass_rate.clamp(0.0, 100.0) / 100.0) * 7.0).round() as usize])
+            .collect();
+
+        let mut md = String::new();
+        md.push_str("## Trend\n\n");
+        md.push_str(&format!("Pass rate over the last {} runs: `{}`\n\n", recent.len(), sparkline));
+        md.push_str("| Run | Tests | Pass Rate | Compatibility | Change |\n");
+        md.push_str("|-----|-------|-----------|---------------|--------|\n");
+
+        let mut previous: Option<f32> = None;
+        for entry in recent {
+            let change = previous
+                .map(|p| format!("{:+.1}", entry.compatibility_score - p))
+                .unwrap_or_else(|| "-".to_string());
+            md.push_str(&format!(
+                "| {} | {} | {:.1}% | {:.1}% | {} |\n",
+                entry.timestamp, entry.total_tests, entry.pass_rate, entry.compatibility_score, change
+            ));
+            previous = Some(entry.compatibility_score);
+        }
+        md.push('\n');
+        md
+    }
+
     /// Print terminal summary
     pub fn print_summary(report: &ValidationReport) {
         Self::print_summary_with_verbose(report, false)
@@ -165,6 +486,20 @@ impl ValidationReporter {
             "Skipped:".white(),
             report.total_skipped.to_string().yellow()
         );
+        println!(
+            "  {} {}",
+            "Expected Failures:".white(),
+            report.total_xfailed.to_string().yellow()
+        );
+        println!(
+            "  {} {}",
+            "Unexpected Passes:".white(),
+            if report.total_xpassed > 0 {
+                report.total_xpassed.to_string().magenta()
+            } else {
+                report.total_xpassed.to_string().white()
+            }
+        );
         println!(
             "  {} {:.2}s",
             "Duration:".white(),
@@ -193,7 +528,7 @@ impl ValidationReporter {
             };
 
             println!(
-                "  {} {}: {} passed, {} failed, {} errors ({:.1}%)",
+                "  {} {}: {} passed, {} failed, {} errors, {} skipped, {} xfail, {} xpass ({:.1}%)",
                 status_icon,
                 suite_result.suite.as_str().to_uppercase().cyan(),
                 suite_result.passed.to_string().green(),
@@ -207,6 +542,13 @@ impl ValidationReporter {
                 } else {
                     suite_result.errors.to_string().white()
                 },
+                suite_result.skipped.to_string().yellow(),
+                suite_result.xfailed.to_string().yellow(),
+                if suite_result.xpassed > 0 {
+                    suite_result.xpassed.to_string().magenta()
+                } else {
+                    suite_result.xpassed.to_string().white()
+                },
                 suite_result.pass_rate()
             );
         }
@@ -217,7 +559,7 @@ impl ValidationReporter {
 
             for suite_result in &report.suite_results {
                 let failed_count = suite_result.results.iter()
-                    .filter(|r| matches!(r.status(), TestStatus::Failed | TestStatus::Error))
+                    .filter(|r| r.status().is_failure())
                     .count();
                 
                 if failed_count > 0 {
@@ -230,7 +572,7 @@ impl ValidationReporter {
                 }
 
                 for result in &suite_result.results {
-                    if matches!(result.status(), TestStatus::Failed | TestStatus::Error) {
+                    if result.status().is_failure() {
                         Self::print_failure_details(result, verbose);
                     }
                 }
@@ -389,3 +731,214 @@ impl ValidationReporter {
     }
 }
 
+
+/// Escape text for use in HTML element content and attribute values
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `src` for a preview image: a data URI for small PNGs, otherwise the path itself
+fn image_source(path: &Path) -> String {
+    let small = fs::metadata(path).is_ok_and(|m| m.len() <= INLINE_IMAGE_LIMIT);
+    match fs::read(path) {
+        Ok(bytes) if small => format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        ),
+        _ => escape_html(&path.display().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::types::{ComparisonTestResult, FuzzTestResult, TestSuite, UnitTestResult};
+    use std::time::Duration;
+
+    fn report_with(passed: usize, failed: usize) -> ValidationReport {
+        let mut suite = SuiteResult::new(TestSuite::Unit);
+        let statuses = std::iter::repeat_n(TestStatus::Passed, passed)
+            .chain(std::iter::repeat_n(TestStatus::Failed, failed));
+        for (i, status) in statuses.enumerate() {
+            suite.add_result(ValidationResult::Unit(UnitTestResult {
+                name: format!("test_{}", i),
+                status,
+                duration: Duration::from_millis(1),
+                error: None,
+            }));
+        }
+        let mut report = ValidationReport::new();
+        report.add_suite_result(suite);
+        report
+    }
+
+    #[test]
+    fn test_history_accumulates_runs() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("validation_history.jsonl");
+
+        ValidationReporter::append_history(&report_with(3, 1), &path)?;
+        ValidationReporter::append_history(&report_with(4, 0), &path)?;
+
+        let history = ValidationReporter::load_history(&path)?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].compatibility_score, 75.0);
+        assert_eq!(history[1].compatibility_score, 100.0);
+        assert_eq!(history[1].suites[0].suite, "unit");
+        assert_eq!(history[1].suites[0].passed, 4);
+
+        let md_path = dir.path().join("report.md");
+        ValidationReporter::write_markdown_with_history(&report_with(4, 0), &history, &md_path)?;
+        let md = fs::read_to_string(&md_path)?;
+        assert!(md.contains("## Trend"));
+        assert!(md.contains("+25.0"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_tolerates_schema_changes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("validation_history.jsonl");
+        fs::write(
+            &path,
+            "{\"timestamp\":\"old\",\"compatibility_score\":50.0}\n\
+             not json\n\
+             {\"timestamp\":\"new\",\"pass_rate\":90.0,\"commit\":\"abc123\"}\n",
+        )?;
+
+        let history = ValidationReporter::load_history(&path)?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].compatibility_score, 50.0);
+        assert!(history[0].suites.is_empty());
+        assert_eq!(history[1].extra["commit"], "abc123");
+
+        // Unknown fields survive a round trip
+        assert!(serde_json::to_string(&history[1])?.contains("\"commit\":\"abc123\""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_lists_skips_and_unexpected_passes() -> Result<()> {
+        let mut suite = SuiteResult::new(TestSuite::Unit);
+        for (name, status) in [
+            ("fixed_bug", TestStatus::Xpass),
+            ("known_bug", TestStatus::Xfail),
+            (
+                "no_reference",
+                TestStatus::Skipped {
+                    reason: "OpenSCAD not installed".to_string(),
+                },
+            ),
+        ] {
+            suite.add_result(ValidationResult::Unit(UnitTestResult {
+                name: name.to_string(),
+                status,
+                duration: Duration::from_millis(1),
+                error: None,
+            }));
+        }
+        let mut report = ValidationReport::new();
+        report.add_suite_result(suite);
+
+        let dir = tempfile::tempdir()?;
+        let md_path = dir.path().join("report.md");
+        ValidationReporter::write_markdown(&report, &md_path)?;
+        let md = fs::read_to_string(&md_path)?;
+        assert!(md.contains("- **Expected Failures:** 1"));
+        assert!(md.contains("#### Unexpected Passes\n\n- `fixed_bug`"));
+        assert!(md.contains("- `no_reference`: OpenSCAD not installed"));
+        assert!(!md.contains("#### Failed Tests"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_junit_has_a_testcase_per_result() -> Result<()> {
+        let mut report = report_with(2, 1);
+        let mut fuzz = SuiteResult::new(TestSuite::Fuzz);
+        fuzz.add_result(ValidationResult::Fuzz(FuzzTestResult {
+            generated_count: 10,
+            parse_success_count: 10,
+            render_success_count: 9,
+            duration: Duration::from_millis(250),
+            errors: vec!["fuzz_3: render failed <&>".to_string()],
+        }));
+        report.add_suite_result(fuzz);
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("junit.xml");
+        ValidationReporter::write_junit(&report, &path)?;
+
+        let xml = fs::read_to_string(&path)?;
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        let (mut suites, mut testcases, mut failures) = (Vec::new(), 0, 0);
+        let mut failure_text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) | Event::Empty(e) => match e.name().as_ref() {
+                    b"testsuite" => {
+                        let name = e.try_get_attribute("name")?.unwrap().unescape_value()?;
+                        suites.push(name.to_string());
+                    }
+                    b"testcase" => testcases += 1,
+                    b"failure" => failures += 1,
+                    _ => {}
+                },
+                Event::Text(text) => failure_text.push_str(&text.unescape()?),
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        assert_eq!(testcases, report.total_tests);
+        assert_eq!(suites, ["unit", "fuzz"]);
+        assert_eq!(failures, 2);
+        assert!(failure_text.contains("fuzz_3: render failed <&>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_inlines_comparison_previews() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let diff_png = dir.path().join("diff.png");
+        fs::write(&diff_png, b"\x89PNG\r\n\x1a\n")?;
+
+        let mut report = report_with(1, 0);
+        let mut suite = SuiteResult::new(TestSuite::Comparison);
+        suite.add_result(ValidationResult::Comparison(ComparisonTestResult {
+            file: "models/<gear>.scad".into(),
+            status: TestStatus::Failed,
+            duration: Duration::from_millis(5),
+            comparison_passed: false,
+            vertex_delta: 0.25,
+            triangle_delta: 0.1,
+            visual_diff_delta: Some(3.5),
+            polyframe_preview: None,
+            openscad_preview: Some(dir.path().join("missing.png")),
+            diff_preview: Some(diff_png),
+            polyframe_stl: None,
+            openscad_stl: None,
+            error: None,
+        }));
+        report.add_suite_result(suite);
+
+        let html_path = dir.path().join("report.html");
+        ValidationReporter::write_html(&report, &html_path)?;
+        let html = fs::read_to_string(&html_path)?;
+
+        assert!(html.contains("<img src=\"data:image/png;base64,iVBORw0KGgo=\" alt=\"Diff preview\">"));
+        // Missing images fall back to a link to the path
+        assert!(html.contains("missing.png\" alt=\"OpenSCAD preview\">"));
+        assert!(!html.contains("alt=\"Polyframe preview\""));
+        assert!(html.contains("models/&lt;gear&gt;.scad"));
+        assert!(html.contains("vertex delta 25.00%"));
+
+        Ok(())
+    }
+}
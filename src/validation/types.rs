@@ -60,12 +60,24 @@ impl TestSuite {
 }
 
 /// Test result status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TestStatus {
     Passed,
     Failed,
-    Skipped,
+    /// Not run, e.g. because no reference renderer was available
+    Skipped { reason: String },
     Error,
+    /// Failed as expected, for a known incompatibility
+    Xfail,
+    /// Passed although it was expected to fail, so the expectation is out of date
+    Xpass,
+}
+
+impl TestStatus {
+    /// Whether the test failed or errored without being expected to
+    pub fn is_failure(&self) -> bool {
+        matches!(self, TestStatus::Failed | TestStatus::Error)
+    }
 }
 
 /// Unit test result
@@ -126,6 +138,32 @@ pub struct ComparisonTestResult {
     pub error: Option<String>,
 }
 
+impl ComparisonTestResult {
+    /// Delete the preview PNGs and STLs from disk and forget their paths
+    ///
+    /// Per-model preview directories left empty are removed too.
+    pub fn discard_artifacts(&mut self) -> std::io::Result<()> {
+        let artifacts = [
+            self.polyframe_preview.take(),
+            self.openscad_preview.take(),
+            self.diff_preview.take(),
+            self.polyframe_stl.take(),
+            self.openscad_stl.take(),
+        ];
+        for path in artifacts.into_iter().flatten() {
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+            if let Some(dir) = path.parent() {
+                // Fails harmlessly while other artifacts remain
+                let _ = std::fs::remove_dir(dir);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Fuzz test result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FuzzTestResult {
@@ -162,10 +200,10 @@ pub enum ValidationResult {
 impl ValidationResult {
     pub fn status(&self) -> TestStatus {
         match self {
-            ValidationResult::Unit(r) => r.status,
-            ValidationResult::Integration(r) => r.status,
-            ValidationResult::Evaluation(r) => r.status,
-            ValidationResult::Comparison(r) => r.status,
+            ValidationResult::Unit(r) => r.status.clone(),
+            ValidationResult::Integration(r) => r.status.clone(),
+            ValidationResult::Evaluation(r) => r.status.clone(),
+            ValidationResult::Comparison(r) => r.status.clone(),
             ValidationResult::Fuzz(r) => {
                 if r.errors.is_empty() {
                     TestStatus::Passed
@@ -173,7 +211,7 @@ impl ValidationResult {
                     TestStatus::Failed
                 }
             }
-            ValidationResult::Regression(r) => r.status,
+            ValidationResult::Regression(r) => r.status.clone(),
         }
     }
 
@@ -188,6 +226,18 @@ impl ValidationResult {
         }
     }
 
+    /// Name, model, or file identifying the test in reports
+    pub fn label(&self) -> String {
+        match self {
+            ValidationResult::Unit(r) => r.name.clone(),
+            ValidationResult::Integration(r) => r.name.clone(),
+            ValidationResult::Evaluation(r) => r.model.clone(),
+            ValidationResult::Comparison(r) => r.file.display().to_string(),
+            ValidationResult::Fuzz(_) => "fuzz".to_string(),
+            ValidationResult::Regression(r) => r.file.display().to_string(),
+        }
+    }
+
     pub fn suite_type(&self) -> TestSuite {
         match self {
             ValidationResult::Unit(_) => TestSuite::Unit,
@@ -209,6 +259,12 @@ pub struct SuiteResult {
     pub failed: usize,
     pub skipped: usize,
     pub errors: usize,
+    /// Expected failures that did fail
+    #[serde(default)]
+    pub xfailed: usize,
+    /// Expected failures that passed
+    #[serde(default)]
+    pub xpassed: usize,
     #[serde(serialize_with = "serialize_duration", deserialize_with = "deserialize_duration")]
     pub duration: Duration,
     pub results: Vec<ValidationResult>,
@@ -223,6 +279,8 @@ impl SuiteResult {
             failed: 0,
             skipped: 0,
             errors: 0,
+            xfailed: 0,
+            xpassed: 0,
             duration: Duration::ZERO,
             results: Vec::new(),
         }
@@ -233,8 +291,10 @@ impl SuiteResult {
         match result.status() {
             TestStatus::Passed => self.passed += 1,
             TestStatus::Failed => self.failed += 1,
-            TestStatus::Skipped => self.skipped += 1,
+            TestStatus::Skipped { .. } => self.skipped += 1,
             TestStatus::Error => self.errors += 1,
+            TestStatus::Xfail => self.xfailed += 1,
+            TestStatus::Xpass => self.xpassed += 1,
         }
         self.duration += result.duration();
         self.results.push(result);
@@ -259,10 +319,16 @@ pub struct ValidationReport {
     pub total_failed: usize,
     pub total_skipped: usize,
     pub total_errors: usize,
+    #[serde(default)]
+    pub total_xfailed: usize,
+    #[serde(default)]
+    pub total_xpassed: usize,
     #[serde(serialize_with = "serialize_duration", deserialize_with = "deserialize_duration")]
     pub total_duration: Duration,
     pub suite_results: Vec<SuiteResult>,
-    pub compatibility_score: f32, // 0-100 score based on all tests
+    /// 0-100 score over the tests that ran with an unknown outcome: passes and unexpected
+    /// passes out of everything but skips and expected failures
+    pub compatibility_score: f32,
 }
 
 impl ValidationReport {
@@ -275,6 +341,8 @@ impl ValidationReport {
             total_failed: 0,
             total_skipped: 0,
             total_errors: 0,
+            total_xfailed: 0,
+            total_xpassed: 0,
             total_duration: Duration::ZERO,
             suite_results: Vec::new(),
             compatibility_score: 0.0,
@@ -288,12 +356,15 @@ impl ValidationReport {
         self.total_failed += suite_result.failed;
         self.total_skipped += suite_result.skipped;
         self.total_errors += suite_result.errors;
+        self.total_xfailed += suite_result.xfailed;
+        self.total_xpassed += suite_result.xpassed;
         self.total_duration += suite_result.duration;
         self.suite_results.push(suite_result);
 
         // Recalculate compatibility score
-        self.compatibility_score = if self.total_tests > 0 {
-            (self.total_passed as f32 / self.total_tests as f32) * 100.0
+        let scored = self.total_tests - self.total_skipped - self.total_xfailed;
+        self.compatibility_score = if scored > 0 {
+            ((self.total_passed + self.total_xpassed) as f32 / scored as f32) * 100.0
         } else {
             0.0
         };
@@ -318,3 +389,125 @@ impl Default for ValidationReport {
     }
 }
 
+
+/// Per-suite counts recorded in a [`HistoryEntry`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuiteSummary {
+    pub suite: String,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    pub xfailed: usize,
+    pub xpassed: usize,
+}
+
+/// One run's summary in the validation history
+///
+/// Every field has a default and unknown fields are kept in `extra`, so entries written by
+/// older or newer versions still load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    pub pass_rate: f32,
+    pub compatibility_score: f32,
+    pub total_tests: usize,
+    pub suites: Vec<SuiteSummary>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl From<&ValidationReport> for HistoryEntry {
+    fn from(report: &ValidationReport) -> Self {
+        Self {
+            timestamp: report.timestamp.clone(),
+            pass_rate: report.overall_pass_rate(),
+            compatibility_score: report.compatibility_score,
+            total_tests: report.total_tests,
+            suites: report
+                .suite_results
+                .iter()
+                .map(|s| SuiteSummary {
+                    suite: s.suite.as_str().to_string(),
+                    total: s.total,
+                    passed: s.passed,
+                    failed: s.failed,
+                    skipped: s.skipped,
+                    errors: s.errors,
+                    xfailed: s.xfailed,
+                    xpassed: s.xpassed,
+                })
+                .collect(),
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_result(name: &str, status: TestStatus) -> ValidationResult {
+        ValidationResult::Unit(UnitTestResult {
+            name: name.to_string(),
+            status,
+            duration: Duration::from_millis(1),
+            error: None,
+        })
+    }
+
+    #[test]
+    fn test_expected_failures_and_skips_are_not_scored() {
+        let mut suite = SuiteResult::new(TestSuite::Comparison);
+        for (name, status) in [
+            ("passes", TestStatus::Passed),
+            ("fails", TestStatus::Failed),
+            ("known_bug", TestStatus::Xfail),
+            ("fixed_bug", TestStatus::Xpass),
+            (
+                "no_openscad",
+                TestStatus::Skipped {
+                    reason: "OpenSCAD not installed".to_string(),
+                },
+            ),
+        ] {
+            suite.add_result(unit_result(name, status));
+        }
+        assert_eq!(
+            (suite.passed, suite.failed, suite.skipped, suite.xfailed, suite.xpassed),
+            (1, 1, 1, 1, 1)
+        );
+
+        let mut report = ValidationReport::new();
+        report.add_suite_result(suite);
+        assert_eq!(report.total_tests, 5);
+        assert_eq!((report.total_xfailed, report.total_xpassed), (1, 1));
+        // Two passes (one unexpected) out of the three tests with a scored outcome
+        assert!((report.compatibility_score - 200.0 / 3.0).abs() < 1e-4);
+        assert!(report.has_failures());
+    }
+
+    #[test]
+    fn test_status_serialization_is_additive() {
+        let passed: TestStatus = serde_json::from_str("\"Passed\"").unwrap();
+        assert_eq!(passed, TestStatus::Passed);
+
+        let skipped = TestStatus::Skipped {
+            reason: "no reference".to_string(),
+        };
+        let json = serde_json::to_string(&skipped).unwrap();
+        assert_eq!(json, r#"{"Skipped":{"reason":"no reference"}}"#);
+        assert_eq!(serde_json::from_str::<TestStatus>(&json).unwrap(), skipped);
+
+        // Reports written before the expected-failure counts existed still load
+        let suite: SuiteResult = serde_json::from_str(
+            r#"{"suite":"Unit","total":0,"passed":0,"failed":0,"skipped":0,"errors":0,
+                "duration":0.0,"results":[]}"#,
+        )
+        .unwrap();
+        assert_eq!((suite.xfailed, suite.xpassed), (0, 0));
+    }
+}
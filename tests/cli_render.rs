@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Tests for the `polyframe render` command
+
+use anyhow::Result;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Render `source` with `--timeout`, returning the exit status, stderr, and whether output exists
+fn render_with_timeout(source: &str, timeout: &str) -> Result<(bool, String, bool)> {
+    let dir = TempDir::new()?;
+    let input = dir.path().join("design.scad");
+    let output = dir.path().join("design.stl");
+    std::fs::write(&input, source)?;
+
+    let result = Command::new(env!("CARGO_BIN_EXE_polyframe"))
+        .arg("render")
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .args(["--timeout", timeout])
+        .output()?;
+
+    Ok((
+        result.status.success(),
+        String::from_utf8_lossy(&result.stderr).into_owned(),
+        output.exists(),
+    ))
+}
+
+#[test]
+fn test_render_timeout_exceeded() -> Result<()> {
+    let slow = "union() { sphere(r=10, $fn=300); translate([5, 0, 0]) sphere(r=10, $fn=300); }";
+    let (success, stderr, wrote_output) = render_with_timeout(slow, "0.2")?;
+
+    assert!(!success);
+    assert!(stderr.contains("timed out"), "stderr: {}", stderr);
+    assert!(!wrote_output);
+
+    Ok(())
+}
+
+#[test]
+fn test_render_within_timeout() -> Result<()> {
+    let (success, stderr, wrote_output) = render_with_timeout("cube(10);", "60")?;
+
+    assert!(success, "stderr: {}", stderr);
+    assert!(wrote_output);
+
+    Ok(())
+}
+
+/// Run `render --dry-run` on `source`, returning the exit status, stdout, stderr, and whether
+/// the output file was written
+fn render_dry_run(source: &str, format: &str) -> Result<(bool, String, String, bool)> {
+    let dir = TempDir::new()?;
+    let input = dir.path().join("design.scad");
+    let output = dir.path().join("design.out");
+    std::fs::write(&input, source)?;
+
+    let result = Command::new(env!("CARGO_BIN_EXE_polyframe"))
+        .arg("render")
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .args(["--format", format, "--dry-run"])
+        .output()?;
+
+    Ok((
+        result.status.success(),
+        String::from_utf8_lossy(&result.stdout).into_owned(),
+        String::from_utf8_lossy(&result.stderr).into_owned(),
+        output.exists(),
+    ))
+}
+
+#[test]
+fn test_render_dry_run_reports_without_writing() -> Result<()> {
+    let (success, stdout, stderr, wrote_output) = render_dry_run("cube([10, 10, 10]);", "3mf")?;
+
+    assert!(success, "stderr: {}", stderr);
+    assert!(!wrote_output);
+    assert!(stdout.contains("Dry run:"), "stdout: {}", stdout);
+    assert!(stdout.contains("Triangles: 12"), "stdout: {}", stdout);
+    assert!(stdout.contains("Closed:    yes"), "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_render_dry_run_fails_like_a_real_render() -> Result<()> {
+    let (success, _, stderr, wrote_output) = render_dry_run("cube([10, 10, 10]", "stl")?;
+    assert!(!success);
+    assert!(!wrote_output);
+    assert!(!stderr.is_empty());
+
+    let (success, _, stderr, _) = render_dry_run("cube([10, 10, 10]);", "dxf")?;
+    assert!(!success);
+    assert!(stderr.contains("Unsupported format"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[test]
+fn test_render_force_writes_invalid_3mf() -> Result<()> {
+    let dir = TempDir::new()?;
+    let input = dir.path().join("open.scad");
+    let output = dir.path().join("open.3mf");
+    // A single triangle is an open surface, which 3MF validation rejects
+    std::fs::write(
+        &input,
+        "polyhedron(points=[[0,0,0],[10,0,0],[0,10,0]], faces=[[0,1,2]]);",
+    )?;
+
+    let render = |force_args: &[&str], after: &[&str]| -> Result<bool> {
+        let status = Command::new(env!("CARGO_BIN_EXE_polyframe"))
+            .args(force_args)
+            .arg("render")
+            .arg(&input)
+            .arg("-o")
+            .arg(&output)
+            .args(["--format", "3mf"])
+            .args(after)
+            .output()?
+            .status;
+        Ok(status.success())
+    };
+
+    assert!(!render(&[], &[])?);
+    assert!(!output.exists());
+
+    // --force is a global flag, accepted before or after the subcommand
+    assert!(render(&["--force"], &[])?);
+    std::fs::remove_file(&output)?;
+    assert!(render(&[], &["--force"])?);
+    assert!(output.exists());
+
+    Ok(())
+}
@@ -153,3 +153,55 @@ fn test_json_loading() {
         panic!("Expected FromJson task");
     }
 }
+
+#[test]
+fn test_openscad_testsuite_loading() {
+    use polyframe::evaluation;
+
+    let root = PathBuf::from("tests/fixtures/openscad_testsuite");
+    let source = evaluation::detect_source(&root);
+    assert!(matches!(
+        source,
+        evaluation::DatasetSource::OpenScadTestsuite(_)
+    ));
+
+    let tasks = evaluation::load_dataset(source).expect("Failed to load testsuite");
+    let names: Vec<String> = tasks.iter().map(|t| t.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "3D/features/cube-tests.scad",
+            "3D/features/hull3-tests.scad"
+        ]
+    );
+
+    let evaluation::ModelTask::FromTestsuite(cube) = &tasks[0] else {
+        panic!("Expected FromTestsuite task");
+    };
+    assert_eq!(cube.expected_outputs.len(), 2);
+    assert!(tasks[0].expected_failure().is_none());
+    assert!(tasks[0].source().unwrap().contains("cube"));
+
+    // Unsupported features still load, but are marked expected-fail
+    assert_eq!(tasks[1].expected_failure(), Some("uses unsupported `hull`"));
+}
+
+#[test]
+fn test_robust_and_exact_csg_agree_on_random_trees() {
+    let mut fuzzer = evaluation::Fuzzer::new(evaluation::FuzzerConfig {
+        count: 50,
+        max_depth: 1,
+        max_primitives: 2,
+        seed: 1040,
+        ..Default::default()
+    });
+    let programs = fuzzer.run_boolean_trees();
+
+    let report = evaluation::run_differential(&programs, &Default::default());
+    assert_eq!(report.cases.len() + report.errors.len(), 50);
+    assert!(
+        report.divergent().next().is_none() && report.errors.is_empty(),
+        "Robust and Exact CSG diverged:\n{}",
+        report.describe_failures()
+    );
+}
@@ -132,6 +132,22 @@ fn test_roundtrip_gltf_export() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_roundtrip_obj_export() -> Result<()> {
+    let original_mesh = Primitive::sphere(8.0, 24).to_mesh();
+
+    let file = NamedTempFile::with_suffix(".obj")?;
+    let path = file.path().to_str().unwrap();
+
+    io::export_obj(&original_mesh, path, io::FaceFormat::VertexNormal)?;
+    let imported = io::import_obj(path)?;
+
+    assert_eq!(imported.vertex_count(), original_mesh.vertex_count());
+    assert_eq!(imported.triangle_count(), original_mesh.triangle_count());
+
+    Ok(())
+}
+
 #[test]
 fn test_roundtrip_step_export() -> Result<()> {
     // Create a test mesh
@@ -201,3 +217,60 @@ fn test_export_format_detection() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_export_units_in_step_and_3mf() -> Result<()> {
+    use std::io::Read;
+
+    let mesh = Primitive::cube(Vector3::new(25.4, 25.4, 25.4), false).to_mesh();
+
+    let step_text = |unit: io::Unit| -> Result<String> {
+        let file = NamedTempFile::with_suffix(".step")?;
+        let path = file.path().to_str().unwrap();
+        let options = io::StepOptions {
+            unit,
+            ..Default::default()
+        };
+        io::export_step_with_options(&mesh, path, &options)?;
+        Ok(std::fs::read_to_string(path)?)
+    };
+    let model_xml = |unit: io::Unit| -> Result<String> {
+        let file = NamedTempFile::with_suffix(".3mf")?;
+        let path = file.path().to_str().unwrap();
+        let options = io::ThreeMfOptions {
+            unit,
+            ..Default::default()
+        };
+        io::export_3mf_with_options(&mesh, path, &options)?;
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+        let mut xml = String::new();
+        archive
+            .by_name("3D/3dmodel.model")?
+            .read_to_string(&mut xml)?;
+        Ok(xml)
+    };
+
+    let length_unit = |step: &str| {
+        step.lines()
+            .rfind(|line| line.contains("LENGTH_UNIT()"))
+            .map(str::to_string)
+    };
+    let mm = step_text(io::Unit::Millimeter)?;
+    let inch = step_text(io::Unit::Inch)?;
+    assert!(length_unit(&mm)
+        .unwrap()
+        .contains("SI_UNIT(.MILLI.,.METRE.)"));
+    assert!(length_unit(&inch)
+        .unwrap()
+        .contains("CONVERSION_BASED_UNIT('INCH'"));
+    assert!(mm.contains("CARTESIAN_POINT('',(25.4,25.4,25.4))"));
+    assert!(inch.contains("CARTESIAN_POINT('',(1.,1.,1.))"));
+
+    let mm = model_xml(io::Unit::Millimeter)?;
+    let inch = model_xml(io::Unit::Inch)?;
+    assert!(mm.contains(r#"unit="millimeter""#));
+    assert!(inch.contains(r#"unit="inch""#));
+    assert!(inch.contains(r#"<vertex x="1" y="1" z="1"/>"#));
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2025 Polyframe Inc.
+
+//! Regression comparisons against stored reference meshes, without OpenSCAD
+
+use anyhow::Result;
+use polyframe::evaluation::CompareOptions;
+use polyframe::io::compare_to_reference;
+use std::path::PathBuf;
+
+const CUBE: &str = "cube([10, 10, 10]);";
+
+fn reference(name: &str) -> PathBuf {
+    PathBuf::from("tests/fixtures/reference").join(name)
+}
+
+#[test]
+fn test_cube_matches_stored_cube() -> Result<()> {
+    let options = CompareOptions {
+        require_topology: true,
+        ..Default::default()
+    };
+
+    let stl = compare_to_reference(CUBE, &reference("cube_10.stl"), &options)?;
+    assert!(stl.passed, "{:?}", stl);
+    assert_eq!(stl.vertex_count_poly, stl.vertex_count_openscad);
+
+    let obj = compare_to_reference(CUBE, &reference("cube_10.obj"), &options)?;
+    assert!(obj.passed, "{:?}", obj);
+
+    Ok(())
+}
+
+#[test]
+fn test_cube_does_not_match_stored_sphere() -> Result<()> {
+    let comparison = compare_to_reference(
+        CUBE,
+        &reference("sphere_r5.stl"),
+        &CompareOptions::default(),
+    )?;
+    assert!(!comparison.passed);
+
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_reference_format() {
+    assert!(
+        compare_to_reference(CUBE, &reference("cube_10.ply"), &CompareOptions::default()).is_err()
+    );
+}
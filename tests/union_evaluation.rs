@@ -4,7 +4,7 @@
 //! Comprehensive union evaluation tests
 //! Tests triangle splitting in robust union across various scenarios
 
-use polyframe::geometry::{Mesh, Primitive, csg_union};
+use polyframe::geometry::{BooleanOp, BooleanQuality, Mesh, Primitive, csg_union};
 use nalgebra::Vector3;
 
 #[derive(Debug, Clone)]
@@ -177,6 +177,36 @@ fn test_union_coplanar_faces() {
     assert!(result.output_triangles > 0, "Output should have triangles");
 }
 
+#[test]
+fn test_union_coplanar_faces_exact() {
+    // Cubes sharing the x = 10 face: the shared face is internal and must vanish entirely
+    let mesh_a = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+    let mut mesh_b = Primitive::cube(Vector3::new(10.0, 10.0, 10.0), false).to_mesh();
+    mesh_b.transform(&nalgebra::Matrix4::new_translation(&Vector3::new(10.0, 0.0, 0.0)));
+
+    let result = mesh_a
+        .boolean_operation_with_quality(&mesh_b, BooleanOp::Union, BooleanQuality::Exact)
+        .expect("Exact union failed");
+
+    let on_shared_face = result
+        .triangles
+        .iter()
+        .filter(|t| t.indices.iter().all(|&i| (result.vertices[i].position.x - 10.0).abs() < 1e-9))
+        .count();
+    assert_eq!(on_shared_face, 0, "Internal face survived the union");
+
+    let mut seen = std::collections::HashSet::new();
+    for triangle in &result.triangles {
+        let mut key = triangle.indices;
+        key.sort_unstable();
+        assert!(seen.insert(key), "Duplicate triangle {:?}", triangle.indices);
+    }
+
+    let analysis = polyframe::geometry::analyze(&result);
+    assert!((analysis.volume - 2000.0).abs() < 1e-6, "Volume was {}", analysis.volume);
+    assert!(result.validate().is_closed, "Union should be closed");
+}
+
 #[test]
 fn test_union_one_inside_another() {
     // Test 12: One shape completely inside another